@@ -0,0 +1,123 @@
+//! Derive macros for the `Estimate`, `Merge` and `Reset` traits of the
+//! `average` crate.
+//!
+//! Use them via the `"derive"` feature of `average`, which reexports them next
+//! to the traits.
+
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, spanned::Spanned, Data, DeriveInput, Error, Fields, Index, Member};
+
+/// Return the members of the fields of a struct.
+fn members<'a>(input: &'a DeriveInput, derive: &str) -> Result<Vec<(Member, &'a syn::Field)>, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(Error::new(input.span(),
+            format!("`{}` can only be derived for structs", derive))),
+    };
+    let members: Vec<_> = match fields {
+        Fields::Named(fields) => fields.named.iter()
+            .map(|f| (Member::Named(f.ident.clone().unwrap()), f))
+            .collect(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().enumerate()
+            .map(|(i, f)| (Member::Unnamed(Index { index: i as u32, span: f.span() }), f))
+            .collect(),
+        Fields::Unit => Vec::new(),
+    };
+    if members.is_empty() {
+        return Err(Error::new(input.span(),
+            format!("`{}` requires a struct with at least one field", derive)));
+    }
+    Ok(members)
+}
+
+/// Implement `Estimate` for a struct whose fields are estimators.
+///
+/// `add` is forwarded to all fields. `estimate` returns the
+/// estimate of the field annotated with `#[estimate]`, which is required if
+/// there is more than one field.
+#[proc_macro_derive(Estimate, attributes(estimate))]
+pub fn derive_estimate(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_estimate(&input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn expand_estimate(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let members = members(input, "Estimate")?;
+    let mut marked = members.iter().filter(|(_, f)| f.attrs.iter().any(|a| a.path.is_ident("estimate")));
+    let estimate = match (marked.next(), marked.next()) {
+        (Some((m, _)), None) => m,
+        (Some(_), Some((_, f))) => return Err(Error::new(f.span(),
+            "only one field can be annotated with `#[estimate]`")),
+        (None, _) if members.len() == 1 => &members[0].0,
+        (None, _) => return Err(Error::new(input.span(),
+            "annotate the field providing the estimate with `#[estimate]`")),
+    };
+    let all: Vec<_> = members.iter().map(|(m, _)| m).collect();
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::average::Estimate for #name #ty_generics #where_clause {
+            #[inline]
+            fn add(&mut self, x: f64) {
+                #( ::average::Estimate::add(&mut self.#all, x); )*
+            }
+
+            #[inline]
+            fn estimate(&self) -> f64 {
+                ::average::Estimate::estimate(&self.#estimate)
+            }
+        }
+    })
+}
+
+/// Implement `Merge` for a struct whose fields are mergeable estimators.
+///
+/// `merge` is forwarded to all fields.
+#[proc_macro_derive(Merge)]
+pub fn derive_merge(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_merge(&input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn expand_merge(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let members = members(input, "Merge")?;
+    let all: Vec<_> = members.iter().map(|(m, _)| m).collect();
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::average::Merge for #name #ty_generics #where_clause {
+            #[inline]
+            fn merge(&mut self, other: &Self) {
+                #( ::average::Merge::merge(&mut self.#all, &other.#all); )*
+            }
+        }
+    })
+}
+
+/// Implement `Reset` for a struct whose fields are estimators.
+///
+/// `reset` is forwarded to all fields.
+#[proc_macro_derive(Reset)]
+pub fn derive_reset(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_reset(&input).unwrap_or_else(|e| e.to_compile_error()).into()
+}
+
+fn expand_reset(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let members = members(input, "Reset")?;
+    let all: Vec<_> = members.iter().map(|(m, _)| m).collect();
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::average::Reset for #name #ty_generics #where_clause {
+            #[inline]
+            fn reset(&mut self) {
+                #( ::average::Reset::reset(&mut self.#all); )*
+            }
+        }
+    })
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 use bencher::{Bencher, benchmark_group, benchmark_main};
 
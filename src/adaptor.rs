@@ -0,0 +1,347 @@
+use core::fmt;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge, Reset, Mean, Variance, Skewness, Kurtosis, Min, Max, Quantile};
+
+/// Wrap an estimator to transform every sample before adding it.
+///
+/// This is what [`Estimate::map`] returns. The estimate is the one of the
+/// transformed samples.
+///
+/// [`Estimate::map`]: ./trait.Estimate.html#method.map
+#[derive(Clone)]
+pub struct Map<E, F> {
+    /// The wrapped estimator.
+    inner: E,
+    /// The transformation of the samples.
+    f: F,
+}
+
+impl<E: Estimate, F: FnMut(f64) -> f64> Map<E, F> {
+    /// Wrap the given estimator, transforming the samples with `f`.
+    #[inline]
+    pub fn new(inner: E, f: F) -> Map<E, F> {
+        Map { inner, f }
+    }
+
+    /// Return a reference to the wrapped estimator.
+    #[inline]
+    pub fn get(&self) -> &E {
+        &self.inner
+    }
+
+    /// Return the wrapped estimator.
+    #[inline]
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: Estimate, F: FnMut(f64) -> f64> Estimate for Map<E, F> {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.inner.add((self.f)(x));
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.inner.estimate()
+    }
+}
+
+impl<E: Reset, F> Reset for Map<E, F> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.reset();
+    }
+}
+
+impl<E: Merge, F> Merge for Map<E, F> {
+    /// Merge the wrapped estimator of another sample into this one.
+    #[inline]
+    fn merge(&mut self, other: &Map<E, F>) {
+        self.inner.merge(&other.inner);
+    }
+}
+
+impl<E: fmt::Debug, F> fmt::Debug for Map<E, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Map").field("inner", &self.inner).finish()
+    }
+}
+
+impl<E: Estimate, F: FnMut(f64) -> f64> core::ops::AddAssign<f64> for Map<E, F> {
+    /// Add an observation sampled from the population, transforming it.
+    #[inline]
+    fn add_assign(&mut self, x: f64) {
+        self.add(x);
+    }
+}
+
+/// Wrap an estimator to only add the samples satisfying a predicate.
+///
+/// This is what [`Estimate::filter`] returns.
+///
+/// [`Estimate::filter`]: ./trait.Estimate.html#method.filter
+#[derive(Clone)]
+pub struct Filter<E, P> {
+    /// The wrapped estimator.
+    inner: E,
+    /// The predicate selecting the samples.
+    predicate: P,
+    /// The number of samples that did not satisfy the predicate.
+    skipped: u64,
+}
+
+impl<E: Estimate, P: FnMut(&f64) -> bool> Filter<E, P> {
+    /// Wrap the given estimator, only adding the samples satisfying
+    /// `predicate`.
+    #[inline]
+    pub fn new(inner: E, predicate: P) -> Filter<E, P> {
+        Filter { inner, predicate, skipped: 0 }
+    }
+
+    /// Return the number of samples that were not added, because they did not
+    /// satisfy the predicate.
+    #[inline]
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Return a reference to the wrapped estimator.
+    #[inline]
+    pub fn get(&self) -> &E {
+        &self.inner
+    }
+
+    /// Return the wrapped estimator.
+    #[inline]
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+}
+
+impl<E: Estimate, P: FnMut(&f64) -> bool> Estimate for Filter<E, P> {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        if (self.predicate)(&x) {
+            self.inner.add(x);
+        } else {
+            self.skipped += 1;
+        }
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.inner.estimate()
+    }
+}
+
+impl<E: Reset, P> Reset for Filter<E, P> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.skipped = 0;
+    }
+}
+
+impl<E: Merge, P> Merge for Filter<E, P> {
+    /// Merge the wrapped estimator of another sample into this one.
+    ///
+    /// The numbers of skipped samples are added.
+    #[inline]
+    fn merge(&mut self, other: &Filter<E, P>) {
+        self.inner.merge(&other.inner);
+        self.skipped += other.skipped;
+    }
+}
+
+impl<E: fmt::Debug, P> fmt::Debug for Filter<E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("inner", &self.inner)
+            .field("skipped", &self.skipped)
+            .finish()
+    }
+}
+
+impl<E: Estimate, P: FnMut(&f64) -> bool> core::ops::AddAssign<f64> for Filter<E, P> {
+    /// Add an observation sampled from the population, if it satisfies the
+    /// predicate.
+    #[inline]
+    fn add_assign(&mut self, x: f64) {
+        self.add(x);
+    }
+}
+
+/// Wrap an estimator to apply the affine transformation `scale * x + offset`
+/// to every sample, and the inverse transformation to the statistics.
+///
+/// The statistics are reported for the original samples, so this can be used
+/// for unit conversion, or to improve the conditioning of poorly scaled data,
+/// for instance by subtracting a large common offset.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, Scaled, Estimate};
+///
+/// let mut a = Scaled::new(Variance::new(), 1., -1e9);
+/// for &x in &[1e9 + 1., 1e9 + 2., 1e9 + 3.] {
+///     a.add(x);
+/// }
+/// assert_eq!(a.get().mean(), 2.);
+/// assert_eq!(a.mean(), 1e9 + 2.);
+/// assert_eq!(a.sample_variance(), 1.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Scaled<E> {
+    /// The wrapped estimator.
+    inner: E,
+    /// The factor the samples are multiplied with.
+    scale: f64,
+    /// The offset added to the samples after scaling.
+    offset: f64,
+}
+
+impl<E: Estimate> Scaled<E> {
+    /// Wrap the given estimator, transforming the samples to
+    /// `scale * x + offset`.
+    ///
+    /// Panics if `scale` is not positive and finite, or if `offset` is not
+    /// finite. A positive scale preserves the order of the samples, so that
+    /// the minimum, maximum and quantiles can be transformed back.
+    #[inline]
+    pub fn new(inner: E, scale: f64, offset: f64) -> Scaled<E> {
+        assert!(scale > 0. && scale.is_finite());
+        assert!(offset.is_finite());
+        Scaled { inner, scale, offset }
+    }
+
+    /// Return the factor the samples are multiplied with.
+    #[inline]
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Return the offset added to the samples after scaling.
+    #[inline]
+    pub fn offset(&self) -> f64 {
+        self.offset
+    }
+
+    /// Return a reference to the wrapped estimator, which reports the
+    /// statistics of the transformed samples.
+    #[inline]
+    pub fn get(&self) -> &E {
+        &self.inner
+    }
+
+    /// Return the wrapped estimator.
+    #[inline]
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    /// Transform a location statistic of the transformed samples back.
+    #[inline]
+    fn location(&self, y: f64) -> f64 {
+        (y - self.offset) / self.scale
+    }
+
+    /// Transform a statistic with the units of the samples back.
+    #[inline]
+    fn scaled(&self, y: f64) -> f64 {
+        y / self.scale
+    }
+
+    /// Transform a statistic with the squared units of the samples back.
+    #[inline]
+    fn squared(&self, y: f64) -> f64 {
+        y / (self.scale * self.scale)
+    }
+
+    /// Leave a statistic that does not depend on the transformation as it is.
+    #[inline]
+    fn invariant(&self, y: f64) -> f64 {
+        y
+    }
+}
+
+impl<E: Merge> Merge for Scaled<E> {
+    /// Merge the wrapped estimator of another sample into this one.
+    ///
+    /// Panics if the transformations differ.
+    #[inline]
+    fn merge(&mut self, other: &Scaled<E>) {
+        assert!(self.scale == other.scale && self.offset == other.offset,
+                "Both estimators must use the same transformation");
+        self.inner.merge(&other.inner);
+    }
+}
+
+impl<E> core::ops::AddAssign<f64> for Scaled<E> where Scaled<E>: Estimate {
+    /// Add an observation sampled from the population, transforming it.
+    #[inline]
+    fn add_assign(&mut self, x: f64) {
+        self.add(x);
+    }
+}
+
+/// Implement `Estimate` and the statistics of the original samples for a
+/// scaled estimator.
+///
+/// Every statistic is given with the kind of the inverse transformation.
+macro_rules! impl_scaled {
+    ( $name:ty, estimate: $estimate:ident, [ $( $statistic:ident: $kind:ident ),* ] ) => {
+        impl Scaled<$name> {
+            $(
+                /// Calculate the statistic of the original samples.
+                #[inline]
+                pub fn $statistic(&self) -> f64 {
+                    self.$kind(self.inner.$statistic())
+                }
+            )*
+        }
+
+        impl Estimate for Scaled<$name> {
+            #[inline]
+            fn add(&mut self, x: f64) {
+                self.inner.add(self.scale * x + self.offset);
+            }
+
+            #[inline]
+            fn estimate(&self) -> f64 {
+                self.$estimate(self.inner.estimate())
+            }
+        }
+
+        impl Reset for Scaled<$name> {
+            #[inline]
+            fn reset(&mut self) {
+                self.inner.reset();
+            }
+        }
+    };
+}
+
+impl_scaled!(Mean, estimate: location, [mean: location]);
+impl_scaled!(Variance, estimate: squared, [
+    mean: location, sample_variance: squared, population_variance: squared,
+    error: scaled
+]);
+impl_scaled!(Skewness, estimate: invariant, [
+    mean: location, sample_variance: squared, population_variance: squared,
+    error_mean: scaled, skewness: invariant
+]);
+impl_scaled!(Kurtosis, estimate: invariant, [
+    mean: location, sample_variance: squared, population_variance: squared,
+    error_mean: scaled, skewness: invariant, kurtosis: invariant
+]);
+impl_scaled!(Min, estimate: location, [min: location]);
+impl_scaled!(Max, estimate: location, [max: location]);
+impl_scaled!(Quantile, estimate: location, [quantile: location]);
@@ -0,0 +1,55 @@
+use approx::{AbsDiffEq, RelativeEq};
+
+use super::{Mean, Variance, Skewness, Kurtosis, Min, Max, Stats};
+
+/// Implement `AbsDiffEq` and `RelativeEq` for an estimator by comparing the
+/// given statistics within the tolerances.
+///
+/// If `len` is given, the sample sizes have to be equal as well.
+macro_rules! impl_approx_eq {
+    ( $name:ty, len, [ $( $statistic:ident ),+ ] ) => {
+        impl_approx_eq!(@impl $name, |a: &$name, b: &$name| a.len() == b.len(), [ $( $statistic ),+ ]);
+    };
+    ( $name:ty, [ $( $statistic:ident ),+ ] ) => {
+        impl_approx_eq!(@impl $name, |_: &$name, _: &$name| true, [ $( $statistic ),+ ]);
+    };
+    ( @impl $name:ty, $same_len:expr, [ $( $statistic:ident ),+ ] ) => {
+        impl AbsDiffEq for $name {
+            type Epsilon = f64;
+
+            #[inline]
+            fn default_epsilon() -> f64 {
+                f64::default_epsilon()
+            }
+
+            #[inline]
+            fn abs_diff_eq(&self, other: &$name, epsilon: f64) -> bool {
+                $same_len(self, other) $(
+                    && self.$statistic().abs_diff_eq(&other.$statistic(), epsilon)
+                )+
+            }
+        }
+
+        impl RelativeEq for $name {
+            #[inline]
+            fn default_max_relative() -> f64 {
+                f64::default_max_relative()
+            }
+
+            #[inline]
+            fn relative_eq(&self, other: &$name, epsilon: f64, max_relative: f64) -> bool {
+                $same_len(self, other) $(
+                    && self.$statistic().relative_eq(&other.$statistic(), epsilon, max_relative)
+                )+
+            }
+        }
+    };
+}
+
+impl_approx_eq!(Mean, len, [mean]);
+impl_approx_eq!(Variance, len, [mean, sample_variance]);
+impl_approx_eq!(Skewness, len, [mean, sample_variance, skewness]);
+impl_approx_eq!(Kurtosis, len, [mean, sample_variance, skewness, kurtosis]);
+impl_approx_eq!(Stats, len, [mean, sample_variance, min, max]);
+impl_approx_eq!(Min, [min]);
+impl_approx_eq!(Max, [max]);
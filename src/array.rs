@@ -0,0 +1,70 @@
+use ndarray::{Array, ArrayBase, Axis, Data, Dimension, RemoveAxis};
+
+use super::{Estimate, IntoSample};
+
+/// Calculate statistics along an axis of an `ndarray` array.
+///
+/// This is implemented for all arrays of samples that can be converted to
+/// `f64`. Like `mean(axis=0)` in NumPy, the statistics are calculated for
+/// each lane along the axis, so the result has one dimension less than the
+/// array. Requires the `"ndarray"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Mean, Variance, StatsArrayExt};
+/// use ndarray::{array, Axis};
+///
+/// let a = array![[1., 2.], [3., 4.], [5., 9.]];
+/// let columns = a.estimators_axis::<Variance>(Axis(0));
+/// assert_eq!(columns[1].mean(), 5.);
+/// assert_eq!(columns[1].sample_variance(), 13.);
+/// let rows = a.estimate_axis::<Mean>(Axis(1));
+/// assert_eq!(rows, array![1.5, 3.5, 7.]);
+/// ```
+pub trait StatsArrayExt<A, D: Dimension> {
+    /// Calculate an estimator for each lane along the axis, creating the
+    /// estimators with the given function.
+    ///
+    /// This is useful for estimators without a default, like `Quantile`.
+    /// Panics if the axis is out of bounds.
+    fn estimators_axis_with<E, F>(&self, axis: Axis, new: F) -> Array<E, D::Smaller>
+        where E: Estimate, F: Fn() -> E, D: RemoveAxis;
+
+    /// Calculate an estimator for each lane along the axis.
+    ///
+    /// Panics if the axis is out of bounds.
+    #[inline]
+    fn estimators_axis<E>(&self, axis: Axis) -> Array<E, D::Smaller>
+        where E: Estimate + Default, D: RemoveAxis
+    {
+        self.estimators_axis_with(axis, E::default)
+    }
+
+    /// Calculate the estimate of an estimator for each lane along the axis.
+    ///
+    /// Panics if the axis is out of bounds.
+    #[inline]
+    fn estimate_axis<E>(&self, axis: Axis) -> Array<f64, D::Smaller>
+        where E: Estimate + Default, D: RemoveAxis
+    {
+        self.estimators_axis::<E>(axis).map(Estimate::estimate)
+    }
+}
+
+impl<A, S, D> StatsArrayExt<A, D> for ArrayBase<S, D>
+    where A: IntoSample + Copy, S: Data<Elem=A>, D: Dimension
+{
+    fn estimators_axis_with<E, F>(&self, axis: Axis, new: F) -> Array<E, D::Smaller>
+        where E: Estimate, F: Fn() -> E, D: RemoveAxis
+    {
+        self.map_axis(axis, |lane| {
+            let mut e = new();
+            for &x in lane {
+                e.add(x.into_sample());
+            }
+            e
+        })
+    }
+}
@@ -0,0 +1,152 @@
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use super::Variance;
+
+/// The bits of a NaN with a non-canonical payload, marking that the shift was
+/// not set yet.
+///
+/// NaN samples are stored as the canonical NaN, so they cannot be confused
+/// with this.
+const UNSET: u64 = 0x7ff8_0000_0000_0001;
+
+/// Atomically add `x` to the `f64` stored as bits in `a`.
+#[inline]
+fn fetch_add_f64(a: &AtomicU64, x: f64) {
+    let _ = a.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bits| {
+        Some((f64::from_bits(bits) + x).to_bits())
+    });
+}
+
+/// Estimate the arithmetic mean and the variance of a sequence of numbers
+/// ("population"), allowing to add samples concurrently from many threads
+/// without locking.
+///
+/// `add` only takes `&self`, so the estimator can be shared between threads,
+/// for instance in a static. The sums of the samples and of their squares are
+/// updated with atomic operations. To avoid catastrophic cancellation, they
+/// are taken relative to the first sample, which works well unless the
+/// samples are spread over many orders of magnitude.
+///
+/// The count and the sums are updated separately, so a snapshot taken while
+/// samples are being added may be slightly inconsistent. Once all additions
+/// happened before the snapshot, for instance after joining the threads, it
+/// is exact.
+///
+/// Use [`snapshot`] to obtain a regular [`Variance`]. Requires 64-bit atomics.
+///
+/// [`snapshot`]: #method.snapshot
+/// [`Variance`]: ./type.Variance.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::AtomicStats;
+///
+/// static STATS: AtomicStats = AtomicStats::new();
+///
+/// std::thread::scope(|s| {
+///     for t in 0..4 {
+///         s.spawn(move || {
+///             for i in 0..100 {
+///                 STATS.add(f64::from(t * 100 + i));
+///             }
+///         });
+///     }
+/// });
+/// let a = STATS.snapshot();
+/// assert_eq!(a.len(), 400);
+/// assert_eq!(a.mean(), 199.5);
+/// ```
+#[derive(Debug)]
+pub struct AtomicStats {
+    /// The first sample, which is subtracted from all samples, as bits.
+    shift: AtomicU64,
+    /// Sample size.
+    n: AtomicU64,
+    /// Sum of the shifted samples, as bits.
+    sum_1: AtomicU64,
+    /// Sum of the squares of the shifted samples, as bits.
+    sum_2: AtomicU64,
+}
+
+impl AtomicStats {
+    /// Create a new estimator.
+    #[inline]
+    pub const fn new() -> AtomicStats {
+        AtomicStats {
+            shift: AtomicU64::new(UNSET),
+            n: AtomicU64::new(0),
+            sum_1: AtomicU64::new(0),
+            sum_2: AtomicU64::new(0),
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    ///
+    /// This can be called concurrently from many threads.
+    #[inline]
+    pub fn add(&self, x: f64) {
+        let bits = if x.is_nan() { f64::NAN.to_bits() } else { x.to_bits() };
+        let shift = match self.shift.compare_exchange(
+            UNSET, bits, Ordering::Relaxed, Ordering::Relaxed)
+        {
+            Ok(_) => x,
+            Err(bits) => f64::from_bits(bits),
+        };
+        let d = x - shift;
+        fetch_add_f64(&self.sum_1, d);
+        fetch_add_f64(&self.sum_2, d * d);
+        self.n.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n.load(Ordering::Relaxed)
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the estimates of the samples added so far.
+    pub fn snapshot(&self) -> Variance {
+        let n = self.len();
+        if n == 0 {
+            return Variance::new();
+        }
+        let shift = f64::from_bits(self.shift.load(Ordering::Relaxed));
+        let sum_1 = f64::from_bits(self.sum_1.load(Ordering::Relaxed));
+        let sum_2 = f64::from_bits(self.sum_2.load(Ordering::Relaxed));
+        let len = n as f64;
+        let mean = sum_1 / len;
+        let sample_variance = if n < 2 {
+            0.
+        } else {
+            ((sum_2 - sum_1 * mean) / (len - 1.)).max(0.)
+        };
+        Variance::from_mean_and_variance(n, shift + mean, sample_variance)
+    }
+
+    /// Reset the estimator to an empty sample.
+    ///
+    /// The fields are reset separately, so this must not race with `add`:
+    /// a concurrent `add` may see a partially reset state, making later
+    /// estimates inconsistent. Reset only while no samples are being added,
+    /// for instance between measurement epochs.
+    pub fn reset(&self) {
+        self.n.store(0, Ordering::Relaxed);
+        self.sum_1.store(0, Ordering::Relaxed);
+        self.sum_2.store(0, Ordering::Relaxed);
+        self.shift.store(UNSET, Ordering::Relaxed);
+    }
+}
+
+impl Default for AtomicStats {
+    fn default() -> AtomicStats {
+        AtomicStats::new()
+    }
+}
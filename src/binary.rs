@@ -0,0 +1,110 @@
+use std::io::{self, Read};
+
+use super::Estimate;
+
+/// The size of the buffer for reading samples.
+const BUFFER_LEN: usize = 8 * 1024;
+
+/// The byte order of binary floating-point numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ByteOrder {
+    /// The least significant byte first, as on most current hardware.
+    #[default]
+    LittleEndian,
+    /// The most significant byte first, also known as network byte order.
+    BigEndian,
+}
+
+impl ByteOrder {
+    /// Decode a floating-point number from its bytes.
+    #[inline]
+    fn decode(self, bytes: [u8; 8]) -> f64 {
+        match self {
+            ByteOrder::LittleEndian => f64::from_le_bytes(bytes),
+            ByteOrder::BigEndian => f64::from_be_bytes(bytes),
+        }
+    }
+}
+
+/// Add binary `f64` samples from a reader to an estimator.
+///
+/// The reader has to yield the samples as consecutive 8-byte IEEE 754
+/// numbers in the given byte order, like a raw binary file or a memory-mapped
+/// file as a byte slice. The samples are decoded via a small buffer, so
+/// arbitrarily large inputs can be processed without collecting them first.
+/// No buffering of the reader is needed.
+///
+/// Returns the number of added samples. Returns an error if reading fails,
+/// or with kind `UnexpectedEof` if the input ends within a sample. The
+/// samples read before an error are still added.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{ByteOrder, Mean, read_samples};
+///
+/// let bytes: Vec<u8> = [1f64, 2., 6.].iter().flat_map(|x| x.to_le_bytes()).collect();
+/// let mut a = Mean::new();
+/// let n = read_samples(&mut a, &bytes[..], ByteOrder::LittleEndian).unwrap();
+/// assert_eq!(n, 3);
+/// assert_eq!(a.mean(), 3.);
+/// ```
+pub fn read_samples<E, R>(estimator: &mut E, mut reader: R, order: ByteOrder) -> io::Result<u64>
+    where E: Estimate + ?Sized, R: Read
+{
+    let mut buffer = [0u8; BUFFER_LEN];
+    // The number of bytes in the buffer not yet decoded, which belong to an
+    // incomplete sample at its start.
+    let mut pending = 0;
+    let mut count = 0;
+    loop {
+        let read = match reader.read(&mut buffer[pending..]) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        let available = pending + read;
+        let complete = available - available % 8;
+        for chunk in buffer[..complete].chunks_exact(8) {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(chunk);
+            estimator.add(order.decode(bytes));
+        }
+        count += (complete / 8) as u64;
+        buffer.copy_within(complete..available, 0);
+        pending = available - complete;
+    }
+    if pending != 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof,
+                                  "the input ends within a sample"));
+    }
+    Ok(count)
+}
+
+/// Create an estimator from binary `f64` samples of a reader.
+///
+/// See `read_samples` for the format and the errors. Requires the `"std"`
+/// feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{ByteOrder, Variance, from_reader};
+///
+/// let bytes: Vec<u8> = [1f64, 3.].iter().flat_map(|x| x.to_be_bytes()).collect();
+/// let a: Variance = from_reader(&bytes[..], ByteOrder::BigEndian).unwrap();
+/// assert_eq!(a.mean(), 2.);
+/// assert_eq!(a.sample_variance(), 2.);
+/// ```
+pub fn from_reader<E, R>(reader: R, order: ByteOrder) -> io::Result<E>
+    where E: Estimate + Default, R: Read
+{
+    let mut estimator = E::default();
+    read_samples(&mut estimator, reader, order)?;
+    Ok(estimator)
+}
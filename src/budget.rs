@@ -0,0 +1,77 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+/// A budget for the size of an approximate estimator, given either as the
+/// memory it may use or as the accuracy it should achieve.
+///
+/// Approximate estimators trade accuracy for memory via their capacity, like
+/// the number of samples in a reservoir or the number of components of a
+/// mixture. Their `with_budget` constructors choose the capacity fulfilling
+/// the budget, so that capacity planning does not require knowing their
+/// internals. The memory they actually use is reported by [`MemoryUsage`].
+///
+/// [`MemoryUsage`]: ./trait.MemoryUsage.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Budget, KendallTau, MemoryUsage};
+///
+/// let a = KendallTau::with_budget(Budget::Memory(16 * 1024)).unwrap();
+/// assert!(a.memory_usage() <= 16 * 1024);
+/// let b = KendallTau::with_budget(Budget::Accuracy(0.01)).unwrap();
+/// assert_eq!(b.capacity(), 10_000);
+/// assert!(KendallTau::with_budget(Budget::Memory(8)).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum Budget {
+    /// The maximal number of bytes used by the estimator, including its
+    /// allocations.
+    Memory(usize),
+    /// The targeted relative error of the estimator, between 0 and 1.
+    ///
+    /// What exactly this means depends on the estimator. For estimators
+    /// based on a random sample, it is the relative sampling error, which
+    /// requires a sample of `1 / ε²`.
+    Accuracy(f64),
+}
+
+impl Budget {
+    /// Calculate the capacity of an estimator fulfilling the budget.
+    ///
+    /// The estimator uses `fixed` bytes plus `per_item` bytes for each unit of
+    /// capacity. `accuracy` calculates the capacity required for a relative
+    /// error. Returns an error if the budget does not allow a capacity of at
+    /// least `minimum`, or if the accuracy is not between 0 and 1.
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub(crate) fn capacity<F>(self, fixed: usize, per_item: usize, minimum: usize, accuracy: F)
+        -> Result<usize, ()>
+        where F: FnOnce(f64) -> f64
+    {
+        let capacity = match self {
+            Budget::Memory(bytes) => bytes.saturating_sub(fixed) / per_item,
+            Budget::Accuracy(error) => {
+                if !(error > 0. && error <= 1.) {
+                    return Err(());
+                }
+                let capacity = crate::math::ceil(accuracy(error));
+                if capacity >= usize::MAX as f64 {
+                    return Err(());
+                }
+                capacity as usize
+            },
+        };
+        if capacity < minimum {
+            return Err(());
+        }
+        Ok(capacity)
+    }
+}
+
+/// Report the memory used by an estimator.
+pub trait MemoryUsage {
+    /// Return the number of bytes used by the estimator, including its
+    /// allocations.
+    fn memory_usage(&self) -> usize;
+}
@@ -0,0 +1,53 @@
+use std::thread;
+use std::vec::Vec;
+
+use super::{Estimate, Merge, merge_reduce};
+
+/// Calculate an estimator of a slice using several threads.
+///
+/// The slice is split into `n_threads` chunks of about equal length, an
+/// estimator is calculated for each chunk by its own thread, and the
+/// estimators are merged via a balanced tree (see [`merge_reduce`]). The
+/// estimators are merged in the order of the chunks, so the result only
+/// depends on the data and the number of threads. Slices not longer than the
+/// number of threads are processed by the calling thread.
+///
+/// Panics if `n_threads` is zero. Requires the `"std"` feature.
+///
+/// [`merge_reduce`]: ./fn.merge_reduce.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, from_slice_parallel};
+///
+/// let data: Vec<f64> = (1..=100_000).map(f64::from).collect();
+/// let a: Variance = from_slice_parallel(&data, 4);
+/// assert_eq!(a.len(), 100_000);
+/// assert_eq!(a.mean(), 50_000.5);
+/// ```
+pub fn from_slice_parallel<E>(data: &[f64], n_threads: usize) -> E
+    where E: Estimate + Merge + Default + Send
+{
+    assert!(n_threads > 0, "at least one thread is required");
+    let chunk_len = data.len().div_ceil(n_threads);
+    if n_threads == 1 || chunk_len <= 1 {
+        return estimate_chunk(data);
+    }
+    thread::scope(|scope| {
+        let handles: Vec<_> = data.chunks(chunk_len)
+            .map(|chunk| scope.spawn(move || estimate_chunk::<E>(chunk)))
+            .collect();
+        merge_reduce(handles.into_iter().map(|h| h.join().unwrap()))
+    })
+}
+
+/// Add all samples of the chunk to a new estimator.
+fn estimate_chunk<E: Estimate + Default>(chunk: &[f64]) -> E {
+    let mut e = E::default();
+    for &x in chunk {
+        e.add(x);
+    }
+    e
+}
@@ -0,0 +1,135 @@
+use std::vec::Vec;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge, Reset};
+
+/// Maintain an estimator for each column of a sequence of rows of numbers,
+/// like the columns of a table or a feature matrix.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Columns, Variance};
+///
+/// let mut a: Columns<Variance> = Columns::new(2);
+/// a.add_row(&[1., 10.]).unwrap();
+/// a.add_row(&[3., 20.]).unwrap();
+/// assert!(a.add_row(&[1.]).is_err());
+/// assert_eq!(a.rows(), 2);
+/// assert_eq!(a.column(0).unwrap().mean(), 2.);
+/// assert_eq!(a.column(1).unwrap().sample_variance(), 50.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Columns<E> {
+    /// The estimators of the columns.
+    columns: Vec<E>,
+    /// The number of added rows.
+    rows: u64,
+}
+
+impl<E> Columns<E> {
+    /// Create a new estimator of the columns, using the given estimators.
+    #[inline]
+    pub fn with_estimators(columns: Vec<E>) -> Columns<E> {
+        Columns { columns, rows: 0 }
+    }
+
+    /// Return the number of columns.
+    #[inline]
+    pub fn num_columns(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Return the number of added rows.
+    #[inline]
+    pub fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    /// Determine whether no rows were added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    /// Return the estimator of the given column.
+    #[inline]
+    pub fn column(&self, i: usize) -> Option<&E> {
+        self.columns.get(i)
+    }
+
+    /// Return the estimators of all columns.
+    #[inline]
+    pub fn columns(&self) -> &[E] {
+        &self.columns
+    }
+
+    /// Return the estimators of all columns.
+    #[inline]
+    pub fn into_columns(self) -> Vec<E> {
+        self.columns
+    }
+}
+
+impl<E: Estimate> Columns<E> {
+    /// Add a row of observations, one for each column.
+    ///
+    /// Returns an error and adds nothing if the length of the row does not
+    /// match the number of columns.
+    #[inline]
+    pub fn add_row(&mut self, row: &[f64]) -> Result<(), ()> {
+        if row.len() != self.columns.len() {
+            return Err(());
+        }
+        for (e, &x) in self.columns.iter_mut().zip(row) {
+            e.add(x);
+        }
+        self.rows += 1;
+        Ok(())
+    }
+
+    /// Return the estimates of all columns.
+    #[inline]
+    pub fn estimates(&self) -> Vec<f64> {
+        self.columns.iter().map(Estimate::estimate).collect()
+    }
+}
+
+impl<E: Reset> Columns<E> {
+    /// Reset the estimators of all columns to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        for e in &mut self.columns {
+            e.reset();
+        }
+        self.rows = 0;
+    }
+}
+
+impl<E: Default> Columns<E> {
+    /// Create a new estimator of the given number of columns.
+    #[inline]
+    pub fn new(columns: usize) -> Columns<E> {
+        Columns::with_estimators((0..columns).map(|_| E::default()).collect())
+    }
+}
+
+impl<E: Merge> Merge for Columns<E> {
+    /// Merge the columns of another sample into this one.
+    ///
+    /// Panics if the numbers of columns differ.
+    #[inline]
+    fn merge(&mut self, other: &Columns<E>) {
+        assert_eq!(self.columns.len(), other.columns.len(),
+                   "the numbers of columns have to be equal");
+        for (e, o) in self.columns.iter_mut().zip(&other.columns) {
+            e.merge(o);
+        }
+        self.rows += other.rows;
+    }
+}
@@ -0,0 +1,248 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Merge, Variance};
+use crate::math::sqrt;
+use crate::special::{beta_inc, normal_quantile};
+
+/// The result of the one-way analysis of variance.
+///
+/// See `anova`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AnovaTest {
+    /// The F statistic, i.e. the ratio of the variance between the group
+    /// means to the variance within the groups.
+    pub statistic: f64,
+    /// The degrees of freedom between the groups, i.e. the number of
+    /// non-empty groups minus one.
+    pub degrees_of_freedom_between: u64,
+    /// The degrees of freedom within the groups, i.e. the total sample size
+    /// minus the number of non-empty groups.
+    pub degrees_of_freedom_within: u64,
+    /// The probability of a test statistic at least as large as the observed
+    /// one, assuming all groups were drawn from normal distributions with the
+    /// same mean and variance.
+    ///
+    /// This is `nan` if all samples are equal.
+    pub p_value: f64,
+}
+
+/// Perform the one-way analysis of variance (ANOVA), testing whether the
+/// means of several groups are equal.
+///
+/// Only the sample sizes, means and variances of the groups are needed, so
+/// the groups can be streamed and merged. Empty groups are ignored. Returns
+/// an error if there are less than two non-empty groups, or if there are no
+/// degrees of freedom within the groups, i.e. each group has only one sample.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, anova, assert_almost_eq};
+///
+/// let groups: [Variance; 3] = [
+///     [1., 2., 3.].iter().collect(),
+///     [4., 5., 6.].iter().collect(),
+///     [7., 8., 9.].iter().collect(),
+/// ];
+/// let test = anova(&groups).unwrap();
+/// assert_eq!(test.statistic, 27.);
+/// assert_eq!(test.degrees_of_freedom_between, 2);
+/// assert_eq!(test.degrees_of_freedom_within, 6);
+/// assert_almost_eq!(test.p_value, 0.001, 1e-12);
+/// ```
+pub fn anova<'a, I>(groups: I) -> Result<AnovaTest, ()>
+    where I: IntoIterator<Item = &'a Variance>
+{
+    // The total sum of squares is the sum of squares of the merged groups.
+    let mut total = Variance::new();
+    let (mut k, mut within) = (0u64, 0.);
+    for g in groups.into_iter().filter(|g| !g.is_empty()) {
+        k += 1;
+        within += (g.len() - 1) as f64 * g.sample_variance();
+        total.merge(g);
+    }
+    let n = total.len();
+    if k < 2 || n <= k {
+        return Err(());
+    }
+    let total_sum = (n - 1) as f64 * total.sample_variance();
+    let between = (total_sum - within).max(0.);
+    let (df_between, df_within) = (k - 1, n - k);
+    let (d1, d2) = (df_between as f64, df_within as f64);
+    let statistic = (between / d1) / (within / d2);
+    let p_value = if statistic.is_nan() {
+        f64::NAN
+    } else {
+        beta_inc(d2 / 2., d1 / 2., d2 / (d2 + d1 * statistic))
+    };
+    Ok(AnovaTest {
+        statistic,
+        degrees_of_freedom_between: df_between,
+        degrees_of_freedom_within: df_within,
+        p_value,
+    })
+}
+
+/// The pooled variance of several groups.
+///
+/// See `pooled_variance`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct PooledVariance {
+    /// The pooled estimate of the variance common to all groups.
+    pub variance: f64,
+    /// The degrees of freedom of the estimate, i.e. the total sample size
+    /// minus the number of non-empty groups.
+    pub degrees_of_freedom: u64,
+}
+
+impl PooledVariance {
+    /// Return the pooled estimate of the standard deviation common to all
+    /// groups.
+    #[inline]
+    pub fn std_dev(&self) -> f64 {
+        sqrt(self.variance)
+    }
+
+    /// Estimate the standard error of the difference of the means of the
+    /// groups `a` and `b`, assuming they have the pooled variance.
+    ///
+    /// This is the denominator of Student's t-test. Returns infinity if one
+    /// of the groups is empty.
+    #[inline]
+    pub fn difference_error(&self, a: &Variance, b: &Variance) -> f64 {
+        let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+        sqrt(self.variance * (1. / n_a + 1. / n_b))
+    }
+}
+
+/// Calculate the pooled variance of several groups, assuming they share the
+/// same variance but possibly differ in their means.
+///
+/// The pooled variance is the average of the sample variances of the groups,
+/// weighted by their degrees of freedom. Empty groups are ignored. Returns an
+/// error if there are no degrees of freedom, i.e. each group has at most one
+/// sample.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, pooled_variance, assert_almost_eq};
+///
+/// let a: Variance = [1., 2., 3.].iter().collect();
+/// let b: Variance = [5., 7., 9.].iter().collect();
+/// let pooled = pooled_variance(&[a.clone(), b.clone()]).unwrap();
+/// assert_eq!(pooled.variance, 2.5);
+/// assert_eq!(pooled.degrees_of_freedom, 4);
+/// let t = (b.mean() - a.mean()) / pooled.difference_error(&a, &b);
+/// assert_almost_eq!(t, f64::sqrt(15.), 1e-14);
+/// ```
+pub fn pooled_variance<'a, I>(groups: I) -> Result<PooledVariance, ()>
+    where I: IntoIterator<Item = &'a Variance>
+{
+    let (mut sum, mut degrees_of_freedom) = (0., 0u64);
+    for g in groups.into_iter().filter(|g| !g.is_empty()) {
+        sum += (g.len() - 1) as f64 * g.sample_variance();
+        degrees_of_freedom += g.len() - 1;
+    }
+    if degrees_of_freedom == 0 {
+        return Err(());
+    }
+    Ok(PooledVariance { variance: sum / degrees_of_freedom as f64, degrees_of_freedom })
+}
+
+/// A standardized difference of the means of two groups and its standard
+/// error.
+///
+/// See `cohens_d`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct EffectSize {
+    /// The estimated effect size.
+    value: f64,
+    /// The estimated standard error of the effect size.
+    error: f64,
+    /// The degrees of freedom of the pooled standard deviation.
+    degrees_of_freedom: u64,
+}
+
+impl EffectSize {
+    /// Return the estimated effect size.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Return the estimated standard error of the effect size.
+    ///
+    /// This uses the large-sample approximation of Hedges and Olkin.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+
+    /// Correct the bias of Cohen's d for small samples, resulting in Hedges'
+    /// g.
+    ///
+    /// Cohen's d overestimates the magnitude of the effect size. The value
+    /// and the error are multiplied by the approximate correction factor
+    /// `1 - 3 / (4 df - 1)`, where `df` is the total sample size minus two.
+    #[inline]
+    pub fn hedges_g(self) -> EffectSize {
+        let correction = 1. - 3. / (4. * self.degrees_of_freedom as f64 - 1.);
+        EffectSize {
+            value: correction * self.value,
+            error: correction * self.error,
+            degrees_of_freedom: self.degrees_of_freedom,
+        }
+    }
+
+    /// Estimate the interval covering the effect size with the given
+    /// `confidence`, assuming its estimate is normally distributed.
+    ///
+    /// Panics if `confidence` is not between 0 and 1.
+    pub fn confidence_interval(&self, confidence: f64) -> (f64, f64) {
+        assert!((0. ..=1.).contains(&confidence));
+        let z = normal_quantile(0.5 * (1. + confidence));
+        (self.value - z * self.error, self.value + z * self.error)
+    }
+}
+
+/// Calculate Cohen's d, the difference of the means of the groups `b` and
+/// `a` in units of their pooled standard deviation.
+///
+/// The effect size is independent of the sample size, unlike the statistic
+/// of Student's t-test. Use `EffectSize::hedges_g` to correct its bias for
+/// small samples. Returns an error if there are less than three samples, if
+/// one of the groups is empty, or if all samples within each group are equal.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, cohens_d};
+///
+/// let a: Variance = [1., 2., 3.].iter().collect();
+/// let b: Variance = [5., 7., 9.].iter().collect();
+/// let d = cohens_d(&a, &b).unwrap();
+/// assert_eq!(d.value(), 5. / 2.5f64.sqrt());
+/// assert!(d.hedges_g().value() < d.value());
+/// let (lower, upper) = d.confidence_interval(0.95);
+/// assert!(0. < lower && lower < d.value() && d.value() < upper);
+/// ```
+pub fn cohens_d(a: &Variance, b: &Variance) -> Result<EffectSize, ()> {
+    if a.is_empty() || b.is_empty() {
+        return Err(());
+    }
+    let pooled = pooled_variance([a, b].iter().copied())?;
+    if pooled.variance == 0. {
+        return Err(());
+    }
+    let value = (b.mean() - a.mean()) / pooled.std_dev();
+    let (n_a, n_b) = (a.len() as f64, b.len() as f64);
+    let error = sqrt((n_a + n_b) / (n_a * n_b) + value * value / (2. * (n_a + n_b)));
+    Ok(EffectSize { value, error, degrees_of_freedom: pooled.degrees_of_freedom })
+}
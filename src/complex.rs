@@ -0,0 +1,229 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use num_complex::Complex64;
+
+use super::{Mean, Variance, Merge};
+
+/// Estimate the arithmetic mean of a sequence of complex numbers
+/// ("population").
+///
+/// The real and imaginary parts are averaged independently. This is useful for
+/// averaging Fourier coefficients or impedance measurements.
+///
+/// Requires the `num-complex` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::ComplexMean;
+/// use num_complex::Complex64;
+///
+/// let a: ComplexMean = [Complex64::new(1., 2.), Complex64::new(3., -4.)]
+///     .iter().collect();
+/// assert_eq!(a.mean(), Complex64::new(2., -1.));
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct ComplexMean {
+    /// Estimator of the real part.
+    re: Mean,
+    /// Estimator of the imaginary part.
+    im: Mean,
+}
+
+impl ComplexMean {
+    /// Create a new mean estimator.
+    #[inline]
+    pub const fn new() -> ComplexMean {
+        ComplexMean { re: Mean::EMPTY, im: Mean::EMPTY }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, z: Complex64) {
+        self.re.add(z.re);
+        self.im.add(z.im);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.re.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.re.len()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> Complex64 {
+        Complex64::new(self.re.mean(), self.im.mean())
+    }
+}
+
+impl Merge for ComplexMean {
+    #[inline]
+    fn merge(&mut self, other: &ComplexMean) {
+        self.re.merge(&other.re);
+        self.im.merge(&other.im);
+    }
+}
+
+/// Estimate the arithmetic mean and the variance of a sequence of complex
+/// numbers ("population").
+///
+/// The variance is the expected squared magnitude of the deviation from the
+/// mean, `E[|z - μ|²]`, which equals the sum of the variances of the real and
+/// imaginary parts.
+///
+/// Requires the `num-complex` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::ComplexVariance;
+/// use num_complex::Complex64;
+///
+/// let a: ComplexVariance = [Complex64::new(1., 0.), Complex64::new(0., 1.)]
+///     .iter().collect();
+/// assert_eq!(a.mean(), Complex64::new(0.5, 0.5));
+/// assert_eq!(a.sample_variance(), 1.);
+/// ```
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct ComplexVariance {
+    /// Estimator of the real part.
+    re: Variance,
+    /// Estimator of the imaginary part.
+    im: Variance,
+}
+
+impl ComplexVariance {
+    /// Create a new variance estimator.
+    #[inline]
+    pub const fn new() -> ComplexVariance {
+        ComplexVariance { re: Variance::EMPTY, im: Variance::EMPTY }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, z: Complex64) {
+        self.re.add(z.re);
+        self.im.add(z.im);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.re.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.re.len()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> Complex64 {
+        Complex64::new(self.re.mean(), self.im.mean())
+    }
+
+    /// Calculate the sample variance.
+    ///
+    /// This is an unbiased estimator of the variance of the population.
+    #[inline]
+    pub fn sample_variance(&self) -> f64 {
+        self.re.sample_variance() + self.im.sample_variance()
+    }
+
+    /// Calculate the population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        self.re.population_variance() + self.im.population_variance()
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    ///
+    /// This is the expected magnitude of the deviation of the estimated mean
+    /// from the mean of the population.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        let n = self.len();
+        if n == 0 {
+            return 0.;
+        }
+        (self.sample_variance() / n as f64).sqrt()
+    }
+}
+
+impl Merge for ComplexVariance {
+    #[inline]
+    fn merge(&mut self, other: &ComplexVariance) {
+        self.re.merge(&other.re);
+        self.im.merge(&other.im);
+    }
+}
+
+macro_rules! impl_complex_from_iterator {
+    ( $name:ident ) => {
+        impl core::iter::FromIterator<Complex64> for $name {
+            fn from_iter<T>(iter: T) -> $name
+                where T: IntoIterator<Item=Complex64>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl core::iter::Extend<Complex64> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=Complex64>
+            {
+                for i in iter {
+                    self.add(i);
+                }
+            }
+        }
+
+        impl<'a> core::iter::FromIterator<&'a Complex64> for $name {
+            fn from_iter<T>(iter: T) -> $name
+                where T: IntoIterator<Item=&'a Complex64>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a> core::iter::Extend<&'a Complex64> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a Complex64>
+            {
+                for &i in iter {
+                    self.add(i);
+                }
+            }
+        }
+    };
+}
+
+impl_complex_from_iterator!(ComplexMean);
+impl_complex_from_iterator!(ComplexVariance);
+impl_add_assign!(ComplexMean; Complex64, merge);
+impl_add_assign!(ComplexVariance; Complex64, merge);
@@ -0,0 +1,56 @@
+use core::fmt::Debug;
+
+/// An unsigned integer type for counting the samples of an estimator.
+///
+/// This is implemented for `u32`, `u64` and `usize`. Smaller types save memory
+/// on tiny targets. The counters saturate at their maximum instead of
+/// overflowing: once the maximum is reached, the sample size does not grow
+/// anymore, and each further sample is weighted as if it was the last one of
+/// a sample of maximal size.
+pub trait Counter: Copy + Ord + Debug {
+    /// The count of an empty sample.
+    const ZERO: Self;
+
+    /// The count of a single sample.
+    const ONE: Self;
+
+    /// Add two counts, saturating at the maximum.
+    fn saturating_add(self, other: Self) -> Self;
+
+    /// Convert the count to `u64`.
+    fn to_u64(self) -> u64;
+
+    /// Convert a `u64` to a count, saturating at the maximum.
+    fn from_u64(n: u64) -> Self;
+}
+
+/// Implement `Counter` for unsigned integer types.
+macro_rules! impl_counter {
+    ( $( $t:ty ),* ) => { $(
+        impl Counter for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+
+            #[inline]
+            fn saturating_add(self, other: $t) -> $t {
+                <$t>::saturating_add(self, other)
+            }
+
+            #[inline]
+            fn to_u64(self) -> u64 {
+                self as u64
+            }
+
+            #[inline]
+            fn from_u64(n: u64) -> $t {
+                if n > <$t>::MAX as u64 {
+                    <$t>::MAX
+                } else {
+                    n as $t
+                }
+            }
+        }
+    )* };
+}
+
+impl_counter!(u32, u64, usize);
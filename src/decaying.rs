@@ -0,0 +1,339 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Reset, IntoSample};
+
+/// Estimate the exponentially weighted mean of a sequence of numbers
+/// ("population").
+///
+/// The weight of a sample halves after each half-life, so recent samples are
+/// weighted more heavily than old ones. This is useful for long-running
+/// services, where the statistics should reflect the recent behavior.
+///
+/// The half-life can be measured in samples or in time:
+///
+/// * `add` advances the time by one unit, so the half-life is a number of
+///   samples.
+/// * `add_at` adds a sample at a given time stamp, so the half-life is a
+///   duration. Time stamps and the half-life can be given as `Duration`,
+///   which is converted to seconds, or as numbers in any consistent unit.
+///
+/// Note that the two should not be mixed for the same estimator.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{DecayingMean, assert_almost_eq};
+/// use core::time::Duration;
+///
+/// let mut a = DecayingMean::new(Duration::from_secs(60));
+/// a.add_at(10., Duration::from_secs(0));
+/// a.add_at(20., Duration::from_secs(60));
+/// // The first sample has half the weight of the second one.
+/// assert_almost_eq!(a.mean(), 50. / 3., 1e-14);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct DecayingMean {
+    /// Half-life of the weights.
+    half_life: f64,
+    /// Time of the most recent sample.
+    time: f64,
+    /// Sum of the decayed weights.
+    weight_sum: f64,
+    /// Weighted mean value.
+    avg: f64,
+}
+
+impl DecayingMean {
+    /// Create a new exponentially weighted mean estimator with the given
+    /// half-life.
+    ///
+    /// Panics if the half-life is not positive.
+    #[inline]
+    pub fn new<T: IntoSample>(half_life: T) -> DecayingMean {
+        let half_life = half_life.into_sample();
+        assert!(half_life > 0.);
+        DecayingMean { half_life, time: 0., weight_sum: 0., avg: 0. }
+    }
+
+    /// Return the half-life of the weights.
+    ///
+    /// Durations are returned in seconds.
+    #[inline]
+    pub fn half_life(&self) -> f64 {
+        self.half_life
+    }
+
+    /// Calculate the factor by which the weights decay over the given time.
+    #[inline]
+    fn decay(&self, dt: f64) -> f64 {
+        crate::math::exp(-dt / self.half_life * core::f64::consts::LN_2)
+    }
+
+    /// Advance the time to `t`, decaying the existing weights.
+    ///
+    /// Returns the decay factor of the existing weights and the weight of a new
+    /// sample at `t`, which is less than one if `t` is in the past.
+    #[inline]
+    fn advance(&mut self, t: f64) -> (f64, f64) {
+        if self.weight_sum == 0. {
+            self.time = t;
+        }
+        if t >= self.time {
+            let decay = self.decay(t - self.time);
+            self.time = t;
+            self.weight_sum *= decay;
+            (decay, 1.)
+        } else {
+            (1., self.decay(self.time - t))
+        }
+    }
+
+    /// Add an observation with the given weight, assuming the time was
+    /// already advanced.
+    #[inline]
+    fn add_inner(&mut self, sample: f64, weight: f64) {
+        self.weight_sum += weight;
+        self.avg += weight / self.weight_sum * (sample - self.avg);
+    }
+
+    /// Add an observation sampled from the population, advancing the time by
+    /// one unit.
+    #[inline]
+    pub fn add(&mut self, sample: f64) {
+        let t = self.time + 1.;
+        self.add_at(sample, t);
+    }
+
+    /// Add an observation sampled from the population at the given time.
+    ///
+    /// Time stamps in the past are supported and result in a smaller weight
+    /// for the sample.
+    #[inline]
+    pub fn add_at<T: IntoSample>(&mut self, sample: f64, time: T) {
+        let (_, weight) = self.advance(time.into_sample());
+        self.add_inner(sample, weight);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.weight_sum == 0.
+    }
+
+    /// Return the sum of the decayed weights.
+    ///
+    /// This is the effective number of samples contributing to the mean.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weight_sum
+    }
+
+    /// Estimate the exponentially weighted mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg
+    }
+}
+
+impl Estimate for DecayingMean {
+    #[inline]
+    fn add(&mut self, sample: f64) {
+        DecayingMean::add(self, sample);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.mean()
+    }
+}
+
+impl Reset for DecayingMean {
+    #[inline]
+    fn reset(&mut self) {
+        *self = DecayingMean::new(self.half_life);
+    }
+}
+
+impl_add_assign!(DecayingMean; f64);
+
+/// Estimate the exponentially weighted mean and variance of a sequence of
+/// numbers ("population").
+///
+/// See [`DecayingMean`] for how the samples are weighted.
+///
+/// [`DecayingMean`]: ./struct.DecayingMean.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::DecayingVariance;
+///
+/// let mut a = DecayingVariance::new(100.);
+/// for i in 0..1000 {
+///     a.add(f64::from(i % 2));
+/// }
+/// println!("The mean is {} ± {}.", a.mean(), a.error());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct DecayingVariance {
+    /// Estimator of the weighted mean.
+    avg: DecayingMean,
+    /// Sum of the squares of the decayed weights.
+    weight_sq_sum: f64,
+    /// Intermediate weighted sum of squares for calculating the variance.
+    sum_2: f64,
+}
+
+impl DecayingVariance {
+    /// Create a new exponentially weighted variance estimator with the given
+    /// half-life.
+    ///
+    /// Panics if the half-life is not positive.
+    #[inline]
+    pub fn new<T: IntoSample>(half_life: T) -> DecayingVariance {
+        DecayingVariance {
+            avg: DecayingMean::new(half_life),
+            weight_sq_sum: 0.,
+            sum_2: 0.,
+        }
+    }
+
+    /// Return the half-life of the weights.
+    ///
+    /// Durations are returned in seconds.
+    #[inline]
+    pub fn half_life(&self) -> f64 {
+        self.avg.half_life()
+    }
+
+    /// Add an observation sampled from the population, advancing the time by
+    /// one unit.
+    #[inline]
+    pub fn add(&mut self, sample: f64) {
+        let t = self.avg.time + 1.;
+        self.add_at(sample, t);
+    }
+
+    /// Add an observation sampled from the population at the given time.
+    ///
+    /// Time stamps in the past are supported and result in a smaller weight
+    /// for the sample.
+    #[inline]
+    pub fn add_at<T: IntoSample>(&mut self, sample: f64, time: T) {
+        // This is the weighted incremental algorithm by West (1979), with the
+        // accumulated sums decayed before each update.
+        let (decay, weight) = self.avg.advance(time.into_sample());
+        self.weight_sq_sum = self.weight_sq_sum * decay * decay + weight * weight;
+        self.sum_2 *= decay;
+        let delta = sample - self.avg.mean();
+        self.avg.add_inner(sample, weight);
+        self.sum_2 += weight * delta * (sample - self.avg.mean());
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sum of the decayed weights.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.avg.sum_weights()
+    }
+
+    /// Return the sum of the squared decayed weights.
+    #[inline]
+    pub fn sum_weights_sq(&self) -> f64 {
+        self.weight_sq_sum
+    }
+
+    /// Return the weighted sum of the squared deviations from the mean.
+    #[inline]
+    pub fn sum_2(&self) -> f64 {
+        self.sum_2
+    }
+
+    /// Return the effective sample size given the decayed weights.
+    ///
+    /// This is Kish's effective sample size, `(Σw)² / Σw²`.
+    #[inline]
+    pub fn effective_len(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let w = self.sum_weights();
+        w * w / self.weight_sq_sum
+    }
+
+    /// Estimate the exponentially weighted mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Calculate the weighted sample variance.
+    ///
+    /// This is an unbiased estimator of the variance of the population,
+    /// treating the decayed weights as reliability weights.
+    #[inline]
+    pub fn sample_variance(&self) -> f64 {
+        let w = self.sum_weights();
+        let denominator = w - self.weight_sq_sum / w;
+        if self.is_empty() || denominator <= 0. {
+            return 0.;
+        }
+        self.sum_2 / denominator
+    }
+
+    /// Calculate the weighted population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        self.sum_2 / self.sum_weights()
+    }
+
+    /// Estimate the standard error of the weighted mean of the population.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        crate::math::sqrt(self.sample_variance() / self.effective_len())
+    }
+}
+
+impl Estimate for DecayingVariance {
+    #[inline]
+    fn add(&mut self, sample: f64) {
+        DecayingVariance::add(self, sample);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.population_variance()
+    }
+}
+
+impl Reset for DecayingVariance {
+    #[inline]
+    fn reset(&mut self) {
+        *self = DecayingVariance::new(self.half_life());
+    }
+}
+
+impl_add_assign!(DecayingVariance; f64);
@@ -0,0 +1,155 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use rust_decimal::Decimal;
+
+use super::Merge;
+
+/// Estimate the arithmetic mean of a sequence of decimal numbers
+/// ("population").
+///
+/// The sum of the samples is accumulated exactly in a [`Decimal`], so there is
+/// no binary floating-point rounding. This is useful for financial data, where
+/// the mean of `0.1` and `0.2` has to be `0.15`. The only rounding happens when
+/// dividing by the sample size, which is done with the 28 significant digits
+/// supported by `Decimal`.
+///
+/// Requires the `rust_decimal` feature.
+///
+/// [`Decimal`]: https://docs.rs/rust_decimal/1/rust_decimal/struct.Decimal.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::DecimalMean;
+/// use rust_decimal::Decimal;
+///
+/// let a: DecimalMean = [Decimal::new(1, 1), Decimal::new(2, 1)]
+///     .iter().collect();
+/// assert_eq!(a.mean(), Decimal::new(15, 2));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DecimalMean {
+    /// Sum of the samples.
+    sum: Decimal,
+    /// Sample size.
+    n: u64,
+}
+
+impl DecimalMean {
+    /// Create a new mean estimator.
+    #[inline]
+    pub const fn new() -> DecimalMean {
+        DecimalMean { sum: Decimal::ZERO, n: 0 }
+    }
+
+    /// Add an observation sampled from the population.
+    ///
+    /// Panics if the sum overflows the range of `Decimal`.
+    #[inline]
+    pub fn add(&mut self, x: Decimal) {
+        self.sum += x;
+        self.n += 1;
+    }
+
+    /// Add an observation sampled from the population, unless the sum would
+    /// overflow.
+    ///
+    /// Returns an error and leaves the estimator unchanged in case of
+    /// overflow.
+    #[inline]
+    pub fn checked_add(&mut self, x: Decimal) -> Result<(), ()> {
+        self.sum = self.sum.checked_add(x).ok_or(())?;
+        self.n += 1;
+        Ok(())
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Return the exact sum of the samples.
+    #[inline]
+    pub fn sum(&self) -> Decimal {
+        self.sum
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> Decimal {
+        if self.n == 0 {
+            return Decimal::ZERO;
+        }
+        self.sum / Decimal::from(self.n)
+    }
+}
+
+impl core::default::Default for DecimalMean {
+    fn default() -> DecimalMean {
+        DecimalMean::new()
+    }
+}
+
+impl Merge for DecimalMean {
+    /// Merge another sample into this one.
+    ///
+    /// In contrast to floating-point estimators, this is exact.
+    #[inline]
+    fn merge(&mut self, other: &DecimalMean) {
+        self.sum += other.sum;
+        self.n += other.n;
+    }
+}
+
+impl core::iter::FromIterator<Decimal> for DecimalMean {
+    fn from_iter<T>(iter: T) -> DecimalMean
+        where T: IntoIterator<Item=Decimal>
+    {
+        let mut e = DecimalMean::new();
+        e.extend(iter);
+        e
+    }
+}
+
+impl core::iter::Extend<Decimal> for DecimalMean {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=Decimal>
+    {
+        for i in iter {
+            self.add(i);
+        }
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a Decimal> for DecimalMean {
+    fn from_iter<T>(iter: T) -> DecimalMean
+        where T: IntoIterator<Item=&'a Decimal>
+    {
+        let mut e = DecimalMean::new();
+        e.extend(iter);
+        e
+    }
+}
+
+impl<'a> core::iter::Extend<&'a Decimal> for DecimalMean {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=&'a Decimal>
+    {
+        for &i in iter {
+            self.add(i);
+        }
+    }
+}
+
+impl_add_assign!(DecimalMean; Decimal, merge);
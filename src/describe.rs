@@ -0,0 +1,264 @@
+use core::fmt;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge, Kurtosis, Min, Max, Quantile};
+
+/// Summarize a sequence of numbers ("population") in one pass, like
+/// `describe()` in pandas.
+///
+/// This estimates the sample size, mean, standard deviation, minimum, maximum,
+/// three quantiles (by default the quartiles), skewness and kurtosis. The
+/// quantiles are estimated with [`Quantile`], so they are approximate for
+/// large samples.
+///
+/// Formatting with `{}` prints a report with one statistic per line, with the
+/// values aligned. The mean is followed by its standard error. The precision
+/// of the formatter, if given, is used for all statistics but the sample
+/// size.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Describe;
+///
+/// let d: Describe = (1..5).map(f64::from).collect();
+/// assert_eq!(d.len(), 4);
+/// assert_eq!(d.mean(), 2.5);
+/// assert_eq!((d.min(), d.max()), (1., 4.));
+/// assert_eq!(d.quantiles()[1], (0.5, 2.5));
+/// assert_eq!(format!("{:.2}", d), "\
+/// count 4
+/// mean  2.50 ± 0.65
+/// std   1.29
+/// min   1.00
+/// 25%   1.50
+/// 50%   2.50
+/// 75%   3.50
+/// max   4.00
+/// skew  0.00
+/// kurt  -1.20
+/// ");
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Describe {
+    /// Estimator of the mean, variance, skewness and kurtosis.
+    avg: Kurtosis,
+    /// Estimator of the minimum.
+    min: Min,
+    /// Estimator of the maximum.
+    max: Max,
+    /// Estimators of the quantiles.
+    quantiles: [Quantile; 3],
+}
+
+impl Describe {
+    /// Create a new estimator of the quartiles and the other statistics.
+    #[inline]
+    pub const fn new() -> Describe {
+        Describe::with_quantiles([0.25, 0.5, 0.75])
+    }
+
+    /// Create a new estimator of the given quantiles and the other
+    /// statistics.
+    ///
+    /// Panics if one of the probabilities is not between 0 and 1.
+    #[inline]
+    pub const fn with_quantiles(p: [f64; 3]) -> Describe {
+        Describe {
+            avg: Kurtosis::new(),
+            min: Min::new(),
+            max: Max::new(),
+            quantiles: [Quantile::new(p[0]), Quantile::new(p[1]), Quantile::new(p[2])],
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.avg.add(x);
+        self.min.add(x);
+        self.max.add(x);
+        for q in &mut self.quantiles {
+            q.add(x);
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Calculate the sample variance.
+    ///
+    /// This is an unbiased estimator of the variance of the population.
+    #[inline]
+    pub fn sample_variance(&self) -> f64 {
+        self.avg.sample_variance()
+    }
+
+    /// Calculate the standard deviation of the sample.
+    ///
+    /// This is the square root of the sample variance.
+    #[inline]
+    pub fn std(&self) -> f64 {
+        crate::math::sqrt(self.avg.sample_variance())
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        self.avg.error_mean()
+    }
+
+    /// Calculate the sample skewness.
+    ///
+    /// See `Skewness::sample_skewness`.
+    #[inline]
+    pub fn sample_skewness(&self) -> f64 {
+        self.avg.sample_skewness()
+    }
+
+    /// Calculate the sample excess kurtosis.
+    ///
+    /// See `Kurtosis::sample_kurtosis`.
+    #[inline]
+    pub fn sample_kurtosis(&self) -> f64 {
+        self.avg.sample_kurtosis()
+    }
+
+    /// Estimate the minimum of the population.
+    ///
+    /// Returns infinity for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.min.min()
+    }
+
+    /// Estimate the maximum of the population.
+    ///
+    /// Returns negative infinity for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.max.max()
+    }
+
+    /// Estimate the quantiles of the population.
+    ///
+    /// Returns pairs of the probability and the estimated quantile.
+    #[inline]
+    pub fn quantiles(&self) -> [(f64, f64); 3] {
+        let [a, b, c] = &self.quantiles;
+        [(a.p(), a.quantile()), (b.p(), b.quantile()), (c.p(), c.quantile())]
+    }
+
+    /// Reset the estimator to an empty sample, keeping the probabilities of
+    /// the quantiles.
+    #[inline]
+    pub fn reset(&mut self) {
+        let [a, b, c] = &self.quantiles;
+        *self = Describe::with_quantiles([a.p(), b.p(), c.p()]);
+    }
+}
+
+impl Default for Describe {
+    fn default() -> Describe {
+        Describe::new()
+    }
+}
+
+impl Merge for Describe {
+    /// Merge another sample into this one.
+    ///
+    /// Merging the quantiles loses some accuracy, see [`Quantile`].
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    #[inline]
+    fn merge(&mut self, other: &Describe) {
+        self.avg.merge(&other.avg);
+        self.min.merge(&other.min);
+        self.max.merge(&other.max);
+        for (a, b) in self.quantiles.iter_mut().zip(other.quantiles.iter()) {
+            a.merge(b);
+        }
+    }
+}
+
+impl_from_iterator!(Describe);
+impl_from_iterator_samples!(Describe; f32, i32, u32, i64);
+impl_add_assign!(Describe; f64, merge);
+
+/// The width of the column of labels in the report.
+const LABEL_WIDTH: usize = 6;
+
+/// Count the length of formatted text.
+struct Len(usize);
+
+impl fmt::Write for Len {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 += s.chars().count();
+        Ok(())
+    }
+}
+
+/// Write the label of a line of the report, padded to align the values.
+fn write_label(f: &mut fmt::Formatter<'_>, label: fmt::Arguments<'_>) -> fmt::Result {
+    let mut len = Len(0);
+    fmt::write(&mut len, label)?;
+    write!(f, "{}{:pad$}", label, "", pad = LABEL_WIDTH.saturating_sub(len.0).max(1))
+}
+
+/// Write a value, using the precision of the formatter.
+fn write_value(f: &mut fmt::Formatter<'_>, x: f64) -> fmt::Result {
+    match f.precision() {
+        Some(p) => write!(f, "{:.*}", p, x),
+        None => write!(f, "{}", x),
+    }
+}
+
+/// Write one line of the report, using the precision of the formatter for the
+/// value.
+fn write_line(f: &mut fmt::Formatter<'_>, label: fmt::Arguments<'_>, x: f64) -> fmt::Result {
+    write_label(f, label)?;
+    write_value(f, x)?;
+    writeln!(f)
+}
+
+impl fmt::Display for Describe {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<width$}{}", "count", self.len(), width = LABEL_WIDTH)?;
+        write_label(f, format_args!("mean"))?;
+        write_value(f, self.mean())?;
+        f.write_str(" ± ")?;
+        write_value(f, self.error())?;
+        writeln!(f)?;
+        write_line(f, format_args!("std"), self.std())?;
+        write_line(f, format_args!("min"), self.min())?;
+        for &(p, q) in &self.quantiles() {
+            write_line(f, format_args!("{}%", p * 100.), q)?;
+        }
+        write_line(f, format_args!("max"), self.max())?;
+        write_line(f, format_args!("skew"), self.sample_skewness())?;
+        write_line(f, format_args!("kurt"), self.sample_kurtosis())
+    }
+}
@@ -0,0 +1,60 @@
+use core::convert::TryFrom;
+
+use super::{Variance, Skewness, Kurtosis, Stats, Describe};
+use crate::fit;
+
+/// Implement conversions of an estimator of the mean and the variance into a
+/// normal distribution.
+macro_rules! impl_to_normal {
+    ( $( $name:ty ),* ) => { $(
+        impl $name {
+            /// Return the normal distribution with the estimated mean and
+            /// standard deviation, for generating samples with `rand_distr`.
+            ///
+            /// The standard deviation is the square root of the sample
+            /// variance. Returns an error for an empty sample.
+            ///
+            /// Requires the `"rand_distr"` feature.
+            #[inline]
+            pub fn to_normal(&self) -> Result<rand_distr::Normal<f64>, ()> {
+                rand_distr::Normal::try_from(self)
+            }
+        }
+
+        impl TryFrom<&$name> for rand_distr::Normal<f64> {
+            type Error = ();
+
+            fn try_from(a: &$name) -> Result<rand_distr::Normal<f64>, ()> {
+                if a.is_empty() {
+                    return Err(());
+                }
+                rand_distr::Normal::new(a.mean(), crate::math::sqrt(a.sample_variance()))
+                    .map_err(|_| ())
+            }
+        }
+    )* };
+}
+
+impl_to_normal!(Variance, Skewness, Kurtosis, Stats, Describe);
+
+/// Implement the conversion of a fitted distribution into the corresponding
+/// distribution of `rand_distr`.
+macro_rules! impl_try_from_fit {
+    ( $( $fit:ident => $distr:ident ( $( $parameter:ident ),+ ) ),* ) => { $(
+        impl TryFrom<&fit::$fit> for rand_distr::$distr<f64> {
+            type Error = ();
+
+            fn try_from(a: &fit::$fit) -> Result<rand_distr::$distr<f64>, ()> {
+                rand_distr::$distr::new( $( a.$parameter().value() ),+ ).map_err(|_| ())
+            }
+        }
+    )* };
+}
+
+impl_try_from_fit!(
+    Normal => Normal(mean, std_dev),
+    LogNormal => LogNormal(mu, sigma),
+    Gamma => Gamma(shape, scale),
+    Beta => Beta(alpha, beta),
+    Exponential => Exp(rate)
+);
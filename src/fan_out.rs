@@ -0,0 +1,170 @@
+#[cfg(feature = "std")] use std::{boxed::Box, vec::Vec};
+
+use super::{Estimate, Merge, Reset};
+
+/// Feed every sample to several estimators.
+///
+/// The estimators are given as a tuple of up to eight estimators of possibly
+/// different types. With the `"std"` feature, they can also be a vector of
+/// boxed estimators, so that the bundle can be assembled at runtime.
+///
+/// For a fixed set of statistics known at compile time, [`concatenate`]
+/// generates a struct with named accessors instead.
+///
+/// [`concatenate`]: ./macro.concatenate.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{FanOut, Mean, Min, Max};
+///
+/// let mut a = FanOut::new((Mean::new(), Min::new(), Max::new()));
+/// for &x in &[1., 4., 2., 3.] {
+///     a.add(x);
+/// }
+/// let (mean, min, max) = a.get();
+/// assert_eq!(mean.mean(), 2.5);
+/// assert_eq!((min.min(), max.max()), (1., 4.));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FanOut<T> {
+    /// The estimators the samples are fed to.
+    estimators: T,
+}
+
+impl<T> FanOut<T> {
+    /// Create a new fan-out to the given estimators.
+    #[inline]
+    pub const fn new(estimators: T) -> FanOut<T> {
+        FanOut { estimators }
+    }
+
+    /// Return a reference to the estimators.
+    #[inline]
+    pub fn get(&self) -> &T {
+        &self.estimators
+    }
+
+    /// Return a mutable reference to the estimators.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.estimators
+    }
+
+    /// Return the estimators.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.estimators
+    }
+}
+
+/// Implement `add`, `reset` and `Merge` for fan-outs to tuples of estimators.
+macro_rules! impl_fan_out_tuple {
+    ( $( ( $( $E:ident $i:tt ),+ ) )* ) => { $(
+        impl<$( $E: Estimate ),+> FanOut<( $( $E, )+ )> {
+            /// Add an observation sampled from the population to all
+            /// estimators.
+            #[inline]
+            pub fn add(&mut self, x: f64) {
+                $( self.estimators.$i.add(x); )+
+            }
+        }
+
+        impl<$( $E: Reset ),+> FanOut<( $( $E, )+ )> {
+            /// Reset all estimators to an empty sample.
+            #[inline]
+            pub fn reset(&mut self) {
+                $( self.estimators.$i.reset(); )+
+            }
+        }
+
+        impl<$( $E: Merge ),+> Merge for FanOut<( $( $E, )+ )> {
+            /// Merge the estimators of another sample into these ones.
+            #[inline]
+            fn merge(&mut self, other: &FanOut<( $( $E, )+ )>) {
+                $( self.estimators.$i.merge(&other.estimators.$i); )+
+            }
+        }
+
+        impl<$( $E: Estimate ),+> core::ops::AddAssign<f64> for FanOut<( $( $E, )+ )> {
+            /// Add an observation sampled from the population to all
+            /// estimators.
+            #[inline]
+            fn add_assign(&mut self, x: f64) {
+                self.add(x);
+            }
+        }
+
+        impl<$( $E: Estimate ),+> core::iter::Extend<f64> for FanOut<( $( $E, )+ )> {
+            fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+                for x in iter {
+                    self.add(x);
+                }
+            }
+        }
+    )* };
+}
+
+impl_fan_out_tuple! {
+    (A 0)
+    (A 0, B 1)
+    (A 0, B 1, C 2)
+    (A 0, B 1, C 2, D 3)
+    (A 0, B 1, C 2, D 3, E 4)
+    (A 0, B 1, C 2, D 3, E 4, F 5)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6)
+    (A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7)
+}
+
+/// A fan-out to estimators chosen at runtime.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{FanOut, Mean, Min, Estimate};
+///
+/// let mut a = FanOut::default();
+/// a.push(Mean::new());
+/// a.push(Min::new());
+/// a.extend(vec![3., 1., 2.]);
+/// let estimates: Vec<f64> = a.get().iter().map(|e| e.estimate()).collect();
+/// assert_eq!(estimates, [2., 1.]);
+/// ```
+#[cfg(feature = "std")]
+impl FanOut<Vec<Box<dyn Estimate>>> {
+    /// Add an estimator the samples are fed to.
+    #[inline]
+    pub fn push<E: Estimate + 'static>(&mut self, estimator: E) {
+        self.estimators.push(Box::new(estimator));
+    }
+
+    /// Add an observation sampled from the population to all estimators.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        for e in &mut self.estimators {
+            e.add(x);
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::ops::AddAssign<f64> for FanOut<Vec<Box<dyn Estimate>>> {
+    /// Add an observation sampled from the population to all estimators.
+    #[inline]
+    fn add_assign(&mut self, x: f64) {
+        self.add(x);
+    }
+}
+
+#[cfg(feature = "std")]
+impl core::iter::Extend<f64> for FanOut<Vec<Box<dyn Estimate>>> {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        for x in iter {
+            self.add(x);
+        }
+    }
+}
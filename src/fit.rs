@@ -0,0 +1,320 @@
+//! Fit common distributions to the moments of a sample.
+//!
+//! The parameters are estimated by the method of moments: they are chosen such
+//! that the mean and the variance of the distribution equal the mean and the
+//! sample variance of a [`Kurtosis`] estimator. This only needs the
+//! aggregates, so it works for streamed or merged samples.
+//!
+//! Every parameter comes with an estimate of its standard error. It is
+//! calculated with the delta method from the sample moments up to the fourth,
+//! so it does not assume that the population actually follows the fitted
+//! distribution. It is only valid asymptotically, for large samples.
+//!
+//! With the `"rand_distr"` or `"statrs"` feature, the fitted distributions can
+//! be converted into the distributions of the respective crate via `TryFrom`.
+//!
+//! [`Kurtosis`]: ../struct.Kurtosis.html
+//!
+//!
+//! ## Example
+//!
+//! ```
+//! use average::{Kurtosis, fit::Gamma};
+//!
+//! let a: Kurtosis = [0., 2., 2., 4., 4., 4., 5.].iter().collect();
+//! let gamma = Gamma::fit(&a).unwrap();
+//! assert_eq!(gamma.shape().value(), 3.);
+//! assert_eq!(gamma.scale().value(), 1.);
+//! assert!(gamma.shape().error() > 0.);
+//! ```
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::Kurtosis;
+use crate::math::{ln, sqrt};
+
+/// An estimated parameter of a distribution and its standard error.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Parameter {
+    /// The estimated value.
+    value: f64,
+    /// The standard error of the estimate.
+    error: f64,
+}
+
+impl Parameter {
+    /// Return the estimated value of the parameter.
+    #[inline]
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Return the estimated standard error of the parameter.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+}
+
+/// The sample moments a fit is calculated from.
+struct SampleMoments {
+    /// The mean.
+    mean: f64,
+    /// The sample variance.
+    variance: f64,
+    /// The variance of the mean.
+    var_mean: f64,
+    /// The covariance of the mean and the sample variance.
+    cov: f64,
+    /// The variance of the sample variance.
+    var_variance: f64,
+}
+
+impl SampleMoments {
+    /// Get the moments of a sample.
+    ///
+    /// Returns an error if there are less than two samples or the sample
+    /// variance is not positive.
+    fn new(a: &Kurtosis) -> Result<SampleMoments, ()> {
+        let variance = a.try_sample_variance().ok_or(())?;
+        if variance <= 0. {
+            return Err(());
+        }
+        let n = a.len() as f64;
+        let m2 = a.population_variance();
+        let m3 = a.sum_3() / n;
+        let m4 = a.sum_4() / n;
+        Ok(SampleMoments {
+            mean: a.mean(),
+            variance,
+            var_mean: variance / n,
+            cov: m3 / n,
+            var_variance: ((m4 - m2 * m2) / n).max(0.),
+        })
+    }
+
+    /// Estimate a parameter, given its value and its derivatives with respect
+    /// to the mean and the variance.
+    fn parameter(&self, value: f64, d_mean: f64, d_variance: f64) -> Parameter {
+        let var = d_mean * d_mean * self.var_mean
+            + 2. * d_mean * d_variance * self.cov
+            + d_variance * d_variance * self.var_variance;
+        Parameter { value, error: sqrt(var.max(0.)) }
+    }
+}
+
+/// A normal distribution fitted to a sample.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Kurtosis, fit::Normal};
+///
+/// let a: Kurtosis = [1., 2., 3., 4., 5.].iter().collect();
+/// let normal = Normal::fit(&a).unwrap();
+/// assert_eq!(normal.mean().value(), 3.);
+/// assert_eq!(normal.std_dev().value(), 2.5f64.sqrt());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Normal {
+    mean: Parameter,
+    std_dev: Parameter,
+}
+
+impl Normal {
+    /// Fit a normal distribution to the mean and the sample variance.
+    ///
+    /// Returns an error if there are less than two samples or the sample
+    /// variance is zero.
+    pub fn fit(a: &Kurtosis) -> Result<Normal, ()> {
+        let m = SampleMoments::new(a)?;
+        let std_dev = sqrt(m.variance);
+        Ok(Normal {
+            mean: m.parameter(m.mean, 1., 0.),
+            std_dev: m.parameter(std_dev, 0., 0.5 / std_dev),
+        })
+    }
+
+    /// Return the mean of the distribution.
+    #[inline]
+    pub fn mean(&self) -> Parameter {
+        self.mean
+    }
+
+    /// Return the standard deviation of the distribution.
+    #[inline]
+    pub fn std_dev(&self) -> Parameter {
+        self.std_dev
+    }
+}
+
+/// A log-normal distribution fitted to a sample.
+///
+/// The parameters are the mean `mu` and the standard deviation `sigma` of the
+/// logarithm of the samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LogNormal {
+    mu: Parameter,
+    sigma: Parameter,
+}
+
+impl LogNormal {
+    /// Fit a log-normal distribution to the mean and the sample variance.
+    ///
+    /// Returns an error if there are less than two samples, the sample
+    /// variance is zero or the mean is not positive.
+    pub fn fit(a: &Kurtosis) -> Result<LogNormal, ()> {
+        let m = SampleMoments::new(a)?;
+        let (mean, variance) = (m.mean, m.variance);
+        if mean <= 0. {
+            return Err(());
+        }
+        let square = mean * mean + variance;
+        let sigma_2 = ln(square / (mean * mean));
+        let sigma = sqrt(sigma_2);
+        // Derivatives of `sigma_2` with respect to the mean and the variance.
+        let d_mean = -2. * variance / (mean * square);
+        let d_variance = 1. / square;
+        Ok(LogNormal {
+            mu: m.parameter(ln(mean) - 0.5 * sigma_2,
+                            1. / mean - 0.5 * d_mean, -0.5 * d_variance),
+            sigma: m.parameter(sigma, 0.5 * d_mean / sigma, 0.5 * d_variance / sigma),
+        })
+    }
+
+    /// Return the mean of the logarithm of the distribution.
+    #[inline]
+    pub fn mu(&self) -> Parameter {
+        self.mu
+    }
+
+    /// Return the standard deviation of the logarithm of the distribution.
+    #[inline]
+    pub fn sigma(&self) -> Parameter {
+        self.sigma
+    }
+}
+
+/// A gamma distribution fitted to a sample.
+///
+/// The distribution is parametrized by its shape `k` and its scale `theta`,
+/// such that the mean is `k * theta`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Gamma {
+    shape: Parameter,
+    scale: Parameter,
+}
+
+impl Gamma {
+    /// Fit a gamma distribution to the mean and the sample variance.
+    ///
+    /// Returns an error if there are less than two samples, the sample
+    /// variance is zero or the mean is not positive.
+    pub fn fit(a: &Kurtosis) -> Result<Gamma, ()> {
+        let m = SampleMoments::new(a)?;
+        let (mean, variance) = (m.mean, m.variance);
+        if mean <= 0. {
+            return Err(());
+        }
+        let shape = mean * mean / variance;
+        let scale = variance / mean;
+        Ok(Gamma {
+            shape: m.parameter(shape, 2. * mean / variance, -shape / variance),
+            scale: m.parameter(scale, -scale / mean, 1. / mean),
+        })
+    }
+
+    /// Return the shape `k` of the distribution.
+    #[inline]
+    pub fn shape(&self) -> Parameter {
+        self.shape
+    }
+
+    /// Return the scale `theta` of the distribution.
+    #[inline]
+    pub fn scale(&self) -> Parameter {
+        self.scale
+    }
+}
+
+/// A beta distribution fitted to a sample.
+///
+/// The samples have to be between 0 and 1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Beta {
+    alpha: Parameter,
+    beta: Parameter,
+}
+
+impl Beta {
+    /// Fit a beta distribution to the mean and the sample variance.
+    ///
+    /// Returns an error if there are less than two samples, the sample
+    /// variance is zero, the mean is not between 0 and 1 or the sample
+    /// variance is too large for a beta distribution with that mean.
+    pub fn fit(a: &Kurtosis) -> Result<Beta, ()> {
+        let m = SampleMoments::new(a)?;
+        let (mean, variance) = (m.mean, m.variance);
+        if !(0. < mean && mean < 1.) || variance >= mean * (1. - mean) {
+            return Err(());
+        }
+        let common = mean * (1. - mean) / variance - 1.;
+        // Derivatives of `common` with respect to the mean and the variance.
+        let d_mean = (1. - 2. * mean) / variance;
+        let d_variance = -mean * (1. - mean) / (variance * variance);
+        Ok(Beta {
+            alpha: m.parameter(mean * common,
+                               common + mean * d_mean, mean * d_variance),
+            beta: m.parameter((1. - mean) * common,
+                              -common + (1. - mean) * d_mean, (1. - mean) * d_variance),
+        })
+    }
+
+    /// Return the first shape parameter `alpha` of the distribution.
+    #[inline]
+    pub fn alpha(&self) -> Parameter {
+        self.alpha
+    }
+
+    /// Return the second shape parameter `beta` of the distribution.
+    #[inline]
+    pub fn beta(&self) -> Parameter {
+        self.beta
+    }
+}
+
+/// An exponential distribution fitted to a sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Exponential {
+    rate: Parameter,
+}
+
+impl Exponential {
+    /// Fit an exponential distribution to the mean.
+    ///
+    /// Returns an error if there are less than two samples, the sample
+    /// variance is zero or the mean is not positive.
+    pub fn fit(a: &Kurtosis) -> Result<Exponential, ()> {
+        let m = SampleMoments::new(a)?;
+        if m.mean <= 0. {
+            return Err(());
+        }
+        let rate = 1. / m.mean;
+        Ok(Exponential {
+            rate: m.parameter(rate, -rate * rate, 0.),
+        })
+    }
+
+    /// Return the rate `lambda` of the distribution.
+    #[inline]
+    pub fn rate(&self) -> Parameter {
+        self.rate
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge, Min, Max, Quantile};
+
+/// The default factor of the interquartile range used by Tukey's whiskers.
+const TUKEY_FACTOR: f64 = 1.5;
+
+/// Estimate the five-number summary of a sequence of numbers ("population"):
+/// the minimum, the quartiles and the maximum.
+///
+/// This is the data needed to draw a box plot. The quartiles are estimated
+/// with [`Quantile`], so they are approximate for large samples.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::FiveNumber;
+///
+/// let a: FiveNumber = [2., 3., 4., 5.].iter().collect();
+/// assert_eq!(a.five_number(), [2., 2.5, 3.5, 4.5, 5.]);
+/// assert_eq!(a.iqr(), 2.);
+/// assert_eq!(a.whiskers(), (2., 5.));
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct FiveNumber {
+    /// Estimator of the minimum.
+    min: Min,
+    /// Estimator of the lower quartile.
+    q1: Quantile,
+    /// Estimator of the median.
+    q2: Quantile,
+    /// Estimator of the upper quartile.
+    q3: Quantile,
+    /// Estimator of the maximum.
+    max: Max,
+}
+
+impl FiveNumber {
+    /// Create a new estimator.
+    #[inline]
+    pub const fn new() -> FiveNumber {
+        FiveNumber {
+            min: Min::new(),
+            q1: Quantile::new(0.25),
+            q2: Quantile::new(0.5),
+            q3: Quantile::new(0.75),
+            max: Max::new(),
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.min.add(x);
+        self.q1.add(x);
+        self.q2.add(x);
+        self.q3.add(x);
+        self.max.add(x);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.q2.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.q2.len()
+    }
+
+    /// Estimate the minimum of the population.
+    ///
+    /// Returns infinity for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.min.min()
+    }
+
+    /// Estimate the lower quartile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn lower_quartile(&self) -> f64 {
+        self.q1.quantile()
+    }
+
+    /// Estimate the median of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn median(&self) -> f64 {
+        self.q2.quantile()
+    }
+
+    /// Estimate the upper quartile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn upper_quartile(&self) -> f64 {
+        self.q3.quantile()
+    }
+
+    /// Estimate the maximum of the population.
+    ///
+    /// Returns negative infinity for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.max.max()
+    }
+
+    /// Return the minimum, lower quartile, median, upper quartile and maximum.
+    #[inline]
+    pub fn five_number(&self) -> [f64; 5] {
+        [self.min(), self.lower_quartile(), self.median(), self.upper_quartile(), self.max()]
+    }
+
+    /// Estimate the interquartile range of the population.
+    #[inline]
+    pub fn iqr(&self) -> f64 {
+        self.upper_quartile() - self.lower_quartile()
+    }
+
+    /// Calculate the endpoints of Tukey's whiskers.
+    ///
+    /// These are the quartiles extended by 1.5 times the interquartile range,
+    /// limited to the minimum and maximum. Samples outside of the whiskers
+    /// are usually drawn as outliers.
+    #[inline]
+    pub fn whiskers(&self) -> (f64, f64) {
+        self.whiskers_with_factor(TUKEY_FACTOR)
+    }
+
+    /// Calculate the endpoints of the whiskers extending the quartiles by the
+    /// given multiple of the interquartile range.
+    ///
+    /// The whiskers are limited to the minimum and maximum. Since the samples
+    /// are not stored, the whiskers end at the fences instead of the most
+    /// extreme samples within them.
+    #[inline]
+    pub fn whiskers_with_factor(&self, k: f64) -> (f64, f64) {
+        let iqr = self.iqr();
+        let lower = (self.lower_quartile() - k * iqr).max(self.min());
+        let upper = (self.upper_quartile() + k * iqr).min(self.max());
+        (lower, upper)
+    }
+
+    /// Reset the estimator to an empty sample.
+    #[inline]
+    pub fn reset(&mut self) {
+        *self = FiveNumber::new();
+    }
+}
+
+impl Default for FiveNumber {
+    fn default() -> FiveNumber {
+        FiveNumber::new()
+    }
+}
+
+impl Merge for FiveNumber {
+    /// Merge another sample into this one.
+    ///
+    /// Merging the quartiles loses some accuracy, see [`Quantile`].
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    #[inline]
+    fn merge(&mut self, other: &FiveNumber) {
+        self.min.merge(&other.min);
+        self.q1.merge(&other.q1);
+        self.q2.merge(&other.q2);
+        self.q3.merge(&other.q3);
+        self.max.merge(&other.max);
+    }
+}
+
+impl_from_iterator!(FiveNumber);
+impl_from_iterator_samples!(FiveNumber; f32, i32, u32, i64);
+impl_add_assign!(FiveNumber; f64, merge);
@@ -0,0 +1,40 @@
+//! Formatting of estimates with their uncertainty.
+
+use core::fmt;
+
+use num_traits::Float;
+
+/// Default number of significant figures of the uncertainty.
+const DEFAULT_SIGNIFICANT_FIGURES: usize = 1;
+
+/// Format a value with its uncertainty like `3.21 ± 0.04`.
+///
+/// The uncertainty is rounded to the number of significant figures given by
+/// the precision of the formatter (1 by default), and the value is rounded to
+/// the same decimal place. If the uncertainty is zero or not finite, both are
+/// formatted without rounding.
+pub(crate) fn fmt_with_error(f: &mut fmt::Formatter<'_>, value: f64, error: f64)
+    -> fmt::Result
+{
+    if !(error.is_finite() && error > 0.) {
+        return write!(f, "{} ± {}", value, error);
+    }
+    let figures = f.precision().unwrap_or(DEFAULT_SIGNIFICANT_FIGURES).max(1) as i32;
+    let mut decimals = figures - 1 - error.log10().floor() as i32;
+    // Rounding can carry into the next digit, for instance 0.096 to 0.1.
+    if round_to(error, decimals) >= Float::powi(10f64, figures - decimals) {
+        decimals -= 1;
+    }
+    if decimals >= 0 {
+        let decimals = decimals as usize;
+        write!(f, "{:.*} ± {:.*}", decimals, value, decimals, error)
+    } else {
+        write!(f, "{:.0} ± {:.0}", round_to(value, decimals), round_to(error, decimals))
+    }
+}
+
+/// Round `x` to the given number of decimal places, which may be negative.
+fn round_to(x: f64, decimals: i32) -> f64 {
+    let scale = Float::powi(10f64, decimals);
+    (x * scale).round() / scale
+}
@@ -0,0 +1,148 @@
+use core::borrow::Borrow;
+use core::hash::Hash;
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use super::{Estimate, Merge};
+
+/// Maintain an estimator for each group of a sequence of keyed numbers, like
+/// `GROUP BY` in SQL.
+///
+/// An estimator is created with `Default` when a key is added for the first
+/// time. Groupings of different threads or processes can be merged.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Grouped, Mean};
+///
+/// let g: Grouped<&str, Mean> = [("a", 1.), ("b", 5.), ("a", 3.), ("c", 4.)]
+///     .iter().cloned().collect();
+/// assert_eq!(g.len(), 3);
+/// assert_eq!(g.get("a").unwrap().mean(), 2.);
+/// let top: Vec<&str> = g.top_n(2).into_iter().map(|(&k, _)| k).collect();
+/// assert_eq!(top, ["b", "c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Grouped<K, E> {
+    /// The estimators by key.
+    groups: HashMap<K, E>,
+}
+
+impl<K: Eq + Hash, E> Grouped<K, E> {
+    /// Create a new empty grouping.
+    #[inline]
+    pub fn new() -> Grouped<K, E> {
+        Grouped { groups: HashMap::new() }
+    }
+
+    /// Return the estimator of the given group.
+    #[inline]
+    pub fn get<Q>(&self, key: &Q) -> Option<&E>
+        where K: Borrow<Q>, Q: Eq + Hash + ?Sized
+    {
+        self.groups.get(key)
+    }
+
+    /// Return the number of groups.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// Determine whether there are no groups.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Iterate over the keys and estimators of the groups, in arbitrary
+    /// order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &E)> {
+        self.groups.iter()
+    }
+
+    /// Remove all groups.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.groups.clear();
+    }
+
+    /// Return the `n` groups with the largest values of `f`, in descending
+    /// order.
+    ///
+    /// Groups for which `f` is NaN are skipped. The order of groups with equal
+    /// values is arbitrary.
+    pub fn top_n_by<F>(&self, n: usize, f: F) -> Vec<(&K, &E)>
+        where F: Fn(&E) -> f64
+    {
+        let mut values: Vec<(f64, (&K, &E))> = self.groups.iter()
+            .map(|(k, e)| (f(e), (k, e)))
+            .filter(|(x, _)| !x.is_nan())
+            .collect();
+        values.sort_unstable_by(|a, b| b.0.partial_cmp(&a.0).unwrap());
+        values.into_iter().take(n).map(|(_, group)| group).collect()
+    }
+}
+
+impl<K: Eq + Hash, E: Estimate + Default> Grouped<K, E> {
+    /// Add an observation sampled from the population of the given group.
+    #[inline]
+    pub fn add(&mut self, key: K, x: f64) {
+        self.groups.entry(key).or_default().add(x);
+    }
+
+    /// Return the `n` groups with the largest estimates, in descending order.
+    ///
+    /// For instance, this returns the groups with the largest means for
+    /// `Mean`. See `top_n_by`.
+    #[inline]
+    pub fn top_n(&self, n: usize) -> Vec<(&K, &E)> {
+        self.top_n_by(n, E::estimate)
+    }
+}
+
+impl<K: Eq + Hash, E> Default for Grouped<K, E> {
+    fn default() -> Grouped<K, E> {
+        Grouped::new()
+    }
+}
+
+impl<K: Eq + Hash + Clone, E: Merge + Clone> Merge for Grouped<K, E> {
+    /// Merge another grouping into this one.
+    ///
+    /// The estimators of groups with the same key are merged, the others are
+    /// copied.
+    fn merge(&mut self, other: &Grouped<K, E>) {
+        for (k, e) in &other.groups {
+            match self.groups.get_mut(k) {
+                Some(mine) => mine.merge(e),
+                None => { self.groups.insert(k.clone(), e.clone()); },
+            }
+        }
+    }
+}
+
+impl<K: Eq + Hash, E: Estimate + Default> core::iter::FromIterator<(K, f64)> for Grouped<K, E> {
+    fn from_iter<T>(iter: T) -> Grouped<K, E>
+        where T: IntoIterator<Item=(K, f64)>
+    {
+        let mut g = Grouped::new();
+        g.extend(iter);
+        g
+    }
+}
+
+impl<K: Eq + Hash, E: Estimate + Default> core::iter::Extend<(K, f64)> for Grouped<K, E> {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(K, f64)>
+    {
+        for (k, x) in iter {
+            self.add(k, x);
+        }
+    }
+}
@@ -1,7 +1,105 @@
+#[cfg(feature = "serde1")] use serde::Serialize;
+
+/// Descriptive metadata for the axis of a histogram.
+///
+/// This is picked up by the `Display` implementation of histograms and by
+/// serialization, so that the output is self-describing. All fields are
+/// optional.
+///
+/// Note that the metadata is only serialized, not deserialized, because the
+/// strings are required to be `'static`.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize))]
+pub struct Axis {
+    /// The title of the histogram.
+    #[cfg_attr(feature = "serde1", serde(skip_serializing_if = "Option::is_none"))]
+    pub title: Option<&'static str>,
+    /// The label of the axis, i.e. the name of the binned quantity.
+    #[cfg_attr(feature = "serde1", serde(skip_serializing_if = "Option::is_none"))]
+    pub label: Option<&'static str>,
+    /// The unit of the binned quantity.
+    #[cfg_attr(feature = "serde1", serde(skip_serializing_if = "Option::is_none"))]
+    pub unit: Option<&'static str>,
+}
+
+impl Axis {
+    /// Create axis metadata without any title, label or unit.
+    #[inline]
+    pub const fn new() -> Axis {
+        Axis { title: None, label: None, unit: None }
+    }
+
+    /// Set the title.
+    #[inline]
+    pub fn title(mut self, title: &'static str) -> Axis {
+        self.title = Some(title);
+        self
+    }
+
+    /// Set the axis label.
+    #[inline]
+    pub fn label(mut self, label: &'static str) -> Axis {
+        self.label = Some(label);
+        self
+    }
+
+    /// Set the unit.
+    #[inline]
+    pub fn unit(mut self, unit: &'static str) -> Axis {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Determine whether no metadata is set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.title.is_none() && self.label.is_none() && self.unit.is_none()
+    }
+}
+
+impl ::core::fmt::Display for Axis {
+    /// Format the axis as `title: label [unit]`, leaving out missing parts.
+    fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+        if let Some(title) = self.title {
+            f.write_str(title)?;
+            if self.label.is_some() || self.unit.is_some() {
+                f.write_str(": ")?;
+            }
+        }
+        if let Some(label) = self.label {
+            f.write_str(label)?;
+            if self.unit.is_some() {
+                f.write_str(" ")?;
+            }
+        }
+        if let Some(unit) = self.unit {
+            write!(f, "[{}]", unit)?;
+        }
+        Ok(())
+    }
+}
+
+/// The result of Pearson's chi-square goodness-of-fit test.
+///
+/// See `Histogram::chi_square_test`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChiSquareTest {
+    /// The chi-square test statistic.
+    pub statistic: f64,
+    /// The degrees of freedom of the chi-square distribution.
+    pub degrees_of_freedom: usize,
+    /// The probability of a test statistic at least as large as the observed
+    /// one, assuming the sample was drawn from the tested distribution.
+    ///
+    /// This is `nan` if there are no degrees of freedom.
+    pub p_value: f64,
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! define_histogram_common {
-    ($LEN:expr) => (
+    ($LEN:expr, $T:ty) => (
+        #[allow(unused_imports)]
         use $crate::Histogram as Trait;
 
         /// The number of bins of the histogram.
@@ -18,6 +116,21 @@ macro_rules! define_histogram_common {
             }
         }
 
+        impl ::core::fmt::Display for Histogram {
+            /// Format the histogram as a table of bins, preceded by the axis
+            /// metadata if there is any.
+            fn fmt(&self, formatter: &mut ::core::fmt::Formatter<'_>)
+                -> ::core::fmt::Result {
+                if !self.axis.is_empty() {
+                    writeln!(formatter, "{}", self.axis)?;
+                }
+                for ((a, b), count) in self.iter() {
+                    writeln!(formatter, "[{}, {}): {}", a, b, count)?;
+                }
+                Ok(())
+            }
+        }
+
         impl Histogram {
             /// Construct a histogram with constant bin width.
             #[inline]
@@ -30,7 +143,8 @@ macro_rules! define_histogram_common {
 
                 Self {
                     range,
-                    bin: [0; LEN],
+                    bin: [0 as $T; LEN],
+                    axis: $crate::Axis::new(),
                 }
             }
 
@@ -66,10 +180,23 @@ macro_rules! define_histogram_common {
                 }
                 Ok(Self {
                     range,
-                    bin: [0; LEN],
+                    bin: [0 as $T; LEN],
+                    axis: $crate::Axis::new(),
                 })
             }
 
+            /// Add a sample to the histogram, unless it is not finite or out of
+            /// range.
+            ///
+            /// In contrast to `add`, this reports why the sample was rejected.
+            #[inline]
+            pub fn add_checked(&mut self, x: f64) -> Result<(), $crate::AddError> {
+                if !x.is_finite() {
+                    return Err($crate::AddError::NonFinite);
+                }
+                self.add(x).map_err(|()| $crate::AddError::OutOfRange)
+            }
+
             /// Find the index of the bin corresponding to the given sample.
             ///
             /// Fails if the sample is out of range of the histogram.
@@ -90,16 +217,11 @@ macro_rules! define_histogram_common {
                 }
             }
 
-            /// Add a sample to the histogram.
-            ///
-            /// Fails if the sample is out of range of the histogram.
+            /// Panic if the ranges of the histograms differ.
             #[inline]
-            pub fn add(&mut self, x: f64) -> Result<(), ()> {
-                if let Ok(i) = self.find(x) {
-                    self.bin[i] += 1;
-                    Ok(())
-                } else {
-                    Err(())
+            fn assert_same_ranges(&self, other: &Self) {
+                for (a, b) in self.range.iter().zip(other.range.iter()) {
+                    assert_eq!(a, b, "Both histograms must have the same ranges");
                 }
             }
 
@@ -109,6 +231,12 @@ macro_rules! define_histogram_common {
                 &self.range[..]
             }
 
+            /// Return the bins of the histogram.
+            #[inline]
+            pub fn bins(&self) -> &[$T] {
+                &self.bin[..]
+            }
+
             /// Return an iterator over the bins and corresponding ranges:
             /// `((lower, upper), count)`
             #[inline]
@@ -119,7 +247,22 @@ macro_rules! define_histogram_common {
             /// Reset all bins to zero.
             #[inline]
             pub fn reset(&mut self) {
-                self.bin = [0; LEN];
+                self.bin = [0 as $T; LEN];
+            }
+
+            /// Return the axis metadata of the histogram.
+            #[inline]
+            pub fn axis(&self) -> &$crate::Axis {
+                &self.axis
+            }
+
+            /// Set the axis metadata of the histogram.
+            ///
+            /// It is used for formatting and serializing the histogram, but
+            /// is otherwise ignored.
+            #[inline]
+            pub fn set_axis(&mut self, axis: $crate::Axis) {
+                self.axis = axis;
             }
 
             /// Return the lower range limit.
@@ -141,13 +284,13 @@ macro_rules! define_histogram_common {
 
         /// Iterate over all `(range, count)` pairs in the histogram.
         pub struct IterHistogram<'a> {
-            remaining_bin: &'a [u64],
+            remaining_bin: &'a [$T],
             remaining_range: &'a [f64],
         }
 
         impl<'a> ::core::iter::Iterator for IterHistogram<'a> {
-            type Item = ((f64, f64), u64);
-            fn next(&mut self) -> Option<((f64, f64), u64)> {
+            type Item = ((f64, f64), $T);
+            fn next(&mut self) -> Option<((f64, f64), $T)> {
                 if let Some((&bin, rest)) = self.remaining_bin.split_first() {
                     let left = self.remaining_range[0];
                     let right = self.remaining_range[1];
@@ -160,7 +303,7 @@ macro_rules! define_histogram_common {
         }
 
         impl<'a> ::core::iter::IntoIterator for &'a Histogram {
-            type Item = ((f64, f64), u64);
+            type Item = ((f64, f64), $T);
             type IntoIter = IterHistogram<'a>;
             fn into_iter(self) -> IterHistogram<'a> {
                 IterHistogram {
@@ -171,24 +314,112 @@ macro_rules! define_histogram_common {
         }
 
         impl $crate::Histogram for Histogram {
+            type Count = $T;
+        }
+
+        impl $crate::MemoryUsage for Histogram {
             #[inline]
-            fn bins(&self) -> &[u64] {
-                &self.bin[..]
+            fn memory_usage(&self) -> usize {
+                ::core::mem::size_of::<Self>()
             }
         }
 
         impl<'a> ::core::ops::AddAssign<&'a Self> for Histogram {
             #[inline]
             fn add_assign(&mut self, other: &Self) {
-                for (a, b) in self.range.iter().zip(other.range.iter()) {
-                    assert_eq!(a, b, "Both histograms must have the same ranges");
-                }
+                self.assert_same_ranges(other);
                 for (x, y) in self.bin.iter_mut().zip(other.bin.iter()) {
                     *x += y;
                 }
             }
         }
 
+        impl $crate::Merge for Histogram {
+            fn merge(&mut self, other: &Self) {
+                assert_eq!(self.bin.len(), other.bin.len());
+                self.assert_same_ranges(other);
+                for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
+                    *a += *b;
+                }
+            }
+        }
+    );
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_histogram_counts {
+    () => (
+        $crate::define_histogram_hdr!();
+
+        impl Histogram {
+            /// Add a sample to the histogram.
+            ///
+            /// Fails if the sample is out of range of the histogram.
+            #[inline]
+            pub fn add(&mut self, x: f64) -> Result<(), ()> {
+                if let Ok(i) = self.find(x) {
+                    self.bin[i] += 1;
+                    Ok(())
+                } else {
+                    Err(())
+                }
+            }
+
+            /// Add a sample to the histogram, checking for overflow of the bin
+            /// count.
+            ///
+            /// Fails if the sample is out of range of the histogram or if the
+            /// count of the corresponding bin would overflow. In both cases,
+            /// the histogram is not modified.
+            #[inline]
+            pub fn checked_add(&mut self, x: f64) -> Result<(), ()> {
+                let i = self.find(x)?;
+                self.bin[i] = self.bin[i].checked_add(1).ok_or(())?;
+                Ok(())
+            }
+
+            /// Add a sample to the histogram, saturating the bin count at
+            /// `u64::MAX` instead of overflowing.
+            ///
+            /// Fails if the sample is out of range of the histogram.
+            #[inline]
+            pub fn saturating_add(&mut self, x: f64) -> Result<(), ()> {
+                let i = self.find(x)?;
+                self.bin[i] = self.bin[i].saturating_add(1);
+                Ok(())
+            }
+
+            /// Merge another histogram into this one, checking for overflow of
+            /// the bin counts.
+            ///
+            /// Fails if any of the bin counts would overflow, in which case the
+            /// histogram is not modified. Panics if the ranges of the
+            /// histograms differ.
+            #[inline]
+            pub fn checked_merge(&mut self, other: &Self) -> Result<(), ()> {
+                self.assert_same_ranges(other);
+                let mut bin = self.bin;
+                for (a, b) in bin.iter_mut().zip(other.bin.iter()) {
+                    *a = a.checked_add(*b).ok_or(())?;
+                }
+                self.bin = bin;
+                Ok(())
+            }
+
+            /// Merge another histogram into this one, saturating the bin counts
+            /// at `u64::MAX` instead of overflowing.
+            ///
+            /// Panics if the ranges of the histograms differ.
+            #[inline]
+            pub fn saturating_merge(&mut self, other: &Self) {
+                self.assert_same_ranges(other);
+                for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
+                    *a = a.saturating_add(*b);
+                }
+            }
+        }
+
         impl ::core::ops::MulAssign<u64> for Histogram {
             #[inline]
             fn mul_assign(&mut self, other: u64) {
@@ -197,16 +428,226 @@ macro_rules! define_histogram_common {
                 }
             }
         }
+    );
+}
 
-        impl $crate::Merge for Histogram {
-            fn merge(&mut self, other: &Self) {
-                assert_eq!(self.bin.len(), other.bin.len());
-                for (a, b) in self.range.iter().zip(other.range.iter()) {
-                    assert_eq!(a, b, "Both histograms must have the same ranges");
+#[cfg(feature = "hdrhistogram")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_histogram_hdr {
+    () => (
+        impl<'a> ::core::convert::TryFrom<&'a Histogram>
+            for $crate::hdrhistogram::Histogram<u64>
+        {
+            type Error = ();
+
+            /// Convert the histogram into an auto-resizing HDR histogram with
+            /// 3 significant figures, recording the counts at the bin centers.
+            ///
+            /// Fails if a non-empty bin has a center that cannot be recorded,
+            /// because it is negative or not finite.
+            fn try_from(histogram: &'a Histogram) -> Result<Self, ()> {
+                let mut hdr = Self::new(3).map_err(|_| ())?;
+                for (center, &count) in histogram.centers().zip(histogram.bin.iter()) {
+                    if count == 0 {
+                        continue;
+                    }
+                    if !(center >= 0. && center < u64::MAX as f64) {
+                        return Err(());
+                    }
+                    hdr.record_n(center.round() as u64, count).map_err(|_| ())?;
                 }
-                for (a, b) in self.bin.iter_mut().zip(other.bin.iter()) {
-                    *a += *b;
+                Ok(hdr)
+            }
+        }
+
+        impl<'a> ::core::convert::From<&'a $crate::hdrhistogram::Histogram<u64>>
+            for Histogram
+        {
+            /// Convert an HDR histogram into a histogram with constant bin
+            /// width covering all recorded values.
+            ///
+            /// Every recorded value is assigned to the bin containing the
+            /// middle of its range of equivalent values.
+            fn from(hdr: &'a $crate::hdrhistogram::Histogram<u64>) -> Histogram {
+                if hdr.is_empty() {
+                    return Histogram::with_const_width(0., 1.);
+                }
+                let mut histogram = Histogram::with_const_width(
+                    hdr.min() as f64, hdr.max() as f64 + 1.);
+                for value in hdr.iter_recorded() {
+                    let x = hdr.median_equivalent(value.value_iterated_to()) as f64;
+                    let i = histogram.find(x).unwrap_or(LEN - 1);
+                    histogram.bin[i] += value.count_at_value();
+                }
+                histogram
+            }
+        }
+    );
+}
+
+#[cfg(not(feature = "hdrhistogram"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_histogram_hdr {
+    () => ();
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_histogram_weights {
+    () => (
+        impl Histogram {
+            /// Add a sample with unit weight to the histogram.
+            ///
+            /// Fails if the sample is out of range of the histogram.
+            #[inline]
+            pub fn add(&mut self, x: f64) -> Result<(), ()> {
+                self.add_weighted(x, 1.)
+            }
+
+            /// Add a sample with the given weight to the histogram.
+            ///
+            /// Fails if the sample is out of range of the histogram.
+            #[inline]
+            pub fn add_weighted(&mut self, x: f64, weight: f64) -> Result<(), ()> {
+                let i = self.find(x)?;
+                self.bin[i] += weight;
+                Ok(())
+            }
+        }
+
+        impl<'a> ::core::ops::SubAssign<&'a Self> for Histogram {
+            #[inline]
+            fn sub_assign(&mut self, other: &Self) {
+                self.assert_same_ranges(other);
+                for (x, y) in self.bin.iter_mut().zip(other.bin.iter()) {
+                    *x -= y;
+                }
+            }
+        }
+
+        impl ::core::ops::MulAssign<f64> for Histogram {
+            #[inline]
+            fn mul_assign(&mut self, other: f64) {
+                for x in &mut self.bin[..] {
+                    *x *= other;
+                }
+            }
+        }
+    );
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_histogram_circular {
+    () => (
+        impl Histogram {
+            /// Construct a circular histogram for angles in radians, i.e. with
+            /// a period of `2π`.
+            #[inline]
+            pub fn new() -> Self {
+                Self::with_period(2. * ::core::f64::consts::PI)
+            }
+
+            /// Construct a circular histogram with the given period, covering
+            /// `[0, period)` with bins of constant width.
+            ///
+            /// For example, use a period of 24 for the time of the day in
+            /// hours.
+            #[inline]
+            pub fn with_period(period: f64) -> Self {
+                Self::with_const_width(0., period)
+            }
+
+            /// Return the period of the histogram.
+            #[inline]
+            pub fn period(&self) -> f64 {
+                self.range_max() - self.range_min()
+            }
+
+            /// Wrap a value into the range of the histogram.
+            #[inline]
+            pub fn wrap(&self, x: f64) -> f64 {
+                let start = self.range_min();
+                let wrapped = start + $crate::math::rem_euclid(x - start, self.period());
+                // Because of rounding, `rem_euclid` might return the period.
+                if wrapped < self.range_max() { wrapped } else { start }
+            }
+
+            /// Add a sample to the histogram, wrapping it into the range of the
+            /// histogram.
+            ///
+            /// Fails if the sample is not finite.
+            #[inline]
+            pub fn add(&mut self, x: f64) -> Result<(), ()> {
+                if !x.is_finite() {
+                    return Err(());
+                }
+                let i = self.find(self.wrap(x))?;
+                self.bin[i] += 1;
+                Ok(())
+            }
+
+            /// Calculate the mean resultant vector `(C, S)` of the sample,
+            /// using the bin centers as angles.
+            ///
+            /// Returns `(0, 0)` for an empty sample.
+            pub fn resultant(&self) -> (f64, f64) {
+                let n = self.sum();
+                if n == 0. {
+                    return (0., 0.);
+                }
+                let scale = 2. * ::core::f64::consts::PI / self.period();
+                let start = self.range_min();
+                let (mut c, mut s) = (0., 0.);
+                for (center, count) in self.centers().zip(self.bin.iter()) {
+                    let angle = scale * (center - start);
+                    let count = *count as f64;
+                    c += count * $crate::math::cos(angle);
+                    s += count * $crate::math::sin(angle);
                 }
+                (c / n, s / n)
+            }
+
+            /// Calculate the length of the mean resultant vector, which is
+            /// between 0 (no concentration) and 1 (all samples in one bin).
+            ///
+            /// This includes the correction for grouping the samples into bins
+            /// of constant width.
+            pub fn mean_resultant_length(&self) -> f64 {
+                let (c, s) = self.resultant();
+                let half_width = ::core::f64::consts::PI / (LEN as f64);
+                let correction = half_width / $crate::math::sin(half_width);
+                ($crate::math::sqrt(c * c + s * s) * correction).min(1.)
+            }
+
+            /// Estimate the circular mean of the population, in the units of
+            /// the histogram.
+            ///
+            /// Returns `nan` if the mean resultant vector vanishes, because
+            /// the mean direction is undefined in this case.
+            pub fn circular_mean(&self) -> f64 {
+                let (c, s) = self.resultant();
+                if c == 0. && s == 0. {
+                    return f64::NAN;
+                }
+                let angle = $crate::math::atan2(s, c);
+                self.wrap(self.range_min() + angle * self.period()
+                          / (2. * ::core::f64::consts::PI))
+            }
+
+            /// Estimate the circular variance of the population, which is
+            /// between 0 and 1.
+            #[inline]
+            pub fn circular_variance(&self) -> f64 {
+                1. - self.mean_resultant_length()
+            }
+        }
+
+        impl ::core::default::Default for Histogram {
+            fn default() -> Histogram {
+                Histogram::new()
             }
         }
     );
@@ -216,15 +657,20 @@ macro_rules! define_histogram_common {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! define_histogram_inner {
-    ($name:ident, $LEN:expr) => (
+    ($name:ident, $LEN:expr, $T:ty, $specific:ident) => (
         mod $name {
-            $crate::define_histogram_common!($LEN);
+            $crate::define_histogram_common!($LEN, $T);
+            $crate::$specific!();
 
             use ::serde::{Serialize, Deserialize};
             serde_big_array::big_array! {
                 BigArray; LEN, (LEN + 1),
             }
 
+            fn axis_is_empty(axis: &$crate::Axis) -> bool {
+                axis.is_empty()
+            }
+
             /// A histogram with a number of bins known at compile time.
             #[derive(Clone, Serialize, Deserialize)]
             pub struct Histogram {
@@ -233,7 +679,11 @@ macro_rules! define_histogram_inner {
                 range: [f64; LEN + 1],
                 /// The bins of the histogram.
                 #[serde(with = "BigArray")]
-                bin: [u64; LEN],
+                bin: [$T; LEN],
+                /// The axis metadata.
+                #[serde(default, skip_deserializing,
+                        skip_serializing_if = "axis_is_empty")]
+                axis: $crate::Axis,
             }
         }
     );
@@ -243,9 +693,10 @@ macro_rules! define_histogram_inner {
 #[doc(hidden)]
 #[macro_export]
 macro_rules! define_histogram_inner {
-    ($name:ident, $LEN:expr) => (
+    ($name:ident, $LEN:expr, $T:ty, $specific:ident) => (
         mod $name {
-            $crate::define_histogram_common!($LEN);
+            $crate::define_histogram_common!($LEN, $T);
+            $crate::$specific!();
 
             /// A histogram with a number of bins known at compile time.
             #[derive(Clone)]
@@ -253,7 +704,9 @@ macro_rules! define_histogram_inner {
                 /// The ranges defining the bins of the histogram.
                 range: [f64; LEN + 1],
                 /// The bins of the histogram.
-                bin: [u64; LEN],
+                bin: [$T; LEN],
+                /// The axis metadata.
+                axis: $crate::Axis,
             }
         }
     );
@@ -261,6 +714,9 @@ macro_rules! define_histogram_inner {
 
 /// Define a histogram with a number of bins known at compile time.
 ///
+/// If the `hdrhistogram` feature is enabled, the histogram can be converted
+/// from and into a `hdrhistogram::Histogram<u64>`.
+///
 /// Because macros are not hygenic for items, everything is defined in a private
 /// module with the given name. This includes the `Histogram` struct, the number
 /// of bins `LEN` and the histogram iterator `HistogramIter`.
@@ -282,5 +738,71 @@ macro_rules! define_histogram_inner {
 /// ```
 #[macro_export]
 macro_rules! define_histogram {
-    ($name:ident, $LEN:expr) => ($crate::define_histogram_inner!($name, $LEN););
+    ($name:ident, $LEN:expr) => (
+        $crate::define_histogram_inner!($name, $LEN, u64, define_histogram_counts);
+    );
+}
+
+/// Define a histogram with `f64` bin contents and a number of bins known at
+/// compile time.
+///
+/// This is useful for weighted samples, scaled histograms and differences of
+/// histograms, where the bin contents are not integers. Apart from the type of
+/// the bin contents, the generated histogram works like the one generated by
+/// [`define_histogram`]. Additionally, it provides `add_weighted` and
+/// supports subtraction and scaling by `f64`.
+///
+/// Note that the variances estimated by the [`Histogram`] trait assume the bin
+/// contents to be counts.
+///
+/// [`define_histogram`]: ./macro.define_histogram.html
+/// [`Histogram`]: ./trait.Histogram.html
+///
+///
+/// # Example
+///
+/// ```
+/// use average::{Histogram, define_weighted_histogram};
+///
+/// define_weighted_histogram!(hist, 10);
+/// let mut h = hist::Histogram::with_const_width(0., 100.);
+/// for i in 0..100 {
+///     h.add_weighted(i as f64, 0.5).unwrap();
+/// }
+/// assert_eq!(h.bins(), &[5., 5., 5., 5., 5., 5., 5., 5., 5., 5.]);
+/// ```
+#[macro_export]
+macro_rules! define_weighted_histogram {
+    ($name:ident, $LEN:expr) => (
+        $crate::define_histogram_inner!($name, $LEN, f64, define_histogram_weights);
+    );
+}
+
+/// Define a circular histogram for periodic variables, with a number of bins
+/// known at compile time.
+///
+/// The histogram covers one period with bins of constant width. Samples are
+/// wrapped into the range of the histogram, so angles or the time of the day
+/// can be added directly. Besides the usual histogram methods, it provides
+/// circular statistics estimated from the bins, such as the circular mean.
+///
+///
+/// # Example
+///
+/// ```
+/// use average::{Histogram, define_circular_histogram, assert_almost_eq};
+///
+/// define_circular_histogram!(hours, 24);
+/// let mut h = hours::Histogram::with_period(24.);
+/// for &hour in &[23.5, 0.5, 1.5, 22.5, 24.5] {
+///     h.add(hour).unwrap();
+/// }
+/// assert_eq!(h.bins()[0], 2);
+/// assert_almost_eq!(h.circular_mean(), 0.1, 1e-2);
+/// ```
+#[macro_export]
+macro_rules! define_circular_histogram {
+    ($name:ident, $LEN:expr) => (
+        $crate::define_histogram_inner!($name, $LEN, u64, define_histogram_circular);
+    );
 }
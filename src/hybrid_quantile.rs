@@ -0,0 +1,238 @@
+use std::vec::Vec;
+
+use float_ord::sort as sort_floats;
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Budget, Estimate, Merge, MemoryUsage, Reset, Quantile};
+
+/// The state of a `HybridQuantile`.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+enum State {
+    /// All samples so far.
+    Exact(Vec<f64>),
+    /// The approximate estimator, once there were too many samples.
+    Approximate(Quantile),
+}
+
+/// Estimate the p-quantile of a sequence of numbers ("population"), exactly
+/// for small samples.
+///
+/// The samples are stored until there are more than a given threshold, so
+/// that the quantile of small samples is calculated exactly. Beyond the
+/// threshold, the stored samples are added to a [`Quantile`] and discarded,
+/// so the memory stays bounded.
+///
+/// The exact quantile uses the same convention as [`Quantile`] for less than
+/// 5 samples: if the quantile lies between two samples, their average is
+/// returned.
+///
+/// Requires the `"std"` feature.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{HybridQuantile, Estimate};
+///
+/// let mut q = HybridQuantile::new(0.9, 100);
+/// for i in 1..=10 {
+///     q.add(f64::from(i));
+/// }
+/// assert!(q.is_exact());
+/// assert_eq!(q.quantile(), 9.5);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HybridQuantile {
+    /// The probability of the quantile.
+    p: f64,
+    /// The maximal number of samples stored exactly.
+    threshold: usize,
+    /// The stored samples or the approximate estimator.
+    state: State,
+}
+
+impl HybridQuantile {
+    /// Create a new p-quantile estimator storing up to `threshold` samples
+    /// exactly.
+    ///
+    /// Panics if `p` is not between 0 and 1.
+    pub fn new(p: f64, threshold: usize) -> HybridQuantile {
+        assert!((0. ..=1.).contains(&p));
+        HybridQuantile { p, threshold, state: State::Exact(Vec::new()) }
+    }
+
+    /// Create a new p-quantile estimator with a threshold fulfilling the
+    /// given budget.
+    ///
+    /// An accuracy of `ε` stores `1 / ε²` samples exactly. Returns an error if
+    /// the budget does not allow storing at least one sample. Panics if `p` is
+    /// not between 0 and 1.
+    pub fn with_budget(p: f64, budget: Budget) -> Result<HybridQuantile, ()> {
+        let threshold = budget.capacity(
+            core::mem::size_of::<HybridQuantile>(), core::mem::size_of::<f64>(), 1,
+            |error| 1. / (error * error))?;
+        Ok(HybridQuantile::new(p, threshold))
+    }
+
+    /// Return the value of `p` for this p-quantile.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Return the maximal number of samples stored exactly.
+    #[inline]
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// Determine whether the quantile is calculated exactly, i.e. whether
+    /// the sample size does not exceed the threshold.
+    #[inline]
+    pub fn is_exact(&self) -> bool {
+        match self.state {
+            State::Exact(_) => true,
+            State::Approximate(_) => false,
+        }
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        match self.state {
+            State::Exact(ref samples) => samples.len() as u64,
+            State::Approximate(ref q) => q.len(),
+        }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Estimate the p-quantile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    pub fn quantile(&self) -> f64 {
+        let samples = match self.state {
+            State::Exact(ref samples) => samples,
+            State::Approximate(ref q) => return q.quantile(),
+        };
+        if samples.is_empty() {
+            return 0.;
+        }
+        let mut sorted = samples.clone();
+        sort_floats(&mut sorted);
+        let len = sorted.len();
+        let desired_index = len as f64 * self.p - 1.;
+        let index = desired_index.ceil();
+        if desired_index == index && index >= 0. && (index as usize) < len - 1 {
+            let index = index as usize;
+            // Both samples are equally valid estimates, by convention we take
+            // their average.
+            return 0.5 * sorted[index] + 0.5 * sorted[index + 1];
+        }
+        sorted[(index.max(0.) as usize).min(len - 1)]
+    }
+
+    /// Estimate the p-quantile of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_quantile(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.quantile())
+    }
+
+    /// Switch to the approximate estimator, adding all stored samples.
+    fn make_approximate(&mut self) {
+        if let State::Exact(ref samples) = self.state {
+            let mut q = Quantile::new(self.p);
+            for &x in samples {
+                q.add(x);
+            }
+            self.state = State::Approximate(q);
+        }
+    }
+}
+
+impl Estimate for HybridQuantile {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        match self.state {
+            State::Exact(ref mut samples) if samples.len() < self.threshold => {
+                if samples.len() == samples.capacity() {
+                    // Grow like `push`, but never beyond the threshold.
+                    let additional = samples.len().max(4).min(self.threshold - samples.len());
+                    samples.reserve_exact(additional);
+                }
+                samples.push(x);
+                return;
+            }
+            State::Exact(_) => self.make_approximate(),
+            State::Approximate(_) => {},
+        }
+        if let State::Approximate(ref mut q) = self.state {
+            q.add(x);
+        }
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.quantile()
+    }
+}
+
+impl Reset for HybridQuantile {
+    #[inline]
+    fn reset(&mut self) {
+        self.state = State::Exact(Vec::new());
+    }
+}
+
+impl MemoryUsage for HybridQuantile {
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        let allocated = match self.state {
+            State::Exact(ref samples) => samples.capacity() * core::mem::size_of::<f64>(),
+            State::Approximate(_) => 0,
+        };
+        core::mem::size_of::<HybridQuantile>() + allocated
+    }
+}
+
+impl Merge for HybridQuantile {
+    /// Merge another sample into this one.
+    ///
+    /// If the merged sample does not exceed the threshold, it stays exact.
+    /// Otherwise, merging loses some accuracy, see [`Quantile`].
+    ///
+    /// Panics if the estimators do not estimate the same quantile.
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    fn merge(&mut self, other: &HybridQuantile) {
+        assert_eq!(self.p, other.p, "Both estimators must estimate the same quantile");
+        match other.state {
+            State::Exact(ref samples) => {
+                for &x in samples {
+                    self.add(x);
+                }
+            }
+            State::Approximate(ref other) => {
+                self.make_approximate();
+                if let State::Approximate(ref mut q) = self.state {
+                    q.merge(other);
+                }
+            }
+        }
+    }
+}
+
+impl_add_assign!(HybridQuantile; f64, merge);
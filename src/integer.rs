@@ -0,0 +1,369 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::Merge;
+
+/// Divide an integer by the sample size, rounding only once.
+#[inline]
+fn divide(x: i128, n: u64) -> f64 {
+    let n = i128::from(n);
+    (x / n) as f64 + (x % n) as f64 / n as f64
+}
+
+/// Estimate the arithmetic mean of a sequence of integers ("population").
+///
+/// In contrast to [`Mean`], this accumulates the exact sum of the samples in
+/// an `i128`, so there is no rounding error until the mean is calculated. The
+/// sum cannot overflow for less than 2^64 samples.
+///
+/// [`Mean`]: ./type.Mean.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::IntegerMean;
+///
+/// let a: IntegerMean = (1..6).collect();
+/// assert_eq!(a.sum(), 15);
+/// assert_eq!(a.mean(), 3.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct IntegerMean {
+    /// Sum of the samples.
+    sum: i128,
+    /// Sample size.
+    n: u64,
+}
+
+impl IntegerMean {
+    /// Create a new mean estimator.
+    #[inline]
+    pub const fn new() -> IntegerMean {
+        IntegerMean { sum: 0, n: 0 }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: i64) {
+        self.sum += i128::from(x);
+        self.n += 1;
+    }
+
+    /// Add an observation sampled from the population `count` times.
+    ///
+    /// This is equivalent to calling `add(x)` `count` times, but takes
+    /// constant time, which is useful for ingesting frequency tables.
+    #[inline]
+    pub fn add_n(&mut self, x: i64, count: u64) {
+        self.sum += i128::from(x) * i128::from(count);
+        self.n += count;
+    }
+
+    /// Remove an observation that was previously added.
+    ///
+    /// In contrast to floating-point estimators, this is exact, which makes it
+    /// suitable for sliding windows. Removing from an empty estimator does
+    /// nothing.
+    #[inline]
+    pub fn remove(&mut self, x: i64) {
+        if self.n == 0 {
+            return;
+        }
+        self.sum -= i128::from(x);
+        self.n -= 1;
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Return the exact sum of the samples.
+    #[inline]
+    pub fn sum(&self) -> i128 {
+        self.sum
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        if self.n == 0 {
+            return 0.;
+        }
+        divide(self.sum, self.n)
+    }
+}
+
+impl core::default::Default for IntegerMean {
+    fn default() -> IntegerMean {
+        IntegerMean::new()
+    }
+}
+
+impl Merge for IntegerMean {
+    /// Merge another sample into this one.
+    ///
+    /// In contrast to floating-point estimators, this is exact.
+    #[inline]
+    fn merge(&mut self, other: &IntegerMean) {
+        self.sum += other.sum;
+        self.n += other.n;
+    }
+}
+
+/// Estimate the arithmetic mean and the variance of a sequence of integers
+/// ("population").
+///
+/// In contrast to [`Variance`], this accumulates the exact sum and sum of
+/// squares of the samples in 128-bit integers, so there is no rounding error
+/// until the statistics are calculated. The sum of squares saturates at
+/// `u128::MAX`, which is reached by as few as four samples of `i64::MIN` or by
+/// 2^64 samples with a magnitude of 2^32. From then on, the variance and the
+/// error are NaN. Otherwise, the variance is calculated exactly from the integers unless the
+/// intermediate results do not fit into 128 bits, in which case the
+/// calculation falls back to floating-point arithmetic.
+///
+/// [`Variance`]: ./type.Variance.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::IntegerVariance;
+///
+/// let a: IntegerVariance = (1..6).collect();
+/// assert_eq!(a.mean(), 3.);
+/// assert_eq!(a.sample_variance(), 2.5);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct IntegerVariance {
+    /// Estimator of the mean.
+    avg: IntegerMean,
+    /// Sum of the squares of the samples.
+    sum_sq: u128,
+}
+
+impl IntegerVariance {
+    /// Create a new variance estimator.
+    #[inline]
+    pub const fn new() -> IntegerVariance {
+        IntegerVariance { avg: IntegerMean::new(), sum_sq: 0 }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: i64) {
+        self.avg.add(x);
+        let x = u128::from(x.unsigned_abs());
+        self.sum_sq = self.sum_sq.saturating_add(x * x);
+    }
+
+    /// Add an observation sampled from the population `count` times.
+    ///
+    /// This is equivalent to calling `add(x)` `count` times, but takes
+    /// constant time, which is useful for ingesting frequency tables.
+    #[inline]
+    pub fn add_n(&mut self, x: i64, count: u64) {
+        self.avg.add_n(x, count);
+        let x = u128::from(x.unsigned_abs());
+        self.sum_sq = self.sum_sq.saturating_add((x * x).saturating_mul(u128::from(count)));
+    }
+
+    /// Remove an observation that was previously added.
+    ///
+    /// In contrast to floating-point estimators, this is exact, which makes it
+    /// suitable for sliding windows. Removing from an empty estimator does
+    /// nothing. Removing a sample that was never added may underflow the sum
+    /// of squares. A saturated sum of squares stays saturated.
+    #[inline]
+    pub fn remove(&mut self, x: i64) {
+        if self.is_empty() {
+            return;
+        }
+        self.avg.remove(x);
+        if self.is_saturated() {
+            return;
+        }
+        let x = u128::from(x.unsigned_abs());
+        self.sum_sq -= x * x;
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Return the exact sum of the samples.
+    #[inline]
+    pub fn sum(&self) -> i128 {
+        self.avg.sum()
+    }
+
+    /// Return the exact sum of the squares of the samples.
+    ///
+    /// Returns `u128::MAX` if the sum of squares saturated.
+    #[inline]
+    pub fn sum_sq(&self) -> u128 {
+        self.sum_sq
+    }
+
+    /// Determine whether the sum of squares saturated, so that the variance
+    /// cannot be calculated anymore.
+    #[inline]
+    pub fn is_saturated(&self) -> bool {
+        self.sum_sq == u128::MAX
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Calculate the sum of squared deviations from the mean, times the
+    /// sample size.
+    ///
+    /// Returns NaN if the sum of squares saturated.
+    fn n_sum_2(&self) -> f64 {
+        if self.is_saturated() {
+            return f64::NAN;
+        }
+        // n * sum(x^2) - sum(x)^2 is exact and non-negative, as long as it
+        // can be represented.
+        let n = u128::from(self.len());
+        let sum = self.sum().unsigned_abs();
+        let exact = n.checked_mul(self.sum_sq).and_then(|a| {
+            sum.checked_mul(sum).map(|b| a - b)
+        });
+        match exact {
+            Some(x) => x as f64,
+            None => {
+                let n = self.len() as f64;
+                let sum = self.sum() as f64;
+                (n * self.sum_sq as f64 - sum * sum).max(0.)
+            },
+        }
+    }
+
+    /// Calculate the sample variance.
+    ///
+    /// This is an unbiased estimator of the variance of the population.
+    #[inline]
+    pub fn sample_variance(&self) -> f64 {
+        let n = self.len();
+        if n < 2 {
+            return 0.;
+        }
+        self.n_sum_2() / (n as f64 * (n - 1) as f64)
+    }
+
+    /// Calculate the population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        let n = self.len();
+        if n < 2 {
+            return 0.;
+        }
+        self.n_sum_2() / (n as f64 * n as f64)
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        let n = self.len();
+        if n == 0 {
+            return 0.;
+        }
+        crate::math::sqrt(self.sample_variance() / n as f64)
+    }
+}
+
+impl core::default::Default for IntegerVariance {
+    fn default() -> IntegerVariance {
+        IntegerVariance::new()
+    }
+}
+
+impl Merge for IntegerVariance {
+    /// Merge another sample into this one.
+    ///
+    /// In contrast to floating-point estimators, this is exact.
+    #[inline]
+    fn merge(&mut self, other: &IntegerVariance) {
+        self.avg.merge(&other.avg);
+        self.sum_sq = self.sum_sq.saturating_add(other.sum_sq);
+    }
+}
+
+macro_rules! impl_integer_from_iterator {
+    ( $name:ident ) => {
+        impl core::iter::FromIterator<i64> for $name {
+            fn from_iter<T>(iter: T) -> $name
+                where T: IntoIterator<Item=i64>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl core::iter::Extend<i64> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=i64>
+            {
+                for i in iter {
+                    self.add(i);
+                }
+            }
+        }
+
+        impl<'a> core::iter::FromIterator<&'a i64> for $name {
+            fn from_iter<T>(iter: T) -> $name
+                where T: IntoIterator<Item=&'a i64>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a> core::iter::Extend<&'a i64> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a i64>
+            {
+                for &i in iter {
+                    self.add(i);
+                }
+            }
+        }
+    };
+}
+
+impl_integer_from_iterator!(IntegerMean);
+impl_integer_from_iterator!(IntegerVariance);
+impl_add_assign!(IntegerMean; i64, merge);
+impl_add_assign!(IntegerVariance; i64, merge);
@@ -0,0 +1,130 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge, Reset, Quantile};
+
+/// Estimate the interquartile range of a sequence of numbers ("population").
+///
+/// The interquartile range is the difference between the upper and the lower
+/// quartile. It is a robust measure of the spread, which is not affected by a
+/// few outliers. The quartiles are estimated with [`Quantile`], so they are
+/// approximate for large samples.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Iqr;
+///
+/// let a: Iqr = [2., 3., 4., 5.].iter().collect();
+/// assert_eq!((a.lower_quartile(), a.upper_quartile()), (2.5, 4.5));
+/// assert_eq!(a.iqr(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Iqr {
+    /// Estimator of the lower quartile.
+    q1: Quantile,
+    /// Estimator of the upper quartile.
+    q3: Quantile,
+}
+
+impl Iqr {
+    /// Create a new interquartile range estimator.
+    #[inline]
+    pub const fn new() -> Iqr {
+        Iqr { q1: Quantile::new(0.25), q3: Quantile::new(0.75) }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.q1.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.q1.len()
+    }
+
+    /// Estimate the lower quartile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn lower_quartile(&self) -> f64 {
+        self.q1.quantile()
+    }
+
+    /// Estimate the upper quartile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn upper_quartile(&self) -> f64 {
+        self.q3.quantile()
+    }
+
+    /// Estimate the interquartile range of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn iqr(&self) -> f64 {
+        self.upper_quartile() - self.lower_quartile()
+    }
+
+    /// Estimate the interquartile range of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_iqr(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.iqr())
+    }
+}
+
+impl core::default::Default for Iqr {
+    fn default() -> Iqr {
+        Iqr::new()
+    }
+}
+
+impl Estimate for Iqr {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.q1.add(x);
+        self.q3.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.iqr()
+    }
+}
+
+impl Reset for Iqr {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Iqr::new();
+    }
+}
+
+impl Merge for Iqr {
+    /// Merge another sample into this one.
+    ///
+    /// Merging the quartiles loses some accuracy, see [`Quantile`].
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    #[inline]
+    fn merge(&mut self, other: &Iqr) {
+        self.q1.merge(&other.q1);
+        self.q3.merge(&other.q3);
+    }
+}
+
+impl_from_iterator!(Iqr);
+impl_from_iterator_samples!(Iqr; f32, i32, u32, i64);
+impl_add_assign!(Iqr; f64, merge);
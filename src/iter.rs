@@ -0,0 +1,315 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge, IntoSample, Mean, Variance, Min, Max};
+
+/// Estimate the mean, variance, minimum and maximum of a sequence of numbers
+/// ("population") in one pass.
+///
+/// This is what [`StatsIteratorExt::stats`] returns.
+///
+/// [`StatsIteratorExt::stats`]: ./trait.StatsIteratorExt.html#method.stats
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Stats;
+///
+/// let s: Stats = (1..6).collect();
+/// assert_eq!(s.mean(), 3.);
+/// assert_eq!(s.sample_variance(), 2.5);
+/// assert_eq!((s.min(), s.max()), (1., 5.));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Stats {
+    /// Estimator of the mean and variance.
+    avg: Variance,
+    /// Estimator of the minimum.
+    min: Min,
+    /// Estimator of the maximum.
+    max: Max,
+}
+
+impl Stats {
+    /// Create a new estimator.
+    #[inline]
+    pub const fn new() -> Stats {
+        Stats { avg: Variance::EMPTY, min: Min::new(), max: Max::new() }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64) {
+        self.avg.add(x);
+        self.min.add(x);
+        self.max.add(x);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> f64 {
+        self.avg.mean()
+    }
+
+    /// Calculate the sample variance.
+    ///
+    /// This is an unbiased estimator of the variance of the population.
+    #[inline]
+    pub fn sample_variance(&self) -> f64 {
+        self.avg.sample_variance()
+    }
+
+    /// Calculate the population variance of the sample.
+    ///
+    /// This is a biased estimator of the variance of the population.
+    #[inline]
+    pub fn population_variance(&self) -> f64 {
+        self.avg.population_variance()
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    #[inline]
+    pub fn error(&self) -> f64 {
+        self.avg.error()
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// This is the sample standard deviation divided by the mean. Returns 0
+    /// for less than two samples.
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> f64 {
+        self.avg.coefficient_of_variation()
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// Returns `None` if there are less than two samples or the mean is zero.
+    #[inline]
+    pub fn try_coefficient_of_variation(&self) -> Option<f64> {
+        self.avg.try_coefficient_of_variation()
+    }
+
+    /// Estimate the coefficient of variation of the population, correcting
+    /// the bias for small samples.
+    ///
+    /// See `Variance::corrected_coefficient_of_variation`.
+    #[inline]
+    pub fn corrected_coefficient_of_variation(&self) -> f64 {
+        self.avg.corrected_coefficient_of_variation()
+    }
+
+    /// Estimate the minimum of the population.
+    ///
+    /// Returns infinity for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.min.min()
+    }
+
+    /// Estimate the maximum of the population.
+    ///
+    /// Returns negative infinity for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.max.max()
+    }
+}
+
+impl Merge for Stats {
+    #[inline]
+    fn merge(&mut self, other: &Stats) {
+        self.avg.merge(&other.avg);
+        self.min.merge(&other.min);
+        self.max.merge(&other.max);
+    }
+}
+
+impl_from_iterator!(Stats);
+impl_from_iterator_samples!(Stats; f32, i32, u32, i64);
+impl_add_assign!(Stats; f64, merge);
+
+/// Calculate statistics of an iterator without naming an estimator type.
+///
+/// This is implemented for all iterators over samples that can be converted to
+/// `f64`, including integers, `Duration` and references.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::StatsIteratorExt;
+///
+/// let data = [1., 2., 3., 4., 5.];
+/// assert_eq!(data.iter().mean(), 3.);
+/// assert_eq!(data.iter().variance(), 2.5);
+/// assert_eq!(data.iter().min_max(), (1., 5.));
+/// ```
+pub trait StatsIteratorExt: Iterator + Sized {
+    /// Calculate the mean of the samples.
+    ///
+    /// Returns 0 for an empty iterator.
+    #[inline]
+    fn mean(self) -> f64 where Self::Item: IntoSample {
+        let mut a = Mean::new();
+        for x in self {
+            a.add(x.into_sample());
+        }
+        a.mean()
+    }
+
+    /// Calculate the sample variance of the samples.
+    ///
+    /// This is an unbiased estimator of the variance of the population. Returns
+    /// 0 for less than two samples.
+    #[inline]
+    fn variance(self) -> f64 where Self::Item: IntoSample {
+        let mut a = Variance::new();
+        for x in self {
+            a.add(x.into_sample());
+        }
+        a.sample_variance()
+    }
+
+    /// Calculate the minimum and the maximum of the samples.
+    ///
+    /// Returns `(inf, -inf)` for an empty iterator.
+    #[inline]
+    fn min_max(self) -> (f64, f64) where Self::Item: IntoSample {
+        let mut min = Min::new();
+        let mut max = Max::new();
+        for x in self {
+            let x = x.into_sample();
+            min.add(x);
+            max.add(x);
+        }
+        (min.min(), max.max())
+    }
+
+    /// Calculate the mean, variance, minimum and maximum of the samples in one
+    /// pass.
+    #[inline]
+    fn stats(self) -> Stats where Self::Item: IntoSample {
+        let mut s = Stats::new();
+        for x in self {
+            s.add(x.into_sample());
+        }
+        s
+    }
+
+    /// Standardize the samples as `(x - mean) / std`, using the mean and the
+    /// sample standard deviation of a previously fitted estimator.
+    ///
+    /// If the standard deviation is zero, only the mean is subtracted.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{StatsIteratorExt, Variance};
+    ///
+    /// let fitted: Variance = [1., 3., 5.].iter().collect();
+    /// let z: Vec<f64> = [3., 7.].iter().whiten(&fitted).collect();
+    /// assert_eq!(z, [0., 2.]);
+    /// ```
+    #[inline]
+    fn whiten(self, fitted: &Variance) -> Whiten<Self> where Self::Item: IntoSample {
+        Whiten::new(self, fitted.clone(), false)
+    }
+
+    /// Standardize the samples as `(x - mean) / std`, adapting the mean and
+    /// the standard deviation while iterating.
+    ///
+    /// Each sample is standardized using the statistics of `initial` and of
+    /// the previous samples, before it is added to them. `initial` can be an
+    /// empty estimator or one fitted to a training set. If the standard
+    /// deviation is zero, in particular for the first two samples of an empty
+    /// estimator, only the mean is subtracted.
+    #[inline]
+    fn whiten_adaptive(self, initial: Variance) -> Whiten<Self> where Self::Item: IntoSample {
+        Whiten::new(self, initial, true)
+    }
+}
+
+impl<I: Iterator> StatsIteratorExt for I {}
+
+/// Standardize the samples of an iterator.
+///
+/// This is what [`StatsIteratorExt::whiten`] and
+/// [`StatsIteratorExt::whiten_adaptive`] return.
+///
+/// [`StatsIteratorExt::whiten`]: ./trait.StatsIteratorExt.html#method.whiten
+/// [`StatsIteratorExt::whiten_adaptive`]: ./trait.StatsIteratorExt.html#method.whiten_adaptive
+#[derive(Debug, Clone)]
+pub struct Whiten<I> {
+    /// The iterator over the samples.
+    iter: I,
+    /// Estimator of the mean and the variance used for standardizing.
+    avg: Variance,
+    /// The mean used for standardizing.
+    mean: f64,
+    /// The standard deviation used for standardizing, or 1 if it is zero.
+    scale: f64,
+    /// Whether the samples are added to the estimator.
+    adaptive: bool,
+}
+
+impl<I> Whiten<I> {
+    /// Create a new standardizing iterator.
+    fn new(iter: I, avg: Variance, adaptive: bool) -> Whiten<I> {
+        let mut w = Whiten { iter, avg, mean: 0., scale: 1., adaptive };
+        w.update();
+        w
+    }
+
+    /// Update the mean and the scale from the estimator.
+    #[inline]
+    fn update(&mut self) {
+        self.mean = self.avg.mean();
+        let std = crate::math::sqrt(self.avg.sample_variance());
+        self.scale = if std > 0. { std } else { 1. };
+    }
+
+    /// Return the estimator of the mean and the variance used for
+    /// standardizing the next sample.
+    #[inline]
+    pub fn estimator(&self) -> &Variance {
+        &self.avg
+    }
+}
+
+impl<I> Iterator for Whiten<I> where I: Iterator, I::Item: IntoSample {
+    type Item = f64;
+
+    #[inline]
+    fn next(&mut self) -> Option<f64> {
+        let x = self.iter.next()?.into_sample();
+        let z = (x - self.mean) / self.scale;
+        if self.adaptive {
+            self.avg.add(x);
+            self.update();
+        }
+        Some(z)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
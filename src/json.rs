@@ -0,0 +1,144 @@
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+use num_traits::AsPrimitive;
+use serde_json::{Map, Value, json};
+
+use super::{Describe, FiveNumber, Histogram, LatencyReport, Stats};
+
+/// Convert a number to a JSON value.
+///
+/// Integral numbers are written without a fractional part, like they are
+/// formatted by Rust, and numbers that are not finite are written as `null`.
+fn number(x: f64) -> Value {
+    // Integers up to this magnitude are exactly representable.
+    const MAX_EXACT: f64 = 9_007_199_254_740_992.;
+    if x.fract() == 0. && x.abs() <= MAX_EXACT {
+        Value::from(x as i64)
+    } else {
+        Value::from(x)
+    }
+}
+
+impl Describe {
+    /// Format the summary as a JSON object.
+    ///
+    /// The object has the keys `count`, `mean`, `error` (of the mean), `std`,
+    /// `min`, `max`, `skewness` and `kurtosis` (the sample skewness and
+    /// excess kurtosis), and `quantiles`, an array of objects with the
+    /// probability `p` and the estimated quantile `value`. Numbers that are
+    /// not finite are written as `null`.
+    ///
+    /// Requires the `"serde_json"` feature.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Describe;
+    ///
+    /// use serde_json::{Value, json};
+    ///
+    /// let d: Describe = [2., 4.].iter().collect();
+    /// let v: Value = serde_json::from_str(&d.to_json()).unwrap();
+    /// assert_eq!(v["count"], 2);
+    /// assert_eq!(v["mean"], 3);
+    /// assert_eq!(v["quantiles"][1], json!({ "p": 0.5, "value": 3 }));
+    /// ```
+    pub fn to_json(&self) -> String {
+        let quantiles: Vec<Value> = self.quantiles().iter()
+            .map(|&(p, q)| json!({ "p": number(p), "value": number(q) }))
+            .collect();
+        json!({
+            "count": self.len(),
+            "mean": number(self.mean()),
+            "error": number(self.error()),
+            "std": number(self.std()),
+            "min": number(self.min()),
+            "quantiles": quantiles,
+            "max": number(self.max()),
+            "skewness": number(self.sample_skewness()),
+            "kurtosis": number(self.sample_kurtosis()),
+        }).to_string()
+    }
+}
+
+impl Stats {
+    /// Format the statistics as a JSON object.
+    ///
+    /// The object has the keys `count`, `mean`, `error` (of the mean),
+    /// `variance` (the sample variance), `min` and `max`. Numbers that are
+    /// not finite are written as `null`.
+    ///
+    /// Requires the `"serde_json"` feature.
+    pub fn to_json(&self) -> String {
+        json!({
+            "count": self.len(),
+            "mean": number(self.mean()),
+            "error": number(self.error()),
+            "variance": number(self.sample_variance()),
+            "min": number(self.min()),
+            "max": number(self.max()),
+        }).to_string()
+    }
+}
+
+impl FiveNumber {
+    /// Format the five-number summary as a JSON object.
+    ///
+    /// The object has the keys `count`, `min`, `lower_quartile`, `median`,
+    /// `upper_quartile` and `max`, and `whiskers`, an array of the lower and
+    /// upper end of Tukey's whiskers. Numbers that are not finite are written
+    /// as `null`.
+    ///
+    /// Requires the `"serde_json"` feature.
+    pub fn to_json(&self) -> String {
+        let (lower, upper) = self.whiskers();
+        json!({
+            "count": self.len(),
+            "min": number(self.min()),
+            "lower_quartile": number(self.lower_quartile()),
+            "median": number(self.median()),
+            "upper_quartile": number(self.upper_quartile()),
+            "max": number(self.max()),
+            "whiskers": [number(lower), number(upper)],
+        }).to_string()
+    }
+}
+
+impl LatencyReport {
+    /// Format the report as a JSON object.
+    ///
+    /// The keys are `count`, the labels of the percentiles (like `p99.9`),
+    /// `max` and, if given, `unit`. Numbers that are not finite are written
+    /// as `null`.
+    ///
+    /// Requires the `"serde_json"` feature.
+    pub fn to_json(&self) -> String {
+        let mut object = Map::new();
+        object.insert("count".to_string(), number(self.count));
+        for &(label, x) in &self.values() {
+            object.insert(label.to_string(), number(x));
+        }
+        if let Some(unit) = self.unit {
+            object.insert("unit".to_string(), Value::from(unit));
+        }
+        Value::Object(object).to_string()
+    }
+}
+
+/// Format a histogram as a JSON object.
+///
+/// See `Histogram::to_json`.
+pub(crate) fn histogram_to_json<H>(histogram: &H) -> String
+    where H: Histogram + ?Sized, for<'a> &'a H: IntoIterator<Item = ((f64, f64), H::Count)>
+{
+    let bins: Vec<Value> = histogram.into_iter()
+        .map(|((lower, upper), count)| json!({
+            "lower": number(lower),
+            "upper": number(upper),
+            "count": number(count.as_()),
+        }))
+        .collect();
+    json!({ "sum": number(histogram.sum()), "bins": bins }).to_string()
+}
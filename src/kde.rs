@@ -0,0 +1,224 @@
+use std::vec::Vec;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Budget, Merge, MemoryUsage, Variance};
+use crate::math::{exp, sqrt};
+
+/// A Gaussian component of the mixture of a `KernelDensity`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Component {
+    /// The number of samples in the component.
+    weight: f64,
+    /// The mean of the samples in the component.
+    mean: f64,
+    /// The population variance of the samples in the component.
+    variance: f64,
+}
+
+impl Component {
+    /// Combine two components, preserving their total weight, mean and
+    /// variance.
+    fn combine(a: Component, b: Component) -> Component {
+        let weight = a.weight + b.weight;
+        let mean = (a.weight * a.mean + b.weight * b.mean) / weight;
+        let (da, db) = (a.mean - mean, b.mean - mean);
+        let variance = (a.weight * (a.variance + da * da)
+                        + b.weight * (b.variance + db * db)) / weight;
+        Component { weight, mean, variance }
+    }
+
+    /// The increase of the sum of squared deviations from the means when
+    /// combining two components (Ward's criterion).
+    fn cost(a: &Component, b: &Component) -> f64 {
+        let d = a.mean - b.mean;
+        a.weight * b.weight / (a.weight + b.weight) * d * d
+    }
+}
+
+/// Estimate the probability density of a sequence of numbers ("population")
+/// with a Gaussian kernel density estimate.
+///
+/// Unlike a classic kernel density estimate, this does not store all samples.
+/// Each sample is added as a component of a mixture of normal distributions.
+/// If there are more components than the given maximum, the two neighboring
+/// components whose combination changes the mixture least are combined into
+/// one with the same weight, mean and variance. Every component is smoothed
+/// by the kernel, whose bandwidth is chosen by Silverman's rule of thumb.
+///
+/// The memory is bounded by the maximal number of components. The more
+/// components, the more details of the density are preserved.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::KernelDensity;
+///
+/// let mut a = KernelDensity::new(32);
+/// for i in 0..1000 {
+///     a.add(f64::from(i % 10));
+/// }
+/// assert!(a.pdf(4.5) > a.pdf(20.));
+/// assert_eq!(a.pdf(1e3), 0.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct KernelDensity {
+    /// The components of the mixture, sorted by their means.
+    components: Vec<Component>,
+    /// The maximal number of components.
+    max_components: usize,
+    /// Estimator of the mean and the variance of all samples.
+    avg: Variance,
+}
+
+impl KernelDensity {
+    /// Create a new density estimator using at most `max_components`
+    /// components.
+    ///
+    /// Panics if `max_components` is zero.
+    pub fn new(max_components: usize) -> KernelDensity {
+        assert!(max_components > 0, "at least one component is required");
+        KernelDensity {
+            components: Vec::with_capacity(max_components + 1),
+            max_components,
+            avg: Variance::new(),
+        }
+    }
+
+    /// Create a new density estimator with a number of components fulfilling
+    /// the given budget.
+    ///
+    /// An accuracy of `ε` uses `1 / ε` components, so that details of about
+    /// `ε` times the range of the samples are preserved. Returns an error if
+    /// the budget does not allow at least one component.
+    pub fn with_budget(budget: Budget) -> Result<KernelDensity, ()> {
+        // One more component is stored temporarily before compressing.
+        let capacity = budget.capacity(
+            core::mem::size_of::<KernelDensity>(), core::mem::size_of::<Component>(), 2,
+            |error| 1. / error + 1.)?;
+        Ok(KernelDensity::new(capacity - 1))
+    }
+
+    /// Return the maximal number of components.
+    #[inline]
+    pub fn max_components(&self) -> usize {
+        self.max_components
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Add an observation sampled from the population.
+    pub fn add(&mut self, x: f64) {
+        self.avg.add(x);
+        self.insert(Component { weight: 1., mean: x, variance: 0. });
+        if self.components.len() > self.max_components {
+            self.compress();
+        }
+    }
+
+    /// Insert a component, keeping the components sorted.
+    fn insert(&mut self, c: Component) {
+        let i = self.components.partition_point(|other| other.mean < c.mean);
+        self.components.insert(i, c);
+    }
+
+    /// Combine neighboring components until there are not more than the
+    /// maximal number of them.
+    fn compress(&mut self) {
+        while self.components.len() > self.max_components {
+            let mut best = 0;
+            let mut best_cost = f64::INFINITY;
+            for (i, pair) in self.components.windows(2).enumerate() {
+                let cost = Component::cost(&pair[0], &pair[1]);
+                if cost < best_cost {
+                    best = i;
+                    best_cost = cost;
+                }
+            }
+            let next = self.components.remove(best + 1);
+            self.components[best] = Component::combine(self.components[best], next);
+        }
+    }
+
+    /// Return the bandwidth of the Gaussian kernel.
+    ///
+    /// This is Silverman's rule of thumb `1.06 σ n^(-1/5)`, where `σ` is the
+    /// sample standard deviation. Returns 0 for less than two samples.
+    pub fn bandwidth(&self) -> f64 {
+        let n = self.len() as f64;
+        1.06 * sqrt(self.avg.sample_variance()) * crate::math::powf(n, -0.2)
+    }
+
+    /// Estimate the probability density of the population at `x`.
+    ///
+    /// Returns 0 for an empty sample. If the bandwidth is zero, the density
+    /// is infinite at the samples and zero everywhere else.
+    pub fn pdf(&self, x: f64) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        let h = self.bandwidth();
+        let total = self.len() as f64;
+        let mut density = 0.;
+        for c in &self.components {
+            let variance = c.variance + h * h;
+            let d = x - c.mean;
+            if variance == 0. {
+                if d == 0. {
+                    return f64::INFINITY;
+                }
+                continue;
+            }
+            density += c.weight / total * exp(-0.5 * d * d / variance)
+                / sqrt(2. * core::f64::consts::PI * variance);
+        }
+        density
+    }
+
+    /// Reset the estimator to an empty sample.
+    pub fn reset(&mut self) {
+        self.components.clear();
+        self.avg = Variance::new();
+    }
+}
+
+impl Merge for KernelDensity {
+    /// Merge another sample into this one.
+    ///
+    /// The components of both estimators are combined until there are not
+    /// more than the maximal number of components of `self`.
+    fn merge(&mut self, other: &KernelDensity) {
+        self.avg.merge(&other.avg);
+        for &c in &other.components {
+            self.insert(c);
+        }
+        self.compress();
+        // Release the memory needed temporarily for the components of both.
+        self.components.shrink_to(self.max_components + 1);
+    }
+}
+
+impl MemoryUsage for KernelDensity {
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of::<KernelDensity>()
+            + self.components.capacity() * core::mem::size_of::<Component>()
+    }
+}
+
+impl_add_assign!(KernelDensity; f64, merge);
@@ -0,0 +1,211 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Budget, Merge, MemoryUsage};
+use crate::reservoir::Reservoir;
+
+/// The seed used by `KendallTau::new`.
+const DEFAULT_SEED: u64 = 0x6b65_6e64_616c_6c21;
+
+/// Estimated numbers of pairs of samples, classified by their concordance.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct PairCounts {
+    /// Pairs ordered the same way in both variables.
+    concordant: f64,
+    /// Pairs ordered differently in both variables.
+    discordant: f64,
+    /// Pairs tied only in the first variable.
+    ties_x: f64,
+    /// Pairs tied only in the second variable.
+    ties_y: f64,
+}
+
+impl PairCounts {
+    /// Classify the pair of `a` and `b`.
+    #[inline]
+    fn add(&mut self, a: (f64, f64), b: (f64, f64)) {
+        let (dx, dy) = (a.0 - b.0, a.1 - b.1);
+        match (dx == 0., dy == 0.) {
+            (true, true) => {},
+            (true, false) => self.ties_x += 1.,
+            (false, true) => self.ties_y += 1.,
+            (false, false) if (dx > 0.) == (dy > 0.) => self.concordant += 1.,
+            (false, false) => self.discordant += 1.,
+        }
+    }
+
+    /// Add the counts of `other`, multiplied by `weight`.
+    #[inline]
+    fn add_weighted(&mut self, other: &PairCounts, weight: f64) {
+        self.concordant += weight * other.concordant;
+        self.discordant += weight * other.discordant;
+        self.ties_x += weight * other.ties_x;
+        self.ties_y += weight * other.ties_y;
+    }
+}
+
+/// Estimate Kendall's rank correlation coefficient of a sequence of pairs of
+/// numbers ("population").
+///
+/// Kendall's tau compares the order of all pairs of samples in both
+/// variables. It is a robust measure of their concordance, which does not
+/// assume a linear relation. Ties are accounted for like in the tau-b
+/// variant.
+///
+/// Comparing all pairs would require storing all samples. Instead, a random
+/// sample of bounded size is kept in a reservoir, and each new pair is only
+/// compared to the pairs in the reservoir. As long as the number of samples
+/// does not exceed the capacity of the reservoir, the result is exact.
+/// Pairs containing NaN are ignored.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::KendallTau;
+///
+/// let mut a = KendallTau::new(100);
+/// for &(x, y) in &[(1., 1.), (2., 3.), (3., 2.), (4., 4.)] {
+///     a.add(x, y);
+/// }
+/// assert_eq!(a.tau(), 4. / 6.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct KendallTau {
+    /// Random sample of the pairs added so far.
+    reservoir: Reservoir<(f64, f64)>,
+    /// Estimated numbers of all pairs of samples.
+    counts: PairCounts,
+}
+
+impl KendallTau {
+    /// Create a new estimator comparing each sample to at most `capacity`
+    /// previous samples.
+    ///
+    /// The reservoir is sampled deterministically. Panics if `capacity` is
+    /// zero.
+    #[inline]
+    pub fn new(capacity: usize) -> KendallTau {
+        KendallTau::with_seed(capacity, DEFAULT_SEED)
+    }
+
+    /// Create a new estimator comparing each sample to at most `capacity`
+    /// previous samples, which are sampled using the given seed.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_seed(capacity: usize, seed: u64) -> KendallTau {
+        KendallTau {
+            reservoir: Reservoir::new(capacity, seed),
+            counts: PairCounts::default(),
+        }
+    }
+
+    /// Create a new estimator with a capacity fulfilling the given budget.
+    ///
+    /// An accuracy of `ε` keeps `1 / ε²` samples. Returns an error if the
+    /// budget does not allow keeping at least one sample.
+    pub fn with_budget(budget: Budget) -> Result<KendallTau, ()> {
+        let capacity = budget.capacity(
+            core::mem::size_of::<KendallTau>(), core::mem::size_of::<(f64, f64)>(), 1,
+            |error| 1. / (error * error))?;
+        Ok(KendallTau::new(capacity))
+    }
+
+    /// Return the maximal number of samples kept in the reservoir.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.reservoir.capacity()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.reservoir.seen()
+    }
+
+    /// Add an observation sampled from the population.
+    pub fn add(&mut self, x: f64, y: f64) {
+        if x.is_nan() || y.is_nan() {
+            return;
+        }
+        let sampled = self.reservoir.items();
+        if !sampled.is_empty() {
+            let mut counts = PairCounts::default();
+            for &other in sampled {
+                counts.add((x, y), other);
+            }
+            // Each sampled pair stands for the same number of previous ones.
+            let weight = self.len() as f64 / sampled.len() as f64;
+            self.counts.add_weighted(&counts, weight);
+        }
+        self.reservoir.add((x, y));
+    }
+
+    /// Estimate Kendall's tau-b of the population.
+    ///
+    /// Returns 0 if all pairs are tied in one of the variables, in particular
+    /// if there are less than two samples.
+    #[inline]
+    pub fn tau(&self) -> f64 {
+        self.try_tau().unwrap_or(0.)
+    }
+
+    /// Estimate Kendall's tau-b of the population.
+    ///
+    /// Returns `None` if all pairs are tied in one of the variables, in
+    /// particular if there are less than two samples.
+    pub fn try_tau(&self) -> Option<f64> {
+        let c = &self.counts;
+        let untied = c.concordant + c.discordant;
+        let denominator = crate::math::sqrt((untied + c.ties_x) * (untied + c.ties_y));
+        if denominator == 0. {
+            return None;
+        }
+        Some((c.concordant - c.discordant) / denominator)
+    }
+
+    /// Reset the estimator to an empty sample, keeping its capacity.
+    pub fn reset(&mut self) {
+        self.reservoir.clear();
+        self.counts = PairCounts::default();
+    }
+}
+
+impl MemoryUsage for KendallTau {
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of::<KendallTau>() + self.reservoir.allocated()
+    }
+}
+
+impl Merge for KendallTau {
+    /// Merge another sample into this one.
+    ///
+    /// The pairs of samples from different estimators are estimated by
+    /// comparing their reservoirs. The capacity of `self` is kept.
+    fn merge(&mut self, other: &KendallTau) {
+        let (mine, theirs) = (self.reservoir.items(), other.reservoir.items());
+        if !mine.is_empty() && !theirs.is_empty() {
+            let mut counts = PairCounts::default();
+            for &a in mine {
+                for &b in theirs {
+                    counts.add(a, b);
+                }
+            }
+            let weight = (self.len() as f64 / mine.len() as f64)
+                * (other.len() as f64 / theirs.len() as f64);
+            self.counts.add_weighted(&counts, weight);
+        }
+        self.counts.add_weighted(&other.counts, 1.);
+        self.reservoir.merge(&other.reservoir);
+    }
+}
@@ -0,0 +1,190 @@
+use std::vec::Vec;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Budget, Merge, MemoryUsage};
+use crate::reservoir::Reservoir;
+use crate::special::kolmogorov_sf;
+
+/// The seed used by `TwoSampleKs::new`.
+const DEFAULT_SEED: u64 = 0x6b6f_6c6d_6f67_6f72;
+
+/// The result of the two-sample Kolmogorov-Smirnov test.
+///
+/// See `TwoSampleKs::test`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KsTest {
+    /// The maximal distance between the empirical distribution functions of
+    /// both samples.
+    pub statistic: f64,
+    /// The probability of a statistic at least as large as the observed one,
+    /// assuming both samples were drawn from the same distribution.
+    ///
+    /// This uses the asymptotic Kolmogorov distribution, so it is
+    /// approximate for small samples.
+    pub p_value: f64,
+}
+
+/// Test whether two sequences of numbers were drawn from the same
+/// distribution, using the two-sample Kolmogorov-Smirnov test.
+///
+/// A random sample of bounded size is kept in a reservoir for each sequence,
+/// so that the streams can be arbitrarily long. The test compares the
+/// empirical distribution functions of the reservoirs, so it is exact as long
+/// as the number of samples does not exceed the capacity. NaN is ignored.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::TwoSampleKs;
+///
+/// let mut a = TwoSampleKs::new(1000);
+/// for i in 0..1000 {
+///     a.add_first(f64::from(i % 100));
+///     a.add_second(f64::from(i % 100) + 50.);
+/// }
+/// let test = a.test().unwrap();
+/// assert_eq!(test.statistic, 0.5);
+/// assert!(test.p_value < 1e-6);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TwoSampleKs {
+    /// Random sample of the first sequence.
+    first: Reservoir<f64>,
+    /// Random sample of the second sequence.
+    second: Reservoir<f64>,
+}
+
+impl TwoSampleKs {
+    /// Create a new test keeping at most `capacity` samples of each sequence.
+    ///
+    /// The reservoirs are sampled deterministically. Panics if `capacity` is
+    /// zero.
+    #[inline]
+    pub fn new(capacity: usize) -> TwoSampleKs {
+        TwoSampleKs::with_seed(capacity, DEFAULT_SEED)
+    }
+
+    /// Create a new test keeping at most `capacity` samples of each sequence,
+    /// which are sampled using the given seed.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_seed(capacity: usize, seed: u64) -> TwoSampleKs {
+        TwoSampleKs {
+            first: Reservoir::new(capacity, seed),
+            second: Reservoir::new(capacity, seed.wrapping_add(1)),
+        }
+    }
+
+    /// Create a new test with a capacity fulfilling the given budget.
+    ///
+    /// An accuracy of `ε` keeps `1 / ε²` samples of each sequence. Returns an
+    /// error if the budget does not allow keeping at least one sample of each
+    /// sequence. Note that the test temporarily allocates sorted copies of
+    /// the samples, which are not included in the budget.
+    pub fn with_budget(budget: Budget) -> Result<TwoSampleKs, ()> {
+        let capacity = budget.capacity(
+            core::mem::size_of::<TwoSampleKs>(), 2 * core::mem::size_of::<f64>(), 1,
+            |error| 1. / (error * error))?;
+        Ok(TwoSampleKs::new(capacity))
+    }
+
+    /// Return the maximal number of samples kept for each sequence.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.first.capacity()
+    }
+
+    /// Return the number of samples of the first sequence.
+    #[inline]
+    pub fn len_first(&self) -> u64 {
+        self.first.seen()
+    }
+
+    /// Return the number of samples of the second sequence.
+    #[inline]
+    pub fn len_second(&self) -> u64 {
+        self.second.seen()
+    }
+
+    /// Add a sample of the first sequence.
+    #[inline]
+    pub fn add_first(&mut self, x: f64) {
+        if !x.is_nan() {
+            self.first.add(x);
+        }
+    }
+
+    /// Add a sample of the second sequence.
+    #[inline]
+    pub fn add_second(&mut self, x: f64) {
+        if !x.is_nan() {
+            self.second.add(x);
+        }
+    }
+
+    /// Calculate the test statistic and the p-value.
+    ///
+    /// The p-value is calculated for the sizes of the reservoirs. Returns
+    /// `None` if one of the sequences is empty.
+    pub fn test(&self) -> Option<KsTest> {
+        let a = sorted(self.first.items());
+        let b = sorted(self.second.items());
+        if a.is_empty() || b.is_empty() {
+            return None;
+        }
+        let (n, m) = (a.len() as f64, b.len() as f64);
+        let (mut i, mut j) = (0, 0);
+        let mut statistic: f64 = 0.;
+        while i < a.len() && j < b.len() {
+            let x = a[i].min(b[j]);
+            while i < a.len() && a[i] <= x {
+                i += 1;
+            }
+            while j < b.len() && b[j] <= x {
+                j += 1;
+            }
+            statistic = statistic.max((i as f64 / n - j as f64 / m).abs());
+        }
+        // See Numerical Recipes, section 14.3.
+        let effective = crate::math::sqrt(n * m / (n + m));
+        let lambda = (effective + 0.12 + 0.11 / effective) * statistic;
+        let p_value = kolmogorov_sf(lambda).clamp(0., 1.);
+        Some(KsTest { statistic, p_value })
+    }
+
+    /// Reset the test to empty sequences, keeping its capacity.
+    pub fn reset(&mut self) {
+        self.first.clear();
+        self.second.clear();
+    }
+}
+
+/// Return a sorted copy of the samples.
+fn sorted(samples: &[f64]) -> Vec<f64> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap());
+    sorted
+}
+
+impl MemoryUsage for TwoSampleKs {
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of::<TwoSampleKs>() + self.first.allocated() + self.second.allocated()
+    }
+}
+
+impl Merge for TwoSampleKs {
+    /// Merge another sample into this one.
+    ///
+    /// The reservoirs are merged sequence by sequence. The capacity of `self`
+    /// is kept.
+    fn merge(&mut self, other: &TwoSampleKs) {
+        self.first.merge(&other.first);
+        self.second.merge(&other.second);
+    }
+}
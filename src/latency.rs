@@ -0,0 +1,146 @@
+use core::fmt;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::Histogram;
+
+/// The width of the labels in the text report.
+const LABEL_WIDTH: usize = 6;
+
+/// A report of the standard percentiles of a latency distribution.
+///
+/// The report is created from a histogram of durations, and reports the
+/// 50th, 75th, 90th, 99th and 99.9th percentile and the maximum. All values
+/// are in the unit of the histogram, which can be given for the output.
+///
+/// Formatting with `{}` prints one percentile per line. The precision of the
+/// formatter, if given, is used for all values but the count. With the
+/// `"serde_json"` feature, `to_json()` returns the report as a JSON object.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{LatencyReport, define_histogram};
+///
+/// define_histogram!(hist, 100);
+/// let mut h = hist::Histogram::with_const_width(0., 100.);
+/// for i in 0..1000 {
+///     h.add(f64::from(i % 100)).unwrap();
+/// }
+/// let report = LatencyReport::from_histogram(&h).unit("ms");
+/// assert_eq!(report.p90, 90.);
+/// assert_eq!(format!("{:.1}", report), "\
+/// count 1000
+/// p50   50.0 ms
+/// p75   75.0 ms
+/// p90   90.0 ms
+/// p99   99.0 ms
+/// p99.9 99.9 ms
+/// max   100.0 ms
+/// ");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LatencyReport {
+    /// The number of samples, or their total weight.
+    pub count: f64,
+    /// The median.
+    pub p50: f64,
+    /// The 75th percentile.
+    pub p75: f64,
+    /// The 90th percentile.
+    pub p90: f64,
+    /// The 99th percentile.
+    pub p99: f64,
+    /// The 99.9th percentile.
+    pub p999: f64,
+    /// The maximum.
+    pub max: f64,
+    /// The unit of the values.
+    #[cfg_attr(feature = "serde1", serde(skip_serializing_if = "Option::is_none"))]
+    #[cfg_attr(feature = "serde1", serde(skip_deserializing))]
+    pub unit: Option<&'static str>,
+}
+
+impl LatencyReport {
+    /// Create a report from the percentiles of a histogram.
+    ///
+    /// The percentiles are estimated by `value_at_percentile`, so they are
+    /// interpolated within the bins. The maximum is the upper edge of the
+    /// last non-empty bin. All values are 0 for an empty histogram.
+    pub fn from_histogram<H>(histogram: &H) -> LatencyReport
+        where H: Histogram, for<'a> &'a H: IntoIterator<Item = ((f64, f64), H::Count)>
+    {
+        let p = |p| histogram.value_at_percentile(p);
+        LatencyReport {
+            count: histogram.sum(),
+            p50: p(50.),
+            p75: p(75.),
+            p90: p(90.),
+            p99: p(99.),
+            p999: p(99.9),
+            max: p(100.),
+            unit: None,
+        }
+    }
+
+    /// Set the unit of the values.
+    #[inline]
+    pub fn unit(mut self, unit: &'static str) -> LatencyReport {
+        self.unit = Some(unit);
+        self
+    }
+
+    /// Return the labels and values of the reported percentiles, followed by
+    /// the maximum.
+    pub(crate) fn values(&self) -> [(&'static str, f64); 6] {
+        [
+            ("p50", self.p50),
+            ("p75", self.p75),
+            ("p90", self.p90),
+            ("p99", self.p99),
+            ("p99.9", self.p999),
+            ("max", self.max),
+        ]
+    }
+}
+
+#[cfg(feature = "hdrhistogram")]
+impl<'a> From<&'a hdrhistogram::Histogram<u64>> for LatencyReport {
+    /// Create a report from the percentiles of an HDR histogram.
+    ///
+    /// The percentiles are the highest values equivalent to the recorded
+    /// values at the percentiles.
+    fn from(hdr: &'a hdrhistogram::Histogram<u64>) -> LatencyReport {
+        let p = |p| hdr.value_at_percentile(p) as f64;
+        LatencyReport {
+            count: hdr.len() as f64,
+            p50: p(50.),
+            p75: p(75.),
+            p90: p(90.),
+            p99: p(99.),
+            p999: p(99.9),
+            max: hdr.max() as f64,
+            unit: None,
+        }
+    }
+}
+
+impl fmt::Display for LatencyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<width$}{}", "count", self.count, width = LABEL_WIDTH)?;
+        for &(label, x) in &self.values() {
+            write!(f, "{:<width$}", label, width = LABEL_WIDTH)?;
+            match f.precision() {
+                Some(p) => write!(f, "{:.*}", p, x)?,
+                None => write!(f, "{}", x)?,
+            }
+            match self.unit {
+                Some(unit) => writeln!(f, " {}", unit)?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
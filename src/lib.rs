@@ -3,34 +3,171 @@
 //!
 //! 1. If necessary, build your custom estimator using [`concatenate`] or
 //!    [`define_moments`].
-//! 2. Initialize the estimator of your choice with `new()`.
+//! 2. Initialize the estimator of your choice with `new()`. For most
+//!    estimators, this is a `const fn`, so they can be placed in statics. For
+//!    the generic [`Mean`] and [`Variance`], use `Mean::EMPTY` instead.
 //! 3. Add some subset (called "sample") of the sequence of numbers (called
 //!    "population") for which you want to estimate the statistic, using `add()`
-//!    or `collect()`.
+//!    or `collect()`. `a += x` is a shorthand for `a.add(x)`.
 //! 4. Calculate the statistic with `mean()` or similar.
 //!
+//! Most statistics return 0 for an empty sample. If you need to distinguish
+//! this case, use the `try_` variants like `try_mean()`, which return `None`
+//! instead.
+//!
+//! Adding a NaN usually makes all following estimates NaN. To reject
+//! non-finite samples instead, use `add_checked()`, or wrap the estimator in
+//! [`WithNanPolicy`] to skip or reject them. To flag samples that are far
+//! from the previous ones while streaming, use [`OutlierDetector`].
+//!
+//! To transform or select the samples before they are added, wrap the
+//! estimator with `map()` or `filter()`, like `Mean::new().map(f64::ln)`.
+//! [`Scaled`] applies an affine transformation to the samples and reports the
+//! statistics of the original ones, which helps with poorly scaled data.
+//!
 //! You can run several estimators in parallel and merge them into one with
-//! `merge()`.
+//! `merge()`, or with the shorthand `a += &b`. Estimators behind trait objects
+//! can be merged with [`MergeDyn`]. Merging the mean and the moments
+//! (including the estimators defined by [`define_moments`]) is exactly
+//! commutative, so `a.merge(&b)` gives bit-identical results to
+//! `b.merge(&a)`. To combine many partial estimators, [`merge_reduce`] merges
+//! them via a balanced tree.
+//! To quickly process a large slice, [`from_slice_parallel`] splits it into
+//! chunks, which are estimated by separate threads and merged. Huge binary
+//! datasets of raw `f64` values can be streamed from any reader into an
+//! estimator with [`read_samples`] or [`from_reader`].
 //!
 //! Everything is calculated iteratively in a single pass using constant memory,
 //! so the sequence of numbers can be an iterator. The used algorithms try to
 //! avoid numerical instabilities.
 //!
+//! The crate is `no_std` and does not require an allocator. The floating-point
+//! math uses the standard library by default (the `"std"` feature). On targets
+//! without it, disable the default features and enable `"libm"` instead.
+//!
+//! The estimators only use the basic arithmetic operations and the square
+//! root, which are correctly rounded by IEEE 754 and are not fused or
+//! reordered by the compiler. If you need bit-identical results on all
+//! platforms and optimization levels for the same sequence of samples, for
+//! instance for reproducible research, enable the `"deterministic"` feature.
+//! This also calculates the remaining functions, like the exponential used by
+//! the decaying estimators, with the `libm` crate, and makes collecting from
+//! parallel iterators independent of the scheduling of the threads. Note that
+//! adding the same samples in a different way, like with `add_slice()` instead
+//! of `add()` or by merging partial estimators, can still give slightly
+//! different results. Targets without SSE2 or equivalent floating-point
+//! hardware, like `i586`, are not covered.
+//!
+//! If you want to reduce the rounding errors of the updates and merges of the
+//! moments, include `"fma"` in your list of features. This uses fused
+//! multiply-add, which is fast on most modern CPUs, but slow on CPUs without
+//! hardware support, where it is emulated. This helps in particular when
+//! merging estimators of samples with a large common offset. You can compare
+//! the speed with `cargo bench --features fma`.
+//!
 //! If you want [Serde](https://github.com/serde-rs/serde) support,
-//! include `"serde1"` in your list of features.
+//! include `"serde1"` in your list of features. This covers all estimators and
+//! histograms, including the types defined by the macros, so that partial
+//! aggregates can be sent to another process and merged there.
+//!
+//! For durable checkpoints without Serde, the simple estimators provide
+//! `to_bytes()` and `from_bytes()`, using a stable, versioned little-endian
+//! layout that earlier and later versions of this crate can read.
 //!
 //! Note that deserializing does not currently check for all invalid inputs.
 //! For example, if you deserialize a corrupted [`Variance`] it may return
 //! a negative value for variance, even though that is mathematically impossible.
 //! In a future minor release some of these checks may be added.
 //!
+//! If you want to archive estimators with the zero-copy serialization
+//! framework [`rkyv`](https://github.com/rkyv/rkyv), include `"rkyv"` in your
+//! list of features. This does not cover the types defined by the macros, like
+//! histograms. The archived estimators have a fixed size, so large collections
+//! of them can be memory-mapped and deserialized individually without
+//! allocating.
+//!
+//! If you want to convert histograms from and into the HDR histograms of the
+//! [`hdrhistogram`](https://github.com/HdrHistogram/HdrHistogram_rust) crate,
+//! include `"hdrhistogram"` in your list of features.
+//!
+//! If you want to average [`rust_decimal`](https://github.com/paupino/rust-decimal)
+//! numbers without binary floating-point rounding (see `DecimalMean`), include
+//! `"rust_decimal"` in your list of features.
+//!
+//! If you want to average complex numbers of the
+//! [`num-complex`](https://github.com/rust-num/num-complex) crate (see
+//! `ComplexMean` and `ComplexVariance`), include `"num-complex"` in your list
+//! of features.
+//!
+//! If you want to collect estimators from parallel iterators of the
+//! [`rayon`](https://github.com/rayon-rs/rayon) crate, or to merge estimators in
+//! parallel (see `par_merge`), include `"rayon"` in your list of features.
+//! Collecting is supported by the mergeable estimators of single samples that
+//! do not require parameters; the others, like histograms, can be merged via
+//! `par_merge`.
+//!
+//! If you want to build estimators from asynchronous streams (see
+//! `from_stream` and `StatsStreamExt`), include `"futures"` in your list of
+//! features.
+//!
+//! If you want to aggregate the histograms of the
+//! [`metrics`](https://github.com/metrics-rs/metrics) crate into estimators
+//! (see `MetricsRecorder`), include `"metrics"` in your list of features.
+//!
+//! If you want to export estimators and histograms as
+//! [OpenTelemetry](https://opentelemetry.io) metrics (see `ToOtlp`), include
+//! `"opentelemetry"` in your list of features.
+//!
+//! If you want to generate samples from a normal distribution with the
+//! estimated mean and variance (see `Variance::to_normal`) or from the
+//! distributions fitted by the [`fit`] module, using the
+//! [`rand_distr`](https://github.com/rust-random/rand) crate, include
+//! `"rand_distr"` in your list of features.
+//!
+//! If you want to use the estimators with the
+//! [`statrs`](https://github.com/statrs-dev/statrs) crate, include `"statrs"`
+//! in your list of features. This implements its `Distribution`, `Min` and
+//! `Max` traits for the estimators and converts them and the distributions
+//! fitted by the [`fit`] module into its distributions.
+//!
+//! If you want to calculate statistics along an axis of the arrays of the
+//! [`ndarray`](https://github.com/rust-ndarray/ndarray) crate (see
+//! `StatsArrayExt`), include `"ndarray"` in your list of features.
+//!
+//! If you want to estimate the mean and the covariance matrix of the vectors
+//! of the [`nalgebra`](https://nalgebra.org) crate (see `VectorMean` and
+//! `VectorCovariance`), include `"nalgebra"` in your list of features.
+//!
+//! If you want to calculate statistics of the series of the
+//! [Polars](https://pola.rs) dataframe library (see `from_series` and
+//! `StatsSeriesExt`), include `"polars"` in your list of features.
+//!
+//! If you want to describe the numeric columns of CSV files (see
+//! `describe_csv`), include `"csv"` in your list of features.
+//!
+//! If you want to export summaries, histograms and latency reports as JSON
+//! for dashboards and scripts (see `Describe::to_json`, `Stats::to_json`,
+//! `FiveNumber::to_json`, `Histogram::to_json` and `LatencyReport::to_json`),
+//! include `"serde_json"` in your list of features.
+//!
+//! If you want to add large slices of samples faster using SIMD (see
+//! `Mean::add_slice_simd`), include `"wide"` in your list of features.
+//!
+//! If you want to derive `Estimate`, `Merge` and `Reset` for your own structs
+//! of estimators, include `"derive"` in your list of features.
+//!
+//! If you want to compare estimators within tolerances using the
+//! [`approx`](https://github.com/brendanzab/approx) crate, include `"approx"`
+//! in your list of features. This compares the sample sizes exactly and the
+//! statistics within the tolerances.
+//!
 //!
 //! ### Example
 //!
 //! ```
 //! use average::{MeanWithError, Estimate};
 //!
-//! let mut a: MeanWithError = (1..6).map(f64::from).collect();
+//! let mut a: MeanWithError = (1..6).collect();
 //! a.add(42.);
 //! println!("The mean is {} ± {}.", a.mean(), a.error());
 //! ```
@@ -44,8 +181,30 @@
 //! * Variance ([`Variance`]), skewness ([`Skewness`]) and kurtosis
 //!   ([`Kurtosis`]).
 //! * Arbitrary higher moments ([`define_moments`]).
-//! * Quantiles ([`Quantile`]).
-//! * Minimum ([`Min`]) and maximum ([`Max`]).
+//! * Quantiles ([`Quantile`]) and weighted quantiles ([`WeightedQuantile`]).
+//!   [`HybridQuantile`] calculates the quantiles of small samples exactly.
+//! * Interquartile range ([`Iqr`]).
+//! * Minimum ([`Min`]) and maximum ([`Max`]), and the range and midrange
+//!   ([`Range`]).
+//! * Simple linear regression of pairs of samples ([`LinearRegression`]),
+//!   including the coefficient of determination and the standard errors of
+//!   the coefficients. [`HuberRegression`] and the Theil-Sen estimator
+//!   ([`TheilSen`]) are robust to outliers.
+//! * Kendall's rank correlation of pairs of samples ([`KendallTau`]).
+//! * Two-sample Kolmogorov-Smirnov test of two sequences ([`TwoSampleKs`]).
+//!
+//! The size of the approximate estimators among these, which keep a bounded
+//! sample or number of components, can be chosen by a memory or accuracy
+//! [`Budget`]. Their actual memory usage is reported by [`MemoryUsage`], as is
+//! the fixed size of [`Quantile`], [`WeightedQuantile`] and the histograms.
+//!
+//! The mean and variance estimators are also available for other
+//! floating-point types than `f64`, for instance to accumulate in `f32` on
+//! memory-constrained targets ([`GenericMean`], [`GenericVariance`]). Their
+//! sample size can be counted in a smaller integer type like `u32`, which
+//! saturates instead of overflowing (see [`Counter`]). For
+//! integer samples, [`IntegerMean`] and [`IntegerVariance`] accumulate exact
+//! sums and only round when calculating the statistics.
 //!
 //!
 //! ## Estimating several statistics at once
@@ -57,6 +216,53 @@
 //! should be no overhead.
 //!
 //! You can avoid the boilerplate code by using the [`concatenate`] macro.
+//! If the set of estimators is only known at runtime, [`FanOut`] feeds every
+//! sample to a tuple or vector of estimators. For application metrics,
+//! [`StatsRegistry`] manages estimators by name and merges whole registries.
+//! To maintain an estimator for each group of keyed samples, like `GROUP BY`
+//! in SQL, use [`Grouped`]. [`Columns`] maintains an estimator for each
+//! column of tabular data, ingesting one row at a time.
+//! To add samples concurrently from many threads without a mutex, use
+//! [`AtomicStats`]. [`ShardedEstimate`] does the same for any mergeable
+//! estimator by keeping one shard per thread.
+//! Alternatively, with the `"derive"` feature, `#[derive(Estimate, Merge,
+//! Reset)]` implements the traits for your struct by forwarding to all
+//! fields. The field providing the value of `estimate()` is annotated with
+//! `#[estimate]`:
+//!
+//! ```
+//! # #[cfg(feature = "derive")] {
+//! use average::{Estimate, Merge, Min, Max};
+//!
+//! #[derive(Default, Estimate, Merge)]
+//! struct Range {
+//!     min: Min,
+//!     #[estimate]
+//!     max: Max,
+//! }
+//!
+//! let mut a = Range::default();
+//! a.add(1.);
+//! a.add(3.);
+//! assert_eq!((a.min.min(), a.estimate()), (1., 3.));
+//! # }
+//! ```
+//!
+//! For one-off calculations on an iterator, the extension trait
+//! [`StatsIteratorExt`] provides `mean()`, `variance()`, `min_max()` and
+//! `stats()` without naming an estimator type. The latter returns [`Stats`],
+//! which estimates the mean, variance, minimum and maximum at once.
+//! To standardize the samples of an iterator for preprocessing, use
+//! `whiten()` or `whiten_adaptive()`.
+//! [`Describe`] additionally estimates the quartiles and prints a summary like
+//! `describe()` in pandas. For box plots, [`FiveNumber`] estimates the
+//! minimum, quartiles and maximum along with the endpoints of the whiskers.
+//! The [`fit`] module estimates the parameters of common distributions from
+//! the moments, with standard errors.
+//! To compare the means of several groups, [`anova`] performs the one-way
+//! analysis of variance of their [`Variance`] estimators, and
+//! [`pooled_variance`] estimates the variance common to them. The effect
+//! size of the difference of two means is estimated by [`cohens_d`].
 //!
 //! Note that calculating moments requires calculating the lower moments, so you
 //! only need to include the highest moment in your struct.
@@ -67,43 +273,225 @@
 //! The [`define_histogram`] macro can be used to define a histogram struct that
 //! uses constant memory. See [`Histogram10`] (defined using
 //! `define_histogram!(..., 10)`) and the extension trait [`Histogram`]
-//! for the methods available to the generated struct.
+//! for the methods available to the generated struct. Histograms can be
+//! annotated with a title, an axis label and a unit via [`Axis`].
+//!
+//! For weighted samples or scaled histograms, [`define_weighted_histogram`]
+//! defines a histogram with `f64` bin contents (see [`WeightedHistogram10`]).
+//! Periodic variables such as angles can be binned with
+//! [`define_circular_histogram`]. The [`Histogram`] trait only requires
+//! iterating over the bins, so it can also be implemented for sparse or
+//! streaming histograms of your own. For a smooth estimate of the density,
+//! use [`KernelDensity`], which approximates a kernel density estimate with a
+//! bounded number of components.
+//!
+//! [`LatencyReport`] summarizes a histogram of durations by the percentiles
+//! commonly reported for the latency of services.
+//!
 //!
+//! ## Sliding windows
 //!
-//! [`Mean`]: ./struct.Mean.html
+//! The [`define_rolling_stats`] macro defines an estimator of the mean and
+//! variance of the most recent samples, using a ring buffer of constant size
+//! (see [`RollingStats10`]). Alternatively, [`DecayingMean`] and
+//! [`DecayingVariance`] weight the samples by an exponential decay with a
+//! given half-life in samples or in time, using constant memory.
+//!
+//! To aggregate metrics per time interval, [`define_time_buckets`] defines a
+//! container of estimators for the most recent intervals, which can be merged
+//! into views over the last `k` intervals.
+//!
+//!
+//! [`Mean`]: ./type.Mean.html
 //! [`MeanWithError`]: ./type.MeanWithError.html
 //! [`WeightedMean`]: ./struct.WeightedMean.html
 //! [`WeightedMeanWithError`]: ./struct.WeightedMeanWithError.html
-//! [`Variance`]: ./struct.Variance.html
+//! [`Variance`]: ./type.Variance.html
+//! [`GenericMean`]: ./struct.GenericMean.html
+//! [`GenericVariance`]: ./struct.GenericVariance.html
+//! [`Counter`]: ./trait.Counter.html
+//! [`IntegerMean`]: ./struct.IntegerMean.html
+//! [`IntegerVariance`]: ./struct.IntegerVariance.html
 //! [`Skewness`]: ./struct.Skewness.html
 //! [`Kurtosis`]: ./struct.Kurtosis.html
 //! [`Quantile`]: ./struct.Quantile.html
+//! [`WeightedQuantile`]: ./struct.WeightedQuantile.html
+//! [`HybridQuantile`]: ./struct.HybridQuantile.html
+//! [`Iqr`]: ./struct.Iqr.html
 //! [`Min`]: ./struct.Min.html
 //! [`Max`]: ./struct.Max.html
+//! [`Range`]: ./struct.Range.html
 //! [`concatenate`]: ./macro.concatenate.html
+//! [`StatsIteratorExt`]: ./trait.StatsIteratorExt.html
+//! [`Stats`]: ./struct.Stats.html
+//! [`Describe`]: ./struct.Describe.html
+//! [`FiveNumber`]: ./struct.FiveNumber.html
+//! [`fit`]: ./fit/index.html
+//! [`merge_reduce`]: ./fn.merge_reduce.html
+//! [`from_slice_parallel`]: ./fn.from_slice_parallel.html
+//! [`read_samples`]: ./fn.read_samples.html
+//! [`from_reader`]: ./fn.from_reader.html
 //! [`define_moments`]: ./macro.define_moments.html
 //! [`define_histogram`]: ./macro.define_histogram.html
 //! [`Histogram10`]: ./struct.Histogram10.html
+//! [`define_weighted_histogram`]: ./macro.define_weighted_histogram.html
+//! [`WeightedHistogram10`]: ./struct.WeightedHistogram10.html
+//! [`define_circular_histogram`]: ./macro.define_circular_histogram.html
 //! [`Histogram`]: ./trait.Histogram.html
+//! [`anova`]: ./fn.anova.html
+//! [`pooled_variance`]: ./fn.pooled_variance.html
+//! [`cohens_d`]: ./fn.cohens_d.html
+//! [`HuberRegression`]: ./struct.HuberRegression.html
+//! [`KendallTau`]: ./struct.KendallTau.html
+//! [`KernelDensity`]: ./struct.KernelDensity.html
+//! [`Budget`]: ./enum.Budget.html
+//! [`MemoryUsage`]: ./trait.MemoryUsage.html
+//! [`LatencyReport`]: ./struct.LatencyReport.html
+//! [`LinearRegression`]: ./struct.LinearRegression.html
+//! [`MergeDyn`]: ./trait.MergeDyn.html
+//! [`WithNanPolicy`]: ./struct.WithNanPolicy.html
+//! [`OutlierDetector`]: ./struct.OutlierDetector.html
+//! [`TheilSen`]: ./struct.TheilSen.html
+//! [`TwoSampleKs`]: ./struct.TwoSampleKs.html
+//! [`Scaled`]: ./struct.Scaled.html
+//! [`FanOut`]: ./struct.FanOut.html
+//! [`StatsRegistry`]: ./struct.StatsRegistry.html
+//! [`Grouped`]: ./struct.Grouped.html
+//! [`Columns`]: ./struct.Columns.html
+//! [`AtomicStats`]: ./struct.AtomicStats.html
+//! [`ShardedEstimate`]: ./struct.ShardedEstimate.html
+//! [`Axis`]: ./struct.Axis.html
+//! [`define_rolling_stats`]: ./macro.define_rolling_stats.html
+//! [`RollingStats10`]: ./struct.RollingStats10.html
+//! [`DecayingMean`]: ./struct.DecayingMean.html
+//! [`DecayingVariance`]: ./struct.DecayingVariance.html
+//! [`define_time_buckets`]: ./macro.define_time_buckets.html
 
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp))]
+#![allow(clippy::float_cmp, clippy::result_unit_err)]
 
 #![no_std]
 
+#[cfg(feature = "std")] extern crate std;
+
+#[cfg(not(any(feature = "std", feature = "libm")))]
+compile_error!("either the `std` or the `libm` feature is required for the floating-point math");
+
 #[macro_use] mod macros;
 #[macro_use] mod moments;
 mod weighted_mean;
 mod minmax;
 mod quantile;
+mod weighted_quantile;
+#[cfg(feature = "std")] mod hybrid_quantile;
+#[cfg(feature = "std")] mod kde;
+#[cfg(feature = "std")] mod reservoir;
+mod budget;
+#[cfg(feature = "std")] mod kendall;
+#[cfg(feature = "std")] mod theil_sen;
+#[cfg(feature = "std")] mod ks;
+mod integer;
+mod decaying;
+mod policy;
+mod outlier;
+mod adaptor;
+mod fan_out;
+#[cfg(feature = "std")] mod registry;
+#[cfg(feature = "std")] mod grouped;
+#[cfg(feature = "std")] mod columns;
+#[cfg(target_has_atomic = "64")] mod atomic;
+#[cfg(feature = "std")] mod sharded;
+#[cfg(feature = "std")] mod chunked;
+#[cfg(feature = "std")] mod binary;
+mod iter;
+mod describe;
+mod five_number;
+mod latency;
+mod iqr;
+mod compare;
+mod regression;
+pub mod fit;
+#[cfg(feature = "rust_decimal")] mod decimal;
+#[cfg(feature = "num-complex")] mod complex;
+#[cfg(feature = "rayon")] mod parallel;
+#[cfg(feature = "futures")] mod stream;
+#[cfg(feature = "metrics")] mod recorder;
+#[cfg(feature = "opentelemetry")] mod otlp;
+#[cfg(feature = "rand_distr")] mod distr;
+#[cfg(feature = "statrs")] mod statistics;
+#[cfg(feature = "ndarray")] mod array;
+#[cfg(feature = "nalgebra")] mod vector;
+#[cfg(feature = "polars")] mod series;
+#[cfg(feature = "csv")] mod profile;
+#[cfg(feature = "serde_json")] mod json;
+#[cfg(feature = "approx")] mod approx_eq;
 mod traits;
+mod reduce;
+mod counter;
+mod special;
+#[doc(hidden)] pub mod math;
+mod format;
+mod snapshot;
 #[macro_use] mod histogram;
+#[macro_use] mod rolling;
+#[macro_use] mod time_buckets;
 
-pub use crate::moments::{Mean, Variance, Skewness, Kurtosis, MeanWithError};
+pub use crate::moments::{Mean, Variance, Skewness, Kurtosis, MeanWithError,
+                         GenericMean, GenericVariance};
 pub use crate::weighted_mean::{WeightedMean, WeightedMeanWithError};
-pub use crate::minmax::{Min, Max};
+pub use crate::minmax::{Min, Max, Range};
 pub use crate::quantile::Quantile;
-pub use crate::traits::{Estimate, Merge, Histogram};
+pub use crate::weighted_quantile::WeightedQuantile;
+#[cfg(feature = "std")] pub use crate::hybrid_quantile::HybridQuantile;
+#[cfg(feature = "std")] pub use crate::kde::KernelDensity;
+pub use crate::budget::{Budget, MemoryUsage};
+pub use crate::regression::{LinearRegression, HuberRegression};
+#[cfg(feature = "std")] pub use crate::kendall::KendallTau;
+#[cfg(feature = "std")] pub use crate::theil_sen::TheilSen;
+#[cfg(feature = "std")] pub use crate::ks::{KsTest, TwoSampleKs};
+pub use crate::integer::{IntegerMean, IntegerVariance};
+pub use crate::decaying::{DecayingMean, DecayingVariance};
+pub use crate::policy::{NanPolicy, WithNanPolicy};
+pub use crate::outlier::OutlierDetector;
+pub use crate::adaptor::{Map, Filter, Scaled};
+pub use crate::fan_out::FanOut;
+#[cfg(feature = "std")] pub use crate::registry::{StatsRegistry, RegisteredEstimator};
+#[cfg(feature = "std")] pub use crate::grouped::Grouped;
+#[cfg(feature = "std")] pub use crate::columns::Columns;
+#[cfg(target_has_atomic = "64")] pub use crate::atomic::AtomicStats;
+#[cfg(feature = "std")] pub use crate::sharded::ShardedEstimate;
+#[cfg(feature = "std")] pub use crate::chunked::from_slice_parallel;
+#[cfg(feature = "std")] pub use crate::binary::{ByteOrder, read_samples, from_reader};
+pub use crate::iter::{Stats, StatsIteratorExt, Whiten};
+pub use crate::describe::Describe;
+pub use crate::five_number::FiveNumber;
+pub use crate::latency::LatencyReport;
+pub use crate::iqr::Iqr;
+pub use crate::compare::{anova, AnovaTest, pooled_variance, PooledVariance,
+                         cohens_d, EffectSize};
+#[cfg(feature = "rust_decimal")] pub use crate::decimal::DecimalMean;
+#[cfg(feature = "num-complex")] pub use crate::complex::{ComplexMean, ComplexVariance};
+#[cfg(feature = "rayon")] pub use crate::parallel::par_merge;
+#[cfg(feature = "futures")] pub use crate::stream::{from_stream, collect_stream,
+    StatsStreamExt, CollectEstimate};
+#[cfg(feature = "metrics")] pub use crate::recorder::MetricsRecorder;
+#[cfg(feature = "opentelemetry")] pub use crate::otlp::ToOtlp;
+#[cfg(feature = "ndarray")] pub use crate::array::StatsArrayExt;
+#[cfg(feature = "nalgebra")] pub use crate::vector::{VectorMean, VectorCovariance};
+#[cfg(feature = "polars")] pub use crate::series::{from_series, StatsSeriesExt};
+#[cfg(feature = "csv")] pub use crate::profile::describe_csv;
+pub use crate::traits::{Estimate, Merge, Reset, MergeDyn, Histogram, IntoSample, AddError};
+pub use crate::counter::Counter;
+pub use crate::reduce::merge_reduce;
+#[cfg(feature = "derive")] pub use average_derive::{Estimate, Merge, Reset};
+pub use crate::histogram::{Axis, ChiSquareTest};
+#[cfg(feature = "hdrhistogram")] pub use hdrhistogram;
+#[cfg(feature = "opentelemetry")] pub use opentelemetry_proto;
+#[cfg(feature = "csv")] pub use csv;
 
 define_histogram!(hist, 10);
 pub use crate::hist::Histogram as Histogram10;
+define_weighted_histogram!(weighted_hist, 10);
+pub use crate::weighted_hist::Histogram as WeightedHistogram10;
+define_rolling_stats!(rolling_stats, 10);
+pub use crate::rolling_stats::RollingStats as RollingStats10;
 define_moments!(Moments4, 4);
@@ -17,7 +17,9 @@ macro_rules! assert_almost_eq {
 
 /// Concatenate several iterative estimators into one.
 ///
-/// `$name` is the name of the new struct. `$statistic` is the name of a
+/// `$name` is the name of the new struct, optionally preceded by a visibility
+/// like `pub` to make the struct usable outside of the current module.
+/// `$statistic` is the name of a
 /// statistic and must exist as a method of the corresponding type `$estimator`.
 /// `$estimator` must have an `add` method for adding new observations to the
 /// sample (taking an `f64` as an argument). It must also implement `Default`.
@@ -29,9 +31,12 @@ macro_rules! assert_almost_eq {
 /// For moments, only an estimator for the highest moment should be used and
 /// reused for the lower moments (see the example below).
 ///
-/// The following methods will be implemented: `new`, `add`, `$statistic`.
+/// The following methods will be implemented: `new`, `add`, `reset`,
+/// `$statistic`.
 ///
-/// The following traits will be implemented: `Default`, `FromIterator<f64>`.
+/// The following traits will be implemented: `Default`, `FromIterator<f64>`,
+/// `Extend<f64>`, `AddAssign<f64>`, and `FromIterator` and `Extend` for `f32`,
+/// `i32`, `u32`, `i64` and references to these types.
 ///
 ///
 /// # Examples
@@ -77,6 +82,24 @@ macro_rules! assert_almost_eq {
 /// }
 /// ```
 ///
+/// The struct is private by default. To export it from a module, add a
+/// visibility:
+///
+/// ```
+/// mod stats {
+///     use average::{Min, Max, Variance, Estimate, concatenate};
+///
+///     concatenate!(pub Summary,
+///         [Min, min, min],
+///         [Max, max, max],
+///         [Variance, variance, mean, sample_variance]);
+/// }
+///
+/// let s: stats::Summary = (1..6).map(f64::from).collect();
+/// assert_eq!(s.min(), 1.0);
+/// assert_eq!(s.mean(), 3.0);
+/// ```
+///
 /// If you want to calculate the mean, variance and the median in one pass, you
 /// can do the following:
 ///
@@ -89,11 +112,11 @@ macro_rules! assert_almost_eq {
 /// ```
 #[macro_export]
 macro_rules! concatenate {
-    ( $name:ident, $([$estimator:ident, $statistic:ident]),+ ) => {
-        concatenate!( $name, $([$estimator, $statistic, $statistic]),* );
+    ( $vis:vis $name:ident, $([$estimator:ident, $statistic:ident]),+ ) => {
+        concatenate!( $vis $name, $([$estimator, $statistic, $statistic]),* );
     };
-    ( $name:ident, $( [$estimator:ident, $field:ident, $($statistic:ident),+] ),+ ) => {
-        struct $name {
+    ( $vis:vis $name:ident, $( [$estimator:ident, $field:ident, $($statistic:ident),+] ),+ ) => {
+        $vis struct $name {
         $(
             $field: $estimator,
         )*
@@ -116,6 +139,13 @@ macro_rules! concatenate {
                 )*
             }
 
+            #[inline]
+            pub fn reset(&mut self) {
+                $(
+                    $crate::Reset::reset(&mut self.$field);
+                )*
+            }
+
             $( $(
                 #[inline]
                 pub fn $statistic(&self) -> f64 {
@@ -131,10 +161,12 @@ macro_rules! concatenate {
         }
 
         $crate::impl_from_iterator!($name);
+        $crate::impl_from_iterator_samples!($name; f32, i32, u32, i64);
+        $crate::impl_add_assign!($name; f64);
     };
 }
 
-/// Implement `FromIterator<f64>` for an iterative estimator.
+/// Implement `FromIterator<f64>` and `Extend<f64>` for an iterative estimator.
 #[macro_export]
 macro_rules! impl_from_iterator {
     ( $name:ident ) => {
@@ -143,10 +175,18 @@ macro_rules! impl_from_iterator {
                 where T: IntoIterator<Item=f64>
             {
                 let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl ::core::iter::Extend<f64> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=f64>
+            {
                 for i in iter {
-                    e.add(i);
+                    self.add(i);
                 }
-                e
             }
         }
 
@@ -155,11 +195,96 @@ macro_rules! impl_from_iterator {
                 where T: IntoIterator<Item=&'a f64>
             {
                 let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a> ::core::iter::Extend<&'a f64> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a f64>
+            {
                 for &i in iter {
-                    e.add(i);
+                    self.add(i);
+                }
+            }
+        }
+    };
+}
+
+/// Implement `FromIterator` and `Extend` for the given sample types and
+/// references to them, converting the samples to `f64` via `IntoSample`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_from_iterator_samples {
+    ( $name:ty; $( $t:ty ),* ) => { $(
+        impl ::core::iter::FromIterator<$t> for $name {
+            fn from_iter<T>(iter: T) -> $name
+                where T: IntoIterator<Item=$t>
+            {
+                let mut e = <$name as ::core::default::Default>::default();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl ::core::iter::Extend<$t> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=$t>
+            {
+                for i in iter {
+                    self.add($crate::IntoSample::into_sample(i));
                 }
+            }
+        }
+
+        impl<'a> ::core::iter::FromIterator<&'a $t> for $name {
+            fn from_iter<T>(iter: T) -> $name
+                where T: IntoIterator<Item=&'a $t>
+            {
+                let mut e = <$name as ::core::default::Default>::default();
+                e.extend(iter);
                 e
             }
         }
+
+        impl<'a> ::core::iter::Extend<&'a $t> for $name {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a $t>
+            {
+                for i in iter {
+                    self.add($crate::IntoSample::into_sample(i));
+                }
+            }
+        }
+    )* };
+}
+
+/// Implement `AddAssign` for an iterative estimator.
+///
+/// `estimator += x` adds the sample `x`. If `merge` is given, `estimator +=
+/// &other` merges another estimator into this one.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_add_assign {
+    ( $name:ty; $sample:ty ) => {
+        impl ::core::ops::AddAssign<$sample> for $name {
+            /// Add an observation sampled from the population.
+            #[inline]
+            fn add_assign(&mut self, x: $sample) {
+                self.add(x);
+            }
+        }
+    };
+    ( $name:ty; $sample:ty, merge ) => {
+        $crate::impl_add_assign!($name; $sample);
+
+        impl<'a> ::core::ops::AddAssign<&'a $name> for $name {
+            /// Merge another sample into this one.
+            #[inline]
+            fn add_assign(&mut self, other: &$name) {
+                $crate::Merge::merge(self, other);
+            }
+        }
     };
 }
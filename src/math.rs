@@ -0,0 +1,49 @@
+//! Elementary functions used by the estimators and the macros.
+//!
+//! The square root is correctly rounded by IEEE 754, so it is the same on all
+//! platforms. With the `"deterministic"` feature, the other functions are
+//! calculated by the `libm` crate instead of the platform's math library, so
+//! that their results do not depend on the platform either.
+
+use num_traits::Float;
+
+/// Calculate the square root.
+#[inline]
+pub fn sqrt(x: f64) -> f64 {
+    Float::sqrt(x)
+}
+
+/// Calculate the least nonnegative remainder of `x` divided by `y`, like
+/// `f64::rem_euclid`.
+#[inline]
+pub fn rem_euclid(x: f64, y: f64) -> f64 {
+    let r = x % y;
+    if r < 0. { r + Float::abs(y) } else { r }
+}
+
+/// Define functions forwarding to `Float` or, with the `"deterministic"`
+/// feature, to `libm`.
+macro_rules! impl_math {
+    ( $( $name:ident => $libm:ident ( $( $arg:ident ),+ ) ),* ) => { $(
+        #[doc = concat!("Calculate `", stringify!($name), "`.")]
+        #[inline]
+        pub fn $name( $( $arg: f64 ),+ ) -> f64 {
+            #[cfg(feature = "deterministic")]
+            { libm::$libm( $( $arg ),+ ) }
+            #[cfg(not(feature = "deterministic"))]
+            { Float::$name( $( $arg ),+ ) }
+        }
+    )* };
+}
+
+impl_math!(
+    exp => exp(x),
+    ln => log(x),
+    powf => pow(x, y),
+    sin => sin(x),
+    cos => cos(x),
+    atan2 => atan2(y, x),
+    floor => floor(x),
+    ceil => ceil(x),
+    round => round(x)
+);
@@ -1,8 +1,7 @@
-use core;
-
 #[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
 
-use super::{Estimate, Merge};
+use super::{Estimate, Merge, Reset};
+use crate::snapshot::{self, tag, Reader, Writer};
 
 /// Calculate the minimum of `a` and `b`.
 fn min(a: f64, b: f64) -> f64 {
@@ -25,8 +24,9 @@ fn max(a: f64, b: f64) -> f64 {
 /// let a: Min = (1..6).map(f64::from).collect();
 /// println!("The minimum is {}.", a.min());
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Min {
     x: f64,
 }
@@ -34,14 +34,14 @@ pub struct Min {
 impl Min {
     /// Create a new minium estimator from a given value.
     #[inline]
-    pub fn from_value(x: f64) -> Min {
+    pub const fn from_value(x: f64) -> Min {
         Min { x }
     }
 
     /// Create a new minimum estimator.
     #[inline]
-    pub fn new() -> Min {
-        Min::from_value(::core::f64::INFINITY)
+    pub const fn new() -> Min {
+        Min::from_value(f64::INFINITY)
     }
 
     /// Estimate the minium of the population.
@@ -51,6 +51,30 @@ impl Min {
     }
 }
 
+impl Min {
+    /// The length of a snapshot in bytes.
+    pub const SNAPSHOT_LEN: usize = snapshot::HEADER_LEN + 8;
+
+    /// Write a snapshot of the estimator.
+    ///
+    /// The snapshot has a stable, versioned little-endian layout, so it can be
+    /// restored with `from_bytes` by earlier and later versions of this crate.
+    pub fn to_bytes(&self) -> [u8; Min::SNAPSHOT_LEN] {
+        let mut buf = [0; Min::SNAPSHOT_LEN];
+        Writer::new(&mut buf, tag::MIN).f64(self.x);
+        buf
+    }
+
+    /// Restore an estimator from a snapshot written by `to_bytes`.
+    ///
+    /// Returns an error if the bytes are not a snapshot of this estimator.
+    /// Fields appended by newer versions of this crate are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Min, ()> {
+        let mut r = Reader::new(bytes, tag::MIN, Min::SNAPSHOT_LEN)?;
+        Ok(Min { x: r.f64() })
+    }
+}
+
 impl core::default::Default for Min {
     fn default() -> Min {
         Min::new()
@@ -58,6 +82,8 @@ impl core::default::Default for Min {
 }
 
 impl_from_iterator!(Min);
+impl_from_iterator_samples!(Min; f32, i32, u32, i64);
+impl_add_assign!(Min; f64, merge);
 
 impl Estimate for Min {
     #[inline]
@@ -71,6 +97,13 @@ impl Estimate for Min {
     }
 }
 
+impl Reset for Min {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Min::new();
+    }
+}
+
 impl Merge for Min {
     /// Merge another sample into this one.
     ///
@@ -105,8 +138,9 @@ impl Merge for Min {
 /// let a: Max = (1..6).map(f64::from).collect();
 /// assert_eq!(a.max(), 5.);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Max {
     x: f64,
 }
@@ -114,14 +148,14 @@ pub struct Max {
 impl Max {
     /// Create a new maxium estimator from a given value.
     #[inline]
-    pub fn from_value(x: f64) -> Max {
+    pub const fn from_value(x: f64) -> Max {
         Max { x }
     }
 
     /// Create a new maximum estimator.
     #[inline]
-    pub fn new() -> Max {
-        Max::from_value(::core::f64::NEG_INFINITY)
+    pub const fn new() -> Max {
+        Max::from_value(f64::NEG_INFINITY)
     }
 
     /// Estimate the maxium of the population.
@@ -131,6 +165,30 @@ impl Max {
     }
 }
 
+impl Max {
+    /// The length of a snapshot in bytes.
+    pub const SNAPSHOT_LEN: usize = snapshot::HEADER_LEN + 8;
+
+    /// Write a snapshot of the estimator.
+    ///
+    /// The snapshot has a stable, versioned little-endian layout, so it can be
+    /// restored with `from_bytes` by earlier and later versions of this crate.
+    pub fn to_bytes(&self) -> [u8; Max::SNAPSHOT_LEN] {
+        let mut buf = [0; Max::SNAPSHOT_LEN];
+        Writer::new(&mut buf, tag::MAX).f64(self.x);
+        buf
+    }
+
+    /// Restore an estimator from a snapshot written by `to_bytes`.
+    ///
+    /// Returns an error if the bytes are not a snapshot of this estimator.
+    /// Fields appended by newer versions of this crate are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Max, ()> {
+        let mut r = Reader::new(bytes, tag::MAX, Max::SNAPSHOT_LEN)?;
+        Ok(Max { x: r.f64() })
+    }
+}
+
 impl core::default::Default for Max {
     fn default() -> Max {
         Max::new()
@@ -138,6 +196,8 @@ impl core::default::Default for Max {
 }
 
 impl_from_iterator!(Max);
+impl_from_iterator_samples!(Max; f32, i32, u32, i64);
+impl_add_assign!(Max; f64, merge);
 
 impl Estimate for Max {
     #[inline]
@@ -151,6 +211,13 @@ impl Estimate for Max {
     }
 }
 
+impl Reset for Max {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Max::new();
+    }
+}
+
 impl Merge for Max {
     /// Merge another sample into this one.
     ///
@@ -173,3 +240,143 @@ impl Merge for Max {
         self.add(other.x);
     }
 }
+
+/// Estimate the range and the midrange of a sequence of numbers
+/// ("population").
+///
+/// The range is the difference between the maximum and the minimum, the
+/// midrange is their average. Both are very sensitive to outliers, which makes
+/// the range a cheap check of the spread alongside the variance.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Range;
+///
+/// let a: Range = [3., 1., 4., 1., 5.].iter().collect();
+/// assert_eq!((a.min(), a.max()), (1., 5.));
+/// assert_eq!(a.range(), 4.);
+/// assert_eq!(a.midrange(), 3.);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct Range {
+    /// Estimator of the minimum.
+    min: Min,
+    /// Estimator of the maximum.
+    max: Max,
+}
+
+impl Range {
+    /// Create a new range estimator.
+    #[inline]
+    pub const fn new() -> Range {
+        Range { min: Min::new(), max: Max::new() }
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.min.min() > self.max.max()
+    }
+
+    /// Estimate the minimum of the population.
+    ///
+    /// Returns infinity for an empty sample.
+    #[inline]
+    pub fn min(&self) -> f64 {
+        self.min.min()
+    }
+
+    /// Estimate the maximum of the population.
+    ///
+    /// Returns negative infinity for an empty sample.
+    #[inline]
+    pub fn max(&self) -> f64 {
+        self.max.max()
+    }
+
+    /// Estimate the range of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn range(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        self.max() - self.min()
+    }
+
+    /// Estimate the range of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_range(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.range())
+    }
+
+    /// Estimate the midrange of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn midrange(&self) -> f64 {
+        if self.is_empty() {
+            return 0.;
+        }
+        0.5 * self.min() + 0.5 * self.max()
+    }
+
+    /// Estimate the midrange of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_midrange(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.midrange())
+    }
+}
+
+impl core::default::Default for Range {
+    fn default() -> Range {
+        Range::new()
+    }
+}
+
+impl_from_iterator!(Range);
+impl_from_iterator_samples!(Range; f32, i32, u32, i64);
+impl_add_assign!(Range; f64, merge);
+
+impl Estimate for Range {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.min.add(x);
+        self.max.add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.range()
+    }
+}
+
+impl Reset for Range {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Range::new();
+    }
+}
+
+impl Merge for Range {
+    #[inline]
+    fn merge(&mut self, other: &Range) {
+        self.min.merge(&other.min);
+        self.max.merge(&other.max);
+    }
+}
@@ -2,8 +2,9 @@
 /// a sequence of numbers ("population").
 ///
 /// This can be used to estimate the standard error of the mean.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Kurtosis {
     /// Estimator of mean, variance and skewness.
     avg: Skewness,
@@ -14,13 +15,34 @@ pub struct Kurtosis {
 impl Kurtosis {
     /// Create a new kurtosis estimator.
     #[inline]
-    pub fn new() -> Kurtosis {
+    pub const fn new() -> Kurtosis {
         Kurtosis {
             avg: Skewness::new(),
             sum_4: 0.,
         }
     }
 
+    /// Create a kurtosis estimator from a sample size, a mean, a sample
+    /// variance, a skewness and an excess kurtosis that were calculated
+    /// elsewhere.
+    ///
+    /// This is useful for merging aggregates imported from other sources with
+    /// locally calculated ones. The skewness and the kurtosis have to be
+    /// calculated like `skewness()` and `kurtosis()` do.
+    #[inline]
+    pub fn from_moments(n: u64, mean: f64, sample_variance: f64, skewness: f64, kurtosis: f64)
+        -> Kurtosis
+    {
+        let avg = Skewness::from_moments(n, mean, sample_variance, skewness);
+        let sum_2 = avg.avg.sum_2;
+        let sum_4 = if sum_2 == 0. {
+            0.
+        } else {
+            (kurtosis + 3.) * sum_2 * sum_2 / n as f64
+        };
+        Kurtosis { avg, sum_4 }
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -39,15 +61,54 @@ impl Kurtosis {
         // This algorithm was suggested by Terriberry.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = self.len().to_f64().unwrap();
+        let n = self.len() as f64;
         let term = delta * delta_n * (n - 1.);
         let delta_n_sq = delta_n*delta_n;
-        self.sum_4 += term * delta_n_sq * (n*n - 3.*n + 3.)
-            + 6. * delta_n_sq * self.avg.avg.sum_2
+        self.sum_4 += mul_add(term * delta_n_sq, n*n - 3.*n + 3., 6. * delta_n_sq * self.avg.avg.sum_2)
             - 4. * delta_n * self.avg.sum_3;
         self.avg.add_inner(delta, delta_n);
     }
 
+    /// Add an observation sampled from the population `count` times.
+    ///
+    /// This is equivalent to calling `add(sample)` `count` times, but takes
+    /// constant time, which is useful for ingesting frequency tables.
+    #[inline]
+    pub fn add_n(&mut self, sample: f64, count: u64) {
+        if count == 0 {
+            return;
+        }
+        self.merge(&Kurtosis {
+            avg: Skewness {
+                avg: MeanWithError { avg: Mean { avg: sample, n: count }, sum_2: 0. },
+                sum_3: 0.,
+            },
+            sum_4: 0.,
+        });
+    }
+
+    /// Calculate the estimator of a slice of samples using a two-pass
+    /// algorithm with pairwise summation.
+    fn of_slice(samples: &[f64]) -> Kurtosis {
+        let avg = Skewness::of_slice(samples);
+        let mean = avg.mean();
+        let sum_4 = pairwise_sum(samples, |x| {
+            let d = x - mean;
+            d * d * d * d
+        });
+        Kurtosis { avg, sum_4 }
+    }
+
+    /// Add a slice of observations sampled from the population.
+    ///
+    /// This is equivalent to adding the samples one by one, but uses a
+    /// two-pass algorithm with pairwise summation, which is faster and more
+    /// accurate for large slices.
+    #[inline]
+    pub fn add_slice(&mut self, samples: &[f64]) {
+        self.merge(&Kurtosis::of_slice(samples));
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -62,12 +123,38 @@ impl Kurtosis {
         self.avg.mean()
     }
 
+    /// Estimate the mean of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_mean(&self) -> Option<f64> {
+        self.avg.try_mean()
+    }
+
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
         self.avg.len()
     }
 
+    /// Return the sum of the squared deviations from the mean.
+    #[inline]
+    pub fn sum_2(&self) -> f64 {
+        self.avg.sum_2()
+    }
+
+    /// Return the sum of the cubed deviations from the mean.
+    #[inline]
+    pub fn sum_3(&self) -> f64 {
+        self.avg.sum_3
+    }
+
+    /// Return the sum of the deviations from the mean to the fourth power.
+    #[inline]
+    pub fn sum_4(&self) -> f64 {
+        self.sum_4
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -76,6 +163,14 @@ impl Kurtosis {
         self.avg.sample_variance()
     }
 
+    /// Calculate the sample variance.
+    ///
+    /// Returns `None` if there are less than two samples.
+    #[inline]
+    pub fn try_sample_variance(&self) -> Option<f64> {
+        self.avg.try_sample_variance()
+    }
+
     /// Calculate the population variance of the sample.
     ///
     /// This is a biased estimator of the variance of the population.
@@ -84,28 +179,195 @@ impl Kurtosis {
         self.avg.population_variance()
     }
 
+    /// Calculate the population variance of the sample.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_population_variance(&self) -> Option<f64> {
+        self.avg.try_population_variance()
+    }
+
+    /// Calculate the variance with the given delta degrees of freedom.
+    ///
+    /// The sum of the squared deviations from the mean is divided by
+    /// `n - ddof`. Returns 0 if there are not more than `ddof` samples.
+    #[inline]
+    pub fn variance_ddof(&self, ddof: u64) -> f64 {
+        self.avg.variance_ddof(ddof)
+    }
+
+    /// Calculate the variance with the given delta degrees of freedom.
+    ///
+    /// Returns `None` if there are not more than `ddof` samples.
+    #[inline]
+    pub fn try_variance_ddof(&self, ddof: u64) -> Option<f64> {
+        self.avg.try_variance_ddof(ddof)
+    }
+
     /// Estimate the standard error of the mean of the population.
     #[inline]
     pub fn error_mean(&self) -> f64 {
         self.avg.error_mean()
     }
 
+    /// Estimate the standard error of the mean of a finite population of the
+    /// given size, from which the samples were drawn without replacement.
+    ///
+    /// This applies the finite population correction to `error_mean()`.
+    ///
+    /// Panics if the population is smaller than the sample.
+    #[inline]
+    pub fn error_mean_with_population_size(&self, population_size: u64) -> f64 {
+        self.avg.error_mean_with_population_size(population_size)
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// This is the sample standard deviation divided by the mean. Returns 0
+    /// for less than two samples.
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> f64 {
+        self.avg.coefficient_of_variation()
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// Returns `None` if there are less than two samples or the mean is zero.
+    #[inline]
+    pub fn try_coefficient_of_variation(&self) -> Option<f64> {
+        self.avg.try_coefficient_of_variation()
+    }
+
+    /// Estimate the coefficient of variation of the population, correcting
+    /// the bias for small samples.
+    ///
+    /// See `Variance::corrected_coefficient_of_variation`.
+    #[inline]
+    pub fn corrected_coefficient_of_variation(&self) -> f64 {
+        self.avg.corrected_coefficient_of_variation()
+    }
+
     /// Estimate the skewness of the population.
+    ///
+    /// This is the biased moment coefficient of skewness `g1`. See
+    /// `sample_skewness` for the bias-corrected estimator.
     #[inline]
     pub fn skewness(&self) -> f64 {
         self.avg.skewness()
     }
 
+    /// Estimate the skewness of the population.
+    ///
+    /// Returns `None` if the sample variance is zero, in particular if there
+    /// are less than two samples.
+    #[inline]
+    pub fn try_skewness(&self) -> Option<f64> {
+        self.avg.try_skewness()
+    }
+
+    /// Calculate the sample skewness.
+    ///
+    /// See `Skewness::sample_skewness`.
+    #[inline]
+    pub fn sample_skewness(&self) -> f64 {
+        self.avg.sample_skewness()
+    }
+
+    /// Calculate the sample skewness.
+    ///
+    /// Returns `None` if there are less than three samples or the sample
+    /// variance is zero.
+    #[inline]
+    pub fn try_sample_skewness(&self) -> Option<f64> {
+        self.avg.try_sample_skewness()
+    }
+
     /// Estimate the excess kurtosis of the population.
+    ///
+    /// This is the biased moment coefficient of excess kurtosis `g2`. See
+    /// `sample_kurtosis` for the bias-corrected estimator.
     #[inline]
     pub fn kurtosis(&self) -> f64 {
         if self.sum_4 == 0. {
             return 0.;
         }
-        let n = self.len().to_f64().unwrap();
+        let n = self.len() as f64;
         n * self.sum_4 / (self.avg.avg.sum_2 * self.avg.avg.sum_2) - 3.
     }
 
+    /// Estimate the excess kurtosis of the population.
+    ///
+    /// Returns `None` if the sample variance is zero, in particular if there
+    /// are less than two samples.
+    #[inline]
+    pub fn try_kurtosis(&self) -> Option<f64> {
+        if self.avg.avg.sum_2 == 0. {
+            return None;
+        }
+        Some(self.kurtosis())
+    }
+
+    /// Calculate the sample excess kurtosis.
+    ///
+    /// This is the adjusted estimator `G2`, which corrects the bias of
+    /// `kurtosis` for small samples, as reported by R and pandas. Returns 0
+    /// for less than four samples.
+    #[inline]
+    pub fn sample_kurtosis(&self) -> f64 {
+        if self.len() < 4 || self.sum_4 == 0. {
+            return 0.;
+        }
+        let n = self.len() as f64;
+        ((n + 1.) * self.kurtosis() + 6.) * (n - 1.) / ((n - 2.) * (n - 3.))
+    }
+
+    /// Calculate the sample excess kurtosis.
+    ///
+    /// Returns `None` if there are less than four samples or the sample
+    /// variance is zero.
+    #[inline]
+    pub fn try_sample_kurtosis(&self) -> Option<f64> {
+        if self.len() < 4 || self.avg.avg.sum_2 == 0. {
+            return None;
+        }
+        Some(self.sample_kurtosis())
+    }
+}
+
+impl Kurtosis {
+    /// The length of a snapshot in bytes.
+    pub const SNAPSHOT_LEN: usize = snapshot::HEADER_LEN + 40;
+
+    /// Write a snapshot of the estimator.
+    ///
+    /// The snapshot has a stable, versioned little-endian layout, so it can be
+    /// restored with `from_bytes` by earlier and later versions of this crate.
+    pub fn to_bytes(&self) -> [u8; Kurtosis::SNAPSHOT_LEN] {
+        let mut buf = [0; Kurtosis::SNAPSHOT_LEN];
+        let s = &self.avg;
+        Writer::new(&mut buf, tag::KURTOSIS)
+            .f64(s.avg.avg.avg).u64(s.avg.avg.n).f64(s.avg.sum_2)
+            .f64(s.sum_3).f64(self.sum_4);
+        buf
+    }
+
+    /// Restore an estimator from a snapshot written by `to_bytes`.
+    ///
+    /// Returns an error if the bytes are not a snapshot of this estimator.
+    /// Fields appended by newer versions of this crate are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Kurtosis, ()> {
+        let mut r = Reader::new(bytes, tag::KURTOSIS, Kurtosis::SNAPSHOT_LEN)?;
+        Ok(Kurtosis {
+            avg: Skewness {
+                avg: MeanWithError {
+                    avg: Mean { avg: r.f64(), n: r.u64() },
+                    sum_2: r.f64(),
+                },
+                sum_3: r.f64(),
+            },
+            sum_4: r.f64(),
+        })
+    }
 }
 
 impl core::default::Default for Kurtosis {
@@ -119,7 +381,7 @@ impl Estimate for Kurtosis {
     fn add(&mut self, x: f64) {
         let delta = x - self.mean();
         self.increment();
-        let n = self.len().to_f64().unwrap();
+        let n = self.len() as f64;
         self.add_inner(delta, delta/n);
     }
 
@@ -129,22 +391,55 @@ impl Estimate for Kurtosis {
     }
 }
 
+impl Reset for Kurtosis {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Kurtosis::new();
+    }
+}
+
 impl Merge for Kurtosis {
     #[inline]
     fn merge(&mut self, other: &Kurtosis) {
-        let len_self = self.len().to_f64().unwrap();
-        let len_other = other.len().to_f64().unwrap();
+        merge_canonical(self, other);
+    }
+}
+
+impl CanonicalMerge for Kurtosis {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        Kurtosis::is_empty(self)
+    }
+
+    #[inline]
+    fn cmp_canonical(&self, other: &Kurtosis) -> Ordering {
+        self.avg.cmp_canonical(&other.avg).then_with(|| cmp_bits(self.sum_4, other.sum_4))
+    }
+
+    #[inline]
+    fn merge_ordered(&mut self, other: &Kurtosis) {
+        let len_self = self.len() as f64;
+        let len_other = other.len() as f64;
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         let delta_n = delta / len_total;
         let delta_n_sq = delta_n * delta_n;
-        self.sum_4 += other.sum_4
-            + delta * delta_n*delta_n_sq * len_self*len_other
-              * (len_self*len_self - len_self*len_other + len_other*len_other)
-            + 6.*delta_n_sq * (len_self*len_self * other.avg.avg.sum_2 + len_other*len_other * self.avg.avg.sum_2)
-            + 4.*delta_n * (len_self * other.avg.sum_3 - len_other * self.avg.sum_3);
+        let sum_2_terms = mul_add(
+            len_self*len_self, other.avg.avg.sum_2, len_other*len_other * self.avg.avg.sum_2);
+        let sum_3_terms = mul_add(len_self, other.avg.sum_3, -(len_other * self.avg.sum_3));
+        self.sum_4 += mul_add(
+            4.*delta_n,
+            sum_3_terms,
+            mul_add(
+                6.*delta_n_sq,
+                sum_2_terms,
+                other.sum_4
+                    + delta * delta_n*delta_n_sq * len_self*len_other
+                      * (len_self*len_self - len_self*len_other + len_other*len_other)));
         self.avg.merge(&other.avg);
     }
 }
 
 impl_from_iterator!(Kurtosis);
+impl_from_iterator_samples!(Kurtosis; f32, i32, u32, i64);
+impl_add_assign!(Kurtosis; f64, merge);
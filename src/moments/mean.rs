@@ -1,28 +1,67 @@
-/// Estimate the arithmetic mean of a sequence of numbers ("population").
+/// Estimate the arithmetic mean of a sequence of numbers ("population"),
+/// accumulating in the floating-point type `F`.
+///
+/// Usually, you want to use the alias [`Mean`] for `f64`. Using `f32` halves
+/// the memory required by the estimator, at the cost of precision.
+///
+/// The sample size is counted in `C`, which is `u64` by default. On tiny
+/// targets, `u32` saves memory; see [`Counter`] for the behavior when the
+/// counter saturates.
+///
+/// [`Counter`]: ./trait.Counter.html
+///
+/// [`Mean`]: ./type.Mean.html
 ///
 ///
 /// ## Example
 ///
 /// ```
-/// use average::Mean;
+/// use average::GenericMean;
 ///
-/// let a: Mean = (1..6).map(f64::from).collect();
-/// println!("The mean is {}.", a.mean());
+/// let a: GenericMean<f32> = (1..6).map(|x| x as f32).collect();
+/// assert_eq!(a.mean(), 3.0f32);
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Mean {
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct GenericMean<F, C = u64> {
     /// Mean value.
-    avg: f64,
+    avg: F,
     /// Sample size.
-    n: u64,
+    n: C,
 }
 
-impl Mean {
+/// Estimate the arithmetic mean of a sequence of numbers ("population").
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::Mean;
+///
+/// let a: Mean = (1..6).map(f64::from).collect();
+/// println!("The mean is {}.", a.mean());
+/// ```
+pub type Mean = GenericMean<f64>;
+
+impl<F: Float, C: Counter> GenericMean<F, C> {
     /// Create a new mean estimator.
     #[inline]
-    pub fn new() -> Mean {
-        Mean { avg: 0., n: 0 }
+    pub fn new() -> GenericMean<F, C> {
+        GenericMean { avg: F::zero(), n: C::ZERO }
+    }
+
+    /// Create a mean estimator from a sample size and a mean that were
+    /// calculated elsewhere.
+    ///
+    /// This is useful for merging aggregates imported from other sources with
+    /// locally calculated ones. The mean is ignored if the sample size is 0.
+    #[inline]
+    pub fn from_mean(n: u64, mean: F) -> GenericMean<F, C> {
+        if n == 0 {
+            return GenericMean::new();
+        }
+        GenericMean { avg: mean, n: C::from_u64(n) }
     }
 
     /// Increment the sample size.
@@ -30,7 +69,7 @@ impl Mean {
     /// This does not update anything else.
     #[inline]
     fn increment(&mut self) {
-        self.n += 1;
+        self.n = self.n.saturating_add(C::ONE);
     }
 
     /// Add an observation given an already calculated difference from the mean
@@ -39,57 +78,199 @@ impl Mean {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta_n: f64) {
+    fn add_inner(&mut self, delta_n: F) {
         // This algorithm introduced by Welford in 1962 trades numerical
         // stability for a division inside the loop.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        self.avg += delta_n;
+        self.avg = self.avg + delta_n;
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, sample: F) {
+        self.increment();
+        let delta_n = (sample - self.avg)
+            / len_to_float(self.len());
+        self.add_inner(delta_n);
+    }
+
+    /// Add an observation sampled from the population `count` times.
+    ///
+    /// This is equivalent to calling `add(sample)` `count` times, but takes
+    /// constant time, which is useful for ingesting frequency tables.
+    #[inline]
+    pub fn add_n(&mut self, sample: F, count: u64) {
+        if count == 0 {
+            return;
+        }
+        self.merge(&GenericMean { avg: sample, n: C::from_u64(count) });
+    }
+
+    /// Calculate the estimator of a slice of samples using pairwise
+    /// summation.
+    fn of_slice(samples: &[F]) -> GenericMean<F, C> {
+        if samples.is_empty() {
+            return GenericMean::new();
+        }
+        let n = samples.len() as u64;
+        GenericMean {
+            avg: pairwise_sum(samples, |x| x) / len_to_float(n),
+            n: C::from_u64(n),
+        }
+    }
+
+    /// Add a slice of observations sampled from the population.
+    ///
+    /// This is equivalent to adding the samples one by one, but uses pairwise
+    /// summation, which is faster and more accurate for large slices.
+    #[inline]
+    pub fn add_slice(&mut self, samples: &[F]) {
+        self.merge(&GenericMean::of_slice(samples));
+    }
+
+    /// Remove an observation that was previously added.
+    ///
+    /// This is useful for sliding windows or for correcting a mistaken
+    /// `add`. Removing from an empty estimator does nothing. Note that the
+    /// rounding errors of adding and removing do not cancel exactly, so they
+    /// accumulate over many removals. Removing a sample that was never added
+    /// results in meaningless estimates.
+    #[inline]
+    pub fn remove(&mut self, sample: F) {
+        if self.len() <= 1 {
+            *self = GenericMean::new();
+            return;
+        }
+        self.n = C::from_u64(self.len() - 1);
+        self.avg = self.avg - (sample - self.avg) / len_to_float(self.len());
     }
 
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.n == 0
+        self.n == C::ZERO
     }
 
     /// Estimate the mean of the population.
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> F {
         self.avg
     }
 
+    /// Estimate the mean of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_mean(&self) -> Option<F> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.mean())
+    }
+
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
-        self.n
+        self.n.to_u64()
+    }
+
+    /// Return the sum of the samples.
+    ///
+    /// This is calculated from the mean, so it is subject to rounding.
+    #[inline]
+    pub fn sum(&self) -> F {
+        self.avg * len_to_float(self.len())
+    }
+}
+
+#[cfg(feature = "wide")]
+impl GenericMean<f64> {
+    /// Add a slice of observations sampled from the population, using SIMD.
+    ///
+    /// This is equivalent to `add_slice`, but several times faster for large
+    /// slices. The results can differ in rounding.
+    ///
+    /// Requires the `wide` feature.
+    #[inline]
+    pub fn add_slice_simd(&mut self, samples: &[f64]) {
+        if samples.is_empty() {
+            return;
+        }
+        let n = samples.len() as u64;
+        let (sum, _) = simd_sums(samples, 0.);
+        self.merge(&GenericMean { avg: sum / n as f64, n });
+    }
+}
+
+impl GenericMean<f64> {
+    /// An empty estimator.
+    ///
+    /// This is equivalent to `Mean::new()`, but can be used in constant
+    /// contexts, for instance to initialize a static.
+    pub const EMPTY: Mean = GenericMean { avg: 0., n: 0 };
+
+    /// The length of a snapshot in bytes.
+    pub const SNAPSHOT_LEN: usize = snapshot::HEADER_LEN + 16;
+
+    /// Write a snapshot of the estimator.
+    ///
+    /// The snapshot has a stable, versioned little-endian layout, so it can be
+    /// restored with `from_bytes` by earlier and later versions of this crate.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Mean;
+    ///
+    /// let a: Mean = (1..6).collect();
+    /// let bytes = a.to_bytes();
+    /// assert_eq!(Mean::from_bytes(&bytes).unwrap().mean(), 3.);
+    /// ```
+    pub fn to_bytes(&self) -> [u8; Mean::SNAPSHOT_LEN] {
+        let mut buf = [0; Mean::SNAPSHOT_LEN];
+        Writer::new(&mut buf, tag::MEAN).f64(self.avg).u64(self.n);
+        buf
     }
 
+    /// Restore an estimator from a snapshot written by `to_bytes`.
+    ///
+    /// Returns an error if the bytes are not a snapshot of this estimator.
+    /// Fields appended by newer versions of this crate are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Mean, ()> {
+        let mut r = Reader::new(bytes, tag::MEAN, Mean::SNAPSHOT_LEN)?;
+        Ok(GenericMean { avg: r.f64(), n: r.u64() })
+    }
 }
 
-impl core::default::Default for Mean {
-    fn default() -> Mean {
-        Mean::new()
+impl<F: Float, C: Counter> core::default::Default for GenericMean<F, C> {
+    fn default() -> GenericMean<F, C> {
+        GenericMean::new()
     }
 }
 
-impl Estimate for Mean {
+impl<F: Float, C: Counter> Estimate for GenericMean<F, C> {
     #[inline]
     fn add(&mut self, sample: f64) {
-        self.increment();
-        let delta_n = (sample - self.avg)
-            / self.n.to_f64().unwrap();
-        self.add_inner(delta_n);
+        GenericMean::add(self, float_from_f64(sample));
     }
 
     fn estimate(&self) -> f64 {
-        self.mean()
+        float_to_f64(self.mean())
     }
 }
 
-impl Merge for Mean {
+impl<F: Float, C: Counter> Reset for GenericMean<F, C> {
+    #[inline]
+    fn reset(&mut self) {
+        *self = GenericMean::new();
+    }
+}
+
+impl<F: Float, C: Counter> Merge for GenericMean<F, C> {
     /// Merge another sample into this one.
     ///
     ///
@@ -107,15 +288,34 @@ impl Merge for Mean {
     /// assert_eq!(avg_total.mean(), avg_left.mean());
     /// ```
     #[inline]
-    fn merge(&mut self, other: &Mean) {
+    fn merge(&mut self, other: &GenericMean<F, C>) {
+        merge_canonical(self, other);
+    }
+}
+
+impl<F: Float, C: Counter> CanonicalMerge for GenericMean<F, C> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.n == C::ZERO
+    }
+
+    #[inline]
+    fn cmp_canonical(&self, other: &GenericMean<F, C>) -> Ordering {
+        self.n.cmp(&other.n).then_with(|| cmp_bits(self.avg, other.avg))
+    }
+
+    #[inline]
+    fn merge_ordered(&mut self, other: &GenericMean<F, C>) {
         // This algorithm was proposed by Chan et al. in 1979.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let len_self = self.n.to_f64().unwrap();
-        let len_other = other.n.to_f64().unwrap();
+        let len_self: F = len_to_float(self.len());
+        let len_other: F = len_to_float(other.len());
         let len_total = len_self + len_other;
-        self.n += other.n;
-        self.avg = (len_self * self.avg + len_other * other.avg) / len_total;
+        self.n = self.n.saturating_add(other.n);
+        // In the canonical order, the other sample is not smaller, so its
+        // product is the one with the larger rounding error to be fused.
+        self.avg = mul_add(len_other, other.avg, len_self * self.avg) / len_total;
         // Chan et al. use
         //
         //     self.avg += delta * len_other / len_total;
@@ -124,4 +324,6 @@ impl Merge for Mean {
     }
 }
 
-impl_from_iterator!(Mean);
+impl_generic_from_iterator!(GenericMean);
+impl_duration!(GenericMean);
+impl_generic_add_assign!(GenericMean);
@@ -1,9 +1,281 @@
-use core;
-
-use num_traits::ToPrimitive;
+use num_traits::Float;
 #[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
 
-use super::{Estimate, Merge};
+use core::cmp::Ordering;
+use core::time::Duration;
+
+use super::{Estimate, Merge, Reset, IntoSample, Counter};
+use crate::snapshot::{self, tag, Reader, Writer};
+
+/// Sum `f(x)` over all `x` in the slice using pairwise summation.
+///
+/// The rounding error grows logarithmically with the length of the slice,
+/// instead of linearly as for naive summation.
+fn pairwise_sum<F: Float, G: Fn(F) -> F + Copy>(x: &[F], f: G) -> F {
+    /// Below this length, the elements are summed naively.
+    const BLOCK_LEN: usize = 128;
+    if x.len() <= BLOCK_LEN {
+        return x.iter().fold(F::zero(), |s, &x| s + f(x));
+    }
+    let (left, right) = x.split_at(x.len() / 2);
+    pairwise_sum(left, f) + pairwise_sum(right, f)
+}
+
+/// Compare two floating-point numbers by their representation.
+///
+/// Unlike the numerical order, this is a total order, which is used for
+/// merging estimators in a canonical order.
+#[inline]
+fn cmp_bits<F: Float>(a: F, b: F) -> Ordering {
+    let (mantissa_a, exponent_a, sign_a) = a.integer_decode();
+    let (mantissa_b, exponent_b, sign_b) = b.integer_decode();
+    (sign_a, exponent_a, mantissa_a).cmp(&(sign_b, exponent_b, mantissa_b))
+}
+
+/// An estimator that is merged in a canonical order.
+trait CanonicalMerge: Clone {
+    /// Determine whether the sample is empty.
+    fn is_empty(&self) -> bool;
+
+    /// Compare the states of two estimators in an arbitrary, but fixed, total
+    /// order.
+    fn cmp_canonical(&self, other: &Self) -> Ordering;
+
+    /// Merge another non-empty sample into this non-empty one, which does not
+    /// come after it in the canonical order.
+    fn merge_ordered(&mut self, other: &Self);
+}
+
+/// Merge another sample into this one in the canonical order.
+///
+/// The floating-point operations of merging are not exactly commutative, so
+/// merging `b` into `a` would give slightly different results than merging
+/// `a` into `b`. Merging in the canonical order makes the result
+/// independent of the order of the arguments, which is important for
+/// reproducible tree reductions.
+#[inline]
+fn merge_canonical<E: CanonicalMerge>(this: &mut E, other: &E) {
+    if other.is_empty() {
+        return;
+    }
+    if this.is_empty() {
+        *this = other.clone();
+        return;
+    }
+    if this.cmp_canonical(other) == Ordering::Greater {
+        let mut merged = other.clone();
+        merged.merge_ordered(this);
+        *this = merged;
+    } else {
+        this.merge_ordered(other);
+    }
+}
+
+/// Calculate `a * b + c`.
+///
+/// With the `"fma"` feature, this uses a fused multiply-add with a single
+/// rounding, which is more accurate.
+#[inline]
+fn mul_add<F: Float>(a: F, b: F, c: F) -> F {
+    #[cfg(feature = "fma")]
+    { a.mul_add(b, c) }
+    #[cfg(not(feature = "fma"))]
+    { a * b + c }
+}
+
+/// Convert a sample size to a floating-point number.
+///
+/// This cannot fail: the sample size is rounded to the nearest representable
+/// value, like with `as`. For types that cannot represent it at all, it
+/// saturates to the largest finite value.
+#[inline]
+fn len_to_float<F: Float>(n: u64) -> F {
+    F::from(n).unwrap_or_else(F::max_value)
+}
+
+/// Convert a sample to a floating-point number, or NaN if it cannot be
+/// represented.
+#[inline]
+fn float_from_f64<F: Float>(x: f64) -> F {
+    F::from(x).unwrap_or_else(F::nan)
+}
+
+/// Convert a floating-point number to `f64`, or NaN if it cannot be
+/// represented.
+#[inline]
+fn float_to_f64<F: Float>(x: F) -> f64 {
+    x.to_f64().unwrap_or(f64::NAN)
+}
+
+/// Calculate the sums of the deviations and of the squared deviations from the
+/// given mean over the slice, using SIMD.
+///
+/// Each of the four lanes accumulates its own sums, which are added at the end.
+#[cfg(feature = "wide")]
+fn simd_sums(x: &[f64], mean: f64) -> (f64, f64) {
+    use wide::f64x4;
+
+    let chunks = x.chunks_exact(4);
+    let remainder = chunks.remainder();
+    let mean_x4 = f64x4::splat(mean);
+    let mut sum_1 = f64x4::ZERO;
+    let mut sum_2 = f64x4::ZERO;
+    for c in chunks {
+        let d = f64x4::new([c[0], c[1], c[2], c[3]]) - mean_x4;
+        sum_1 += d;
+        sum_2 += d * d;
+    }
+    let mut sum_1 = sum_1.reduce_add();
+    let mut sum_2 = sum_2.reduce_add();
+    for &x in remainder {
+        let d = x - mean;
+        sum_1 += d;
+        sum_2 += d * d;
+    }
+    (sum_1, sum_2)
+}
+
+/// Implement `FromIterator<F>` and `Extend<F>` for an iterative estimator
+/// generic over the floating-point type `F`.
+macro_rules! impl_generic_from_iterator {
+    ( $name:ident ) => {
+        impl<F: Float, C: Counter> core::iter::FromIterator<F> for $name<F, C> {
+            fn from_iter<T>(iter: T) -> $name<F, C>
+                where T: IntoIterator<Item=F>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<F: Float, C: Counter> core::iter::Extend<F> for $name<F, C> {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=F>
+            {
+                for i in iter {
+                    self.add(i);
+                }
+            }
+        }
+
+        impl<'a, F: Float, C: Counter> core::iter::FromIterator<&'a F> for $name<F, C> {
+            fn from_iter<T>(iter: T) -> $name<F, C>
+                where T: IntoIterator<Item=&'a F>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a, F: Float, C: Counter> core::iter::Extend<&'a F> for $name<F, C> {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a F>
+            {
+                for &i in iter {
+                    self.add(i);
+                }
+            }
+        }
+
+        $crate::impl_from_iterator_samples!($name<f64>; f32, i32, u32, i64);
+    };
+}
+
+/// Implement `AddAssign` for an iterative estimator generic over the
+/// floating-point type `F`.
+///
+/// `estimator += x` adds the sample `x`, and `estimator += &other` merges
+/// another estimator into this one.
+macro_rules! impl_generic_add_assign {
+    ( $name:ident ) => {
+        impl<F: Float, C: Counter> core::ops::AddAssign<F> for $name<F, C> {
+            /// Add an observation sampled from the population.
+            #[inline]
+            fn add_assign(&mut self, x: F) {
+                self.add(x);
+            }
+        }
+
+        impl<'a, F: Float, C: Counter> core::ops::AddAssign<&'a $name<F, C>> for $name<F, C> {
+            /// Merge another sample into this one.
+            #[inline]
+            fn add_assign(&mut self, other: &$name<F, C>) {
+                self.merge(other);
+            }
+        }
+    };
+}
+
+/// Implement adding `Duration` samples (converted to seconds) and calculating
+/// the mean as a `Duration` for an iterative estimator generic over the
+/// floating-point type `F`.
+macro_rules! impl_duration {
+    ( $name:ident ) => {
+        impl<F: Float, C: Counter> $name<F, C> {
+            /// Add a duration sampled from the population, converted to
+            /// seconds.
+            ///
+            /// This avoids mixing up units when timing code. Don't add samples
+            /// in other units to the same estimator.
+            #[inline]
+            pub fn add_duration(&mut self, d: Duration) {
+                self.add(float_from_f64(d.into_sample()));
+            }
+
+            /// Estimate the mean of the population as a duration, assuming
+            /// that the samples are durations in seconds.
+            ///
+            /// Returns a zero duration for an empty sample. Panics if the mean
+            /// is negative, not finite or too large for a `Duration`.
+            #[inline]
+            pub fn mean_duration(&self) -> Duration {
+                Duration::from_secs_f64(float_to_f64(self.mean()))
+            }
+        }
+
+        impl<C: Counter> core::iter::FromIterator<Duration> for $name<f64, C> {
+            fn from_iter<T>(iter: T) -> $name<f64, C>
+                where T: IntoIterator<Item=Duration>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<C: Counter> core::iter::Extend<Duration> for $name<f64, C> {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=Duration>
+            {
+                for i in iter {
+                    self.add_duration(i);
+                }
+            }
+        }
+
+        impl<'a, C: Counter> core::iter::FromIterator<&'a Duration> for $name<f64, C> {
+            fn from_iter<T>(iter: T) -> $name<f64, C>
+                where T: IntoIterator<Item=&'a Duration>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a, C: Counter> core::iter::Extend<&'a Duration> for $name<f64, C> {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a Duration>
+            {
+                for &i in iter {
+                    self.add_duration(i);
+                }
+            }
+        }
+    };
+}
 
 include!("mean.rs");
 include!("variance.rs");
@@ -17,7 +289,7 @@ pub type MeanWithError = Variance;
 #[macro_export]
 macro_rules! define_moments_common {
     ($name:ident, $MAX_MOMENT:expr) => (
-        use num_traits::{pow, ToPrimitive};
+        use num_traits::pow;
 
         /// An iterator over binomial coefficients.
         struct IterBinomial {
@@ -33,7 +305,7 @@ macro_rules! define_moments_common {
                 IterBinomial {
                     k: 0,
                     a: 1,
-                    n: n,
+                    n,
                 }
             }
         }
@@ -93,7 +365,7 @@ macro_rules! define_moments_common {
             /// Estimate the `p`th central moment of the population.
             #[inline]
             pub fn central_moment(&self, p: usize) -> f64 {
-                let n = self.n.to_f64().unwrap();
+                let n = self.n as f64;
                 match p {
                     0 => 1.,
                     1 => 0.,
@@ -102,20 +374,35 @@ macro_rules! define_moments_common {
             }
 
             /// Estimate the `p`th standardized moment of the population.
+            ///
+            /// Panics if `p` is larger than the maximal order of the moments,
+            /// or if the variance is zero for `p > 2`.
             #[inline]
             pub fn standardized_moment(&self, p: usize) -> f64 {
                 match p {
-                    0 => self.n.to_f64().unwrap(),
+                    0 => self.n as f64,
                     1 => 0.,
                     2 => 1.,
                     _ => {
                         let variance = self.central_moment(2);
                         assert_ne!(variance, 0.);
-                        self.central_moment(p) / pow(variance.sqrt(), p)
+                        self.central_moment(p) / pow($crate::math::sqrt(variance), p)
                     },
                 }
             }
 
+            /// Estimate the `p`th standardized moment of the population.
+            ///
+            /// Returns `None` if `p` is larger than the maximal order of the
+            /// moments, or if the variance is zero for `p > 2`.
+            #[inline]
+            pub fn try_standardized_moment(&self, p: usize) -> Option<f64> {
+                if p > MAX_MOMENT || (p > 2 && (self.n == 0 || self.m[0] == 0.)) {
+                    return None;
+                }
+                Some(self.standardized_moment(p))
+            }
+
             /// Calculate the sample variance.
             ///
             /// This is an unbiased estimator of the variance of the population.
@@ -124,7 +411,7 @@ macro_rules! define_moments_common {
                 if self.n < 2 {
                     return 0.;
                 }
-                self.m[0] / (self.n - 1).to_f64().unwrap()
+                self.m[0] / (self.n - 1) as f64
             }
 
             /// Calculate the sample skewness.
@@ -133,15 +420,17 @@ macro_rules! define_moments_common {
                 if self.n < 2 {
                     return 0.;
                 }
-                let n = self.n.to_f64().unwrap();
+                let n = self.n as f64;
                 if self.n < 3 {
                     // Method of moments
+                    let variance = self.central_moment(2) / (n - 1.);
                     return self.central_moment(3) /
-                        (n * (self.central_moment(2) / (n - 1.)).powf(1.5))
+                        (n * variance * $crate::math::sqrt(variance))
                 }
                 // Adjusted Fisher-Pearson standardized moment coefficient
-                (n * (n - 1.)).sqrt() / (n * (n - 2.)) *
-                    self.central_moment(3) / (self.central_moment(2) / n).powf(1.5)
+                let variance = self.central_moment(2) / n;
+                $crate::math::sqrt(n * (n - 1.)) / (n * (n - 2.)) *
+                    self.central_moment(3) / (variance * $crate::math::sqrt(variance))
             }
 
             /// Calculate the sample excess kurtosis.
@@ -150,7 +439,7 @@ macro_rules! define_moments_common {
                 if self.n < 4 {
                     return 0.;
                 }
-                let n = self.n.to_f64().unwrap();
+                let n = self.n as f64;
                 (n + 1.) * n * self.central_moment(4) /
                     ((n - 1.) * (n - 2.) * (n - 3.) * pow(self.central_moment(2), 2)) -
                     3. * pow(n - 1., 2) / ((n - 2.) * (n - 3.))
@@ -161,7 +450,7 @@ macro_rules! define_moments_common {
             pub fn add(&mut self, x: f64) {
                 self.n += 1;
                 let delta = x - self.avg;
-                let n = self.n.to_f64().unwrap();
+                let n = self.n as f64;
                 self.avg += delta / n;
 
                 let mut coeff_delta = delta;
@@ -185,7 +474,7 @@ macro_rules! define_moments_common {
                     binom.next().unwrap();  // Skip k = 0.
                     for k in 1..(p - 1) {
                         coeff *= factor_coeff;
-                        self.m[p - 2] += binom.next().unwrap().to_f64().unwrap() *
+                        self.m[p - 2] += binom.next().unwrap() as f64 *
                             prev_m[p - 2 - k] * coeff;
                     }
                 }
@@ -193,14 +482,41 @@ macro_rules! define_moments_common {
         }
 
         impl $crate::Merge for $name {
+            /// Merge another sample into this one.
+            ///
+            /// The samples are merged in a canonical order of their states,
+            /// so that merging is exactly commutative.
             #[inline]
             fn merge(&mut self, other: &$name) {
-                let n_a = self.n.to_f64().unwrap();
-                let n_b = other.n.to_f64().unwrap();
+                if other.n == 0 {
+                    return;
+                }
+                if self.n == 0 {
+                    *self = other.clone();
+                    return;
+                }
+                let key = |e: &$name| (e.n, e.avg.to_bits(), e.m.map(f64::to_bits));
+                if key(self) > key(other) {
+                    let mut merged = other.clone();
+                    merged.merge_ordered(self);
+                    *self = merged;
+                } else {
+                    self.merge_ordered(other);
+                }
+            }
+        }
+
+        impl $name {
+            /// Merge another non-empty sample into this one, which does not
+            /// come after it in the canonical order.
+            #[inline]
+            fn merge_ordered(&mut self, other: &$name) {
+                let n_a = self.n as f64;
+                let n_b = other.n as f64;
                 let delta = other.avg - self.avg;
 
                 self.n += other.n;
-                let n = self.n.to_f64().unwrap();
+                let n = self.n as f64;
                 let n_a_over_n = n_a / n;
                 let n_b_over_n = n_b / n;
                 self.avg += n_b_over_n * delta;
@@ -225,7 +541,7 @@ macro_rules! define_moments_common {
                         coeff_b *= n_a_over_n;
                         coeff_delta *= delta;
                         self.m[p - 2] +=
-                            binom.next().unwrap().to_f64().unwrap() *
+                            binom.next().unwrap() as f64 *
                             coeff_delta * (prev_m[p - 2 - k] * coeff_a +
                             other.m[p - 2 - k] * coeff_b);
                     }
@@ -240,6 +556,8 @@ macro_rules! define_moments_common {
         }
 
         $crate::impl_from_iterator!($name);
+        $crate::impl_from_iterator_samples!($name; f32, i32, u32, i64);
+        $crate::impl_add_assign!($name; f64, merge);
     );
 }
 
@@ -307,8 +625,8 @@ macro_rules! define_moments_inner {
 /// numerical issues.)
 ///
 /// [paper]: https://doi.org/10.1007/s00180-015-0637-z.
-/// [`Mean`]: ./struct.Mean.html
-/// [`Variance`]: ./struct.Variance.html
+/// [`Mean`]: ./type.Mean.html
+/// [`Variance`]: ./type.Variance.html
 /// [`Skewness`]: ./struct.Skewness.html
 /// [`Kurtosis`]: ./struct.Kurtosis.html
 ///
@@ -2,8 +2,9 @@
 /// numbers ("population").
 ///
 /// This can be used to estimate the standard error of the mean.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Skewness {
     /// Estimator of mean and variance.
     avg: MeanWithError,
@@ -14,13 +15,31 @@ pub struct Skewness {
 impl Skewness {
     /// Create a new skewness estimator.
     #[inline]
-    pub fn new() -> Skewness {
+    pub const fn new() -> Skewness {
         Skewness {
-            avg: MeanWithError::new(),
+            avg: MeanWithError::EMPTY,
             sum_3: 0.,
         }
     }
 
+    /// Create a skewness estimator from a sample size, a mean, a sample
+    /// variance and a skewness that were calculated elsewhere.
+    ///
+    /// This is useful for merging aggregates imported from other sources with
+    /// locally calculated ones. The skewness has to be calculated like
+    /// `skewness()` does.
+    #[inline]
+    pub fn from_moments(n: u64, mean: f64, sample_variance: f64, skewness: f64) -> Skewness {
+        let avg = MeanWithError::from_mean_and_variance(n, mean, sample_variance);
+        let sum_2 = avg.sum_2;
+        let sum_3 = if sum_2 == 0. {
+            0.
+        } else {
+            skewness * (sum_2*sum_2*sum_2).sqrt() / (n as f64).sqrt()
+        };
+        Skewness { avg, sum_3 }
+    }
+
     /// Increment the sample size.
     ///
     /// This does not update anything else.
@@ -39,13 +58,49 @@ impl Skewness {
         // This algorithm was suggested by Terriberry.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = self.len().to_f64().unwrap();
+        let n = self.len() as f64;
         let term = delta * delta_n * (n - 1.);
-        self.sum_3 += term * delta_n * (n - 2.)
-            - 3.*delta_n * self.avg.sum_2;
+        self.sum_3 += mul_add(term * delta_n, n - 2., -3.*delta_n * self.avg.sum_2);
         self.avg.add_inner(delta_n);
     }
 
+    /// Add an observation sampled from the population `count` times.
+    ///
+    /// This is equivalent to calling `add(sample)` `count` times, but takes
+    /// constant time, which is useful for ingesting frequency tables.
+    #[inline]
+    pub fn add_n(&mut self, sample: f64, count: u64) {
+        if count == 0 {
+            return;
+        }
+        self.merge(&Skewness {
+            avg: MeanWithError { avg: Mean { avg: sample, n: count }, sum_2: 0. },
+            sum_3: 0.,
+        });
+    }
+
+    /// Calculate the estimator of a slice of samples using a two-pass
+    /// algorithm with pairwise summation.
+    fn of_slice(samples: &[f64]) -> Skewness {
+        let avg = MeanWithError::of_slice(samples);
+        let mean = avg.mean();
+        let sum_3 = pairwise_sum(samples, |x| {
+            let d = x - mean;
+            d * d * d
+        });
+        Skewness { avg, sum_3 }
+    }
+
+    /// Add a slice of observations sampled from the population.
+    ///
+    /// This is equivalent to adding the samples one by one, but uses a
+    /// two-pass algorithm with pairwise summation, which is faster and more
+    /// accurate for large slices.
+    #[inline]
+    pub fn add_slice(&mut self, samples: &[f64]) {
+        self.merge(&Skewness::of_slice(samples));
+    }
+
     /// Determine whether the sample is empty.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -60,12 +115,32 @@ impl Skewness {
         self.avg.mean()
     }
 
+    /// Estimate the mean of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_mean(&self) -> Option<f64> {
+        self.avg.try_mean()
+    }
+
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
         self.avg.len()
     }
 
+    /// Return the sum of the squared deviations from the mean.
+    #[inline]
+    pub fn sum_2(&self) -> f64 {
+        self.avg.sum_2
+    }
+
+    /// Return the sum of the cubed deviations from the mean.
+    #[inline]
+    pub fn sum_3(&self) -> f64 {
+        self.sum_3
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
@@ -74,6 +149,14 @@ impl Skewness {
         self.avg.sample_variance()
     }
 
+    /// Calculate the sample variance.
+    ///
+    /// Returns `None` if there are less than two samples.
+    #[inline]
+    pub fn try_sample_variance(&self) -> Option<f64> {
+        self.avg.try_sample_variance()
+    }
+
     /// Calculate the population variance of the sample.
     ///
     /// This is a biased estimator of the variance of the population.
@@ -82,23 +165,158 @@ impl Skewness {
         self.avg.population_variance()
     }
 
+    /// Calculate the population variance of the sample.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_population_variance(&self) -> Option<f64> {
+        self.avg.try_population_variance()
+    }
+
+    /// Calculate the variance with the given delta degrees of freedom.
+    ///
+    /// The sum of the squared deviations from the mean is divided by
+    /// `n - ddof`. Returns 0 if there are not more than `ddof` samples.
+    #[inline]
+    pub fn variance_ddof(&self, ddof: u64) -> f64 {
+        self.avg.variance_ddof(ddof)
+    }
+
+    /// Calculate the variance with the given delta degrees of freedom.
+    ///
+    /// Returns `None` if there are not more than `ddof` samples.
+    #[inline]
+    pub fn try_variance_ddof(&self, ddof: u64) -> Option<f64> {
+        self.avg.try_variance_ddof(ddof)
+    }
+
     /// Estimate the standard error of the mean of the population.
     #[inline]
     pub fn error_mean(&self) -> f64 {
         self.avg.error()
     }
 
+    /// Estimate the standard error of the mean of a finite population of the
+    /// given size, from which the samples were drawn without replacement.
+    ///
+    /// This applies the finite population correction to `error_mean()`.
+    ///
+    /// Panics if the population is smaller than the sample.
+    #[inline]
+    pub fn error_mean_with_population_size(&self, population_size: u64) -> f64 {
+        self.avg.error_with_population_size(population_size)
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// This is the sample standard deviation divided by the mean. Returns 0
+    /// for less than two samples.
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> f64 {
+        self.avg.coefficient_of_variation()
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// Returns `None` if there are less than two samples or the mean is zero.
+    #[inline]
+    pub fn try_coefficient_of_variation(&self) -> Option<f64> {
+        self.avg.try_coefficient_of_variation()
+    }
+
+    /// Estimate the coefficient of variation of the population, correcting
+    /// the bias for small samples.
+    ///
+    /// See `Variance::corrected_coefficient_of_variation`.
+    #[inline]
+    pub fn corrected_coefficient_of_variation(&self) -> f64 {
+        self.avg.corrected_coefficient_of_variation()
+    }
+
     /// Estimate the skewness of the population.
+    ///
+    /// This is the biased moment coefficient of skewness `g1`. See
+    /// `sample_skewness` for the bias-corrected estimator.
     #[inline]
     pub fn skewness(&self) -> f64 {
         if self.sum_3 == 0. {
             return 0.;
         }
-        let n = self.len().to_f64().unwrap();
+        let n = self.len() as f64;
         let sum_2 = self.avg.sum_2;
         debug_assert_ne!(sum_2, 0.);
         n.sqrt() * self.sum_3 / (sum_2*sum_2*sum_2).sqrt()
     }
+
+    /// Estimate the skewness of the population.
+    ///
+    /// Returns `None` if the sample variance is zero, in particular if there
+    /// are less than two samples.
+    #[inline]
+    pub fn try_skewness(&self) -> Option<f64> {
+        if self.avg.sum_2 == 0. {
+            return None;
+        }
+        Some(self.skewness())
+    }
+
+    /// Calculate the sample skewness.
+    ///
+    /// This is the adjusted Fisher-Pearson standardized moment coefficient
+    /// `G1`, which corrects the bias of `skewness` for small samples, as
+    /// reported by R and pandas. Returns 0 for less than three samples.
+    #[inline]
+    pub fn sample_skewness(&self) -> f64 {
+        if self.len() < 3 {
+            return 0.;
+        }
+        let n = self.len() as f64;
+        self.skewness() * (n * (n - 1.)).sqrt() / (n - 2.)
+    }
+
+    /// Calculate the sample skewness.
+    ///
+    /// Returns `None` if there are less than three samples or the sample
+    /// variance is zero.
+    #[inline]
+    pub fn try_sample_skewness(&self) -> Option<f64> {
+        if self.len() < 3 || self.avg.sum_2 == 0. {
+            return None;
+        }
+        Some(self.sample_skewness())
+    }
+}
+
+impl Skewness {
+    /// The length of a snapshot in bytes.
+    pub const SNAPSHOT_LEN: usize = snapshot::HEADER_LEN + 32;
+
+    /// Write a snapshot of the estimator.
+    ///
+    /// The snapshot has a stable, versioned little-endian layout, so it can be
+    /// restored with `from_bytes` by earlier and later versions of this crate.
+    pub fn to_bytes(&self) -> [u8; Skewness::SNAPSHOT_LEN] {
+        let mut buf = [0; Skewness::SNAPSHOT_LEN];
+        Writer::new(&mut buf, tag::SKEWNESS)
+            .f64(self.avg.avg.avg).u64(self.avg.avg.n).f64(self.avg.sum_2)
+            .f64(self.sum_3);
+        buf
+    }
+
+    /// Restore an estimator from a snapshot written by `to_bytes`.
+    ///
+    /// Returns an error if the bytes are not a snapshot of this estimator.
+    /// Fields appended by newer versions of this crate are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Skewness, ()> {
+        let mut r = Reader::new(bytes, tag::SKEWNESS, Skewness::SNAPSHOT_LEN)?;
+        Ok(Skewness {
+            avg: MeanWithError {
+                avg: Mean { avg: r.f64(), n: r.u64() },
+                sum_2: r.f64(),
+            },
+            sum_3: r.f64(),
+        })
+    }
 }
 
 impl Default for Skewness {
@@ -112,7 +330,7 @@ impl Estimate for Skewness {
     fn add(&mut self, x: f64) {
         let delta = x - self.mean();
         self.increment();
-        let n = self.len().to_f64().unwrap();
+        let n = self.len() as f64;
         self.add_inner(delta, delta/n);
     }
 
@@ -122,19 +340,47 @@ impl Estimate for Skewness {
     }
 }
 
+impl Reset for Skewness {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Skewness::new();
+    }
+}
+
 impl Merge for Skewness {
     #[inline]
     fn merge(&mut self, other: &Skewness) {
-        let len_self = self.len().to_f64().unwrap();
-        let len_other = other.len().to_f64().unwrap();
+        merge_canonical(self, other);
+    }
+}
+
+impl CanonicalMerge for Skewness {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        Skewness::is_empty(self)
+    }
+
+    #[inline]
+    fn cmp_canonical(&self, other: &Skewness) -> Ordering {
+        self.avg.cmp_canonical(&other.avg).then_with(|| cmp_bits(self.sum_3, other.sum_3))
+    }
+
+    #[inline]
+    fn merge_ordered(&mut self, other: &Skewness) {
+        let len_self = self.len() as f64;
+        let len_other = other.len() as f64;
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         let delta_n = delta / len_total;
-        self.sum_3 += other.sum_3
-            + delta*delta_n*delta_n * len_self*len_other*(len_self - len_other)
-            + 3.*delta_n * (len_self * other.avg.sum_2 - len_other * self.avg.sum_2);
+        self.sum_3 += mul_add(
+            3.*delta_n,
+            mul_add(len_self, other.avg.sum_2, -(len_other * self.avg.sum_2)),
+            other.sum_3
+                + delta*delta_n*delta_n * len_self*len_other*(len_self - len_other));
         self.avg.merge(&other.avg);
     }
 }
 
 impl_from_iterator!(Skewness);
+impl_from_iterator_samples!(Skewness; f32, i32, u32, i64);
+impl_add_assign!(Skewness; f64, merge);
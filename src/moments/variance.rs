@@ -1,3 +1,34 @@
+/// Estimate the arithmetic mean and the variance of a sequence of numbers
+/// ("population"), accumulating in the floating-point type `F`.
+///
+/// Usually, you want to use the alias [`Variance`] for `f64`. Using `f32`
+/// halves the memory required by the estimator, at the cost of precision.
+/// Like for [`GenericMean`], the sample size is counted in `C`.
+///
+/// [`GenericMean`]: ./struct.GenericMean.html
+///
+/// [`Variance`]: ./type.Variance.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::GenericVariance;
+///
+/// let a: GenericVariance<f32> = (1..6).map(|x| x as f32).collect();
+/// assert_eq!(a.mean(), 3.0f32);
+/// assert_eq!(a.sample_variance(), 2.5f32);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct GenericVariance<F, C = u64> {
+    /// Estimator of average.
+    avg: GenericMean<F, C>,
+    /// Intermediate sum of squares for calculating the variance.
+    sum_2: F,
+}
+
 /// Estimate the arithmetic mean and the variance of a sequence of numbers
 /// ("population").
 ///
@@ -12,20 +43,41 @@
 /// let a: Variance = (1..6).map(f64::from).collect();
 /// println!("The mean is {} ± {}.", a.mean(), a.error());
 /// ```
-#[derive(Debug, Clone)]
-#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct Variance {
-    /// Estimator of average.
-    avg: Mean,
-    /// Intermediate sum of squares for calculating the variance.
-    sum_2: f64,
-}
+pub type Variance = GenericVariance<f64>;
 
-impl Variance {
+impl<F: Float, C: Counter> GenericVariance<F, C> {
     /// Create a new variance estimator.
     #[inline]
-    pub fn new() -> Variance {
-        Variance { avg: Mean::new(), sum_2: 0. }
+    pub fn new() -> GenericVariance<F, C> {
+        GenericVariance { avg: GenericMean::new(), sum_2: F::zero() }
+    }
+
+    /// Create a variance estimator from a sample size, a mean and a sample
+    /// variance that were calculated elsewhere.
+    ///
+    /// This is useful for merging aggregates imported from other sources, like
+    /// databases, with locally calculated ones. The sample variance is ignored
+    /// if there are less than two samples.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Variance, Merge};
+    ///
+    /// let mut a: Variance = [1., 2.].iter().collect();
+    /// a.merge(&Variance::from_mean_and_variance(3, 4., 1.));
+    /// assert_eq!(a.len(), 5);
+    /// assert_eq!(a.mean(), 3.);
+    /// assert_eq!(a.sample_variance(), 2.5);
+    /// ```
+    #[inline]
+    pub fn from_mean_and_variance(n: u64, mean: F, sample_variance: F) -> GenericVariance<F, C> {
+        let avg = GenericMean::from_mean(n, mean);
+        if n < 2 {
+            return GenericVariance { avg, sum_2: F::zero() };
+        }
+        GenericVariance { avg, sum_2: sample_variance * len_to_float(n - 1) }
     }
 
     /// Increment the sample size.
@@ -42,14 +94,88 @@ impl Variance {
     ///
     /// This is useful for avoiding unnecessary divisions in the inner loop.
     #[inline]
-    fn add_inner(&mut self, delta_n: f64) {
+    fn add_inner(&mut self, delta_n: F) {
         // This algorithm introduced by Welford in 1962 trades numerical
         // stability for a division inside the loop.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let n = self.avg.len().to_f64().unwrap();
+        let n = len_to_float(self.avg.len());
         self.avg.add_inner(delta_n);
-        self.sum_2 += delta_n * delta_n * n * (n - 1.);
+        self.sum_2 = mul_add(delta_n * delta_n * n, n - F::one(), self.sum_2);
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, sample: F) {
+        self.increment();
+        let delta_n = (sample - self.avg.mean())
+            / len_to_float(self.len());
+        self.add_inner(delta_n);
+    }
+
+    /// Add an observation sampled from the population `count` times.
+    ///
+    /// This is equivalent to calling `add(sample)` `count` times, but takes
+    /// constant time, which is useful for ingesting frequency tables.
+    #[inline]
+    pub fn add_n(&mut self, sample: F, count: u64) {
+        if count == 0 {
+            return;
+        }
+        self.merge(&GenericVariance {
+            avg: GenericMean { avg: sample, n: C::from_u64(count) },
+            sum_2: F::zero(),
+        });
+    }
+
+    /// Calculate the estimator of a slice of samples using a two-pass
+    /// algorithm with pairwise summation.
+    fn of_slice(samples: &[F]) -> GenericVariance<F, C> {
+        let avg = GenericMean::of_slice(samples);
+        if avg.is_empty() {
+            return GenericVariance::new();
+        }
+        let mean = avg.mean();
+        // The second sum corrects for the rounding error of the mean, see the
+        // corrected two-pass algorithm by Chan, Golub and LeVeque.
+        let sum_2 = pairwise_sum(samples, |x| (x - mean) * (x - mean));
+        let sum_1 = pairwise_sum(samples, |x| x - mean);
+        let n = len_to_float(avg.len());
+        let sum_2 = (sum_2 - sum_1 * sum_1 / n).max(F::zero());
+        GenericVariance { avg, sum_2 }
+    }
+
+    /// Add a slice of observations sampled from the population.
+    ///
+    /// This is equivalent to adding the samples one by one, but uses a
+    /// two-pass algorithm with pairwise summation, which is faster and more
+    /// accurate for large slices.
+    #[inline]
+    pub fn add_slice(&mut self, samples: &[F]) {
+        self.merge(&GenericVariance::of_slice(samples));
+    }
+
+    /// Remove an observation that was previously added.
+    ///
+    /// This is useful for sliding windows or for correcting a mistaken
+    /// `add`. Removing from an empty estimator does nothing. Note that the
+    /// rounding errors of adding and removing do not cancel exactly, so they
+    /// accumulate over many removals, and that removing samples far from the
+    /// mean suffers from cancellation in the variance. Removing a sample that
+    /// was never added results in meaningless estimates.
+    #[inline]
+    pub fn remove(&mut self, sample: F) {
+        // This reverses the update by Welford.
+        let delta = sample - self.avg.mean();
+        self.avg.remove(sample);
+        if self.avg.is_empty() {
+            self.sum_2 = F::zero();
+            return;
+        }
+        self.sum_2 = self.sum_2 - delta * (sample - self.avg.mean());
+        if self.sum_2 < F::zero() {
+            self.sum_2 = F::zero();
+        }
     }
 
     /// Determine whether the sample is empty.
@@ -62,73 +188,352 @@ impl Variance {
     ///
     /// Returns 0 for an empty sample.
     #[inline]
-    pub fn mean(&self) -> f64 {
+    pub fn mean(&self) -> F {
         self.avg.mean()
     }
 
+    /// Estimate the mean of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_mean(&self) -> Option<F> {
+        self.avg.try_mean()
+    }
+
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
         self.avg.len()
     }
 
+    /// Return the sum of the squared deviations from the mean.
+    ///
+    /// This is the raw component of the variance, which is useful for
+    /// statistics like ANOVA or the pooled variance.
+    #[inline]
+    pub fn sum_2(&self) -> F {
+        self.sum_2
+    }
+
     /// Calculate the sample variance.
     ///
     /// This is an unbiased estimator of the variance of the population.
     #[inline]
-    pub fn sample_variance(&self) -> f64 {
+    pub fn sample_variance(&self) -> F {
         if self.avg.len() < 2 {
-            return 0.;
+            return F::zero();
+        }
+        self.sum_2 / len_to_float(self.avg.len() - 1)
+    }
+
+    /// Calculate the sample variance.
+    ///
+    /// Returns `None` if there are less than two samples.
+    #[inline]
+    pub fn try_sample_variance(&self) -> Option<F> {
+        if self.len() < 2 {
+            return None;
         }
-        self.sum_2 / (self.avg.len() - 1).to_f64().unwrap()
+        Some(self.sample_variance())
     }
 
     /// Calculate the population variance of the sample.
     ///
     /// This is a biased estimator of the variance of the population.
     #[inline]
-    pub fn population_variance(&self) -> f64 {
+    pub fn population_variance(&self) -> F {
         let n = self.avg.len();
         if n < 2 {
-            return 0.;
+            return F::zero();
+        }
+        self.sum_2 / len_to_float(n)
+    }
+
+    /// Calculate the population variance of the sample.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_population_variance(&self) -> Option<F> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.population_variance())
+    }
+
+    /// Calculate the variance with the given delta degrees of freedom.
+    ///
+    /// The sum of the squared deviations from the mean is divided by
+    /// `n - ddof`, like `var` of NumPy. `ddof = 1` gives the sample variance
+    /// and `ddof = 0` the population variance. Returns 0 if there are not more
+    /// than `ddof` samples.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// let a: Variance = (1..6).collect();
+    /// assert_eq!(a.variance_ddof(1), a.sample_variance());
+    /// assert_eq!(a.variance_ddof(2), 10. / 3.);
+    /// ```
+    #[inline]
+    pub fn variance_ddof(&self, ddof: u64) -> F {
+        let n = self.avg.len();
+        if n <= ddof {
+            return F::zero();
+        }
+        self.sum_2 / len_to_float(n - ddof)
+    }
+
+    /// Calculate the variance with the given delta degrees of freedom.
+    ///
+    /// Returns `None` if there are not more than `ddof` samples.
+    #[inline]
+    pub fn try_variance_ddof(&self, ddof: u64) -> Option<F> {
+        if self.len() <= ddof {
+            return None;
+        }
+        Some(self.variance_ddof(ddof))
+    }
+
+    /// Calculate the variance around a known mean of the population.
+    ///
+    /// If the mean of the population is known, this is an unbiased estimator
+    /// of the variance of the population that does not lose a degree of
+    /// freedom for estimating the mean. Returns 0 for an empty sample.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// let a: Variance = [1., 3.].iter().collect();
+    /// assert_eq!(a.variance_with_known_mean(2.), 1.);
+    /// assert_eq!(a.variance_with_known_mean(0.), 5.);
+    /// ```
+    #[inline]
+    pub fn variance_with_known_mean(&self, mean: F) -> F {
+        let n = self.avg.len();
+        if n == 0 {
+            return F::zero();
         }
-        self.sum_2 / n.to_f64().unwrap()
+        let n = len_to_float(n);
+        let delta = self.mean() - mean;
+        (self.sum_2 + n * delta * delta) / n
     }
 
     /// Estimate the standard error of the mean of the population.
     #[inline]
-    pub fn error(&self) -> f64 {
+    pub fn error(&self) -> F {
         let n = self.avg.len();
         if n == 0 {
-            return 0.;
+            return F::zero();
+        }
+        (self.sample_variance() / len_to_float(n)).sqrt()
+    }
+
+    /// Estimate the standard error of the mean of the population.
+    ///
+    /// Returns `None` if there are less than two samples.
+    #[inline]
+    pub fn try_error(&self) -> Option<F> {
+        if self.len() < 2 {
+            return None;
+        }
+        Some(self.error())
+    }
+
+    /// Estimate the standard error of the mean of a finite population of the
+    /// given size, from which the samples were drawn without replacement.
+    ///
+    /// This applies the finite population correction `sqrt((N - n) / (N - 1))`
+    /// to `error()`. If the whole population was sampled, the error is 0.
+    /// Returns 0 for an empty sample.
+    ///
+    /// Panics if the population is smaller than the sample.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// let a: Variance = (1..6).collect();
+    /// assert!(a.error_with_population_size(9) < a.error());
+    /// assert_eq!(a.error_with_population_size(5), 0.);
+    /// ```
+    #[inline]
+    pub fn error_with_population_size(&self, population_size: u64) -> F {
+        let n = self.avg.len();
+        assert!(n <= population_size, "the population must not be smaller than the sample");
+        if n == 0 || n == population_size {
+            return F::zero();
+        }
+        let correction = len_to_float::<F>(population_size - n)
+            / len_to_float(population_size - 1);
+        self.error() * correction.sqrt()
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// This is the sample standard deviation divided by the mean. It is only
+    /// meaningful for samples on a ratio scale, where the mean is positive.
+    /// Returns 0 for less than two samples.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::Variance;
+    ///
+    /// let a: Variance = [2., 4., 6.].iter().collect();
+    /// assert_eq!(a.coefficient_of_variation(), 0.5);
+    /// assert_eq!(a.corrected_coefficient_of_variation(), 0.5 * (1. + 1. / 12.));
+    /// ```
+    #[inline]
+    pub fn coefficient_of_variation(&self) -> F {
+        if self.avg.len() < 2 {
+            return F::zero();
+        }
+        self.sample_variance().sqrt() / self.mean()
+    }
+
+    /// Estimate the coefficient of variation of the population.
+    ///
+    /// Returns `None` if there are less than two samples or the mean is zero.
+    #[inline]
+    pub fn try_coefficient_of_variation(&self) -> Option<F> {
+        if self.len() < 2 || self.mean() == F::zero() {
+            return None;
         }
-        (self.sample_variance() / n.to_f64().unwrap()).sqrt()
+        Some(self.coefficient_of_variation())
     }
 
+    /// Estimate the coefficient of variation of the population, correcting
+    /// the bias for small samples.
+    ///
+    /// The coefficient of variation of the sample underestimates the one of
+    /// the population. For normally distributed samples, multiplying it by
+    /// `1 + 1 / (4 n)` corrects most of the bias. Returns 0 for less than two
+    /// samples.
+    #[inline]
+    pub fn corrected_coefficient_of_variation(&self) -> F {
+        if self.avg.len() < 2 {
+            return F::zero();
+        }
+        let n: F = len_to_float(self.avg.len());
+        let four = F::one() + F::one() + F::one() + F::one();
+        self.coefficient_of_variation() * (F::one() + F::one() / (four * n))
+    }
 }
 
-impl core::default::Default for Variance {
-    fn default() -> Variance {
-        Variance::new()
+#[cfg(feature = "wide")]
+impl GenericVariance<f64> {
+    /// Add a slice of observations sampled from the population, using SIMD.
+    ///
+    /// This is equivalent to `add_slice`, but several times faster for large
+    /// slices. The results can differ in rounding.
+    ///
+    /// Requires the `wide` feature.
+    #[inline]
+    pub fn add_slice_simd(&mut self, samples: &[f64]) {
+        if samples.is_empty() {
+            return;
+        }
+        let n = samples.len() as u64;
+        let (sum, _) = simd_sums(samples, 0.);
+        let mean = sum / n as f64;
+        let (sum_1, sum_2) = simd_sums(samples, mean);
+        self.merge(&GenericVariance {
+            avg: GenericMean { avg: mean, n },
+            sum_2: (sum_2 - sum_1 * sum_1 / n as f64).max(0.),
+        });
     }
 }
 
-impl Estimate for Variance {
+impl GenericVariance<f64> {
+    /// An empty estimator.
+    ///
+    /// This is equivalent to `Variance::new()`, but can be used in constant
+    /// contexts, for instance to initialize a static.
+    pub const EMPTY: Variance = GenericVariance { avg: Mean::EMPTY, sum_2: 0. };
+
+    /// The length of a snapshot in bytes.
+    pub const SNAPSHOT_LEN: usize = snapshot::HEADER_LEN + 24;
+
+    /// Write a snapshot of the estimator.
+    ///
+    /// The snapshot has a stable, versioned little-endian layout, so it can be
+    /// restored with `from_bytes` by earlier and later versions of this crate.
+    pub fn to_bytes(&self) -> [u8; Variance::SNAPSHOT_LEN] {
+        let mut buf = [0; Variance::SNAPSHOT_LEN];
+        Writer::new(&mut buf, tag::VARIANCE)
+            .f64(self.avg.avg).u64(self.avg.n).f64(self.sum_2);
+        buf
+    }
+
+    /// Restore an estimator from a snapshot written by `to_bytes`.
+    ///
+    /// Returns an error if the bytes are not a snapshot of this estimator.
+    /// Fields appended by newer versions of this crate are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Variance, ()> {
+        let mut r = Reader::new(bytes, tag::VARIANCE, Variance::SNAPSHOT_LEN)?;
+        Ok(GenericVariance {
+            avg: GenericMean { avg: r.f64(), n: r.u64() },
+            sum_2: r.f64(),
+        })
+    }
+}
+
+impl<F: Float, C: Counter> core::default::Default for GenericVariance<F, C> {
+    fn default() -> GenericVariance<F, C> {
+        GenericVariance::new()
+    }
+}
+
+impl<F: Float, C: Counter> Estimate for GenericVariance<F, C> {
     #[inline]
     fn add(&mut self, sample: f64) {
-        self.increment();
-        let delta_n = (sample - self.avg.mean())
-            / self.len().to_f64().unwrap();
-        self.add_inner(delta_n);
+        GenericVariance::add(self, float_from_f64(sample));
     }
 
     #[inline]
     fn estimate(&self) -> f64 {
-        self.population_variance()
+        float_to_f64(self.population_variance())
     }
 }
 
-impl Merge for Variance {
+impl<F: Float, C: Counter> Reset for GenericVariance<F, C> {
+    #[inline]
+    fn reset(&mut self) {
+        *self = GenericVariance::new();
+    }
+}
+
+impl<F: Float, C: Counter> core::fmt::Display for GenericVariance<F, C> {
+    /// Format the mean with its standard error, like `3.21 ± 0.04`.
+    ///
+    /// The error is rounded to the number of significant figures given by the
+    /// precision (1 by default), and the mean is rounded to match.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::MeanWithError;
+    ///
+    /// let a = MeanWithError::from_mean_and_variance(100, 3.2149, 0.1681);
+    /// assert_eq!(format!("{}", a), "3.21 ± 0.04");
+    /// assert_eq!(format!("{:.2}", a), "3.215 ± 0.041");
+    /// ```
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        crate::format::fmt_with_error(f, float_to_f64(self.mean()), float_to_f64(self.error()))
+    }
+}
+
+impl<F: Float, C: Counter> Merge for GenericVariance<F, C> {
     /// Merge another sample into this one.
     ///
     ///
@@ -147,17 +552,37 @@ impl Merge for Variance {
     /// assert_eq!(avg_total.sample_variance(), avg_left.sample_variance());
     /// ```
     #[inline]
-    fn merge(&mut self, other: &Variance) {
+    fn merge(&mut self, other: &GenericVariance<F, C>) {
+        merge_canonical(self, other);
+    }
+}
+
+impl<F: Float, C: Counter> CanonicalMerge for GenericVariance<F, C> {
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.avg.n == C::ZERO
+    }
+
+    #[inline]
+    fn cmp_canonical(&self, other: &GenericVariance<F, C>) -> Ordering {
+        self.avg.cmp_canonical(&other.avg).then_with(|| cmp_bits(self.sum_2, other.sum_2))
+    }
+
+    #[inline]
+    fn merge_ordered(&mut self, other: &GenericVariance<F, C>) {
         // This algorithm was proposed by Chan et al. in 1979.
         //
         // See https://en.wikipedia.org/wiki/Algorithms_for_calculating_variance.
-        let len_self = self.len().to_f64().unwrap();
-        let len_other = other.len().to_f64().unwrap();
+        let len_self: F = len_to_float(self.len());
+        let len_other: F = len_to_float(other.len());
         let len_total = len_self + len_other;
         let delta = other.mean() - self.mean();
         self.avg.merge(&other.avg);
-        self.sum_2 += other.sum_2 + delta*delta * len_self * len_other / len_total;
+        self.sum_2 = self.sum_2 + other.sum_2
+            + delta*delta * len_self * len_other / len_total;
     }
 }
 
-impl_from_iterator!(Variance);
+impl_generic_from_iterator!(GenericVariance);
+impl_duration!(GenericVariance);
+impl_generic_add_assign!(GenericVariance);
@@ -0,0 +1,160 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::vec;
+use std::string::ToString;
+
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric::Data, number_data_point::Value, summary_data_point::ValueAtQuantile,
+    Gauge, Histogram as OtlpHistogram, HistogramDataPoint, Metric, NumberDataPoint,
+    Summary, SummaryDataPoint};
+
+use super::{Histogram, Mean, Variance, Skewness, Kurtosis, Min, Max, Quantile, Stats};
+
+/// Convert an estimator into OpenTelemetry metric data.
+///
+/// Estimators of a single statistic become gauges. Estimators of the mean
+/// and higher moments become summaries with the sample size and the sum;
+/// `Stats` additionally reports its minimum and maximum as the quantiles 0
+/// and 1. Histograms with counts become histograms with explicit bounds. As
+/// required by OpenTelemetry, they have an empty bucket below and above the
+/// range. Note that OpenTelemetry assumes the buckets to include their upper
+/// bound instead of the lower one.
+///
+/// The data uses the protobuf types of the `opentelemetry-proto` crate, which
+/// can be sent directly to an OTLP collector. Requires the `"opentelemetry"`
+/// feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, ToOtlp};
+/// use average::opentelemetry_proto::tonic::metrics::v1::metric::Data;
+///
+/// let a: Variance = (1..6).collect();
+/// let metric = a.to_otlp_metric("latency", std::time::SystemTime::now());
+/// assert_eq!(metric.name, "latency");
+/// match metric.data {
+///     Some(Data::Summary(s)) => {
+///         assert_eq!(s.data_points[0].count, 5);
+///         assert_eq!(s.data_points[0].sum, 15.);
+///     }
+///     _ => unreachable!(),
+/// }
+/// ```
+pub trait ToOtlp {
+    /// Convert the estimator into metric data.
+    ///
+    /// The timestamps of the data points are not set.
+    fn to_otlp_data(&self) -> Data;
+
+    /// Convert the estimator into a metric with the given name, taken at the
+    /// given time.
+    fn to_otlp_metric(&self, name: &str, time: SystemTime) -> Metric {
+        let time = time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64);
+        let mut data = self.to_otlp_data();
+        match data {
+            Data::Gauge(ref mut g) => for p in &mut g.data_points {
+                p.time_unix_nano = time;
+            },
+            Data::Summary(ref mut s) => for p in &mut s.data_points {
+                p.time_unix_nano = time;
+            },
+            Data::Histogram(ref mut h) => for p in &mut h.data_points {
+                p.time_unix_nano = time;
+            },
+            _ => {},
+        }
+        Metric {
+            name: name.to_string(),
+            data: Some(data),
+            ..Metric::default()
+        }
+    }
+}
+
+/// Create gauge data with a single data point.
+fn gauge(value: f64) -> Data {
+    Data::Gauge(Gauge {
+        data_points: vec![NumberDataPoint {
+            value: Some(Value::AsDouble(value)),
+            ..NumberDataPoint::default()
+        }],
+    })
+}
+
+/// Create summary data with a single data point.
+fn summary(count: u64, mean: f64, quantile_values: vec::Vec<ValueAtQuantile>) -> Data {
+    Data::Summary(Summary {
+        data_points: vec![SummaryDataPoint {
+            count,
+            sum: mean * count as f64,
+            quantile_values,
+            ..SummaryDataPoint::default()
+        }],
+    })
+}
+
+macro_rules! impl_to_otlp {
+    ( gauge: $( $name:ty => $statistic:ident ),* ) => { $(
+        impl ToOtlp for $name {
+            #[inline]
+            fn to_otlp_data(&self) -> Data {
+                gauge(self.$statistic())
+            }
+        }
+    )* };
+    ( summary: $( $name:ty ),* ) => { $(
+        impl ToOtlp for $name {
+            #[inline]
+            fn to_otlp_data(&self) -> Data {
+                summary(self.len(), self.mean(), vec![])
+            }
+        }
+    )* };
+}
+
+impl_to_otlp!(gauge: Min => min, Max => max, Quantile => quantile);
+impl_to_otlp!(summary: Mean, Variance, Skewness, Kurtosis);
+
+impl ToOtlp for Stats {
+    fn to_otlp_data(&self) -> Data {
+        let quantile_values = if self.is_empty() {
+            vec![]
+        } else {
+            vec![
+                ValueAtQuantile { quantile: 0., value: self.min() },
+                ValueAtQuantile { quantile: 1., value: self.max() },
+            ]
+        };
+        summary(self.len(), self.mean(), quantile_values)
+    }
+}
+
+impl<H> ToOtlp for H
+    where H: Histogram<Count = u64>,
+          for<'a> &'a H: IntoIterator<Item = ((f64, f64), u64)>
+{
+    fn to_otlp_data(&self) -> Data {
+        let mut bucket_counts = vec![0];
+        let mut explicit_bounds = vec![];
+        let mut count = 0;
+        for ((lower, _), n) in self {
+            explicit_bounds.push(lower);
+            bucket_counts.push(n);
+            count += n;
+        }
+        if let Some(((_, upper), _)) = self.into_iter().last() {
+            explicit_bounds.push(upper);
+            bucket_counts.push(0);
+        }
+        Data::Histogram(OtlpHistogram {
+            data_points: vec![HistogramDataPoint {
+                count,
+                bucket_counts,
+                explicit_bounds,
+                ..HistogramDataPoint::default()
+            }],
+            ..OtlpHistogram::default()
+        })
+    }
+}
@@ -0,0 +1,159 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Merge, Variance};
+
+/// Flag outliers in a sequence of numbers ("population") by their z-score.
+///
+/// Each sample is compared to the mean and the sample standard deviation of
+/// the previous samples before it is added. It is an outlier if it deviates
+/// from the mean by more than the threshold times the standard deviation.
+/// Samples are only flagged once enough samples were added for a meaningful
+/// standard deviation.
+///
+/// Outliers are added like all other samples, so that the detector adapts to
+/// a changing population. To exclude them instead, use `check()` and only
+/// `add()` the samples that are not flagged.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::OutlierDetector;
+///
+/// let mut a = OutlierDetector::new(3.);
+/// for i in 0..100 {
+///     assert!(!a.add(f64::from(i % 10)));
+/// }
+/// assert!(a.check(100.));
+/// assert!(a.add(100.));
+/// assert_eq!(a.outliers(), 1);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct OutlierDetector {
+    /// Estimator of the mean and the variance.
+    avg: Variance,
+    /// The maximal absolute z-score of samples that are not outliers.
+    threshold: f64,
+    /// The number of samples required before flagging outliers.
+    min_samples: u64,
+    /// The number of flagged outliers.
+    outliers: u64,
+}
+
+impl OutlierDetector {
+    /// The default number of samples required before flagging outliers.
+    const MIN_SAMPLES: u64 = 10;
+
+    /// Create a new detector flagging samples with an absolute z-score larger
+    /// than `threshold`.
+    ///
+    /// Outliers are only flagged after 10 samples were added.
+    #[inline]
+    pub const fn new(threshold: f64) -> OutlierDetector {
+        OutlierDetector::with_min_samples(threshold, OutlierDetector::MIN_SAMPLES)
+    }
+
+    /// Create a new detector flagging samples with an absolute z-score larger
+    /// than `threshold`, once `min_samples` samples were added.
+    ///
+    /// At least two samples are always required.
+    #[inline]
+    pub const fn with_min_samples(threshold: f64, min_samples: u64) -> OutlierDetector {
+        OutlierDetector {
+            avg: Variance::EMPTY,
+            threshold,
+            min_samples: if min_samples < 2 { 2 } else { min_samples },
+            outliers: 0,
+        }
+    }
+
+    /// Return the threshold of the absolute z-score.
+    #[inline]
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size, including the outliers.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Return the number of samples that were flagged as outliers by `add()`.
+    #[inline]
+    pub fn outliers(&self) -> u64 {
+        self.outliers
+    }
+
+    /// Return the estimator of the mean and the variance of the samples.
+    #[inline]
+    pub fn variance(&self) -> &Variance {
+        &self.avg
+    }
+
+    /// Calculate the z-score of a sample relative to the samples added so far.
+    ///
+    /// Returns `None` if there are less than the required number of samples
+    /// or their standard deviation is zero.
+    pub fn z_score(&self, x: f64) -> Option<f64> {
+        if self.avg.len() < self.min_samples {
+            return None;
+        }
+        let std_dev = crate::math::sqrt(self.avg.sample_variance());
+        if std_dev == 0. {
+            return None;
+        }
+        Some((x - self.avg.mean()) / std_dev)
+    }
+
+    /// Determine whether a sample is an outlier, without adding it.
+    ///
+    /// If all previous samples are equal, any other sample is an outlier.
+    pub fn check(&self, x: f64) -> bool {
+        if self.avg.len() < self.min_samples {
+            return false;
+        }
+        match self.z_score(x) {
+            Some(z) => z.abs() > self.threshold || z.is_nan(),
+            None => x != self.avg.mean(),
+        }
+    }
+
+    /// Add an observation sampled from the population and return whether it
+    /// is an outlier relative to the previous samples.
+    #[inline]
+    pub fn add(&mut self, x: f64) -> bool {
+        let outlier = self.check(x);
+        if outlier {
+            self.outliers += 1;
+        }
+        self.avg.add(x);
+        outlier
+    }
+
+    /// Reset the detector to an empty sample, keeping its configuration.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.avg = Variance::EMPTY;
+        self.outliers = 0;
+    }
+}
+
+impl Merge for OutlierDetector {
+    /// Merge another sample into this one.
+    ///
+    /// The numbers of outliers are added, the configuration of `self` is kept.
+    #[inline]
+    fn merge(&mut self, other: &OutlierDetector) {
+        self.avg.merge(&other.avg);
+        self.outliers += other.outliers;
+    }
+}
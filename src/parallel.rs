@@ -0,0 +1,119 @@
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "deterministic")] use rayon::slice::ParallelSlice;
+#[cfg(feature = "deterministic")] use std::vec::Vec;
+
+use super::{Estimate, Merge, IntoSample, Mean, Variance, Skewness, Kurtosis,
+            Moments4, Min, Max, Range, Quantile, WeightedQuantile, Iqr, FiveNumber,
+            Describe, Stats};
+
+/// Merge estimators in parallel.
+///
+/// This is useful for combining estimators that were calculated for chunks of
+/// the data, for instance by different threads. Returns the default estimator
+/// if there are none.
+///
+/// With the `"deterministic"` feature, the estimators are merged sequentially
+/// in their original order, so that the result does not depend on the
+/// scheduling of the threads.
+///
+/// Requires the `rayon` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, par_merge};
+/// use rayon::prelude::*;
+///
+/// let data: Vec<f64> = (1..=100).map(f64::from).collect();
+/// let chunks: Vec<Variance> = data.par_chunks(10)
+///     .map(|c| c.iter().collect())
+///     .collect();
+/// let a: Variance = par_merge(chunks);
+/// assert_eq!(a.len(), 100);
+/// assert_eq!(a.mean(), 50.5);
+/// ```
+pub fn par_merge<E, I>(estimators: I) -> E
+    where E: Merge + Default + Send, I: IntoParallelIterator<Item=E>
+{
+    #[cfg(not(feature = "deterministic"))]
+    {
+        estimators.into_par_iter().reduce(E::default, |mut a, b| {
+            a.merge(&b);
+            a
+        })
+    }
+    #[cfg(feature = "deterministic")]
+    {
+        let estimators: Vec<E> = estimators.into_par_iter().collect();
+        merge_in_order(estimators)
+    }
+}
+
+/// Merge estimators sequentially in the given order.
+#[cfg(feature = "deterministic")]
+fn merge_in_order<E: Merge + Default>(estimators: Vec<E>) -> E {
+    let mut iter = estimators.into_iter();
+    let mut result = iter.next().unwrap_or_default();
+    for e in iter {
+        result.merge(&e);
+    }
+    result
+}
+
+/// The number of samples added to each estimator before merging, with the
+/// `"deterministic"` feature.
+#[cfg(feature = "deterministic")]
+const CHUNK_LEN: usize = 1 << 12;
+
+/// Implement `FromParallelIterator` for a mergeable estimator.
+///
+/// This is implemented for all mergeable estimators of single samples with a
+/// default configuration. Estimators requiring parameters, like histograms, or
+/// adding pairs of samples can be folded per thread and merged via
+/// `par_merge` instead.
+///
+/// The samples are added to one estimator per thread, which are then merged.
+///
+/// With the `"deterministic"` feature, the samples are collected first and
+/// split into chunks of constant length, so that the result does not depend on
+/// the number of threads and their scheduling. This requires memory for all
+/// samples.
+macro_rules! impl_from_par_iterator {
+    ( $( $name:ty ),* ) => { $(
+        impl<T: IntoSample + Send> FromParallelIterator<T> for $name {
+            #[cfg(not(feature = "deterministic"))]
+            fn from_par_iter<I>(par_iter: I) -> $name
+                where I: IntoParallelIterator<Item=T>
+            {
+                let estimators = par_iter.into_par_iter().fold(<$name>::default, |mut e, x| {
+                    e.add(x.into_sample());
+                    e
+                });
+                par_merge(estimators)
+            }
+
+            #[cfg(feature = "deterministic")]
+            fn from_par_iter<I>(par_iter: I) -> $name
+                where I: IntoParallelIterator<Item=T>
+            {
+                let samples: Vec<f64> = par_iter.into_par_iter()
+                    .map(IntoSample::into_sample)
+                    .collect();
+                let estimators: Vec<$name> = samples.par_chunks(CHUNK_LEN)
+                    .map(|chunk| {
+                        let mut e = <$name>::default();
+                        for &x in chunk {
+                            e.add(x);
+                        }
+                        e
+                    })
+                    .collect();
+                merge_in_order(estimators)
+            }
+        }
+    )* };
+}
+
+impl_from_par_iterator!(Mean, Variance, Skewness, Kurtosis, Moments4, Min, Max, Range,
+                        Quantile, WeightedQuantile, Iqr, FiveNumber, Describe, Stats);
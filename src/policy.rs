@@ -0,0 +1,163 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Estimate, Merge, Reset, AddError};
+
+/// What to do when a sample that is NaN or infinite is added to an estimator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub enum NanPolicy {
+    /// Ignore the sample.
+    Skip,
+    /// Add the sample anyway. A single NaN usually makes all following
+    /// estimates NaN. This is the behavior of the estimators without a policy.
+    #[default]
+    Propagate,
+    /// Ignore the sample and report an error.
+    Error,
+}
+
+/// Wrap an estimator to control what happens when a non-finite sample is
+/// added.
+///
+/// By default, a single NaN silently poisons a running estimate forever. This
+/// wrapper applies a [`NanPolicy`] to every sample before forwarding it to the
+/// inner estimator.
+///
+/// [`NanPolicy`]: ./enum.NanPolicy.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Mean, NanPolicy, WithNanPolicy};
+///
+/// let mut a = WithNanPolicy::new(Mean::new(), NanPolicy::Error);
+/// a.try_add(1.).unwrap();
+/// assert!(a.try_add(f64::NAN).is_err());
+/// a.try_add(3.).unwrap();
+/// assert_eq!(a.get().mean(), 2.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct WithNanPolicy<E> {
+    /// The wrapped estimator.
+    inner: E,
+    /// The policy for non-finite samples.
+    policy: NanPolicy,
+    /// The number of samples that were not added because of the policy.
+    #[cfg_attr(feature = "serde1", serde(default))]
+    skipped: u64,
+}
+
+impl<E: Estimate> WithNanPolicy<E> {
+    /// Wrap the given estimator, applying the given policy.
+    #[inline]
+    pub fn new(inner: E, policy: NanPolicy) -> WithNanPolicy<E> {
+        WithNanPolicy { inner, policy, skipped: 0 }
+    }
+
+    /// Return the policy for non-finite samples.
+    #[inline]
+    pub fn policy(&self) -> NanPolicy {
+        self.policy
+    }
+
+    /// Return the number of samples that were skipped or rejected because of
+    /// the policy.
+    ///
+    /// This makes data-quality problems visible instead of letting the
+    /// samples silently vanish.
+    #[inline]
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+
+    /// Return a reference to the wrapped estimator.
+    #[inline]
+    pub fn get(&self) -> &E {
+        &self.inner
+    }
+
+    /// Return the wrapped estimator.
+    #[inline]
+    pub fn into_inner(self) -> E {
+        self.inner
+    }
+
+    /// Add an observation sampled from the population, applying the policy.
+    ///
+    /// Returns an error if the sample is not finite and the policy is
+    /// `NanPolicy::Error`. The sample is not added in this case.
+    #[inline]
+    pub fn try_add(&mut self, x: f64) -> Result<(), AddError> {
+        if !x.is_finite() {
+            match self.policy {
+                NanPolicy::Skip => {
+                    self.skipped += 1;
+                    return Ok(());
+                },
+                NanPolicy::Propagate => {},
+                NanPolicy::Error => {
+                    self.skipped += 1;
+                    return Err(AddError::NonFinite);
+                },
+            }
+        }
+        self.inner.add(x);
+        Ok(())
+    }
+}
+
+impl<E: Estimate> Estimate for WithNanPolicy<E> {
+    /// Add an observation sampled from the population, applying the policy.
+    ///
+    /// Errors are ignored, so `NanPolicy::Error` behaves like
+    /// `NanPolicy::Skip`. Use `try_add` to observe them.
+    #[inline]
+    fn add(&mut self, x: f64) {
+        let _ = self.try_add(x);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.inner.estimate()
+    }
+}
+
+impl<E: Reset> Reset for WithNanPolicy<E> {
+    #[inline]
+    fn reset(&mut self) {
+        self.inner.reset();
+        self.skipped = 0;
+    }
+}
+
+impl<E: Merge> Merge for WithNanPolicy<E> {
+    /// Merge the wrapped estimator of another sample into this one, keeping
+    /// the policy of this one.
+    ///
+    /// The numbers of skipped samples are added.
+    #[inline]
+    fn merge(&mut self, other: &WithNanPolicy<E>) {
+        self.inner.merge(&other.inner);
+        self.skipped += other.skipped;
+    }
+}
+
+impl<E: Estimate> core::ops::AddAssign<f64> for WithNanPolicy<E> {
+    /// Add an observation sampled from the population, applying the policy.
+    #[inline]
+    fn add_assign(&mut self, x: f64) {
+        self.add(x);
+    }
+}
+
+impl<E: Merge> core::ops::AddAssign<&WithNanPolicy<E>> for WithNanPolicy<E> {
+    /// Merge another sample into this one.
+    #[inline]
+    fn add_assign(&mut self, other: &WithNanPolicy<E>) {
+        self.merge(other);
+    }
+}
@@ -0,0 +1,58 @@
+use std::io::Read;
+use std::string::String;
+use std::vec::Vec;
+
+use super::Describe;
+
+/// Describe the numeric columns of a CSV file.
+///
+/// The first row has to contain the names of the columns. A column is numeric
+/// if all its fields can be parsed as floating-point numbers, ignoring
+/// surrounding whitespace. Empty fields and `NaN` are skipped as missing
+/// values. The other columns are left out. Returns the names and
+/// descriptions of the numeric columns in their original order, or an error
+/// if the CSV cannot be read or parsed.
+///
+/// Requires the `"csv"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::describe_csv;
+///
+/// let data = "name,age,height\nAda,36,1.65\nAlan,41,\nGrace,85,1.57\n";
+/// let columns = describe_csv(data.as_bytes()).unwrap();
+/// assert_eq!(columns.len(), 2);
+/// let (name, age) = &columns[0];
+/// assert_eq!(name, "age");
+/// assert_eq!(age.len(), 3);
+/// assert_eq!(age.max(), 85.);
+/// assert_eq!(columns[1].1.len(), 2);
+/// ```
+pub fn describe_csv<R: Read>(reader: R) -> Result<Vec<(String, Describe)>, csv::Error> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let names: Vec<String> = reader.headers()?.iter().map(String::from).collect();
+    // The descriptions of the columns that are still numeric.
+    let mut columns: Vec<Option<Describe>> = names.iter().map(|_| Some(Describe::new())).collect();
+    let mut record = csv::StringRecord::new();
+    while reader.read_record(&mut record)? {
+        for (column, field) in columns.iter_mut().zip(record.iter()) {
+            if field.is_empty() {
+                continue;
+            }
+            if let Some(d) = column {
+                match field.parse::<f64>() {
+                    Ok(x) if x.is_nan() => {},
+                    Ok(x) => d.add(x),
+                    Err(_) => *column = None,
+                }
+            }
+        }
+    }
+    Ok(names.into_iter().zip(columns)
+        .filter_map(|(name, column)| column.map(|d| (name, d)))
+        .collect())
+}
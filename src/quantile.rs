@@ -1,23 +1,24 @@
-use core;
 use core::cmp::min;
 
-use conv::{ConvAsUtil, ConvUtil, ValueFrom};
-use num_traits::ToPrimitive;
 use float_ord::sort as sort_floats;
 #[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
 
-use super::Estimate;
+use super::{Estimate, Merge, MemoryUsage, Reset};
+use crate::special::binomial_cdf;
+use crate::snapshot::{self, tag, Reader, Writer};
 
 /// Estimate the p-quantile of a sequence of numbers ("population").
 ///
 /// The algorithm uses constant space but does not provide any upper bound on
-/// the error. For algorithms that use growing space with bounded error, see
-/// the [`quantiles`](https://crates.io/crates/quantiles) crate. They are
+/// the error. Estimators can be merged, at the cost of some accuracy. For
+/// algorithms that use growing space with bounded error, see the
+/// [`quantiles`](https://crates.io/crates/quantiles) crate. They are
 /// recommended over this algorithm for small samples.
 // This uses the P² algorithm introduced here:
 // http://www.cs.wustl.edu/~jain/papers/ftp/psqr.pdf
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Quantile {
     /// Marker heights.
     q: [f64; 5],
@@ -34,8 +35,9 @@ impl Quantile {
     ///
     /// Panics if `p` is not between 0 and 1.
     #[inline]
-    pub fn new(p: f64) -> Quantile {
-        assert!(0. <= p && p <= 1.);
+    #[allow(clippy::manual_range_contains)]  // `contains` is not `const`
+    pub const fn new(p: f64) -> Quantile {
+        assert!(p >= 0. && p <= 1.);
         Quantile {
             q: [0.; 5],
             n: [1, 2, 3, 4, 0],
@@ -54,14 +56,14 @@ impl Quantile {
     #[inline]
     fn parabolic(&self, i: usize, d: f64) -> f64 {
         debug_assert_eq!(d.abs(), 1.);
-        let s: i64 = d.approx().unwrap();  // FIXME
-        self.q[i] + d / (self.n[i + 1] - self.n[i - 1]).to_f64().unwrap()
-            * ((self.n[i] - self.n[i - 1] + s).to_f64().unwrap()
+        let s = d as i64;  // d == +-1
+        self.q[i] + d / (self.n[i + 1] - self.n[i - 1]) as f64
+            * ((self.n[i] - self.n[i - 1] + s) as f64
                * (self.q[i + 1] - self.q[i])
-               / (self.n[i + 1] - self.n[i]).to_f64().unwrap()
-               + (self.n[i + 1] - self.n[i] - s).to_f64().unwrap()
+               / (self.n[i + 1] - self.n[i]) as f64
+               + (self.n[i + 1] - self.n[i] - s) as f64
                * (self.q[i] - self.q[i - 1])
-               / (self.n[i] - self.n[i - 1]).to_f64().unwrap())
+               / (self.n[i] - self.n[i - 1]) as f64)
     }
 
     /// Linear prediction for marker height.
@@ -70,7 +72,7 @@ impl Quantile {
         debug_assert_eq!(d.abs(), 1.);
         let sum = if d < 0. { i - 1 } else { i + 1 };
         self.q[i] + d * (self.q[sum] - self.q[i])
-            / (self.n[sum] - self.n[i]).to_f64().unwrap()
+            / (self.n[sum] - self.n[i]) as f64
     }
 
     /// Estimate the p-quantile of the population.
@@ -89,28 +91,126 @@ impl Quantile {
         let mut heights: [f64; 4] = [
             self.q[0], self.q[1], self.q[2], self.q[3]
         ];
-        let len = usize::value_from(self.len()).unwrap();  // < 5
+        let len = self.len() as usize;  // < 5
         sort_floats(&mut heights[..len]);
-        let desired_index = ConvUtil::approx_as::<f64>(len).unwrap() * self.p() - 1.;
-        let mut index = desired_index.ceil();
+        let desired_index = len as f64 * self.p() - 1.;
+        let index = crate::math::ceil(desired_index);
         if desired_index == index && index >= 0. {
-            let index: usize = index.approx().unwrap();  // < 5
+            let index = index as usize;  // 0 <= index < 5
             if index < len - 1 {
                 // `q[index]` and `q[index + 1]` are equally valid estimates,
                 // by convention we take their average.
-                return 0.5*self.q[index] + 0.5*self.q[index + 1];
+                return 0.5*heights[index] + 0.5*heights[index + 1];
             }
         }
-        index = index.max(0.);
-        let mut index: usize = index.approx().unwrap();  // < 5
-        index = min(index, len - 1);
-        self.q[index]
+        let index = min(index.max(0.) as usize, len - 1);  // 0 <= index < 5
+        heights[index]
+    }
+
+    /// Estimate the p-quantile of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_quantile(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.quantile())
+    }
+
+    /// Estimate a distribution-free confidence interval for the p-quantile of
+    /// the population.
+    ///
+    /// The number of samples below the p-quantile follows a binomial
+    /// distribution, so the interval between two order statistics covers it
+    /// with a known probability. This returns the pair of order statistics
+    /// that covers the p-quantile with at least the given `confidence`, with
+    /// equal probability of missing it on either side. If the sample is too
+    /// small for this, the interval extends to the minimum or maximum, and its
+    /// actual coverage is lower. For more than 5 samples, the order statistics
+    /// are interpolated between the markers, so they are approximate.
+    ///
+    /// Panics if `confidence` is not between 0 and 1. Returns `None` for an
+    /// empty sample.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Quantile, Estimate};
+    ///
+    /// let mut q = Quantile::new(0.5);
+    /// for i in 0..1000 {
+    ///     q.add(f64::from(i));
+    /// }
+    /// let (lower, upper) = q.confidence_interval(0.95).unwrap();
+    /// assert!(lower < q.quantile() && q.quantile() < upper);
+    /// ```
+    pub fn confidence_interval(&self, confidence: f64) -> Option<(f64, f64)> {
+        assert!((0. ..=1.).contains(&confidence));
+        if self.is_empty() {
+            return None;
+        }
+        let n = self.len();
+        let p = self.p();
+        let alpha = 0.5 * (1. - confidence);
+        // The interval `[x_(l), x_(u)]` misses the quantile if less than `l`
+        // or at least `u` samples are below it. The probability of missing on
+        // either side is increasing in `l` or decreasing in `u`, so find the
+        // largest `l` and the smallest `u` keeping it below `alpha` by
+        // bisection.
+        let (mut lo, mut hi) = (1, n);
+        while lo < hi {
+            let mid = hi - (hi - lo) / 2;
+            if binomial_cdf(mid - 1, n, p) <= alpha {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        let lower = lo;
+        let (mut lo, mut hi) = (1, n);
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if binomial_cdf(mid - 1, n, p) >= 1. - alpha {
+                hi = mid;
+            } else {
+                lo = mid + 1;
+            }
+        }
+        let upper = lo.max(lower);
+        Some((self.order_statistic(lower), self.order_statistic(upper)))
+    }
+
+    /// Estimate the `r`-th smallest sample, starting at 1.
+    fn order_statistic(&self, r: u64) -> f64 {
+        debug_assert!(1 <= r && r <= self.len());
+        if self.len() < 5 {
+            let len = self.len() as usize;  // < 5
+            let mut heights: [f64; 4] = [
+                self.q[0], self.q[1], self.q[2], self.q[3]
+            ];
+            sort_floats(&mut heights[..len]);
+            return heights[r as usize - 1];  // 1 <= r <= len
+        }
+        let r = r as f64;
+        for i in 1..5 {
+            let (a, b) = (self.n[i - 1] as f64, self.n[i] as f64);
+            if r <= b {
+                if r <= a {
+                    return self.q[i - 1];
+                }
+                return self.q[i - 1] + (r - a) / (b - a) * (self.q[i] - self.q[i - 1]);
+            }
+        }
+        self.q[4]
     }
 
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
-        u64::value_from(self.n[4]).unwrap()  // n[4] >= 0
+        debug_assert!(self.n[4] >= 0);
+        self.n[4] as u64
     }
 
     /// Determine whether the sample is empty.
@@ -120,6 +220,44 @@ impl Quantile {
     }
 }
 
+impl Quantile {
+    /// The length of a snapshot in bytes.
+    pub const SNAPSHOT_LEN: usize = snapshot::HEADER_LEN + 128;
+
+    /// Write a snapshot of the estimator.
+    ///
+    /// The snapshot has a stable, versioned little-endian layout, so it can be
+    /// restored with `from_bytes` by earlier and later versions of this crate.
+    pub fn to_bytes(&self) -> [u8; Quantile::SNAPSHOT_LEN] {
+        let mut buf = [0; Quantile::SNAPSHOT_LEN];
+        let mut w = Writer::new(&mut buf, tag::QUANTILE);
+        w.f64(self.p());
+        for i in 0..5 {
+            w.f64(self.q[i]).i64(self.n[i]).f64(self.m[i]);
+        }
+        buf
+    }
+
+    /// Restore an estimator from a snapshot written by `to_bytes`.
+    ///
+    /// Returns an error if the bytes are not a snapshot of this estimator.
+    /// Fields appended by newer versions of this crate are ignored.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Quantile, ()> {
+        let mut r = Reader::new(bytes, tag::QUANTILE, Quantile::SNAPSHOT_LEN)?;
+        let p = r.f64();
+        if !(0. ..=1.).contains(&p) {
+            return Err(());
+        }
+        let mut quantile = Quantile::new(p);
+        for i in 0..5 {
+            quantile.q[i] = r.f64();
+            quantile.n[i] = r.i64();
+            quantile.m[i] = r.f64();
+        }
+        Ok(quantile)
+    }
+}
+
 impl core::default::Default for Quantile {
     /// Create a new median estimator.
     fn default() -> Quantile {
@@ -132,7 +270,7 @@ impl Estimate for Quantile {
     fn add(&mut self, x: f64) {
         // n[4] is the sample size.
         if self.n[4] < 5 {
-            self.q[usize::value_from(self.n[4]).unwrap()] = x;  // n[4] < 5
+            self.q[self.n[4] as usize] = x;  // 0 <= n[4] < 5
             self.n[4] += 1;
             if self.n[4] == 5 {
                 sort_floats(&mut self.q);
@@ -168,7 +306,7 @@ impl Estimate for Quantile {
 
         // Adjust height of markers.
         for i in 1..4 {
-            let d = self.m[i] - self.n[i].to_f64().unwrap();
+            let d = self.m[i] - self.n[i] as f64;
             if d >= 1. && self.n[i + 1] - self.n[i] > 1 ||
                d <= -1. && self.n[i - 1] - self.n[i] < -1 {
                 let d = d.signum();
@@ -178,8 +316,7 @@ impl Estimate for Quantile {
                 } else {
                     self.q[i] = self.linear(i, d);
                 }
-                let delta: i64 = d.approx().unwrap();  // d == +-1
-                self.n[i] += delta;
+                self.n[i] += d as i64;  // d == +-1
             }
         }
     }
@@ -189,6 +326,118 @@ impl Estimate for Quantile {
     }
 }
 
+impl Reset for Quantile {
+    #[inline]
+    fn reset(&mut self) {
+        *self = Quantile::new(self.p());
+    }
+}
+
+impl MemoryUsage for Quantile {
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of::<Quantile>()
+    }
+}
+
+impl Quantile {
+    /// Return the samples of an estimator with less than 5 samples.
+    #[inline]
+    fn initial_samples(&self) -> &[f64] {
+        debug_assert!(self.len() < 5);
+        &self.q[..self.n[4] as usize]  // 0 <= n[4] < 5
+    }
+
+    /// Estimate the number of samples smaller than or equal to `x`, by
+    /// interpolating linearly between the markers.
+    ///
+    /// Requires at least 5 samples.
+    fn rank(&self, x: f64) -> f64 {
+        if x < self.q[0] {
+            return 0.;
+        }
+        for i in 1..5 {
+            if x < self.q[i] {
+                let fraction = (x - self.q[i - 1]) / (self.q[i] - self.q[i - 1]);
+                let (a, b) = (self.n[i - 1] as f64, self.n[i] as f64);
+                return a + fraction * (b - a);
+            }
+        }
+        self.n[4] as f64
+    }
+}
+
+impl Merge for Quantile {
+    /// Merge another sample into this one.
+    ///
+    /// If one of the estimators has less than 5 samples, they are added to
+    /// the other one, which is exact. Otherwise, the markers of the merged
+    /// estimator are interpolated from the ranks estimated by the markers of
+    /// both estimators. This loses some accuracy compared to adding all
+    /// samples to one estimator, in particular if the estimators saw samples
+    /// from different distributions.
+    ///
+    /// Panics if the estimators do not estimate the same quantile.
+    fn merge(&mut self, other: &Quantile) {
+        assert_eq!(self.p(), other.p(), "Both estimators must estimate the same quantile");
+        if other.is_empty() {
+            return;
+        }
+        if other.len() < 5 {
+            for &x in other.initial_samples() {
+                self.add(x);
+            }
+            return;
+        }
+        if self.len() < 5 {
+            let mut merged = other.clone();
+            for &x in self.initial_samples() {
+                merged.add(x);
+            }
+            *self = merged;
+            return;
+        }
+
+        // The combined rank is piecewise linear between the marker heights of
+        // both estimators, so it is enough to evaluate it at those.
+        let mut heights = [0.; 10];
+        heights[..5].copy_from_slice(&self.q);
+        heights[5..].copy_from_slice(&other.q);
+        sort_floats(&mut heights);
+        let mut ranks = [0.; 10];
+        for (r, &x) in ranks.iter_mut().zip(heights.iter()) {
+            *r = self.rank(x) + other.rank(x);
+        }
+
+        let len = self.n[4] + other.n[4];
+        let len_f = len as f64;
+        let mut merged = Quantile::new(self.p());
+        for i in 0..5 {
+            let desired = 1. + (len_f - 1.) * merged.dm[i];
+            merged.m[i] = desired;
+            // Find the height where the combined rank reaches the desired
+            // position.
+            let j = ranks.iter().position(|&r| r >= desired).unwrap_or(9);
+            merged.q[i] = if j == 0 || ranks[j] == ranks[j - 1] {
+                heights[j]
+            } else {
+                let fraction = (desired - ranks[j - 1]) / (ranks[j] - ranks[j - 1]);
+                heights[j - 1] + fraction * (heights[j] - heights[j - 1])
+            };
+            // Keep the positions strictly increasing and within the sample.
+            let lowest = if i == 0 { 1 } else { merged.n[i - 1] + 1 };
+            let highest = len - (4 - i as i64);
+            // The cast saturates, and the position is clamped to the sample.
+            merged.n[i] = (crate::math::round(desired) as i64).max(lowest).min(highest);
+        }
+        merged.q[0] = self.q[0].min(other.q[0]);
+        merged.q[4] = self.q[4].max(other.q[4]);
+        *self = merged;
+    }
+}
+
+impl_add_assign!(Quantile; f64, merge);
+
 #[test]
 fn reference() {
     let observations = [
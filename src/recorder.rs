@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::vec::Vec;
+
+use ::metrics::{Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata,
+                Recorder, SharedString, Unit};
+
+use super::Estimate;
+
+/// Lock an estimator, ignoring poisoning.
+#[inline]
+fn lock<E>(e: &Mutex<E>) -> MutexGuard<'_, E> {
+    e.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// The estimator backing a histogram of the `metrics` crate.
+#[derive(Debug)]
+struct Handle<E>(Mutex<E>);
+
+impl<E: Estimate> HistogramFn for Handle<E> {
+    #[inline]
+    fn record(&self, value: f64) {
+        lock(&self.0).add(value);
+    }
+
+    fn record_many(&self, value: f64, count: usize) {
+        let mut e = lock(&self.0);
+        for _ in 0..count {
+            e.add(value);
+        }
+    }
+}
+
+/// A recorder of the `metrics` crate aggregating histograms into estimators.
+///
+/// Every histogram key, including its labels, gets its own estimator of type
+/// `E`, which receives all values recorded with `metrics::histogram!`. This
+/// lets applications that already emit metrics aggregate them without
+/// storing the values. Counters and gauges are ignored; combine this recorder
+/// with another one if you need them.
+///
+/// Requires the `"metrics"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{MetricsRecorder, Variance};
+///
+/// let recorder: MetricsRecorder<Variance> = MetricsRecorder::new();
+/// metrics::with_local_recorder(&recorder, || {
+///     metrics::histogram!("latency").record(12.);
+///     metrics::histogram!("latency").record(18.);
+/// });
+/// let latency = recorder.get(&"latency".into()).unwrap();
+/// assert_eq!(latency.mean(), 15.);
+/// ```
+#[derive(Debug)]
+pub struct MetricsRecorder<E> {
+    histograms: Mutex<BTreeMap<Key, Arc<Handle<E>>>>,
+}
+
+impl<E> MetricsRecorder<E> {
+    /// Create a new recorder without any histograms.
+    pub fn new() -> MetricsRecorder<E> {
+        MetricsRecorder { histograms: Mutex::new(BTreeMap::new()) }
+    }
+
+    /// Return the number of histograms registered so far.
+    pub fn len(&self) -> usize {
+        lock(&self.histograms).len()
+    }
+
+    /// Determine whether no histogram was registered so far.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<E: Clone> MetricsRecorder<E> {
+    /// Return a copy of the estimator of the histogram with the given key.
+    pub fn get(&self, key: &Key) -> Option<E> {
+        lock(&self.histograms).get(key).map(|h| lock(&h.0).clone())
+    }
+
+    /// Return copies of the estimators of all histograms, ordered by key.
+    pub fn snapshot(&self) -> Vec<(Key, E)> {
+        lock(&self.histograms).iter()
+            .map(|(k, h)| (k.clone(), lock(&h.0).clone()))
+            .collect()
+    }
+}
+
+impl<E: Default> MetricsRecorder<E> {
+    /// Reset the estimators of all histograms to an empty sample.
+    ///
+    /// The histograms stay registered.
+    pub fn reset(&self) {
+        for h in lock(&self.histograms).values() {
+            *lock(&h.0) = E::default();
+        }
+    }
+}
+
+impl<E> Default for MetricsRecorder<E> {
+    fn default() -> MetricsRecorder<E> {
+        MetricsRecorder::new()
+    }
+}
+
+impl<E: Estimate + Default + Send + 'static> Recorder for MetricsRecorder<E> {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+    fn register_counter(&self, _: &Key, _: &Metadata<'_>) -> Counter {
+        Counter::noop()
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        let handle = lock(&self.histograms).entry(key.clone())
+            .or_insert_with(|| Arc::new(Handle(Mutex::new(E::default()))))
+            .clone();
+        Histogram::from_arc(handle)
+    }
+}
@@ -0,0 +1,58 @@
+use super::Merge;
+
+/// The maximal depth of the merge tree, enough for `u64::MAX` estimators.
+const DEPTH: usize = 64;
+
+/// Merge many estimators via a balanced binary tree.
+///
+/// Instead of merging every estimator into an accumulator, which is a
+/// sequence of increasingly unbalanced merges, the estimators are merged in
+/// pairs, then pairs of pairs and so on. This reduces the accumulation of
+/// rounding errors when combining thousands of partial estimators, similar to
+/// pairwise summation. The order of the estimators is preserved and only
+/// a logarithmic number of estimators is kept at a time, without allocating.
+///
+/// Returns the default estimator if there are none.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, merge_reduce};
+///
+/// let shards = (0..1000).map(|i| {
+///     let x = f64::from(i);
+///     [x, x + 0.5].iter().collect::<Variance>()
+/// });
+/// let a: Variance = merge_reduce(shards);
+/// assert_eq!(a.len(), 2000);
+/// assert_eq!(a.mean(), 499.75);
+/// ```
+pub fn merge_reduce<E, I>(estimators: I) -> E
+    where E: Merge + Default, I: IntoIterator<Item = E>
+{
+    // `levels[i]` holds the merged result of `2^i` estimators, if any. This
+    // works like incrementing a binary counter.
+    let mut levels: [Option<E>; DEPTH] = core::array::from_fn(|_| None);
+    for mut e in estimators {
+        for level in levels.iter_mut() {
+            match level.take() {
+                Some(mut earlier) => {
+                    earlier.merge(&e);
+                    e = earlier;
+                }
+                None => {
+                    *level = Some(e);
+                    break;
+                }
+            }
+        }
+    }
+    // The higher levels contain the earlier estimators.
+    let mut iter = levels.iter_mut().rev().filter_map(Option::take);
+    let mut result = iter.next().unwrap_or_default();
+    for e in iter {
+        result.merge(&e);
+    }
+    result
+}
@@ -0,0 +1,171 @@
+use std::{boxed::Box, collections::BTreeMap, string::String};
+
+use super::{Estimate, Merge, Reset, MergeDyn};
+
+/// An estimator that can be stored in a [`StatsRegistry`].
+///
+/// This is implemented for all cloneable estimators that can be merged and
+/// reset.
+///
+/// [`StatsRegistry`]: ./struct.StatsRegistry.html
+pub trait RegisteredEstimator: Estimate + Reset + MergeDyn {
+    /// Clone the estimator into a box.
+    fn box_clone(&self) -> Box<dyn RegisteredEstimator>;
+
+    /// Return the estimator as `MergeDyn`.
+    fn as_merge_dyn(&self) -> &dyn MergeDyn;
+}
+
+impl<T: Estimate + Merge + Reset + Clone + 'static> RegisteredEstimator for T {
+    #[inline]
+    fn box_clone(&self) -> Box<dyn RegisteredEstimator> {
+        Box::new(self.clone())
+    }
+
+    #[inline]
+    fn as_merge_dyn(&self) -> &dyn MergeDyn {
+        self
+    }
+}
+
+impl Clone for Box<dyn RegisteredEstimator> {
+    #[inline]
+    fn clone(&self) -> Box<dyn RegisteredEstimator> {
+        self.box_clone()
+    }
+}
+
+/// A collection of estimators of possibly different types, identified by
+/// names.
+///
+/// This is a building block for lightweight application metrics: register an
+/// estimator for every metric, add the measured values by name, and
+/// periodically take a snapshot of the estimates. Registries of different
+/// threads or processes can be merged.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{StatsRegistry, Mean, Max};
+///
+/// let mut a = StatsRegistry::new();
+/// a.register("latency", Mean::new());
+/// a.register("queue_len", Max::new());
+/// a.add("latency", 12.).unwrap();
+/// a.add("latency", 18.).unwrap();
+/// a.add("queue_len", 3.).unwrap();
+/// assert!(a.add("unknown", 1.).is_err());
+///
+/// let mut b = a.clone();
+/// b.reset();
+/// b.add("queue_len", 7.).unwrap();
+/// a.merge(&b).unwrap();
+///
+/// let snapshot = a.snapshot();
+/// assert_eq!(snapshot["latency"], 15.);
+/// assert_eq!(snapshot["queue_len"], 7.);
+/// ```
+#[derive(Clone, Default)]
+pub struct StatsRegistry {
+    /// The estimators by name.
+    estimators: BTreeMap<String, Box<dyn RegisteredEstimator>>,
+}
+
+impl StatsRegistry {
+    /// Create a new empty registry.
+    #[inline]
+    pub fn new() -> StatsRegistry {
+        StatsRegistry { estimators: BTreeMap::new() }
+    }
+
+    /// Register an estimator under the given name.
+    ///
+    /// Returns the estimator previously registered under the name, if any.
+    pub fn register<E: RegisteredEstimator + 'static>(&mut self, name: &str, estimator: E)
+        -> Option<Box<dyn RegisteredEstimator>>
+    {
+        self.estimators.insert(name.into(), Box::new(estimator))
+    }
+
+    /// Remove the estimator registered under the given name.
+    pub fn unregister(&mut self, name: &str) -> Option<Box<dyn RegisteredEstimator>> {
+        self.estimators.remove(name)
+    }
+
+    /// Add an observation to the estimator registered under the given name.
+    ///
+    /// Returns an error if no estimator is registered under the name.
+    #[inline]
+    pub fn add(&mut self, name: &str, x: f64) -> Result<(), ()> {
+        self.estimators.get_mut(name).ok_or(())?.add(x);
+        Ok(())
+    }
+
+    /// Return the estimator registered under the given name.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&dyn RegisteredEstimator> {
+        self.estimators.get(name).map(|e| e.as_ref())
+    }
+
+    /// Return the number of registered estimators.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.estimators.len()
+    }
+
+    /// Determine whether no estimators are registered.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.estimators.is_empty()
+    }
+
+    /// Iterate over the names and estimators, ordered by name.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &dyn RegisteredEstimator)> {
+        self.estimators.iter().map(|(k, e)| (k.as_str(), e.as_ref()))
+    }
+
+    /// Return the current estimates by name.
+    pub fn snapshot(&self) -> BTreeMap<String, f64> {
+        self.estimators.iter().map(|(k, e)| (k.clone(), e.estimate())).collect()
+    }
+
+    /// Reset all estimators to an empty sample, keeping them registered.
+    ///
+    /// This allows reusing the registry between reporting intervals.
+    pub fn reset(&mut self) {
+        for e in self.estimators.values_mut() {
+            e.reset();
+        }
+    }
+
+    /// Merge the estimators of another registry into this one.
+    ///
+    /// Estimators registered under the same name are merged, the others are
+    /// copied. Returns an error and leaves the registry unchanged if
+    /// estimators registered under the same name have different types.
+    pub fn merge(&mut self, other: &StatsRegistry) -> Result<(), ()> {
+        for (k, e) in &other.estimators {
+            if let Some(mine) = self.estimators.get(k) {
+                if mine.as_any().type_id() != e.as_any().type_id() {
+                    return Err(());
+                }
+            }
+        }
+        for (k, e) in &other.estimators {
+            match self.estimators.get_mut(k) {
+                Some(mine) => mine.merge_dyn(e.as_ref().as_merge_dyn())?,
+                None => { self.estimators.insert(k.clone(), e.clone()); },
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for StatsRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_map().entries(self.estimators.iter().map(|(k, e)| (k, e.estimate()))).finish()
+    }
+}
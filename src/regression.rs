@@ -0,0 +1,510 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::Merge;
+use crate::math::sqrt;
+
+/// Estimate a simple linear regression `y = intercept + slope * x` of a
+/// sequence of pairs of numbers ("population") by ordinary least squares.
+///
+/// The means and the sums of squares and products of the deviations from the
+/// means are updated incrementally, generalizing the algorithm of
+/// [`Variance`]. From these, the fit and its diagnostics are calculated in
+/// constant time: the coefficient of determination, the standard errors of
+/// the coefficients and the standard deviation of the residuals.
+///
+/// [`Variance`]: ./type.Variance.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::LinearRegression;
+///
+/// let a: LinearRegression = [(1., 3.), (2., 5.), (3., 7.)].iter().collect();
+/// assert_eq!((a.intercept(), a.slope()), (1., 2.));
+/// assert_eq!(a.predict(4.), 9.);
+/// assert_eq!(a.r_squared(), 1.);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct LinearRegression {
+    /// Sample size.
+    n: u64,
+    /// Mean of `x`.
+    mean_x: f64,
+    /// Mean of `y`.
+    mean_y: f64,
+    /// Sum of the squared deviations of `x` from its mean.
+    sum_xx: f64,
+    /// Sum of the squared deviations of `y` from its mean.
+    sum_yy: f64,
+    /// Sum of the products of the deviations of `x` and `y` from their means.
+    sum_xy: f64,
+}
+
+impl LinearRegression {
+    /// Create a new linear regression estimator.
+    #[inline]
+    pub const fn new() -> LinearRegression {
+        LinearRegression { n: 0, mean_x: 0., mean_y: 0., sum_xx: 0., sum_yy: 0., sum_xy: 0. }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        self.n += 1;
+        let n = self.n as f64;
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.mean_x += dx / n;
+        self.mean_y += dy / n;
+        self.sum_xx += dx * (x - self.mean_x);
+        self.sum_yy += dy * (y - self.mean_y);
+        self.sum_xy += dx * (y - self.mean_y);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the mean of `x`.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean_x(&self) -> f64 {
+        self.mean_x
+    }
+
+    /// Estimate the mean of `y`.
+    ///
+    /// Returns 0 for an empty sample.
+    #[inline]
+    pub fn mean_y(&self) -> f64 {
+        self.mean_y
+    }
+
+    /// Calculate the sample covariance of `x` and `y`.
+    ///
+    /// Returns 0 for less than two samples.
+    #[inline]
+    pub fn sample_covariance(&self) -> f64 {
+        if self.n < 2 {
+            return 0.;
+        }
+        self.sum_xy / (self.n - 1) as f64
+    }
+
+    /// Estimate Pearson's correlation coefficient of `x` and `y`.
+    ///
+    /// Returns `None` if `x` or `y` is constant, in particular if there are
+    /// less than two samples.
+    #[inline]
+    pub fn try_correlation(&self) -> Option<f64> {
+        let denominator = sqrt(self.sum_xx * self.sum_yy);
+        if denominator == 0. {
+            return None;
+        }
+        Some(self.sum_xy / denominator)
+    }
+
+    /// Estimate the slope of the regression line.
+    ///
+    /// Returns 0 if `x` is constant, in particular if there are less than two
+    /// samples.
+    #[inline]
+    pub fn slope(&self) -> f64 {
+        self.try_slope().unwrap_or(0.)
+    }
+
+    /// Estimate the slope of the regression line.
+    ///
+    /// Returns `None` if `x` is constant, in particular if there are less than
+    /// two samples.
+    #[inline]
+    pub fn try_slope(&self) -> Option<f64> {
+        if self.sum_xx == 0. {
+            return None;
+        }
+        Some(self.sum_xy / self.sum_xx)
+    }
+
+    /// Estimate the intercept of the regression line.
+    ///
+    /// If `x` is constant, this is the mean of `y`.
+    #[inline]
+    pub fn intercept(&self) -> f64 {
+        self.mean_y - self.slope() * self.mean_x
+    }
+
+    /// Predict `y` for the given `x` using the regression line.
+    #[inline]
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept() + self.slope() * x
+    }
+
+    /// Calculate the sum of the squared residuals of the regression line.
+    #[inline]
+    pub fn residual_sum_of_squares(&self) -> f64 {
+        let explained = self.slope() * self.sum_xy;
+        (self.sum_yy - explained).max(0.)
+    }
+
+    /// Calculate the coefficient of determination `R²`, i.e. the fraction of
+    /// the variance of `y` explained by the regression line.
+    ///
+    /// Returns 0 if `x` or `y` is constant.
+    #[inline]
+    pub fn r_squared(&self) -> f64 {
+        self.try_r_squared().unwrap_or(0.)
+    }
+
+    /// Calculate the coefficient of determination `R²`.
+    ///
+    /// Returns `None` if `x` or `y` is constant, in particular if there are
+    /// less than two samples.
+    #[inline]
+    pub fn try_r_squared(&self) -> Option<f64> {
+        let r = self.try_correlation()?;
+        Some(r * r)
+    }
+
+    /// Calculate the adjusted coefficient of determination, which corrects
+    /// `R²` for the degree of freedom used by the slope.
+    ///
+    /// Returns `None` if there are less than three samples or if `x` or `y`
+    /// is constant.
+    #[inline]
+    pub fn try_adjusted_r_squared(&self) -> Option<f64> {
+        if self.n < 3 {
+            return None;
+        }
+        let r_squared = self.try_r_squared()?;
+        let n = self.n as f64;
+        Some(1. - (1. - r_squared) * (n - 1.) / (n - 2.))
+    }
+
+    /// Estimate the standard deviation of the residuals around the
+    /// regression line.
+    ///
+    /// This accounts for the two estimated coefficients. Returns `None` if
+    /// there are less than three samples.
+    #[inline]
+    pub fn try_residual_std(&self) -> Option<f64> {
+        if self.n < 3 {
+            return None;
+        }
+        Some(sqrt(self.residual_sum_of_squares() / (self.n - 2) as f64))
+    }
+
+    /// Estimate the standard error of the slope.
+    ///
+    /// Returns `None` if there are less than three samples or if `x` is
+    /// constant.
+    #[inline]
+    pub fn try_slope_error(&self) -> Option<f64> {
+        if self.sum_xx == 0. {
+            return None;
+        }
+        Some(self.try_residual_std()? / sqrt(self.sum_xx))
+    }
+
+    /// Estimate the standard error of the intercept.
+    ///
+    /// Returns `None` if there are less than three samples or if `x` is
+    /// constant.
+    #[inline]
+    pub fn try_intercept_error(&self) -> Option<f64> {
+        if self.sum_xx == 0. {
+            return None;
+        }
+        let n = self.n as f64;
+        let s = self.try_residual_std()?;
+        Some(s * sqrt(1. / n + self.mean_x * self.mean_x / self.sum_xx))
+    }
+}
+
+impl Merge for LinearRegression {
+    #[inline]
+    fn merge(&mut self, other: &LinearRegression) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        let (len_self, len_other) = (self.n as f64, other.n as f64);
+        let len_total = len_self + len_other;
+        let dx = other.mean_x - self.mean_x;
+        let dy = other.mean_y - self.mean_y;
+        let factor = len_self * len_other / len_total;
+        self.n += other.n;
+        self.mean_x += dx * len_other / len_total;
+        self.mean_y += dy * len_other / len_total;
+        self.sum_xx += other.sum_xx + dx * dx * factor;
+        self.sum_yy += other.sum_yy + dy * dy * factor;
+        self.sum_xy += other.sum_xy + dx * dy * factor;
+    }
+}
+
+/// Estimate a simple linear regression `y = intercept + slope * x` of a
+/// sequence of pairs of numbers ("population"), which is robust to outliers.
+///
+/// Every observation is weighted according to its residual with respect to
+/// the regression line fitted so far, like in an iteration of iteratively
+/// reweighted least squares for the Huber loss: observations with a residual
+/// up to the threshold get a weight of 1, the weight of the others is the
+/// threshold divided by the absolute residual. Thus, a few wild outliers only
+/// shift the fitted line as much as observations with a residual of the
+/// threshold would. Observations are weighted by 1 until the slope can be
+/// estimated, so the first observations should not be outliers.
+///
+/// The threshold is in the unit of `y`. A common choice is 1.345 times the
+/// standard deviation of the residuals without outliers.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{HuberRegression, LinearRegression};
+///
+/// let data = [(0., 1.), (1., 3.), (2., 5.), (3., 1000.), (4., 9.), (5., 11.)];
+/// let mut robust = HuberRegression::new(1.);
+/// robust.extend(data.iter());
+/// let ols: LinearRegression = data.iter().collect();
+/// assert!((robust.slope() - 2.).abs() < 0.5);
+/// assert!(ols.slope() > 20.);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct HuberRegression {
+    /// Residual above which observations are downweighted.
+    threshold: f64,
+    /// Sample size.
+    n: u64,
+    /// Sum of the weights.
+    weight_sum: f64,
+    /// Weighted mean of `x`.
+    mean_x: f64,
+    /// Weighted mean of `y`.
+    mean_y: f64,
+    /// Weighted sum of the squared deviations of `x` from its mean.
+    sum_xx: f64,
+    /// Weighted sum of the squared deviations of `y` from its mean.
+    sum_yy: f64,
+    /// Weighted sum of the products of the deviations of `x` and `y` from
+    /// their means.
+    sum_xy: f64,
+}
+
+impl HuberRegression {
+    /// Create a new robust linear regression estimator with the given Huber
+    /// threshold.
+    ///
+    /// Panics if the threshold is not positive.
+    #[inline]
+    pub fn new(threshold: f64) -> HuberRegression {
+        assert!(threshold > 0.);
+        HuberRegression {
+            threshold, n: 0, weight_sum: 0.,
+            mean_x: 0., mean_y: 0., sum_xx: 0., sum_yy: 0., sum_xy: 0.,
+        }
+    }
+
+    /// Return the Huber threshold.
+    #[inline]
+    pub fn threshold(&self) -> f64 {
+        self.threshold
+    }
+
+    /// Return the weight an observation would get with respect to the
+    /// current regression line.
+    #[inline]
+    pub fn weight(&self, x: f64, y: f64) -> f64 {
+        if self.sum_xx == 0. {
+            return 1.;
+        }
+        let residual = (y - self.predict(x)).abs();
+        if residual <= self.threshold {
+            1.
+        } else {
+            self.threshold / residual
+        }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        let weight = self.weight(x, y);
+        self.n += 1;
+        self.weight_sum += weight;
+        let dx = x - self.mean_x;
+        let dy = y - self.mean_y;
+        self.mean_x += dx * weight / self.weight_sum;
+        self.mean_y += dy * weight / self.weight_sum;
+        self.sum_xx += weight * dx * (x - self.mean_x);
+        self.sum_yy += weight * dy * (y - self.mean_y);
+        self.sum_xy += weight * dx * (y - self.mean_y);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Return the sum of the weights of the observations.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        self.weight_sum
+    }
+
+    /// Estimate the slope of the regression line.
+    ///
+    /// Returns 0 if `x` is constant, in particular if there are less than two
+    /// samples.
+    #[inline]
+    pub fn slope(&self) -> f64 {
+        self.try_slope().unwrap_or(0.)
+    }
+
+    /// Estimate the slope of the regression line.
+    ///
+    /// Returns `None` if `x` is constant, in particular if there are less than
+    /// two samples.
+    #[inline]
+    pub fn try_slope(&self) -> Option<f64> {
+        if self.sum_xx == 0. {
+            return None;
+        }
+        Some(self.sum_xy / self.sum_xx)
+    }
+
+    /// Estimate the intercept of the regression line.
+    ///
+    /// If `x` is constant, this is the weighted mean of `y`.
+    #[inline]
+    pub fn intercept(&self) -> f64 {
+        self.mean_y - self.slope() * self.mean_x
+    }
+
+    /// Predict `y` for the given `x` using the regression line.
+    #[inline]
+    pub fn predict(&self, x: f64) -> f64 {
+        self.intercept() + self.slope() * x
+    }
+
+    /// Calculate the weighted coefficient of determination `R²`.
+    ///
+    /// Returns `None` if `x` or `y` is constant, in particular if there are
+    /// less than two samples.
+    #[inline]
+    pub fn try_r_squared(&self) -> Option<f64> {
+        let denominator = self.sum_xx * self.sum_yy;
+        if denominator == 0. {
+            return None;
+        }
+        Some(self.sum_xy * self.sum_xy / denominator)
+    }
+}
+
+impl Merge for HuberRegression {
+    /// Merge another sample into this one.
+    ///
+    /// The weights of the observations of both samples are kept, and the
+    /// threshold of `self` is used for further observations.
+    #[inline]
+    fn merge(&mut self, other: &HuberRegression) {
+        if other.weight_sum == 0. {
+            self.n += other.n;
+            return;
+        }
+        let total_weight_sum = self.weight_sum + other.weight_sum;
+        let dx = other.mean_x - self.mean_x;
+        let dy = other.mean_y - self.mean_y;
+        let factor = self.weight_sum * other.weight_sum / total_weight_sum;
+        self.n += other.n;
+        self.mean_x += dx * other.weight_sum / total_weight_sum;
+        self.mean_y += dy * other.weight_sum / total_weight_sum;
+        self.sum_xx += other.sum_xx + dx * dx * factor;
+        self.sum_yy += other.sum_yy + dy * dy * factor;
+        self.sum_xy += other.sum_xy + dx * dy * factor;
+        self.weight_sum = total_weight_sum;
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for HuberRegression {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64)> for HuberRegression {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        for &(x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for LinearRegression {
+    fn from_iter<T>(iter: T) -> LinearRegression
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = LinearRegression::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for LinearRegression {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
+
+impl<'a> core::iter::FromIterator<&'a (f64, f64)> for LinearRegression {
+    fn from_iter<T>(iter: T) -> LinearRegression
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        let mut a = LinearRegression::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64)> for LinearRegression {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
+        for &(x, y) in iter {
+            self.add(x, y);
+        }
+    }
+}
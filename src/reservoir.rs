@@ -0,0 +1,151 @@
+use std::vec::Vec;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+/// A small pseudo-random number generator (SplitMix64).
+///
+/// It is only used for sampling, so it does not need to be cryptographically
+/// secure, but it should be deterministic for a given seed.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a new generator from a seed.
+    #[inline]
+    pub const fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    /// Generate a uniformly distributed `u64`.
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a uniformly distributed integer in `0..n`.
+    ///
+    /// `n` must not be zero.
+    #[inline]
+    pub fn below(&mut self, n: u64) -> u64 {
+        debug_assert_ne!(n, 0);
+        ((u128::from(self.next_u64()) * u128::from(n)) >> 64) as u64
+    }
+
+    /// Generate a uniformly distributed `f64` in `[0, 1)`.
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1. / (1u64 << 53) as f64)
+    }
+}
+
+/// A uniform random sample of bounded size from a stream (Algorithm R).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub(crate) struct Reservoir<T> {
+    /// The sampled items.
+    items: Vec<T>,
+    /// The maximal number of sampled items.
+    capacity: usize,
+    /// The number of items seen.
+    seen: u64,
+    /// The generator deciding which items are sampled.
+    rng: Rng,
+}
+
+impl<T: Clone> Reservoir<T> {
+    /// Create an empty reservoir holding at most `capacity` items.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn new(capacity: usize, seed: u64) -> Reservoir<T> {
+        assert!(capacity > 0, "the capacity must not be zero");
+        Reservoir {
+            items: Vec::with_capacity(capacity),
+            capacity,
+            seen: 0,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Return the maximal number of sampled items.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Return the number of items seen.
+    #[inline]
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// Return the sampled items, in no particular order.
+    #[inline]
+    pub fn items(&self) -> &[T] {
+        &self.items
+    }
+
+    /// Offer an item to the sample.
+    pub fn add(&mut self, x: T) {
+        self.seen += 1;
+        if self.items.len() < self.capacity {
+            self.items.push(x);
+            return;
+        }
+        let i = self.rng.below(self.seen);
+        if i < self.capacity as u64 {
+            self.items[i as usize] = x;
+        }
+    }
+
+    /// Merge another reservoir into this one.
+    ///
+    /// The result is a random sample of the union of both streams, where each
+    /// sampled item represents the same number of items of its stream. The
+    /// capacity of `self` is kept.
+    pub fn merge(&mut self, other: &Reservoir<T>) {
+        if other.seen == 0 {
+            return;
+        }
+        if self.seen <= self.capacity as u64 && other.seen <= other.capacity as u64 {
+            // Both samples are complete, so this is exact.
+            for x in &other.items {
+                self.add(x.clone());
+            }
+            return;
+        }
+        let mut mine = core::mem::replace(&mut self.items, Vec::with_capacity(self.capacity));
+        let mut theirs = other.items.clone();
+        // The number of stream items represented by each sampled item.
+        let weight_mine = self.seen as f64 / mine.len().max(1) as f64;
+        let weight_theirs = other.seen as f64 / theirs.len() as f64;
+        let size = self.capacity.min(mine.len() + theirs.len());
+        while self.items.len() < size {
+            let remaining_mine = weight_mine * mine.len() as f64;
+            let remaining_theirs = weight_theirs * theirs.len() as f64;
+            let p = remaining_mine / (remaining_mine + remaining_theirs);
+            let source = if self.rng.next_f64() < p { &mut mine } else { &mut theirs };
+            let i = self.rng.below(source.len() as u64) as usize;
+            self.items.push(source.swap_remove(i));
+        }
+        self.seen += other.seen;
+    }
+
+    /// Remove all items, keeping the state of the generator.
+    pub fn clear(&mut self) {
+        self.items.clear();
+        self.seen = 0;
+    }
+
+    /// Return the number of bytes allocated for the sampled items.
+    #[inline]
+    pub fn allocated(&self) -> usize {
+        self.items.capacity() * core::mem::size_of::<T>()
+    }
+}
@@ -0,0 +1,301 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_rolling_stats_common {
+    ($LEN:expr) => (
+        use $crate::{Estimate, Reset};
+
+        /// The size of the window.
+        pub const LEN: usize = $LEN;
+
+        impl RollingStats {
+            /// Create a new estimator with an empty window.
+            #[inline]
+            pub const fn new() -> RollingStats {
+                RollingStats {
+                    buffer: [0.; LEN],
+                    head: 0,
+                    len: 0,
+                    stats: $crate::Variance::EMPTY,
+                }
+            }
+
+            /// Add an observation, evicting the oldest one if the window is
+            /// full.
+            #[inline]
+            pub fn add(&mut self, x: f64) {
+                if self.len == LEN {
+                    self.stats.remove(self.buffer[self.head]);
+                } else {
+                    self.len += 1;
+                }
+                self.buffer[self.head] = x;
+                self.stats.add(x);
+                self.head += 1;
+                if self.head == LEN {
+                    self.head = 0;
+                    // Recalculate the statistics from scratch once per
+                    // window, so that rounding errors from removing samples
+                    // do not accumulate.
+                    self.stats = self.buffer.iter().collect();
+                }
+            }
+
+            /// Determine whether the window is empty.
+            #[inline]
+            pub fn is_empty(&self) -> bool {
+                self.len == 0
+            }
+
+            /// Determine whether the window is full.
+            #[inline]
+            pub fn is_full(&self) -> bool {
+                self.len == LEN
+            }
+
+            /// Return the number of samples in the window.
+            #[inline]
+            pub fn len(&self) -> usize {
+                self.len
+            }
+
+            /// Return the size of the window.
+            #[inline]
+            pub fn capacity(&self) -> usize {
+                LEN
+            }
+
+            /// Iterate over the samples in the window, from the oldest to the
+            /// most recent one.
+            #[inline]
+            pub fn iter(&self) -> impl Iterator<Item=f64> + '_ {
+                let (newer, older) = self.buffer[..self.len].split_at(self.head);
+                older.iter().chain(newer.iter()).cloned()
+            }
+
+            /// Calculate the mean of the window.
+            ///
+            /// Returns 0 for an empty window.
+            #[inline]
+            pub fn mean(&self) -> f64 {
+                self.stats.mean()
+            }
+
+            /// Calculate the sample variance of the window.
+            #[inline]
+            pub fn sample_variance(&self) -> f64 {
+                self.stats.sample_variance()
+            }
+
+            /// Calculate the population variance of the window.
+            #[inline]
+            pub fn population_variance(&self) -> f64 {
+                self.stats.population_variance()
+            }
+
+            /// Estimate the standard error of the mean of the window.
+            #[inline]
+            pub fn error(&self) -> f64 {
+                self.stats.error()
+            }
+        }
+
+        impl core::default::Default for RollingStats {
+            fn default() -> RollingStats {
+                RollingStats::new()
+            }
+        }
+
+        impl core::fmt::Debug for RollingStats {
+            fn fmt(&self, formatter: &mut core::fmt::Formatter) -> core::fmt::Result {
+                formatter.write_str("RollingStats { window: [")?;
+                for (i, x) in self.iter().enumerate() {
+                    if i > 0 {
+                        formatter.write_str(", ")?;
+                    }
+                    core::fmt::Debug::fmt(&x, formatter)?;
+                }
+                formatter.write_str("] }")
+            }
+        }
+
+        impl Estimate for RollingStats {
+            #[inline]
+            fn add(&mut self, x: f64) {
+                RollingStats::add(self, x);
+            }
+
+            /// Calculate the mean of the window.
+            #[inline]
+            fn estimate(&self) -> f64 {
+                self.mean()
+            }
+        }
+
+        impl Reset for RollingStats {
+            #[inline]
+            fn reset(&mut self) {
+                *self = RollingStats::new();
+            }
+        }
+
+        impl core::iter::FromIterator<f64> for RollingStats {
+            fn from_iter<T>(iter: T) -> RollingStats
+                where T: IntoIterator<Item=f64>
+            {
+                let mut e = RollingStats::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl core::iter::Extend<f64> for RollingStats {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=f64>
+            {
+                for i in iter {
+                    self.add(i);
+                }
+            }
+        }
+
+        impl<'a> core::iter::FromIterator<&'a f64> for RollingStats {
+            fn from_iter<T>(iter: T) -> RollingStats
+                where T: IntoIterator<Item=&'a f64>
+            {
+                let mut e = RollingStats::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a> core::iter::Extend<&'a f64> for RollingStats {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a f64>
+            {
+                for &i in iter {
+                    self.add(i);
+                }
+            }
+        }
+
+        $crate::impl_from_iterator_samples!(RollingStats; f32, i32, u32, i64);
+        $crate::impl_add_assign!(RollingStats; f64);
+    );
+}
+
+#[cfg(feature = "serde1")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_rolling_stats_inner {
+    ($name:ident, $LEN:expr) => (
+        mod $name {
+            $crate::define_rolling_stats_common!($LEN);
+
+            use ::serde::{Serialize, Deserialize};
+            serde_big_array::big_array! {
+                BigArray; LEN,
+            }
+
+            /// Estimate the mean and variance of the most recent samples,
+            /// with a window size known at compile time.
+            #[derive(Clone, Serialize, Deserialize)]
+            #[serde(try_from = "Fields")]
+            pub struct RollingStats {
+                /// The ring buffer holding the window.
+                #[serde(with = "BigArray")]
+                buffer: [f64; LEN],
+                /// The position of the next sample in the ring buffer.
+                head: usize,
+                /// The number of samples in the window.
+                len: usize,
+                /// The statistics of the window.
+                stats: $crate::Variance,
+            }
+
+            /// The deserialized fields of `RollingStats`, before checking
+            /// that they describe a valid ring buffer.
+            #[derive(Deserialize)]
+            struct Fields {
+                #[serde(with = "BigArray")]
+                buffer: [f64; LEN],
+                head: usize,
+                len: usize,
+                stats: $crate::Variance,
+            }
+
+            impl ::core::convert::TryFrom<Fields> for RollingStats {
+                type Error = &'static str;
+
+                fn try_from(fields: Fields) -> Result<RollingStats, &'static str> {
+                    let Fields { buffer, head, len, stats } = fields;
+                    // The buffer is filled from the start until it is full.
+                    if head >= LEN || len > LEN || (len < LEN && head != len) {
+                        return Err("invalid position in the ring buffer");
+                    }
+                    Ok(RollingStats { buffer, head, len, stats })
+                }
+            }
+        }
+    );
+}
+
+#[cfg(not(feature = "serde1"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_rolling_stats_inner {
+    ($name:ident, $LEN:expr) => (
+        mod $name {
+            $crate::define_rolling_stats_common!($LEN);
+
+            /// Estimate the mean and variance of the most recent samples,
+            /// with a window size known at compile time.
+            #[derive(Clone)]
+            pub struct RollingStats {
+                /// The ring buffer holding the window.
+                buffer: [f64; LEN],
+                /// The position of the next sample in the ring buffer.
+                head: usize,
+                /// The number of samples in the window.
+                len: usize,
+                /// The statistics of the window.
+                stats: $crate::Variance,
+            }
+        }
+    );
+}
+
+/// Define an estimator of the mean and variance over a sliding window of the
+/// most recent samples, with a window size known at compile time.
+///
+/// The samples are kept in a ring buffer, so adding a sample evicts the oldest
+/// one once the window is full. Adding takes constant time. The statistics are
+/// recalculated from the buffer once per window, so rounding errors do not
+/// accumulate over long streams.
+///
+/// Because macros are not hygenic for items, everything is defined in a private
+/// module with the given name. This includes the `RollingStats` struct and the
+/// window size `LEN`.
+///
+/// Note that you need to make sure that `core` is accessible to the macro.
+///
+///
+/// # Example
+///
+/// ```
+/// use average::define_rolling_stats;
+///
+/// define_rolling_stats!(window, 3);
+/// let mut s = window::RollingStats::new();
+/// for i in 1..6 {
+///     s.add(f64::from(i));
+/// }
+/// assert_eq!(s.len(), 3);
+/// assert_eq!(s.mean(), 4.);
+/// assert_eq!(s.sample_variance(), 1.);
+/// ```
+#[macro_export]
+macro_rules! define_rolling_stats {
+    ($name:ident, $LEN:expr) => (
+        $crate::define_rolling_stats_inner!($name, $LEN);
+    );
+}
@@ -0,0 +1,87 @@
+use polars::prelude::{ChunkedArray, DataType, PolarsNumericType, Series};
+
+use super::{Estimate, Merge, IntoSample, merge_reduce};
+
+/// Create an estimator from all values of a Polars series.
+///
+/// Null values are skipped. An estimator is calculated for each chunk of the
+/// series, and the estimators are merged. Returns an error if the series is
+/// not numeric.
+///
+/// Requires the `"polars"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, from_series};
+/// use polars::prelude::*;
+///
+/// let s = Series::new("x".into(), &[Some(1i32), None, Some(2), Some(3)]);
+/// let a: Variance = from_series(&s).unwrap();
+/// assert_eq!(a.len(), 3);
+/// assert_eq!(a.mean(), 2.);
+/// ```
+#[inline]
+pub fn from_series<E>(series: &Series) -> Result<E, ()>
+    where E: Estimate + Merge + Default
+{
+    series.estimator()
+}
+
+/// Calculate statistics of Polars series and chunked arrays.
+///
+/// Null values are skipped. An estimator is calculated for each chunk, and
+/// the estimators are merged, so the chunks could also be processed
+/// separately. Requires the `"polars"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Max, Mean, StatsSeriesExt};
+/// use polars::prelude::*;
+///
+/// let mut a = Float64Chunked::from_slice("x".into(), &[1., 2.]);
+/// a.append(&Float64Chunked::from_slice("x".into(), &[3., 6.])).unwrap();
+/// assert_eq!(a.chunks().len(), 2);
+/// let mean: Mean = a.estimator().unwrap();
+/// assert_eq!(mean.mean(), 3.);
+/// let max: Max = a.into_series().estimator().unwrap();
+/// assert_eq!(max.max(), 6.);
+/// ```
+pub trait StatsSeriesExt {
+    /// Create an estimator from all non-null values.
+    ///
+    /// Returns an error if the values are not numeric.
+    fn estimator<E>(&self) -> Result<E, ()>
+        where E: Estimate + Merge + Default;
+}
+
+impl<T> StatsSeriesExt for ChunkedArray<T>
+    where T: PolarsNumericType, T::Native: IntoSample
+{
+    fn estimator<E>(&self) -> Result<E, ()>
+        where E: Estimate + Merge + Default
+    {
+        Ok(merge_reduce(self.downcast_iter().map(|chunk| {
+            let mut e = E::default();
+            for x in chunk.iter().flatten() {
+                e.add(x.into_sample());
+            }
+            e
+        })))
+    }
+}
+
+impl StatsSeriesExt for Series {
+    fn estimator<E>(&self) -> Result<E, ()>
+        where E: Estimate + Merge + Default
+    {
+        if !self.dtype().is_primitive_numeric() {
+            return Err(());
+        }
+        let series = self.cast(&DataType::Float64).map_err(|_| ())?;
+        series.f64().map_err(|_| ())?.estimator()
+    }
+}
@@ -0,0 +1,141 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::vec::Vec;
+
+use super::{Estimate, Merge};
+
+/// Counter used to assign a shard to every thread.
+static NEXT_THREAD: AtomicUsize = AtomicUsize::new(0);
+
+std::thread_local! {
+    /// The number of the current thread, assigned on first use.
+    static THREAD: usize = NEXT_THREAD.fetch_add(1, Ordering::Relaxed);
+}
+
+/// Lock a shard, ignoring poisoning.
+///
+/// A panic while holding the lock cannot leave an estimator in a state that is
+/// less valid than losing a sample.
+#[inline]
+fn lock<E>(shard: &Mutex<E>) -> MutexGuard<'_, E> {
+    shard.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Share an estimator between threads by keeping several shards of it.
+///
+/// Every thread adds its samples to its own shard, so that threads rarely
+/// contend for the same lock. The shards are merged when the estimate is
+/// queried. If there are more threads than shards, some threads share a
+/// shard.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{ShardedEstimate, Variance};
+///
+/// let a: ShardedEstimate<Variance> = ShardedEstimate::new();
+/// std::thread::scope(|s| {
+///     for t in 0..4 {
+///         let a = &a;
+///         s.spawn(move || {
+///             for i in 0..100 {
+///                 a.add(f64::from(t * 100 + i));
+///             }
+///         });
+///     }
+/// });
+/// let v = a.merged();
+/// assert_eq!(v.len(), 400);
+/// assert!((v.mean() - 199.5).abs() < 1e-9);
+/// ```
+#[derive(Debug)]
+pub struct ShardedEstimate<E> {
+    shards: Vec<Mutex<E>>,
+}
+
+impl<E: Default> ShardedEstimate<E> {
+    /// Create a new estimator with one shard per available CPU.
+    pub fn new() -> ShardedEstimate<E> {
+        let n = std::thread::available_parallelism().map_or(1, |n| n.get());
+        ShardedEstimate::with_shards(n)
+    }
+
+    /// Create a new estimator with the given number of shards.
+    ///
+    /// Panics if `n` is zero.
+    pub fn with_shards(n: usize) -> ShardedEstimate<E> {
+        assert!(n > 0, "need at least one shard");
+        ShardedEstimate { shards: (0..n).map(|_| Mutex::new(E::default())).collect() }
+    }
+
+    /// Reset all shards to an empty sample.
+    pub fn reset(&self) {
+        for shard in &self.shards {
+            *lock(shard) = E::default();
+        }
+    }
+}
+
+impl<E> ShardedEstimate<E> {
+    /// Return the number of shards.
+    #[inline]
+    pub fn shards(&self) -> usize {
+        self.shards.len()
+    }
+
+    /// Return the shard of the current thread.
+    #[inline]
+    fn shard(&self) -> &Mutex<E> {
+        let i = THREAD.with(|&t| t) % self.shards.len();
+        &self.shards[i]
+    }
+}
+
+impl<E: Estimate> ShardedEstimate<E> {
+    /// Add an observation sampled from the population.
+    ///
+    /// This can be called concurrently from many threads.
+    #[inline]
+    pub fn add(&self, x: f64) {
+        lock(self.shard()).add(x);
+    }
+}
+
+impl<E: Merge + Clone> ShardedEstimate<E> {
+    /// Merge all shards into one estimator.
+    pub fn merged(&self) -> E {
+        let mut result = lock(&self.shards[0]).clone();
+        for shard in &self.shards[1..] {
+            result.merge(&lock(shard));
+        }
+        result
+    }
+
+    /// Merge all shards into one estimator, consuming the shards.
+    pub fn into_inner(self) -> E {
+        let mut shards = self.shards.into_iter()
+            .map(|s| s.into_inner().unwrap_or_else(PoisonError::into_inner));
+        let mut result = shards.next().unwrap();
+        for shard in shards {
+            result.merge(&shard);
+        }
+        result
+    }
+}
+
+impl<E: Estimate + Merge + Clone> ShardedEstimate<E> {
+    /// Estimate the statistic of the merged shards.
+    #[inline]
+    pub fn estimate(&self) -> f64 {
+        self.merged().estimate()
+    }
+}
+
+impl<E: Default> Default for ShardedEstimate<E> {
+    fn default() -> ShardedEstimate<E> {
+        ShardedEstimate::new()
+    }
+}
@@ -0,0 +1,111 @@
+//! Helpers for the versioned binary snapshots of the estimators.
+//!
+//! A snapshot starts with a header of two bytes: the version of the layout and
+//! a tag identifying the estimator. The fields of the estimator follow in a
+//! fixed order, encoded as little-endian. Later versions of the layout must
+//! still be able to read snapshots of all earlier versions, and may only
+//! append fields, so that earlier versions can read their snapshots by
+//! ignoring the trailing bytes.
+
+/// The current version of the snapshot layout.
+pub(crate) const VERSION: u8 = 1;
+
+/// The length of the header of a snapshot.
+pub(crate) const HEADER_LEN: usize = 2;
+
+/// Tags identifying the estimator in a snapshot.
+///
+/// Never change the value of an existing tag.
+pub(crate) mod tag {
+    pub(crate) const MEAN: u8 = 1;
+    pub(crate) const VARIANCE: u8 = 2;
+    pub(crate) const SKEWNESS: u8 = 3;
+    pub(crate) const KURTOSIS: u8 = 4;
+    pub(crate) const MIN: u8 = 5;
+    pub(crate) const MAX: u8 = 6;
+    pub(crate) const QUANTILE: u8 = 7;
+}
+
+/// Write the fields of a snapshot into a buffer.
+pub(crate) struct Writer<'a> {
+    buf: &'a mut [u8],
+    pos: usize,
+}
+
+impl<'a> Writer<'a> {
+    /// Start writing a snapshot of the estimator with the given tag.
+    #[inline]
+    pub(crate) fn new(buf: &'a mut [u8], tag: u8) -> Writer<'a> {
+        buf[0] = VERSION;
+        buf[1] = tag;
+        Writer { buf, pos: HEADER_LEN }
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: [u8; 8]) {
+        self.buf[self.pos..self.pos + 8].copy_from_slice(&bytes);
+        self.pos += 8;
+    }
+
+    #[inline]
+    pub(crate) fn f64(&mut self, x: f64) -> &mut Self {
+        self.write(x.to_le_bytes());
+        self
+    }
+
+    #[inline]
+    pub(crate) fn u64(&mut self, x: u64) -> &mut Self {
+        self.write(x.to_le_bytes());
+        self
+    }
+
+    #[inline]
+    pub(crate) fn i64(&mut self, x: i64) -> &mut Self {
+        self.write(x.to_le_bytes());
+        self
+    }
+}
+
+/// Read the fields of a snapshot from a buffer.
+pub(crate) struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Start reading a snapshot of the estimator with the given tag.
+    ///
+    /// Returns an error if the snapshot is shorter than the expected length or
+    /// is of another estimator. Trailing bytes appended by newer versions are
+    /// ignored.
+    #[inline]
+    pub(crate) fn new(buf: &'a [u8], tag: u8, len: usize) -> Result<Reader<'a>, ()> {
+        if buf.len() < len || buf[0] == 0 || buf[1] != tag {
+            return Err(());
+        }
+        Ok(Reader { buf, pos: HEADER_LEN })
+    }
+
+    #[inline]
+    fn read(&mut self) -> [u8; 8] {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(&self.buf[self.pos..self.pos + 8]);
+        self.pos += 8;
+        bytes
+    }
+
+    #[inline]
+    pub(crate) fn f64(&mut self) -> f64 {
+        f64::from_le_bytes(self.read())
+    }
+
+    #[inline]
+    pub(crate) fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.read())
+    }
+
+    #[inline]
+    pub(crate) fn i64(&mut self) -> i64 {
+        i64::from_le_bytes(self.read())
+    }
+}
@@ -0,0 +1,323 @@
+//! Special functions needed for calculating p-values.
+
+use crate::math::{exp, ln, sin};
+
+/// Coefficients of the Lanczos approximation with `g = 7` and `n = 9`.
+const LANCZOS_COEFFICIENTS: [f64; 9] = [
+    0.999_999_999_999_809_9,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_1,
+    -176.615_029_162_140_6,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_572e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// Maximal number of iterations for the series and continued fractions.
+const MAX_ITERATIONS: usize = 1000;
+
+/// Relative precision targeted by the series and continued fractions.
+const EPSILON: f64 = 1e-15;
+
+/// Calculate the logarithm of the gamma function for `x > 0`.
+pub(crate) fn ln_gamma(x: f64) -> f64 {
+    // Lanczos approximation, see https://en.wikipedia.org/wiki/Lanczos_approximation.
+    if x < 0.5 {
+        // Reflection formula
+        let pi = core::f64::consts::PI;
+        return ln(pi / sin(pi * x)) - ln_gamma(1. - x);
+    }
+    let x = x - 1.;
+    let mut a = LANCZOS_COEFFICIENTS[0];
+    let t = x + 7.5;
+    for (i, &c) in LANCZOS_COEFFICIENTS.iter().enumerate().skip(1) {
+        a += c / (x + i as f64);
+    }
+    0.5 * ln(2. * core::f64::consts::PI) + (x + 0.5) * ln(t) - t + ln(a)
+}
+
+/// Calculate the regularized lower incomplete gamma function `P(a, x)` via its
+/// series representation. Converges quickly for `x < a + 1`.
+fn gamma_p_series(a: f64, x: f64) -> f64 {
+    let mut sum = 1. / a;
+    let mut term = sum;
+    let mut n = a;
+    for _ in 0..MAX_ITERATIONS {
+        n += 1.;
+        term *= x / n;
+        sum += term;
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+    sum * exp(-x + a * ln(x) - ln_gamma(a))
+}
+
+/// Calculate the regularized upper incomplete gamma function `Q(a, x)` via its
+/// continued fraction representation. Converges quickly for `x >= a + 1`.
+fn gamma_q_continued_fraction(a: f64, x: f64) -> f64 {
+    // Modified Lentz's method, see Numerical Recipes, section 6.2.
+    let tiny = f64::MIN_POSITIVE / EPSILON;
+    let mut b = x + 1. - a;
+    let mut c = 1. / tiny;
+    let mut d = 1. / b;
+    let mut h = d;
+    for i in 1..MAX_ITERATIONS {
+        let an = -(i as f64) * (i as f64 - a);
+        b += 2.;
+        d = an * d + b;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = b + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPSILON {
+            break;
+        }
+    }
+    exp(-x + a * ln(x) - ln_gamma(a)) * h
+}
+
+/// Calculate the regularized upper incomplete gamma function `Q(a, x)`.
+pub(crate) fn gamma_q(a: f64, x: f64) -> f64 {
+    debug_assert!(a > 0.);
+    if x <= 0. {
+        return 1.;
+    }
+    if x < a + 1. {
+        1. - gamma_p_series(a, x)
+    } else {
+        gamma_q_continued_fraction(a, x)
+    }
+}
+
+/// Calculate the survival function of the chi-square distribution with `k`
+/// degrees of freedom, i.e. the probability of a value larger than `x`.
+pub(crate) fn chi_square_sf(x: f64, k: f64) -> f64 {
+    gamma_q(0.5 * k, 0.5 * x)
+}
+
+/// Calculate the survival function of the Kolmogorov distribution, i.e. the
+/// probability of a value larger than `x`.
+#[cfg(feature = "std")]
+pub(crate) fn kolmogorov_sf(x: f64) -> f64 {
+    use core::f64::consts::PI;
+
+    if x <= 0. {
+        return 1.;
+    }
+    if x < 1.18 {
+        // This series converges quickly for small `x`.
+        let mut sum = 0.;
+        for k in 1..=MAX_ITERATIONS {
+            let j = (2 * k - 1) as f64;
+            let term = exp(-j * j * PI * PI / (8. * x * x));
+            sum += term;
+            if term <= EPSILON * sum {
+                break;
+            }
+        }
+        return 1. - crate::math::sqrt(2. * PI) / x * sum;
+    }
+    let mut sum = 0.;
+    let mut sign = 1.;
+    for k in 1..=MAX_ITERATIONS {
+        let k = k as f64;
+        let term = exp(-2. * k * k * x * x);
+        sum += sign * term;
+        if term <= EPSILON * sum {
+            break;
+        }
+        sign = -sign;
+    }
+    2. * sum
+}
+
+/// Calculate the quantile function of the standard normal distribution, i.e.
+/// the value below which the probability `p` lies.
+///
+/// This uses Acklam's rational approximation, refined by one step of
+/// Halley's method.
+pub(crate) fn normal_quantile(p: f64) -> f64 {
+    use core::f64::consts::PI;
+
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1, 2.209_460_984_245_205e2, -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2, -3.066_479_806_614_716e1, 2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1, 1.615_858_368_580_409e2, -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1, -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3, -3.223_964_580_411_365e-1, -2.400_758_277_161_838,
+        -2.549_671_010_229_297, 4.374_664_141_464_968, 2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3, 3.224_671_290_700_398e-1, 2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    if !(0. ..=1.).contains(&p) {
+        return f64::NAN;
+    }
+    if p > 0.5 {
+        return -normal_quantile(1. - p);
+    }
+    if p == 0. {
+        return f64::NEG_INFINITY;
+    }
+    let x = if p < P_LOW {
+        let q = crate::math::sqrt(-2. * ln(p));
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.)
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.)
+    };
+    // For `x <= 0`, the normal distribution function is `Q(1/2, x²/2) / 2`.
+    let error = 0.5 * gamma_q(0.5, 0.5 * x * x) - p;
+    let u = error * crate::math::sqrt(2. * PI) * exp(0.5 * x * x);
+    x - u / (1. + 0.5 * x * u)
+}
+
+/// Evaluate the continued fraction of the regularized incomplete beta
+/// function.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    // Modified Lentz's method, see Numerical Recipes, section 6.4.
+    let tiny = f64::MIN_POSITIVE / EPSILON;
+    let mut c = 1.;
+    let mut d = 1. - (a + b) * x / (a + 1.);
+    if d.abs() < tiny {
+        d = tiny;
+    }
+    d = 1. / d;
+    let mut h = d;
+    for m in 1..MAX_ITERATIONS {
+        let m = m as f64;
+        let m2 = 2. * m;
+        // Even step
+        let an = m * (b - m) * x / ((a + m2 - 1.) * (a + m2));
+        d = 1. + an * d;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = 1. + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1. / d;
+        h *= d * c;
+        // Odd step
+        let an = -(a + m) * (a + b + m) * x / ((a + m2) * (a + m2 + 1.));
+        d = 1. + an * d;
+        if d.abs() < tiny {
+            d = tiny;
+        }
+        c = 1. + an / c;
+        if c.abs() < tiny {
+            c = tiny;
+        }
+        d = 1. / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.).abs() < EPSILON {
+            break;
+        }
+    }
+    h
+}
+
+/// Calculate the regularized incomplete beta function `I_x(a, b)`.
+pub(crate) fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    debug_assert!(a > 0. && b > 0.);
+    if x <= 0. {
+        return 0.;
+    }
+    if x >= 1. {
+        return 1.;
+    }
+    let front = exp(ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b)
+                    + a * ln(x) + b * ln(1. - x));
+    // The continued fraction converges quickly on this side, otherwise use
+    // the symmetry `I_x(a, b) = 1 - I_{1-x}(b, a)`.
+    if x < (a + 1.) / (a + b + 2.) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1. - front * beta_continued_fraction(b, a, 1. - x) / b
+    }
+}
+
+/// Calculate the cumulative distribution function of the binomial
+/// distribution, i.e. the probability of at most `k` successes in `n` trials
+/// with success probability `p`.
+pub(crate) fn binomial_cdf(k: u64, n: u64, p: f64) -> f64 {
+    if k >= n {
+        return 1.;
+    }
+    beta_inc((n - k) as f64, k as f64 + 1., 1. - p)
+}
+
+#[test]
+fn ln_gamma_values() {
+    assert!((ln_gamma(1.)).abs() < 1e-14);
+    assert!((ln_gamma(2.)).abs() < 1e-14);
+    assert!((ln_gamma(5.) - 24f64.ln()).abs() < 1e-13);
+    assert!((ln_gamma(0.5) - core::f64::consts::PI.sqrt().ln()).abs() < 1e-14);
+    assert!((ln_gamma(100.) - 359.134_205_369_575_4).abs() < 1e-10);
+}
+
+#[test]
+fn chi_square_sf_values() {
+    // Reference values calculated with SciPy.
+    assert!((chi_square_sf(0., 3.) - 1.).abs() < 1e-15);
+    assert!((chi_square_sf(3.841_458_820_694_124, 1.) - 0.05).abs() < 1e-12);
+    assert!((chi_square_sf(2., 2.) - (-1f64).exp()).abs() < 1e-14);
+    assert!((chi_square_sf(18.307_038_053_275_146, 10.) - 0.05).abs() < 1e-12);
+    assert!((chi_square_sf(5., 10.) - 0.891_178_018_914_151).abs() < 1e-10);
+}
+
+#[test]
+fn binomial_cdf_values() {
+    // Reference values calculated exactly with rational arithmetic.
+    assert!((beta_inc(2., 5., 0.3) - 0.579_825).abs() < 1e-14);
+    assert!((binomial_cdf(3, 10, 0.5) - 0.171_875).abs() < 1e-14);
+    assert!((binomial_cdf(40, 100, 0.5) - 0.028_443_966_820_490_395).abs() < 1e-13);
+    assert!((binomial_cdf(985, 1000, 0.99) - 0.082_412_319_516_088_75).abs() < 1e-12);
+    assert_eq!(binomial_cdf(10, 10, 0.5), 1.);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn kolmogorov_sf_values() {
+    // Reference values calculated with the series definition.
+    assert_eq!(kolmogorov_sf(0.), 1.);
+    assert!((kolmogorov_sf(0.3) - 0.999_990_694_198_665_5).abs() < 1e-14);
+    assert!((kolmogorov_sf(0.5) - 0.963_945_243_664_875_1).abs() < 1e-14);
+    assert!((kolmogorov_sf(1.) - 0.269_999_671_677_354_56).abs() < 1e-14);
+    assert!((kolmogorov_sf(1.36) - 0.049_485_876_755_377_876).abs() < 1e-14);
+    assert!((kolmogorov_sf(2.) - 0.000_670_925_255_779_695_3).abs() < 1e-15);
+}
+
+#[test]
+fn normal_quantile_values() {
+    // Reference values calculated with SciPy.
+    assert_eq!(normal_quantile(0.5), 0.);
+    assert_eq!(normal_quantile(0.), f64::NEG_INFINITY);
+    assert_eq!(normal_quantile(1.), f64::INFINITY);
+    assert!(normal_quantile(1.5).is_nan());
+    assert!((normal_quantile(0.975) - 1.959_963_984_540_054).abs() < 1e-13);
+    assert!((normal_quantile(0.01) + 2.326_347_874_040_841).abs() < 1e-13);
+    assert!((normal_quantile(0.3) + 0.524_400_512_708_041).abs() < 1e-13);
+    assert!((normal_quantile(1e-10) + 6.361_340_902_404_056).abs() < 1e-12);
+}
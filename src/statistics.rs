@@ -0,0 +1,131 @@
+use core::convert::TryFrom;
+
+use statrs::distribution;
+use statrs::statistics::{Distribution, Min as MinTrait, Max as MaxTrait};
+
+use super::{Variance, Skewness, Kurtosis, Stats, Describe, Min, Max, ChiSquareTest};
+use crate::fit;
+
+/// Implement `statrs::statistics::Distribution` and the conversion into a
+/// normal distribution for an estimator of the mean and the variance.
+///
+/// If `skewness` is given, the estimator also provides the skewness.
+macro_rules! impl_distribution {
+    ( $( $name:ty $( : $skewness:ident )? ),* ) => { $(
+        impl Distribution<f64> for $name {
+            /// Estimate the mean of the population.
+            ///
+            /// Returns `None` for an empty sample.
+            #[inline]
+            fn mean(&self) -> Option<f64> {
+                if self.is_empty() {
+                    return None;
+                }
+                Some(<$name>::mean(self))
+            }
+
+            /// Calculate the sample variance.
+            ///
+            /// Returns `None` if there are less than two samples.
+            #[inline]
+            fn variance(&self) -> Option<f64> {
+                if self.len() < 2 {
+                    return None;
+                }
+                Some(self.sample_variance())
+            }
+
+            $(
+            /// Estimate the skewness of the population.
+            ///
+            /// Returns `None` if the sample variance is zero.
+            #[inline]
+            fn $skewness(&self) -> Option<f64> {
+                self.try_skewness()
+            }
+            )?
+        }
+
+        impl TryFrom<&$name> for distribution::Normal {
+            type Error = ();
+
+            /// Return the normal distribution with the estimated mean and
+            /// standard deviation.
+            ///
+            /// Returns an error if the standard deviation is zero, in
+            /// particular if there are less than two samples.
+            fn try_from(a: &$name) -> Result<distribution::Normal, ()> {
+                if a.len() < 2 {
+                    return Err(());
+                }
+                distribution::Normal::new(a.mean(), crate::math::sqrt(a.sample_variance()))
+                    .map_err(|_| ())
+            }
+        }
+    )* };
+}
+
+impl_distribution!(Variance, Skewness: skewness, Kurtosis: skewness, Stats, Describe);
+
+impl MinTrait<f64> for Min {
+    #[inline]
+    fn min(&self) -> f64 {
+        Min::min(self)
+    }
+}
+
+impl MaxTrait<f64> for Max {
+    #[inline]
+    fn max(&self) -> f64 {
+        Max::max(self)
+    }
+}
+
+impl MinTrait<f64> for Stats {
+    #[inline]
+    fn min(&self) -> f64 {
+        Stats::min(self)
+    }
+}
+
+impl MaxTrait<f64> for Stats {
+    #[inline]
+    fn max(&self) -> f64 {
+        Stats::max(self)
+    }
+}
+
+/// Implement the conversion of a fitted distribution into the corresponding
+/// distribution of `statrs`, given the arguments of its constructor.
+macro_rules! impl_try_from_fit {
+    ( $( $fit:ident => $distr:ident |$a:ident| ( $( $argument:expr ),+ ) ),* ) => { $(
+        impl TryFrom<&fit::$fit> for distribution::$distr {
+            type Error = ();
+
+            fn try_from($a: &fit::$fit) -> Result<distribution::$distr, ()> {
+                distribution::$distr::new( $( $argument ),+ ).map_err(|_| ())
+            }
+        }
+    )* };
+}
+
+impl_try_from_fit!(
+    Normal => Normal |a| (a.mean().value(), a.std_dev().value()),
+    LogNormal => LogNormal |a| (a.mu().value(), a.sigma().value()),
+    // `statrs` parametrizes the gamma distribution by the rate.
+    Gamma => Gamma |a| (a.shape().value(), 1. / a.scale().value()),
+    Beta => Beta |a| (a.alpha().value(), a.beta().value()),
+    Exponential => Exp |a| (a.rate().value())
+);
+
+impl ChiSquareTest {
+    /// Return the distribution of the test statistic under the null
+    /// hypothesis, for instance to calculate critical values.
+    ///
+    /// Returns an error if there are no degrees of freedom.
+    ///
+    /// Requires the `"statrs"` feature.
+    pub fn null_distribution(&self) -> Result<distribution::ChiSquared, ()> {
+        distribution::ChiSquared::new(self.degrees_of_freedom as f64).map_err(|_| ())
+    }
+}
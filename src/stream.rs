@@ -0,0 +1,119 @@
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use futures_core::{ready, Stream};
+use pin_project_lite::pin_project;
+
+use super::{Estimate, IntoSample};
+
+pin_project! {
+    /// Future adding all samples of a stream to an estimator.
+    ///
+    /// This is returned by [`from_stream`], [`collect_stream`] and the methods
+    /// of [`StatsStreamExt`]. It resolves to the estimator once the stream is
+    /// exhausted.
+    ///
+    /// [`from_stream`]: ./fn.from_stream.html
+    /// [`collect_stream`]: ./fn.collect_stream.html
+    /// [`StatsStreamExt`]: ./trait.StatsStreamExt.html
+    #[derive(Debug)]
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    pub struct CollectEstimate<S, E> {
+        #[pin]
+        stream: S,
+        estimator: Option<E>,
+    }
+}
+
+impl<S, E> Future for CollectEstimate<S, E>
+    where S: Stream, S::Item: IntoSample, E: Estimate
+{
+    type Output = E;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<E> {
+        let mut this = self.project();
+        loop {
+            let item = ready!(this.stream.as_mut().poll_next(cx));
+            let estimator = this.estimator.as_mut()
+                .expect("`CollectEstimate` polled after completion");
+            match item {
+                Some(x) => estimator.add(x.into_sample()),
+                None => return Poll::Ready(this.estimator.take().unwrap()),
+            }
+        }
+    }
+}
+
+/// Create an estimator from all samples of a stream.
+///
+/// Requires the `"futures"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Variance, from_stream};
+/// use futures::{executor::block_on, stream};
+///
+/// let a: Variance = block_on(from_stream(stream::iter(1..6)));
+/// assert_eq!(a.mean(), 3.);
+/// assert_eq!(a.sample_variance(), 2.5);
+/// ```
+#[inline]
+pub fn from_stream<E, S>(stream: S) -> CollectEstimate<S, E>
+    where E: Estimate + Default, S: Stream, S::Item: IntoSample
+{
+    collect_stream(stream, E::default())
+}
+
+/// Add all samples of a stream to an existing estimator.
+///
+/// Requires the `"futures"` feature.
+#[inline]
+pub fn collect_stream<E, S>(stream: S, estimator: E) -> CollectEstimate<S, E>
+    where E: Estimate, S: Stream, S::Item: IntoSample
+{
+    CollectEstimate { stream, estimator: Some(estimator) }
+}
+
+/// Calculate statistics of a stream.
+///
+/// This is implemented for all streams of samples that can be converted to
+/// `f64`. Requires the `"futures"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Mean, Max, Estimate, StatsStreamExt};
+/// use futures::{executor::block_on, stream};
+///
+/// block_on(async {
+///     let a: Mean = stream::iter(vec![1., 2., 3.]).collect_estimator().await;
+///     assert_eq!(a.mean(), 2.);
+///     let mut m = Max::new();
+///     m.add(5.);
+///     let m = stream::iter(1..4).add_to_estimator(m).await;
+///     assert_eq!(m.max(), 5.);
+/// });
+/// ```
+pub trait StatsStreamExt: Stream + Sized {
+    /// Create an estimator from all samples of the stream.
+    #[inline]
+    fn collect_estimator<E>(self) -> CollectEstimate<Self, E>
+        where E: Estimate + Default, Self::Item: IntoSample
+    {
+        from_stream(self)
+    }
+
+    /// Add all samples of the stream to an existing estimator.
+    #[inline]
+    fn add_to_estimator<E>(self, estimator: E) -> CollectEstimate<Self, E>
+        where E: Estimate, Self::Item: IntoSample
+    {
+        collect_stream(self, estimator)
+    }
+}
+
+impl<S: Stream> StatsStreamExt for S {}
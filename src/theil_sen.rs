@@ -0,0 +1,197 @@
+use std::vec::Vec;
+
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+
+use super::{Budget, Merge, MemoryUsage};
+use crate::reservoir::Reservoir;
+
+/// The seed used by `TheilSen::new`.
+const DEFAULT_SEED: u64 = 0x7468_6569_6c73_656e;
+
+/// Estimate a linear trend `y = intercept + slope * x` of a sequence of pairs
+/// of numbers ("population") with the Theil-Sen estimator.
+///
+/// The slope is the median of the slopes between all pairs of samples, and
+/// the intercept is the median of `y - slope * x`. This is robust to outliers
+/// in both variables: almost 30 % of the samples can be arbitrarily corrupted
+/// without breaking the estimate, which makes it well suited for trends in
+/// noisy monitoring data.
+///
+/// Comparing all pairs would require storing all samples. Instead, a random
+/// sample of bounded size is kept in a reservoir, and the estimates are
+/// calculated from the pairs of samples in the reservoir when requested. This
+/// takes `O(capacity²)` time. As long as the number of samples does not exceed
+/// the capacity, the result is exact. Pairs containing NaN or infinity are
+/// ignored, as are slopes and offsets that overflow.
+///
+/// Requires the `"std"` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::TheilSen;
+///
+/// let mut a = TheilSen::new(100);
+/// for &(x, y) in &[(0., 1.), (1., 3.), (2., 5.), (3., 1000.), (4., 9.)] {
+///     a.add(x, y);
+/// }
+/// assert_eq!(a.slope(), 2.);
+/// assert_eq!(a.intercept(), 1.);
+/// assert_eq!(a.predict(10.), 21.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TheilSen {
+    /// Random sample of the pairs added so far.
+    reservoir: Reservoir<(f64, f64)>,
+}
+
+impl TheilSen {
+    /// Create a new estimator keeping at most `capacity` samples.
+    ///
+    /// The reservoir is sampled deterministically. Panics if `capacity` is
+    /// zero.
+    #[inline]
+    pub fn new(capacity: usize) -> TheilSen {
+        TheilSen::with_seed(capacity, DEFAULT_SEED)
+    }
+
+    /// Create a new estimator keeping at most `capacity` samples, which are
+    /// sampled using the given seed.
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_seed(capacity: usize, seed: u64) -> TheilSen {
+        TheilSen { reservoir: Reservoir::new(capacity, seed) }
+    }
+
+    /// Create a new estimator with a capacity fulfilling the given budget.
+    ///
+    /// An accuracy of `ε` keeps `1 / ε²` samples. Returns an error if the
+    /// budget does not allow keeping at least two samples. Note that
+    /// calculating the slope temporarily allocates memory for the slopes of
+    /// all pairs of samples, which is not included in the budget.
+    pub fn with_budget(budget: Budget) -> Result<TheilSen, ()> {
+        let capacity = budget.capacity(
+            core::mem::size_of::<TheilSen>(), core::mem::size_of::<(f64, f64)>(), 2,
+            |error| 1. / (error * error))?;
+        Ok(TheilSen::new(capacity))
+    }
+
+    /// Return the maximal number of samples kept in the reservoir.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.reservoir.capacity()
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.reservoir.seen()
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: f64, y: f64) {
+        if x.is_finite() && y.is_finite() {
+            self.reservoir.add((x, y));
+        }
+    }
+
+    /// Estimate the slope of the trend.
+    ///
+    /// Returns 0 if all samples have the same `x`, in particular if there are
+    /// less than two samples.
+    #[inline]
+    pub fn slope(&self) -> f64 {
+        self.try_slope().unwrap_or(0.)
+    }
+
+    /// Estimate the slope of the trend.
+    ///
+    /// Returns `None` if all samples have the same `x`, in particular if there
+    /// are less than two samples.
+    pub fn try_slope(&self) -> Option<f64> {
+        let sampled = self.reservoir.items();
+        let mut slopes = Vec::new();
+        for (i, a) in sampled.iter().enumerate() {
+            for b in &sampled[..i] {
+                let slope = (a.1 - b.1) / (a.0 - b.0);
+                // Equal `x` give NaN or infinity, which are skipped like
+                // slopes that overflow.
+                if slope.is_finite() {
+                    slopes.push(slope);
+                }
+            }
+        }
+        median(slopes)
+    }
+
+    /// Estimate the intercept of the trend.
+    ///
+    /// If all samples have the same `x`, this is the median of `y`. Returns 0
+    /// for an empty sample.
+    pub fn intercept(&self) -> f64 {
+        self.intercept_for(self.slope())
+    }
+
+    /// Predict `y` for the given `x` using the trend.
+    pub fn predict(&self, x: f64) -> f64 {
+        let slope = self.slope();
+        self.intercept_for(slope) + slope * x
+    }
+
+    /// Calculate the median of `y - slope * x`.
+    fn intercept_for(&self, slope: f64) -> f64 {
+        let offsets = self.reservoir.items().iter()
+            .map(|&(x, y)| y - slope * x)
+            .filter(|offset| offset.is_finite())
+            .collect();
+        median(offsets).unwrap_or(0.)
+    }
+
+    /// Reset the estimator to an empty sample, keeping its capacity.
+    pub fn reset(&mut self) {
+        self.reservoir.clear();
+    }
+}
+
+/// Calculate the median of the values, or `None` if there are none.
+fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let len = values.len();
+    let mid = len / 2;
+    let (lower, &mut upper, _) = values.select_nth_unstable_by(mid, f64::total_cmp);
+    if mid * 2 == len {
+        let below = lower.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        // Halving first avoids overflowing.
+        Some(below / 2. + upper / 2.)
+    } else {
+        Some(upper)
+    }
+}
+
+impl MemoryUsage for TheilSen {
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of::<TheilSen>() + self.reservoir.allocated()
+    }
+}
+
+impl Merge for TheilSen {
+    /// Merge another sample into this one.
+    ///
+    /// The reservoirs are merged, so the capacity of `self` is kept.
+    #[inline]
+    fn merge(&mut self, other: &TheilSen) {
+        self.reservoir.merge(&other.reservoir);
+    }
+}
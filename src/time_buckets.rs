@@ -0,0 +1,270 @@
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_time_buckets_common {
+    ($LEN:expr) => (
+        use $crate::{Estimate, Merge, IntoSample};
+
+        /// The number of intervals.
+        pub const LEN: usize = $LEN;
+
+        impl<E: Estimate + Merge + Default> TimeBuckets<E> {
+            /// Create new empty time buckets with the given length of an
+            /// interval.
+            ///
+            /// The interval can be given as a `Duration`, which is
+            /// converted to seconds, or as a number in the unit of the
+            /// time stamps.
+            ///
+            /// Panics if the interval is not positive.
+            #[inline]
+            pub fn new<T: IntoSample>(interval: T) -> TimeBuckets<E> {
+                let interval = interval.into_sample();
+                assert!(interval > 0.);
+                TimeBuckets {
+                    buckets: core::array::from_fn(|_| E::default()),
+                    interval,
+                    current: None,
+                }
+            }
+
+            /// Return the length of an interval.
+            #[inline]
+            pub fn interval(&self) -> f64 {
+                self.interval
+            }
+
+            /// Return the index of the most recent interval, i.e. the
+            /// number of intervals between time 0 and the latest time.
+            ///
+            /// Returns `None` if no time was observed yet.
+            #[inline]
+            pub fn current_interval(&self) -> Option<i64> {
+                self.current
+            }
+
+            /// Calculate the index of the interval containing the given
+            /// time.
+            #[inline]
+            fn index(&self, time: f64) -> i64 {
+                $crate::math::floor(time / self.interval) as i64
+            }
+
+            /// Return the position of the given interval in the ring
+            /// buffer.
+            #[inline]
+            fn position(index: i64) -> usize {
+                index.rem_euclid(LEN as i64) as usize
+            }
+
+            /// Advance the time, expiring the intervals that are older than
+            /// the last `LEN` intervals.
+            ///
+            /// Times before the most recent one do nothing.
+            #[inline]
+            pub fn advance_to<T: IntoSample>(&mut self, time: T) {
+                let index = self.index(time.into_sample());
+                let current = match self.current {
+                    Some(current) if index <= current => return,
+                    Some(current) => current,
+                    None => index - 1,
+                };
+                let expired = (index - current).min(LEN as i64);
+                for i in (index - expired + 1)..=index {
+                    self.buckets[Self::position(i)] = E::default();
+                }
+                self.current = Some(index);
+            }
+
+            /// Add an observation at the given time, advancing the time if
+            /// necessary.
+            ///
+            /// Returns an error if the time belongs to an interval that
+            /// already expired.
+            #[inline]
+            pub fn add_at<T: IntoSample>(&mut self, x: f64, time: T) -> Result<(), ()> {
+                let time = time.into_sample();
+                self.advance_to(time);
+                let index = self.index(time);
+                let current = self.current.unwrap();
+                if current - index >= LEN as i64 {
+                    return Err(());
+                }
+                self.buckets[Self::position(index)].add(x);
+                Ok(())
+            }
+
+            /// Return the estimator of the interval `ago` intervals before
+            /// the most recent one.
+            ///
+            /// Panics if `ago` is not less than `LEN`.
+            #[inline]
+            pub fn bucket(&self, ago: usize) -> &E {
+                assert!(ago < LEN);
+                let current = self.current.unwrap_or(0);
+                &self.buckets[Self::position(current - ago as i64)]
+            }
+
+            /// Merge the estimators of the last `k` intervals, including the
+            /// most recent one.
+            ///
+            /// Panics if `k` is larger than `LEN`.
+            #[inline]
+            pub fn merged(&self, k: usize) -> E {
+                assert!(k <= LEN);
+                let mut e = E::default();
+                for ago in 0..k {
+                    e.merge(self.bucket(ago));
+                }
+                e
+            }
+
+            /// Reset all intervals, keeping the length of an interval.
+            #[inline]
+            pub fn reset(&mut self) {
+                for b in &mut self.buckets {
+                    *b = E::default();
+                }
+                self.current = None;
+            }
+        }
+    );
+}
+
+#[cfg(feature = "serde1")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_time_buckets_inner {
+    ($name:ident, $LEN:expr) => (
+        mod $name {
+            $crate::define_time_buckets_common!($LEN);
+
+            use ::serde::{Serialize, Deserialize};
+
+            /// Estimators for a number of consecutive time intervals known at
+            /// compile time.
+            #[derive(Debug, Clone, Serialize, Deserialize)]
+            #[serde(bound(serialize = "E: Serialize",
+                          deserialize = "E: Deserialize<'de> + Default"))]
+            pub struct TimeBuckets<E> {
+                /// The estimators, indexed by interval modulo `LEN`.
+                #[serde(with = "buckets")]
+                buckets: [E; LEN],
+                /// The length of an interval.
+                interval: f64,
+                /// The index of the most recent interval, if any.
+                current: Option<i64>,
+            }
+
+            /// Serialize the estimators as a sequence, since arrays of
+            /// arbitrary length are not supported by Serde.
+            mod buckets {
+                use ::serde::{Serialize, Serializer, Deserialize, Deserializer};
+                use ::serde::de::{SeqAccess, Visitor, Error};
+                use super::LEN;
+
+                pub fn serialize<E, S>(buckets: &[E; LEN], serializer: S) -> Result<S::Ok, S::Error>
+                    where E: Serialize, S: Serializer
+                {
+                    serializer.collect_seq(buckets.iter())
+                }
+
+                pub fn deserialize<'de, E, D>(deserializer: D) -> Result<[E; LEN], D::Error>
+                    where E: Deserialize<'de> + Default, D: Deserializer<'de>
+                {
+                    struct BucketsVisitor<E>(::core::marker::PhantomData<E>);
+
+                    impl<'de, E: Deserialize<'de> + Default> Visitor<'de> for BucketsVisitor<E> {
+                        type Value = [E; LEN];
+
+                        fn expecting(&self, formatter: &mut ::core::fmt::Formatter<'_>)
+                            -> ::core::fmt::Result
+                        {
+                            write!(formatter, "a sequence of {} estimators", LEN)
+                        }
+
+                        fn visit_seq<A>(self, mut seq: A) -> Result<[E; LEN], A::Error>
+                            where A: SeqAccess<'de>
+                        {
+                            let mut buckets: [E; LEN] = ::core::array::from_fn(|_| E::default());
+                            for (i, b) in buckets.iter_mut().enumerate() {
+                                *b = seq.next_element()?
+                                    .ok_or_else(|| A::Error::invalid_length(i, &self))?;
+                            }
+                            if seq.next_element::<E>()?.is_some() {
+                                return Err(A::Error::invalid_length(LEN + 1, &self));
+                            }
+                            Ok(buckets)
+                        }
+                    }
+
+                    deserializer.deserialize_seq(BucketsVisitor(::core::marker::PhantomData))
+                }
+            }
+        }
+    );
+}
+
+#[cfg(not(feature = "serde1"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! define_time_buckets_inner {
+    ($name:ident, $LEN:expr) => (
+        mod $name {
+            $crate::define_time_buckets_common!($LEN);
+
+            /// Estimators for a number of consecutive time intervals known at
+            /// compile time.
+            #[derive(Debug, Clone)]
+            pub struct TimeBuckets<E> {
+                /// The estimators, indexed by interval modulo `LEN`.
+                buckets: [E; LEN],
+                /// The length of an interval.
+                interval: f64,
+                /// The index of the most recent interval, if any.
+                current: Option<i64>,
+            }
+        }
+    );
+}
+
+/// Define a container of estimators for consecutive time intervals, with a
+/// number of intervals known at compile time.
+///
+/// This is the usual rollup pattern for metrics: samples are added to the
+/// estimator of the interval (for instance the minute) they belong to, old
+/// intervals expire as time advances, and merged views over the last `k`
+/// intervals can be calculated. The estimators have to implement [`Estimate`],
+/// [`Merge`] and `Default`.
+///
+/// Because macros are not hygenic for items, everything is defined in a private
+/// module with the given name. This includes the `TimeBuckets` struct and the
+/// number of intervals `LEN`.
+///
+/// Note that you need to make sure that `core` is accessible to the macro. If
+/// the `serde1` feature is enabled, `serde` has to be accessible as well.
+///
+/// [`Estimate`]: ./trait.Estimate.html
+/// [`Merge`]: ./trait.Merge.html
+///
+///
+/// # Example
+///
+/// ```
+/// use average::{Mean, define_time_buckets};
+/// use core::time::Duration;
+///
+/// define_time_buckets!(minutes, 5);
+/// let mut b: minutes::TimeBuckets<Mean> = minutes::TimeBuckets::new(Duration::from_secs(60));
+/// b.add_at(1., Duration::from_secs(0)).unwrap();
+/// b.add_at(2., Duration::from_secs(30)).unwrap();
+/// b.add_at(6., Duration::from_secs(90)).unwrap();
+/// assert_eq!(b.bucket(0).mean(), 6.);
+/// assert_eq!(b.bucket(1).mean(), 1.5);
+/// assert_eq!(b.merged(2).mean(), 3.);
+/// ```
+#[macro_export]
+macro_rules! define_time_buckets {
+    ($name:ident, $LEN:expr) => (
+        $crate::define_time_buckets_inner!($name, $LEN);
+    );
+}
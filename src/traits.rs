@@ -1,3 +1,12 @@
+use core::any::Any;
+
+use num_traits::AsPrimitive;
+
+use crate::adaptor::{Map, Filter};
+use crate::histogram::ChiSquareTest;
+use crate::math::{ln, sqrt};
+use crate::special::{chi_square_sf, normal_quantile};
+
 /// Estimate a statistic of a sequence of numbers ("population").
 pub trait Estimate {
     /// Add an observation sampled from the population.
@@ -5,6 +14,145 @@ pub trait Estimate {
 
     /// Estimate the statistic of the population.
     fn estimate(&self) -> f64;
+
+    /// Add an observation of any type that can be converted into an `f64`
+    /// sample, such as integers, `f32` or `Duration`.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Mean, Estimate};
+    ///
+    /// let mut a = Mean::new();
+    /// a.add_sample(1u8);
+    /// a.add_sample(2f32);
+    /// a.add_sample(-3i64);
+    /// assert_eq!(a.mean(), 0.);
+    /// ```
+    #[inline]
+    fn add_sample<T: IntoSample>(&mut self, x: T) where Self: Sized {
+        self.add(x.into_sample());
+    }
+
+    /// Add an observation, unless it is NaN or infinite.
+    ///
+    /// Returns an error and leaves the estimator unchanged if the sample is
+    /// not finite.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Mean, Estimate, AddError};
+    ///
+    /// let mut a = Mean::new();
+    /// a.add_checked(1.).unwrap();
+    /// assert_eq!(a.add_checked(f64::NAN), Err(AddError::NonFinite));
+    /// assert_eq!(a.mean(), 1.);
+    /// ```
+    #[inline]
+    fn add_checked(&mut self, x: f64) -> Result<(), AddError> {
+        if !x.is_finite() {
+            return Err(AddError::NonFinite);
+        }
+        self.add(x);
+        Ok(())
+    }
+
+    /// Wrap the estimator to transform every sample before adding it.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Mean, Estimate};
+    ///
+    /// let mut a = Mean::new().map(f64::ln);
+    /// a.add(1.);
+    /// a.add(core::f64::consts::E.powi(2));
+    /// assert_eq!(a.get().mean(), 1.);
+    /// ```
+    #[inline]
+    fn map<F: FnMut(f64) -> f64>(self, f: F) -> Map<Self, F> where Self: Sized {
+        Map::new(self, f)
+    }
+
+    /// Wrap the estimator to only add the samples satisfying a predicate.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::{Mean, Estimate};
+    ///
+    /// let mut a = Mean::new().filter(|x| *x > 0.);
+    /// a.add(-1.);
+    /// a.add(2.);
+    /// a.add(4.);
+    /// assert_eq!(a.get().mean(), 3.);
+    /// assert_eq!(a.skipped(), 1);
+    /// ```
+    #[inline]
+    fn filter<P: FnMut(&f64) -> bool>(self, predicate: P) -> Filter<Self, P> where Self: Sized {
+        Filter::new(self, predicate)
+    }
+}
+
+/// The reason why a sample was rejected by an estimator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddError {
+    /// The sample is NaN or infinite.
+    NonFinite,
+    /// The sample is outside of the range of a histogram.
+    OutOfRange,
+}
+
+impl core::fmt::Display for AddError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match *self {
+            AddError::NonFinite => "sample is not finite",
+            AddError::OutOfRange => "sample is out of range",
+        })
+    }
+}
+
+/// Convert a value into an `f64` sample that can be added to an estimator.
+///
+/// This is implemented for all primitive numbers, `Duration` (converted to
+/// seconds) and references to these types. Integers with more than 53
+/// significant bits are rounded to the nearest representable `f64`.
+pub trait IntoSample {
+    /// Convert the value into an `f64` sample.
+    fn into_sample(self) -> f64;
+}
+
+macro_rules! impl_into_sample {
+    ( $( $t:ty ),* ) => { $(
+        impl IntoSample for $t {
+            #[inline]
+            fn into_sample(self) -> f64 {
+                self as f64
+            }
+        }
+    )* };
+}
+
+impl_into_sample!(f64, f32, i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl IntoSample for core::time::Duration {
+    /// Convert the duration into seconds.
+    #[inline]
+    fn into_sample(self) -> f64 {
+        self.as_secs() as f64 + f64::from(self.subsec_nanos()) / 1e9
+    }
+}
+
+impl<T: IntoSample + Copy> IntoSample for &T {
+    #[inline]
+    fn into_sample(self) -> f64 {
+        (*self).into_sample()
+    }
 }
 
 /// Merge another sample into this one.
@@ -12,6 +160,61 @@ pub trait Merge {
     fn merge(&mut self, other: &Self);
 }
 
+/// Reset an estimator to an empty sample.
+///
+/// This allows reusing long-lived estimators between measurement epochs.
+pub trait Reset {
+    /// Reset the estimator to an empty sample, keeping its parameters (such
+    /// as `p` of a quantile).
+    fn reset(&mut self);
+}
+
+/// Merge another sample into this one, where the estimators are trait
+/// objects.
+///
+/// `Merge` cannot be used with `dyn`, because it takes `&Self`. This trait is
+/// object-safe and implemented for all `'static` types implementing `Merge`,
+/// so collections of boxed estimators of different types can be aggregated
+/// generically. The estimators are downcast at runtime.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::{Mean, Max, MergeDyn};
+///
+/// let mut a: [&mut dyn MergeDyn; 2] = [&mut Mean::new(), &mut Max::new()];
+/// let b: [&dyn MergeDyn; 2] = [&Mean::new(), &Max::new()];
+/// for (a, b) in a.iter_mut().zip(&b) {
+///     a.merge_dyn(*b).unwrap();
+/// }
+/// assert_eq!(a[0].merge_dyn(b[1]), Err(()));
+/// ```
+pub trait MergeDyn {
+    /// Return the estimator as `Any`, for downcasting.
+    fn as_any(&self) -> &dyn Any;
+
+    /// Merge another sample into this one.
+    ///
+    /// Returns an error and leaves the estimator unchanged if the other
+    /// estimator is not of the same type.
+    fn merge_dyn(&mut self, other: &dyn MergeDyn) -> Result<(), ()>;
+}
+
+impl<T: Merge + Any> MergeDyn for T {
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    #[inline]
+    fn merge_dyn(&mut self, other: &dyn MergeDyn) -> Result<(), ()> {
+        let other = other.as_any().downcast_ref::<T>().ok_or(())?;
+        self.merge(other);
+        Ok(())
+    }
+}
+
 /// Calculate the multinomial variance. Relevant for histograms.
 #[inline(always)]
 fn multinomal_variance(n: f64, n_tot_inv: f64) -> f64 {
@@ -19,20 +222,40 @@ fn multinomal_variance(n: f64, n_tot_inv: f64) -> f64 {
 }
 
 /// Get the bins and ranges from a histogram.
+///
+/// A histogram is anything that can be iterated by reference over pairs of
+/// the range of a bin and its content, `((lower, upper), count)`. The bins
+/// have to be in increasing order and must not overlap, but they do not have
+/// to be stored densely: bins that are not yielded are considered empty. This
+/// allows sparse, weighted and streaming histograms to implement the trait.
 pub trait Histogram:
-    where for<'a> &'a Self: IntoIterator<Item = ((f64, f64), u64)>
+    where for<'a> &'a Self: IntoIterator<Item = ((f64, f64), <Self as Histogram>::Count)>
 {
-    /// Return the bins of the histogram.
-    fn bins(&self) -> &[u64];
+    /// The type of the bin contents, `u64` for counts or `f64` for weights.
+    type Count: AsPrimitive<f64>;
+
+    /// Return an iterator over the bin contents.
+    #[inline]
+    fn counts(&self) -> IterCounts<<&Self as IntoIterator>::IntoIter> {
+        IterCounts { histogram_iter: self.into_iter() }
+    }
+
+    /// Return the sum of all bin contents.
+    #[inline]
+    fn sum(&self) -> f64 {
+        self.counts().map(|c| c.as_()).sum()
+    }
 
     /// Estimate the variance for the given bin.
     ///
     /// The square root of this estimates the error of the bin count.
+    ///
+    /// The bin is given by its index among the bins yielded by the histogram,
+    /// so for sparse histograms, only the stored bins are counted. Returns
+    /// `None` if the histogram does not have the given bin.
     #[inline]
-    fn variance(&self, bin: usize) -> f64 {
-        let count = self.bins()[bin];
-        let sum: u64 = self.bins().iter().sum();
-        multinomal_variance(count as f64, 1./(sum as f64))
+    fn variance(&self, bin: usize) -> Option<f64> {
+        self.variances().nth(bin)
     }
 
     /// Return an iterator over the bins normalized by the bin widths.
@@ -53,46 +276,274 @@ pub trait Histogram:
         IterBinCenters { histogram_iter: self.into_iter() }
     }
 
+    /// Estimate the percentile rank of `x`, i.e. the percentage of the
+    /// population smaller than `x`.
+    ///
+    /// Within a bin, the samples are assumed to be uniformly distributed, so
+    /// this interpolates linearly. The contents of bins with infinite width
+    /// are assumed to be located at their finite edge.
+    ///
+    /// Returns a value between 0 and 100, or 0 for an empty histogram.
+    fn percentile_of(&self, x: f64) -> f64 {
+        let sum = self.sum();
+        if sum == 0. {
+            return 0.;
+        }
+        let mut below = 0.;
+        for ((a, b), count) in self.into_iter() {
+            let count: f64 = count.as_();
+            if x >= b {
+                below += count;
+            } else if x > a {
+                let width = b - a;
+                if width.is_finite() {
+                    below += count * (x - a) / width;
+                } else if a.is_finite() {
+                    below += count;
+                }
+            } else {
+                break;
+            }
+        }
+        100. * below / sum
+    }
+
+    /// Estimate the value below which the given percentage `p` of the
+    /// population lies.
+    ///
+    /// This is the inverse of `percentile_of`. Within a bin, the samples are
+    /// assumed to be uniformly distributed, so this interpolates linearly. The
+    /// contents of bins with infinite width are assumed to be located at their
+    /// finite edge.
+    ///
+    /// Panics if `p` is not between 0 and 100. Returns 0 for an empty
+    /// histogram.
+    fn value_at_percentile(&self, p: f64) -> f64 {
+        assert!((0. ..=100.).contains(&p));
+        let target = p / 100. * self.sum();
+        let mut cumulative = 0.;
+        let mut last = 0.;
+        for ((a, b), count) in self.into_iter() {
+            let count: f64 = count.as_();
+            if count <= 0. {
+                continue;
+            }
+            if cumulative + count >= target {
+                if a.is_infinite() {
+                    return b;
+                }
+                if b.is_infinite() {
+                    return a;
+                }
+                let fraction = (target - cumulative) / count;
+                return a + fraction * (b - a);
+            }
+            cumulative += count;
+            last = if b.is_finite() { b } else { a };
+        }
+        last
+    }
+
+    /// Perform Pearson's chi-square goodness-of-fit test, comparing the
+    /// histogram to a distribution given by its cumulative distribution
+    /// function `cdf`.
+    ///
+    /// `fitted_parameters` is the number of parameters of the distribution
+    /// that were estimated from the sample, for instance 2 for a normal
+    /// distribution with fitted mean and variance. It is subtracted from the
+    /// degrees of freedom.
+    ///
+    /// The expected bin contents are normalized to the probability covered by
+    /// the range of the histogram, so samples out of range do not bias the
+    /// test. Bins with a vanishing expected content are not considered. Note
+    /// that the chi-square approximation is only valid if the expected bin
+    /// contents are not too small (at least 5 is a common rule of thumb).
+    fn chi_square_test<F>(&self, cdf: F, fitted_parameters: usize) -> ChiSquareTest
+        where F: Fn(f64) -> f64
+    {
+        let mut range = (f64::INFINITY, f64::NEG_INFINITY);
+        for ((a, b), _) in self.into_iter() {
+            range = (range.0.min(a), range.1.max(b));
+        }
+        let covered = cdf(range.1) - cdf(range.0);
+        let n = self.sum();
+        let mut statistic = 0.;
+        let mut bins: usize = 0;
+        for ((a, b), observed) in self.into_iter() {
+            let expected = n * (cdf(b) - cdf(a)) / covered;
+            if expected.is_nan() || expected <= 0. {
+                continue;
+            }
+            let delta = observed.as_() - expected;
+            statistic += delta * delta / expected;
+            bins += 1;
+        }
+        let degrees_of_freedom = bins.saturating_sub(1 + fitted_parameters);
+        let p_value = if degrees_of_freedom == 0 {
+            f64::NAN
+        } else {
+            chi_square_sf(statistic, degrees_of_freedom as f64)
+        };
+        ChiSquareTest { statistic, degrees_of_freedom, p_value }
+    }
+
+    /// Return an iterator over the points of a quantile-quantile plot,
+    /// comparing the histogram to a distribution given by its quantile
+    /// function `quantile`.
+    ///
+    /// The iterator yields `points` pairs of the theoretical and the
+    /// empirical quantile, at the probabilities `(i + 0.5) / points`. The
+    /// empirical quantiles are estimated by `value_at_percentile`. If the
+    /// population follows the distribution up to a shift and a scale, the
+    /// points lie on a straight line. Yields nothing for an empty histogram.
+    #[inline]
+    fn qq_plot<F>(&self, quantile: F, points: usize) -> IterQqPlot<'_, Self, F>
+        where F: Fn(f64) -> f64
+    {
+        let points = if self.sum() == 0. { 0 } else { points };
+        IterQqPlot { histogram: self, quantile, points, i: 0 }
+    }
+
+    /// Return an iterator over the points of a quantile-quantile plot,
+    /// comparing the histogram to the standard normal distribution.
+    ///
+    /// This is useful for checking whether the population is normally
+    /// distributed. See `qq_plot`.
+    #[inline]
+    fn normal_qq_plot(&self, points: usize) -> IterQqPlot<'_, Self, fn(f64) -> f64> {
+        self.qq_plot(normal_quantile, points)
+    }
+
+    /// Calculate the Bhattacharyya coefficient of the distributions of two
+    /// histograms, measuring their overlap.
+    ///
+    /// The coefficient is the sum of `sqrt(p * q)` over the bins, where `p`
+    /// and `q` are the fractions of the bin contents of both histograms. It
+    /// is 1 for identical distributions and 0 for disjoint ones.
+    ///
+    /// The histograms have to be compatible: their bins have to be equal or
+    /// disjoint, which is the case for histograms with the same binning.
+    /// Bins yielded by only one of the histograms are considered empty in the
+    /// other. Returns an error if bins partially overlap or if one of the
+    /// histograms is empty.
+    fn bhattacharyya_coefficient<H>(&self, other: &H) -> Result<f64, ()>
+        where H: Histogram, for<'a> &'a H: IntoIterator<Item = ((f64, f64), H::Count)>
+    {
+        let normalization = sqrt(self.sum() * other.sum());
+        if normalization == 0. {
+            return Err(());
+        }
+        let mut mine = self.into_iter().peekable();
+        let mut theirs = other.into_iter().peekable();
+        let mut coefficient = 0.;
+        while let (Some(&((a, b), p)), Some(&((c, d), q))) = (mine.peek(), theirs.peek()) {
+            if (a, b) == (c, d) {
+                coefficient += sqrt(p.as_() * q.as_());
+                mine.next();
+                theirs.next();
+            } else if b <= c {
+                mine.next();
+            } else if d <= a {
+                theirs.next();
+            } else {
+                return Err(());
+            }
+        }
+        Ok((coefficient / normalization).min(1.))
+    }
+
+    /// Calculate the Bhattacharyya distance of the distributions of two
+    /// histograms, `-ln(BC)` of their Bhattacharyya coefficient.
+    ///
+    /// The distance is 0 for identical distributions and infinite for
+    /// disjoint ones. See `bhattacharyya_coefficient` for the requirements
+    /// on the histograms.
+    #[inline]
+    fn bhattacharyya_distance<H>(&self, other: &H) -> Result<f64, ()>
+        where H: Histogram, for<'a> &'a H: IntoIterator<Item = ((f64, f64), H::Count)>
+    {
+        Ok(-ln(self.bhattacharyya_coefficient(other)?))
+    }
+
+    /// Calculate the Hellinger distance of the distributions of two
+    /// histograms, `sqrt(1 - BC)` of their Bhattacharyya coefficient.
+    ///
+    /// Unlike divergences, the distance is a metric bounded by 0 for identical
+    /// and 1 for disjoint distributions, which makes it well suited for
+    /// thresholds, like alerting on drift from a reference distribution. See
+    /// `bhattacharyya_coefficient` for the requirements on the histograms.
+    #[inline]
+    fn hellinger_distance<H>(&self, other: &H) -> Result<f64, ()>
+        where H: Histogram, for<'a> &'a H: IntoIterator<Item = ((f64, f64), H::Count)>
+    {
+        Ok(sqrt(1. - self.bhattacharyya_coefficient(other)?))
+    }
+
+    /// Format the histogram as a JSON object.
+    ///
+    /// The object has the keys `bins`, an array of objects with the `lower`
+    /// and `upper` edge and the `count` of each bin in increasing order, and
+    /// `sum`, the sum of all bin contents. Numbers that are not finite, like
+    /// the edges of unbounded bins, are written as `null`.
+    ///
+    /// Requires the `"serde_json"` feature.
+    #[cfg(feature = "serde_json")]
+    #[inline]
+    fn to_json(&self) -> std::string::String {
+        crate::json::histogram_to_json(self)
+    }
+
     /// Return an iterator over the bin variances.
     ///
     /// This is more efficient than calling `variance()` for each bin.
     #[inline]
     fn variances(&self) -> IterVariances<<&Self as IntoIterator>::IntoIter> {
-        let sum: u64 = self.bins().iter().sum();
         IterVariances {
             histogram_iter: self.into_iter(),
-            sum_inv: 1./(sum as f64)
+            sum_inv: 1./self.sum(),
         }
     }
 }
 
-/// Iterate over the bins normalized by bin width.
-pub struct IterNormalized<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
+/// Iterate over the bin contents.
+pub struct IterCounts<T> {
+    histogram_iter: T,
+}
+
+impl<T, C> Iterator for IterCounts<T>
+    where T: Iterator<Item = ((f64, f64), C)>
 {
+    type Item = C;
+
+    #[inline]
+    fn next(&mut self) -> Option<C> {
+        self.histogram_iter.next().map(|(_, count)| count)
+    }
+}
+
+/// Iterate over the bins normalized by bin width.
+pub struct IterNormalized<T> {
     histogram_iter: T,
 }
 
-impl<T> Iterator for IterNormalized<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
+impl<T, C> Iterator for IterNormalized<T>
+    where T: Iterator<Item = ((f64, f64), C)>, C: AsPrimitive<f64>
 {
     type Item = f64;
 
     #[inline]
     fn next(&mut self) -> Option<f64> {
-        self.histogram_iter.next().map(|((a, b), count)| (count as f64) / (b - a))
+        self.histogram_iter.next().map(|((a, b), count)| count.as_() / (b - a))
     }
 }
 
 /// Iterate over the widths of the bins.
-pub struct IterWidths<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
-{
+pub struct IterWidths<T> {
     histogram_iter: T,
 }
 
-impl<T> Iterator for IterWidths<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
+impl<T, C> Iterator for IterWidths<T>
+    where T: Iterator<Item = ((f64, f64), C)>
 {
     type Item = f64;
 
@@ -103,14 +554,12 @@ impl<T> Iterator for IterWidths<T>
 }
 
 /// Iterate over the bin centers.
-pub struct IterBinCenters<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
-{
+pub struct IterBinCenters<T> {
     histogram_iter: T,
 }
 
-impl<T> Iterator for IterBinCenters<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
+impl<T, C> Iterator for IterBinCenters<T>
+    where T: Iterator<Item = ((f64, f64), C)>
 {
     type Item = f64;
 
@@ -121,21 +570,50 @@ impl<T> Iterator for IterBinCenters<T>
 }
 
 /// Iterate over the variances.
-pub struct IterVariances<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
-{
+pub struct IterVariances<T> {
     histogram_iter: T,
     sum_inv: f64,
 }
 
-impl<T> Iterator for IterVariances<T>
-    where T: Iterator<Item = ((f64, f64), u64)>
+impl<T, C> Iterator for IterVariances<T>
+    where T: Iterator<Item = ((f64, f64), C)>, C: AsPrimitive<f64>
 {
     type Item = f64;
 
     #[inline]
     fn next(&mut self) -> Option<f64> {
         self.histogram_iter.next()
-            .map(|(_, n)| multinomal_variance(n as f64, self.sum_inv))
+            .map(|(_, n)| multinomal_variance(n.as_(), self.sum_inv))
+    }
+}
+
+/// Iterate over the points of a quantile-quantile plot.
+pub struct IterQqPlot<'a, H: ?Sized, F> {
+    histogram: &'a H,
+    quantile: F,
+    points: usize,
+    i: usize,
+}
+
+impl<'a, H, F> Iterator for IterQqPlot<'a, H, F>
+    where H: Histogram + ?Sized, for<'b> &'b H: IntoIterator<Item = ((f64, f64), H::Count)>,
+          F: Fn(f64) -> f64
+{
+    type Item = (f64, f64);
+
+    #[inline]
+    fn next(&mut self) -> Option<(f64, f64)> {
+        if self.i >= self.points {
+            return None;
+        }
+        let p = (self.i as f64 + 0.5) / self.points as f64;
+        self.i += 1;
+        Some(((self.quantile)(p), self.histogram.value_at_percentile(100. * p)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.points - self.i;
+        (remaining, Some(remaining))
     }
 }
@@ -0,0 +1,293 @@
+use nalgebra::{SMatrix, SVector};
+
+use super::Merge;
+
+/// Estimate the component-wise arithmetic mean of a sequence of vectors
+/// ("population").
+///
+/// Requires the `nalgebra` feature.
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::VectorMean;
+/// use nalgebra::Vector2;
+///
+/// let a: VectorMean<2> = [Vector2::new(1., 2.), Vector2::new(3., -4.)]
+///     .iter().collect();
+/// assert_eq!(a.mean(), Vector2::new(2., -1.));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorMean<const N: usize> {
+    /// Mean value.
+    avg: SVector<f64, N>,
+    /// Sample size.
+    n: u64,
+}
+
+impl<const N: usize> VectorMean<N> {
+    /// Create a new mean estimator.
+    #[inline]
+    pub fn new() -> VectorMean<N> {
+        VectorMean { avg: SVector::zeros(), n: 0 }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: SVector<f64, N>) {
+        self.n += 1;
+        self.avg += (x - self.avg) / self.n as f64;
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.n
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns the zero vector for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> SVector<f64, N> {
+        self.avg
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_mean(&self) -> Option<SVector<f64, N>> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.avg)
+    }
+}
+
+impl<const N: usize> core::default::Default for VectorMean<N> {
+    fn default() -> VectorMean<N> {
+        VectorMean::new()
+    }
+}
+
+impl<const N: usize> Merge for VectorMean<N> {
+    #[inline]
+    fn merge(&mut self, other: &VectorMean<N>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        self.n += other.n;
+        self.avg += (other.avg - self.avg) * (other.n as f64 / self.n as f64);
+    }
+}
+
+/// Estimate the component-wise arithmetic mean and the covariance matrix of a
+/// sequence of vectors ("population").
+///
+/// The covariance matrix is updated with the outer product of the deviations
+/// from the mean, generalizing the algorithm of [`Variance`]. It is exactly
+/// symmetric.
+///
+/// Requires the `nalgebra` feature.
+///
+/// [`Variance`]: ./type.Variance.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::VectorCovariance;
+/// use nalgebra::{Matrix2, Vector2};
+///
+/// let a: VectorCovariance<2> = [Vector2::new(1., 2.), Vector2::new(3., 6.)]
+///     .iter().collect();
+/// assert_eq!(a.mean(), Vector2::new(2., 4.));
+/// assert_eq!(a.sample_covariance(), Matrix2::new(2., 4., 4., 8.));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VectorCovariance<const N: usize> {
+    /// Estimator of the mean.
+    avg: VectorMean<N>,
+    /// Intermediate sum of the outer products of the deviations from the
+    /// mean.
+    sum_2: SMatrix<f64, N, N>,
+}
+
+impl<const N: usize> VectorCovariance<N> {
+    /// Create a new covariance estimator.
+    #[inline]
+    pub fn new() -> VectorCovariance<N> {
+        VectorCovariance { avg: VectorMean::new(), sum_2: SMatrix::zeros() }
+    }
+
+    /// Add an observation sampled from the population.
+    #[inline]
+    pub fn add(&mut self, x: SVector<f64, N>) {
+        let delta = x - self.avg.avg;
+        self.avg.add(x);
+        let n = self.avg.n as f64;
+        self.sum_2 += delta * delta.transpose() * ((n - 1.) / n);
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.avg.is_empty()
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.avg.len()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns the zero vector for an empty sample.
+    #[inline]
+    pub fn mean(&self) -> SVector<f64, N> {
+        self.avg.mean()
+    }
+
+    /// Estimate the mean of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_mean(&self) -> Option<SVector<f64, N>> {
+        self.avg.try_mean()
+    }
+
+    /// Calculate the sample covariance matrix.
+    ///
+    /// This is an unbiased estimator of the covariance matrix of the
+    /// population. Returns the zero matrix for less than two samples.
+    #[inline]
+    pub fn sample_covariance(&self) -> SMatrix<f64, N, N> {
+        if self.avg.n < 2 {
+            return SMatrix::zeros();
+        }
+        self.sum_2 / (self.avg.n - 1) as f64
+    }
+
+    /// Calculate the population covariance matrix of the sample.
+    ///
+    /// This is a biased estimator of the covariance matrix of the population.
+    /// Returns the zero matrix for an empty sample.
+    #[inline]
+    pub fn population_covariance(&self) -> SMatrix<f64, N, N> {
+        if self.avg.n == 0 {
+            return SMatrix::zeros();
+        }
+        self.sum_2 / self.avg.n as f64
+    }
+
+    /// Estimate the standard errors of the components of the mean of the
+    /// population.
+    #[inline]
+    pub fn error(&self) -> SVector<f64, N> {
+        let n = self.avg.n;
+        if n == 0 {
+            return SVector::zeros();
+        }
+        (self.sample_covariance().diagonal() / n as f64).map(crate::math::sqrt)
+    }
+}
+
+impl<const N: usize> core::default::Default for VectorCovariance<N> {
+    fn default() -> VectorCovariance<N> {
+        VectorCovariance::new()
+    }
+}
+
+impl<const N: usize> Merge for VectorCovariance<N> {
+    #[inline]
+    fn merge(&mut self, other: &VectorCovariance<N>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other.clone();
+            return;
+        }
+        let (len_self, len_other) = (self.avg.n as f64, other.avg.n as f64);
+        let delta = other.avg.avg - self.avg.avg;
+        self.avg.merge(&other.avg);
+        self.sum_2 += other.sum_2
+            + delta * delta.transpose() * (len_self * len_other / self.avg.n as f64);
+    }
+}
+
+macro_rules! impl_vector_traits {
+    ( $name:ident ) => {
+        impl<const N: usize> core::iter::FromIterator<SVector<f64, N>> for $name<N> {
+            fn from_iter<T>(iter: T) -> $name<N>
+                where T: IntoIterator<Item=SVector<f64, N>>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<const N: usize> core::iter::Extend<SVector<f64, N>> for $name<N> {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=SVector<f64, N>>
+            {
+                for x in iter {
+                    self.add(x);
+                }
+            }
+        }
+
+        impl<'a, const N: usize> core::iter::FromIterator<&'a SVector<f64, N>> for $name<N> {
+            fn from_iter<T>(iter: T) -> $name<N>
+                where T: IntoIterator<Item=&'a SVector<f64, N>>
+            {
+                let mut e = $name::new();
+                e.extend(iter);
+                e
+            }
+        }
+
+        impl<'a, const N: usize> core::iter::Extend<&'a SVector<f64, N>> for $name<N> {
+            fn extend<T>(&mut self, iter: T)
+                where T: IntoIterator<Item=&'a SVector<f64, N>>
+            {
+                for &x in iter {
+                    self.add(x);
+                }
+            }
+        }
+
+        impl<const N: usize> core::ops::AddAssign<SVector<f64, N>> for $name<N> {
+            /// Add an observation sampled from the population.
+            #[inline]
+            fn add_assign(&mut self, x: SVector<f64, N>) {
+                self.add(x);
+            }
+        }
+
+        impl<'a, const N: usize> core::ops::AddAssign<&'a $name<N>> for $name<N> {
+            /// Merge another sample into this one.
+            #[inline]
+            fn add_assign(&mut self, other: &$name<N>) {
+                self.merge(other);
+            }
+        }
+    };
+}
+
+impl_vector_traits!(VectorMean);
+impl_vector_traits!(VectorCovariance);
@@ -1,7 +1,5 @@
-use core;
-
 #[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
-use super::{MeanWithError, Estimate, Merge};
+use super::{MeanWithError, Merge};
 
 
 /// Estimate the weighted and unweighted arithmetic mean of a sequence of
@@ -19,6 +17,7 @@ use super::{MeanWithError, Estimate, Merge};
 /// ```
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct WeightedMean  {
     /// Sum of the weights.
     weight_sum: f64,
@@ -28,7 +27,7 @@ pub struct WeightedMean  {
 
 impl WeightedMean {
     /// Create a new weighted and unweighted mean estimator.
-    pub fn new() -> WeightedMean {
+    pub const fn new() -> WeightedMean {
         WeightedMean {
             weight_sum: 0., weighted_avg: 0.,
         }
@@ -44,6 +43,10 @@ impl WeightedMean {
         // and
         // http://people.ds.cam.ac.uk/fanf2/hermes/doc/antiforgery/stats.pdf.
         self.weight_sum += weight;
+        if self.weight_sum == 0. {
+            // Samples with zero weight do not contribute to the mean.
+            return;
+        }
 
         let prev_avg = self.weighted_avg;
         self.weighted_avg = prev_avg + (weight / self.weight_sum) * (sample - prev_avg);
@@ -72,6 +75,49 @@ impl WeightedMean {
     pub fn mean(&self) -> f64 {
         self.weighted_avg
     }
+
+    /// Estimate the weighted mean of the population.
+    ///
+    /// Returns `None` if the sum of weights is zero.
+    #[inline]
+    pub fn try_mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.mean())
+    }
+
+    /// Merge another sample into this one, multiplying its weights by
+    /// `weight`.
+    ///
+    /// This is equivalent to adding the samples of `other` with their weights
+    /// scaled by `weight`, which is useful for down-weighting stale
+    /// aggregates.
+    ///
+    ///
+    /// ## Example
+    ///
+    /// ```
+    /// use average::WeightedMean;
+    ///
+    /// let mut a: WeightedMean = [(1., 1.), (2., 1.)].iter().collect();
+    /// let b: WeightedMean = [(4., 2.)].iter().collect();
+    /// a.merge_weighted(&b, 0.5);
+    /// assert_eq!(a.sum_weights(), 3.);
+    /// assert_eq!(a.mean(), 7. / 3.);
+    /// ```
+    #[inline]
+    pub fn merge_weighted(&mut self, other: &WeightedMean, weight: f64) {
+        let other_weight_sum = weight * other.weight_sum;
+        if other_weight_sum == 0. {
+            return;
+        }
+        let total_weight_sum = self.weight_sum + other_weight_sum;
+        self.weighted_avg = (self.weight_sum * self.weighted_avg
+                             + other_weight_sum * other.weighted_avg)
+                            / total_weight_sum;
+        self.weight_sum = total_weight_sum;
+    }
 }
 
 impl core::default::Default for WeightedMean {
@@ -85,10 +131,18 @@ impl core::iter::FromIterator<(f64, f64)> for WeightedMean {
         where T: IntoIterator<Item=(f64, f64)>
     {
         let mut a = WeightedMean::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for WeightedMean {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
         for (i, w) in iter {
-            a.add(i, w);
+            self.add(i, w);
         }
-        a
     }
 }
 
@@ -97,10 +151,18 @@ impl<'a> core::iter::FromIterator<&'a (f64, f64)> for WeightedMean {
         where T: IntoIterator<Item=&'a (f64, f64)>
     {
         let mut a = WeightedMean::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64)> for WeightedMean {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
         for &(i, w) in iter {
-            a.add(i, w);
+            self.add(i, w);
         }
-        a
     }
 }
 
@@ -125,6 +187,9 @@ impl Merge for WeightedMean {
     /// ```
     #[inline]
     fn merge(&mut self, other: &WeightedMean) {
+        if other.weight_sum == 0. {
+            return;
+        }
         let total_weight_sum = self.weight_sum + other.weight_sum;
         self.weighted_avg = (self.weight_sum * self.weighted_avg
                              + other.weight_sum * other.weighted_avg)
@@ -150,6 +215,7 @@ impl Merge for WeightedMean {
 /// ```
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct WeightedMeanWithError {
     /// Sum of the squares of the weights.
     weight_sum_sq: f64,
@@ -162,11 +228,11 @@ pub struct WeightedMeanWithError {
 impl WeightedMeanWithError {
     /// Create a new weighted and unweighted mean estimator.
     #[inline]
-    pub fn new() -> WeightedMeanWithError {
+    pub const fn new() -> WeightedMeanWithError {
         WeightedMeanWithError {
             weight_sum_sq: 0.,
             weighted_avg: WeightedMean::new(),
-            unweighted_avg: MeanWithError::new(),
+            unweighted_avg: MeanWithError::EMPTY,
         }
     }
 
@@ -215,6 +281,14 @@ impl WeightedMeanWithError {
         self.weighted_avg.mean()
     }
 
+    /// Estimate the weighted mean of the population.
+    ///
+    /// Returns `None` if the sum of weights is zero.
+    #[inline]
+    pub fn try_weighted_mean(&self) -> Option<f64> {
+        self.weighted_avg.try_mean()
+    }
+
     /// Estimate the unweighted mean of the population.
     ///
     /// Returns 0 for an empty sample.
@@ -223,6 +297,14 @@ impl WeightedMeanWithError {
         self.unweighted_avg.mean()
     }
 
+    /// Estimate the unweighted mean of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_unweighted_mean(&self) -> Option<f64> {
+        self.unweighted_avg.try_mean()
+    }
+
     /// Return the sample size.
     #[inline]
     pub fn len(&self) -> u64 {
@@ -272,7 +354,43 @@ impl WeightedMeanWithError {
             return 0.;
         }
         let inv_effective_len = self.weight_sum_sq / (weight_sum * weight_sum);
-        (self.sample_variance() * inv_effective_len).sqrt()
+        crate::math::sqrt(self.sample_variance() * inv_effective_len)
+    }
+
+    /// Estimate the standard error of the *weighted* mean of the population.
+    ///
+    /// Returns `None` if there are less than two samples or if the sum of
+    /// weights is zero.
+    #[inline]
+    pub fn try_error(&self) -> Option<f64> {
+        if self.len() < 2 || self.sum_weights() == 0. {
+            return None;
+        }
+        Some(self.error())
+    }
+
+    /// Merge another sample into this one, multiplying its weights by
+    /// `weight`.
+    ///
+    /// This is equivalent to adding the samples of `other` with their weights
+    /// scaled by `weight`, so the effective sample size and the error of the
+    /// weighted mean account for the scaling. The unweighted statistics are
+    /// merged like with `merge`.
+    #[inline]
+    pub fn merge_weighted(&mut self, other: &WeightedMeanWithError, weight: f64) {
+        self.weight_sum_sq += weight * weight * other.weight_sum_sq;
+        self.weighted_avg.merge_weighted(&other.weighted_avg, weight);
+        self.unweighted_avg.merge(&other.unweighted_avg);
+    }
+}
+
+impl core::fmt::Display for WeightedMeanWithError {
+    /// Format the weighted mean with its standard error, like `3.21 ± 0.04`.
+    ///
+    /// The error is rounded to the number of significant figures given by the
+    /// precision (1 by default), and the mean is rounded to match.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        crate::format::fmt_with_error(f, self.weighted_mean(), self.error())
     }
 }
 
@@ -315,10 +433,18 @@ impl core::iter::FromIterator<(f64, f64)> for WeightedMeanWithError {
         where T: IntoIterator<Item=(f64, f64)>
     {
         let mut a = WeightedMeanWithError::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for WeightedMeanWithError {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
         for (i, w) in iter {
-            a.add(i, w);
+            self.add(i, w);
         }
-        a
     }
 }
 
@@ -327,9 +453,17 @@ impl<'a> core::iter::FromIterator<&'a (f64, f64)> for WeightedMeanWithError {
         where T: IntoIterator<Item=&'a (f64, f64)>
     {
         let mut a = WeightedMeanWithError::new();
+        a.extend(iter);
+        a
+    }
+}
+
+impl<'a> core::iter::Extend<&'a (f64, f64)> for WeightedMeanWithError {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=&'a (f64, f64)>
+    {
         for &(i, w) in iter {
-            a.add(i, w);
+            self.add(i, w);
         }
-        a
     }
 }
@@ -0,0 +1,371 @@
+#[cfg(feature = "serde1")] use serde::{Serialize, Deserialize};
+use float_ord::FloatOrd;
+
+use super::{Estimate, Merge, MemoryUsage, Reset};
+
+/// Estimate the weighted p-quantile of a sequence of numbers ("population").
+///
+/// This generalizes the algorithm of [`Quantile`] to weighted samples: the
+/// positions of the markers are cumulative weights instead of ranks. A sample
+/// with weight `w` counts like `w` samples, so integer weights can be used
+/// for frequencies. Like [`Quantile`], it uses constant space but does not
+/// provide any upper bound on the error.
+///
+/// Adding a sample via `Estimate::add` gives it unit weight.
+///
+/// [`Quantile`]: ./struct.Quantile.html
+///
+///
+/// ## Example
+///
+/// ```
+/// use average::WeightedQuantile;
+///
+/// let mut a = WeightedQuantile::new(0.5);
+/// a.add_weighted(1., 1.);
+/// a.add_weighted(2., 1.);
+/// a.add_weighted(10., 6.);
+/// assert_eq!(a.quantile(), 10.);
+/// assert_eq!(a.sum_weights(), 8.);
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct WeightedQuantile {
+    /// Marker heights.
+    q: [f64; 5],
+    /// Marker positions, as cumulative weights.
+    ///
+    /// For less than 5 samples, the weights of the samples in `q`.
+    n: [f64; 5],
+    /// The probability of the quantile.
+    p: f64,
+    /// Sample size.
+    len: u64,
+}
+
+impl WeightedQuantile {
+    /// Create a new weighted p-quantile estimator.
+    ///
+    /// Panics if `p` is not between 0 and 1.
+    #[inline]
+    #[allow(clippy::manual_range_contains)]  // `contains` is not `const`
+    pub const fn new(p: f64) -> WeightedQuantile {
+        assert!(p >= 0. && p <= 1.);
+        WeightedQuantile { q: [0.; 5], n: [0.; 5], p, len: 0 }
+    }
+
+    /// Return the value of `p` for this p-quantile.
+    #[inline]
+    pub fn p(&self) -> f64 {
+        self.p
+    }
+
+    /// Return the sample size.
+    #[inline]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Determine whether the sample is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Return the sum of the weights.
+    #[inline]
+    pub fn sum_weights(&self) -> f64 {
+        if self.len < 5 {
+            self.n[..self.len as usize].iter().sum()
+        } else {
+            self.n[4]
+        }
+    }
+
+    /// Return the desired position of the given marker.
+    #[inline]
+    fn desired(&self, i: usize) -> f64 {
+        let dm = [0., self.p / 2., self.p, (1. + self.p) / 2., 1.];
+        self.n[0] + (self.n[4] - self.n[0]) * dm[i]
+    }
+
+    /// Add an observation sampled from the population with the given weight.
+    ///
+    /// Samples with zero weight are ignored. Panics if the weight is negative
+    /// or NaN.
+    pub fn add_weighted(&mut self, x: f64, weight: f64) {
+        assert!(weight >= 0., "weights must not be negative");
+        if weight == 0. {
+            return;
+        }
+        if self.len < 5 {
+            let i = self.len as usize;
+            self.q[i] = x;
+            self.n[i] = weight;
+            self.len += 1;
+            if self.len == 5 {
+                self.initialize_markers();
+            }
+            return;
+        }
+        self.len += 1;
+
+        // Find the first marker above the sample and update the extremes.
+        let k = if x < self.q[0] {
+            self.q[0] = x;
+            self.n[0] = weight;
+            1
+        } else {
+            let k = (1..5).find(|&i| x < self.q[i]).unwrap_or(4);
+            if self.q[4] < x {
+                self.q[4] = x;
+            }
+            k
+        };
+        for n in &mut self.n[k..] {
+            *n += weight;
+        }
+
+        // Adjust the heights of the markers, keeping their positions strictly
+        // increasing.
+        for i in 1..4 {
+            let d = self.desired(i) - self.n[i];
+            let d = if d > 0. {
+                d.min(0.5 * (self.n[i + 1] - self.n[i]))
+            } else {
+                d.max(0.5 * (self.n[i - 1] - self.n[i]))
+            };
+            if d == 0. {
+                continue;
+            }
+            let q_new = self.parabolic(i, d);
+            self.q[i] = if self.q[i - 1] <= q_new && q_new <= self.q[i + 1] {
+                q_new
+            } else {
+                self.linear(i, d)
+            };
+            self.n[i] += d;
+        }
+    }
+
+    /// Sort the first 5 samples and calculate the positions of the markers.
+    fn initialize_markers(&mut self) {
+        let mut samples = [(0., 0.); 5];
+        for (s, (&q, &n)) in samples.iter_mut().zip(self.q.iter().zip(self.n.iter())) {
+            *s = (q, n);
+        }
+        samples.sort_unstable_by_key(|&(x, _)| FloatOrd(x));
+        let mut cumulative = 0.;
+        for (i, &(x, w)) in samples.iter().enumerate() {
+            cumulative += w;
+            self.q[i] = x;
+            self.n[i] = cumulative;
+        }
+    }
+
+    /// Parabolic prediction for marker height.
+    #[inline]
+    fn parabolic(&self, i: usize, d: f64) -> f64 {
+        self.q[i] + d / (self.n[i + 1] - self.n[i - 1])
+            * ((self.n[i] - self.n[i - 1] + d)
+               * (self.q[i + 1] - self.q[i])
+               / (self.n[i + 1] - self.n[i])
+               + (self.n[i + 1] - self.n[i] - d)
+               * (self.q[i] - self.q[i - 1])
+               / (self.n[i] - self.n[i - 1]))
+    }
+
+    /// Linear prediction for marker height.
+    #[inline]
+    fn linear(&self, i: usize, d: f64) -> f64 {
+        let j = if d < 0. { i - 1 } else { i + 1 };
+        self.q[i] + d * (self.q[j] - self.q[i]) / (self.n[j] - self.n[i])
+    }
+
+    /// Estimate the weighted p-quantile of the population.
+    ///
+    /// Returns 0 for an empty sample.
+    pub fn quantile(&self) -> f64 {
+        if self.len >= 5 {
+            return self.q[2];
+        }
+        if self.is_empty() {
+            return 0.;
+        }
+
+        // Find the sample where the cumulative weight reaches the quantile.
+        let len = self.len as usize;
+        let mut samples = [(0., 0.); 4];
+        for (s, (&q, &n)) in samples.iter_mut().zip(self.q.iter().zip(self.n.iter())) {
+            *s = (q, n);
+        }
+        let samples = &mut samples[..len];
+        samples.sort_unstable_by_key(|&(x, _)| FloatOrd(x));
+        let target = self.p * self.sum_weights();
+        let mut cumulative = 0.;
+        for (i, &(x, w)) in samples.iter().enumerate() {
+            cumulative += w;
+            if cumulative == target && i + 1 < len {
+                // Both samples are equally valid estimates, by convention we
+                // take their average.
+                return 0.5 * x + 0.5 * samples[i + 1].0;
+            }
+            if cumulative >= target {
+                return x;
+            }
+        }
+        samples[len - 1].0
+    }
+
+    /// Estimate the weighted p-quantile of the population.
+    ///
+    /// Returns `None` for an empty sample.
+    #[inline]
+    pub fn try_quantile(&self) -> Option<f64> {
+        if self.is_empty() {
+            return None;
+        }
+        Some(self.quantile())
+    }
+
+    /// Estimate the total weight of the samples smaller than or equal to `x`,
+    /// by interpolating linearly between the markers.
+    ///
+    /// Requires at least 5 samples.
+    fn rank(&self, x: f64) -> f64 {
+        if x < self.q[0] {
+            return 0.;
+        }
+        for i in 1..5 {
+            if x < self.q[i] {
+                let fraction = (x - self.q[i - 1]) / (self.q[i] - self.q[i - 1]);
+                return self.n[i - 1] + fraction * (self.n[i] - self.n[i - 1]);
+            }
+        }
+        self.n[4]
+    }
+}
+
+impl core::default::Default for WeightedQuantile {
+    /// Create a new weighted median estimator.
+    fn default() -> WeightedQuantile {
+        WeightedQuantile::new(0.5)
+    }
+}
+
+impl Estimate for WeightedQuantile {
+    #[inline]
+    fn add(&mut self, x: f64) {
+        self.add_weighted(x, 1.);
+    }
+
+    #[inline]
+    fn estimate(&self) -> f64 {
+        self.quantile()
+    }
+}
+
+impl Reset for WeightedQuantile {
+    #[inline]
+    fn reset(&mut self) {
+        *self = WeightedQuantile::new(self.p);
+    }
+}
+
+impl MemoryUsage for WeightedQuantile {
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        core::mem::size_of::<WeightedQuantile>()
+    }
+}
+
+impl Merge for WeightedQuantile {
+    /// Merge another sample into this one.
+    ///
+    /// Like for [`Quantile`], this loses some accuracy compared to adding all
+    /// samples to one estimator, unless one of them has less than 5 samples.
+    ///
+    /// Panics if the estimators do not estimate the same quantile.
+    ///
+    /// [`Quantile`]: ./struct.Quantile.html
+    fn merge(&mut self, other: &WeightedQuantile) {
+        assert_eq!(self.p, other.p, "Both estimators must estimate the same quantile");
+        if other.is_empty() {
+            return;
+        }
+        if other.len < 5 {
+            for i in 0..other.len as usize {
+                self.add_weighted(other.q[i], other.n[i]);
+            }
+            return;
+        }
+        if self.len < 5 {
+            let mut merged = other.clone();
+            for i in 0..self.len as usize {
+                merged.add_weighted(self.q[i], self.n[i]);
+            }
+            *self = merged;
+            return;
+        }
+
+        // The combined rank is piecewise linear between the marker heights of
+        // both estimators, so it is enough to evaluate it at those.
+        let mut heights = [0.; 10];
+        heights[..5].copy_from_slice(&self.q);
+        heights[5..].copy_from_slice(&other.q);
+        heights.sort_unstable_by_key(|&x| FloatOrd(x));
+        let mut ranks = [0.; 10];
+        for (r, &x) in ranks.iter_mut().zip(heights.iter()) {
+            *r = self.rank(x) + other.rank(x);
+        }
+
+        let mut merged = WeightedQuantile::new(self.p);
+        merged.len = self.len + other.len;
+        merged.n[0] = if self.q[0] <= other.q[0] { self.n[0] } else { other.n[0] };
+        merged.n[4] = self.n[4] + other.n[4];
+        // Keep the positions strictly increasing, even for the extreme
+        // quantiles where some desired positions coincide.
+        let gap = 1e-9 * (merged.n[4] - merged.n[0]);
+        for i in 0..5 {
+            let desired = merged.desired(i);
+            if 0 < i && i < 4 {
+                merged.n[i] = desired.max(merged.n[i - 1] + gap)
+                    .min(merged.n[4] - (4 - i) as f64 * gap);
+            }
+            let j = ranks.iter().position(|&r| r >= desired).unwrap_or(9);
+            merged.q[i] = if j == 0 || ranks[j] == ranks[j - 1] {
+                heights[j]
+            } else {
+                let fraction = (desired - ranks[j - 1]) / (ranks[j] - ranks[j - 1]);
+                heights[j - 1] + fraction * (heights[j] - heights[j - 1])
+            };
+        }
+        merged.q[0] = self.q[0].min(other.q[0]);
+        merged.q[4] = self.q[4].max(other.q[4]);
+        *self = merged;
+    }
+}
+
+impl core::iter::FromIterator<(f64, f64)> for WeightedQuantile {
+    /// Create a weighted median estimator from pairs of samples and weights.
+    fn from_iter<T>(iter: T) -> WeightedQuantile
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        let mut a = WeightedQuantile::default();
+        a.extend(iter);
+        a
+    }
+}
+
+impl core::iter::Extend<(f64, f64)> for WeightedQuantile {
+    fn extend<T>(&mut self, iter: T)
+        where T: IntoIterator<Item=(f64, f64)>
+    {
+        for (x, w) in iter {
+            self.add_weighted(x, w);
+        }
+    }
+}
+
+impl_add_assign!(WeightedQuantile; f64, merge);
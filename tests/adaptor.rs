@@ -0,0 +1,107 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Estimate, Merge, Reset, Mean, Variance, Kurtosis, Max, Quantile, Scaled};
+
+#[test]
+fn map() {
+    let mut a = Variance::new().map(|x| 2. * x);
+    for &x in &[1., 2., 3.] {
+        a.add(x);
+    }
+    a += 4.;
+    assert_eq!(a.estimate(), 5.);
+    assert_eq!(a.get().sample_variance(), 4. * 5. / 3.);
+    a.reset();
+    assert!(a.into_inner().is_empty());
+}
+
+#[test]
+fn filter() {
+    let mut a = Max::new().filter(|x| x.is_finite());
+    for &x in &[1., f64::INFINITY, 3., f64::NAN] {
+        a.add(x);
+    }
+    assert_eq!(a.estimate(), 3.);
+    assert_eq!(a.skipped(), 2);
+    a.reset();
+    assert_eq!(a.skipped(), 0);
+}
+
+#[test]
+fn merge() {
+    let positive = |x: &f64| *x > 0.;
+    let mut a = Mean::new().filter(positive);
+    let mut b = Mean::new().filter(positive);
+    a.add(-1.);
+    a.add(1.);
+    b.add(3.);
+    b.add(-2.);
+    a.merge(&b);
+    assert_eq!(a.estimate(), 2.);
+    assert_eq!(a.skipped(), 2);
+}
+
+#[test]
+fn chained() {
+    let mut a = Mean::new().filter(|x| *x > 0.).map(f64::sqrt);
+    for &x in &[4., -1., 16.] {
+        a.add(x);
+    }
+    assert_eq!(a.estimate(), 3.);
+    assert_eq!(a.get().skipped(), 1);
+}
+
+#[test]
+fn scaled_unit_conversion() {
+    // Celsius to Fahrenheit
+    let mut a = Scaled::new(Kurtosis::new(), 1.8, 32.);
+    let mut b = Kurtosis::new();
+    for &x in &[-3., 0., 1., 5., 12.] {
+        a.add(x);
+        b.add(x);
+    }
+    assert_eq!(a.scale(), 1.8);
+    assert_eq!(a.offset(), 32.);
+    assert_eq!(a.get().mean(), 1.8 * b.mean() + 32.);
+    assert!((a.mean() - b.mean()).abs() < 1e-14);
+    assert!((a.sample_variance() - b.sample_variance()).abs() < 1e-12);
+    assert!((a.error_mean() - b.error_mean()).abs() < 1e-14);
+    assert!((a.skewness() - b.skewness()).abs() < 1e-12);
+    assert!((a.kurtosis() - b.kurtosis()).abs() < 1e-12);
+    assert_eq!(a.estimate(), a.kurtosis());
+}
+
+#[test]
+fn scaled_conditioning() {
+    let mut a = Scaled::new(Variance::new(), 1e-3, -1e6);
+    let mut q = Scaled::new(Quantile::new(0.5), 1e-3, -1e6);
+    for i in 0..10 {
+        let x = 1e9 + f64::from(i) * 1e3;
+        a += x;
+        q += x;
+    }
+    assert_eq!(a.mean(), 1e9 + 4.5e3);
+    assert!((a.sample_variance() / 1e6 - 55. / 6.).abs() < 1e-12);
+    assert!((a.estimate() / 1e6 - 8.25).abs() < 1e-12);
+    assert_eq!(q.estimate(), q.quantile());
+    assert!((q.quantile() - (1e9 + 4.5e3)).abs() <= 1e3);
+
+    let mut b = a.clone();
+    b.merge(&a);
+    assert_eq!(b.get().len(), 20);
+    b.reset();
+    assert!(b.into_inner().is_empty());
+}
+
+#[test]
+#[should_panic]
+fn scaled_negative() {
+    Scaled::new(Max::new(), -1., 0.);
+}
+
+#[test]
+#[should_panic]
+fn scaled_merge_mismatch() {
+    let mut a = Scaled::new(Mean::new(), 2., 0.);
+    a.merge(&Scaled::new(Mean::new(), 1., 0.));
+}
@@ -0,0 +1,34 @@
+#![cfg(feature = "approx")]
+
+use approx::{assert_abs_diff_eq, assert_relative_eq, assert_relative_ne, AbsDiffEq};
+
+use average::{Kurtosis, Max, Mean, Merge, Variance};
+
+#[test]
+fn merged_equals_sequential() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let (left, right) = sequence.split_at(3);
+    let total: Kurtosis = sequence.iter().collect();
+    let mut a: Kurtosis = left.iter().collect();
+    a.merge(&right.iter().collect());
+    assert_relative_eq!(a, total, max_relative = 1e-12);
+    assert_abs_diff_eq!(a, total, epsilon = 1e-12);
+}
+
+#[test]
+fn different() {
+    let a: Variance = [1., 2., 3.].iter().collect();
+    let b: Variance = [1., 2., 3.1].iter().collect();
+    assert_relative_ne!(a, b);
+    assert!(a.abs_diff_eq(&b, 0.2));
+    assert!(!a.abs_diff_eq(&b, 0.01));
+
+    // The sample sizes have to match.
+    let c: Mean = [2.].iter().collect();
+    let d: Mean = [2., 2.].iter().collect();
+    assert!(!c.abs_diff_eq(&d, 1.));
+
+    let e: Max = [1., 2.].iter().collect();
+    let f: Max = [2.].iter().collect();
+    assert_relative_eq!(e, f);
+}
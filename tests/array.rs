@@ -0,0 +1,45 @@
+#![cfg(feature = "ndarray")]
+#![allow(clippy::float_cmp)]
+
+use average::{Mean, Quantile, Variance, Max, StatsArrayExt};
+use ndarray::{Array2, Array3, Axis, array};
+
+#[test]
+fn axis() {
+    let a = array![[1., 2., 3.], [4., 5., 6.]];
+    assert_eq!(a.estimate_axis::<Mean>(Axis(0)), array![2.5, 3.5, 4.5]);
+    assert_eq!(a.estimate_axis::<Mean>(Axis(1)), array![2., 5.]);
+    assert_eq!(a.estimate_axis::<Max>(Axis(1)), array![3., 6.]);
+    let columns = a.estimators_axis::<Variance>(Axis(0));
+    assert_eq!(columns.len(), 3);
+    assert!(columns.iter().all(|v| v.len() == 2 && v.sample_variance() == 4.5));
+}
+
+#[test]
+fn integers() {
+    let a = array![[1u8, 3], [5, 7]];
+    assert_eq!(a.view().estimate_axis::<Mean>(Axis(0)), array![3., 5.]);
+}
+
+#[test]
+fn higher_dimensions() {
+    let a = Array3::from_shape_fn((2, 3, 4), |(i, j, k)| (i * 12 + j * 4 + k) as f64);
+    let means = a.estimate_axis::<Mean>(Axis(2));
+    assert_eq!(means.shape(), &[2, 3]);
+    assert_eq!(means[[1, 2]], 21.5);
+}
+
+#[test]
+fn with() {
+    let a = Array2::from_shape_fn((101, 2), |(i, j)| (i * (j + 1)) as f64);
+    let q = a.estimators_axis_with(Axis(0), || Quantile::new(0.5));
+    assert_eq!(q[0].quantile(), 50.);
+    assert_eq!(q[1].quantile(), 100.);
+}
+
+#[test]
+fn empty() {
+    let a = Array2::<f64>::zeros((0, 2));
+    let means = a.estimators_axis::<Mean>(Axis(0));
+    assert!(means.iter().all(Mean::is_empty));
+}
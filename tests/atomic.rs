@@ -0,0 +1,41 @@
+#![cfg(feature = "std")]
+
+use average::{AtomicStats, Variance, assert_almost_eq};
+
+#[test]
+fn concurrent() {
+    let a = AtomicStats::new();
+    assert!(a.is_empty());
+    assert_eq!(a.snapshot().len(), 0);
+    std::thread::scope(|s| {
+        for t in 0..8 {
+            let a = &a;
+            s.spawn(move || {
+                for i in 0..1000 {
+                    a.add(1e9 + f64::from(t * 1000 + i) * 0.1);
+                }
+            });
+        }
+    });
+    let b: Variance = a.snapshot();
+    assert_eq!(b.len(), 8000);
+    assert_almost_eq!(b.mean(), 1e9 + 399.95, 1e-6);
+    assert_almost_eq!(b.sample_variance(), 0.01 * 8000. * 8001. / 12., 1e-6);
+
+    a.reset();
+    assert!(a.is_empty());
+    a.add(2.);
+    let c = a.snapshot();
+    assert_eq!(c.mean(), 2.);
+    assert_eq!(c.sample_variance(), 0.);
+}
+
+#[test]
+fn nan_first() {
+    let a = AtomicStats::new();
+    a.add(f64::NAN);
+    a.add(1.);
+    let b = a.snapshot();
+    assert_eq!(b.len(), 2);
+    assert!(b.mean().is_nan());
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use std::io::{self, Read};
+
+use average::{ByteOrder, Mean, Variance, from_reader, read_samples};
+
+/// A reader yielding its data in small, irregular pieces, with interruptions.
+struct Trickle<'a> {
+    data: &'a [u8],
+    calls: usize,
+}
+
+impl<'a> Read for Trickle<'a> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.calls += 1;
+        if self.calls % 5 == 0 {
+            return Err(io::Error::new(io::ErrorKind::Interrupted, "interrupted"));
+        }
+        let n = (self.calls % 13 + 1).min(buf.len()).min(self.data.len());
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Ok(n)
+    }
+}
+
+fn encode(samples: &[f64], order: ByteOrder) -> Vec<u8> {
+    samples.iter().flat_map(|x| match order {
+        ByteOrder::LittleEndian => x.to_le_bytes(),
+        ByteOrder::BigEndian => x.to_be_bytes(),
+    }).collect()
+}
+
+#[test]
+fn large() {
+    let samples: Vec<f64> = (0..100_000).map(f64::from).collect();
+    let expected: Variance = samples.iter().collect();
+    for &order in &[ByteOrder::LittleEndian, ByteOrder::BigEndian] {
+        let bytes = encode(&samples, order);
+        let a: Variance = from_reader(&bytes[..], order).unwrap();
+        assert_eq!(a, expected);
+    }
+}
+
+#[test]
+fn partial_reads() {
+    let samples: Vec<f64> = (0..1000).map(|i| f64::from(i).sqrt()).collect();
+    let bytes = encode(&samples, ByteOrder::default());
+    let mut a = Mean::new();
+    let n = read_samples(&mut a, Trickle { data: &bytes, calls: 0 }, ByteOrder::default()).unwrap();
+    assert_eq!(n, 1000);
+    let expected: Mean = samples.iter().collect();
+    assert_eq!(a.mean(), expected.mean());
+}
+
+#[test]
+fn truncated() {
+    let bytes = encode(&[1., 2., 3.], ByteOrder::LittleEndian);
+    let mut a = Mean::new();
+    let e = read_samples(&mut a, &bytes[..20], ByteOrder::LittleEndian).unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(a.len(), 2);
+    let empty: Mean = from_reader(&[][..], ByteOrder::LittleEndian).unwrap();
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "std")]
+
+use average::{Budget, Estimate, HybridQuantile, KendallTau, KernelDensity, Merge, MemoryUsage,
+              Quantile, TheilSen, TwoSampleKs, WeightedQuantile, define_histogram};
+
+define_histogram!(hist, 10);
+
+#[test]
+fn memory() {
+    let budget = Budget::Memory(4096);
+
+    let mut kendall = KendallTau::with_budget(budget).unwrap();
+    let mut theil_sen = TheilSen::with_budget(budget).unwrap();
+    let mut ks = TwoSampleKs::with_budget(budget).unwrap();
+    let mut kde = KernelDensity::with_budget(budget).unwrap();
+    assert!(kendall.capacity() > 200);
+    assert!(theil_sen.capacity() > 200);
+    assert!(ks.capacity() > 200);
+    assert!(kde.max_components() > 100);
+
+    for i in 0..10_000 {
+        let x = f64::from(i * 7919 % 10_007);
+        kendall.add(x, -x);
+        theil_sen.add(x, 2. * x);
+        ks.add_first(x);
+        ks.add_second(x + 1.);
+        kde.add(x);
+    }
+    let (kendall_2, theil_sen_2, ks_2, kde_2) =
+        (kendall.clone(), theil_sen.clone(), ks.clone(), kde.clone());
+    kendall.merge(&kendall_2);
+    theil_sen.merge(&theil_sen_2);
+    ks.merge(&ks_2);
+    kde.merge(&kde_2);
+
+    assert!(kendall.memory_usage() <= 4096);
+    assert!(theil_sen.memory_usage() <= 4096);
+    assert!(ks.memory_usage() <= 4096);
+    assert!(kde.memory_usage() <= 4096);
+    assert!(kendall.memory_usage() > 3500);
+    assert!(kde.memory_usage() > 3500);
+}
+
+#[test]
+fn hybrid_quantile() {
+    let mut q = HybridQuantile::with_budget(0.5, Budget::Memory(4096)).unwrap();
+    assert!(q.threshold() > 400);
+    for i in 0..q.threshold() {
+        q.add(i as f64);
+        assert!(q.memory_usage() <= 4096);
+    }
+    assert!(q.is_exact());
+    assert!(q.memory_usage() > 4000);
+    q.add(0.);
+    assert!(!q.is_exact());
+    assert!(q.memory_usage() < 4096);
+}
+
+#[test]
+fn fixed_size() {
+    assert_eq!(Quantile::new(0.5).memory_usage(), core::mem::size_of::<Quantile>());
+    assert_eq!(WeightedQuantile::new(0.5).memory_usage(),
+               core::mem::size_of::<WeightedQuantile>());
+    assert_eq!(hist::Histogram::with_const_width(0., 10.).memory_usage(),
+               core::mem::size_of::<hist::Histogram>());
+}
+
+#[test]
+fn accuracy() {
+    let budget = Budget::Accuracy(0.1);
+    assert_eq!(KendallTau::with_budget(budget).unwrap().capacity(), 100);
+    assert_eq!(TheilSen::with_budget(budget).unwrap().capacity(), 100);
+    assert_eq!(TwoSampleKs::with_budget(budget).unwrap().capacity(), 100);
+    assert_eq!(KernelDensity::with_budget(budget).unwrap().max_components(), 10);
+    assert_eq!(KernelDensity::with_budget(Budget::Accuracy(1.)).unwrap().max_components(), 1);
+    assert_eq!(HybridQuantile::with_budget(0.5, budget).unwrap().threshold(), 100);
+}
+
+#[test]
+fn invalid() {
+    assert!(KendallTau::with_budget(Budget::Memory(0)).is_err());
+    assert!(TheilSen::with_budget(Budget::Memory(0)).is_err());
+    assert!(TwoSampleKs::with_budget(Budget::Memory(0)).is_err());
+    assert!(KernelDensity::with_budget(Budget::Memory(0)).is_err());
+    assert!(HybridQuantile::with_budget(0.5, Budget::Memory(0)).is_err());
+    assert!(KendallTau::with_budget(Budget::Accuracy(0.)).is_err());
+    assert!(KendallTau::with_budget(Budget::Accuracy(1.5)).is_err());
+    assert!(KendallTau::with_budget(Budget::Accuracy(f64::NAN)).is_err());
+    assert!(KendallTau::with_budget(Budget::Accuracy(1e-300)).is_err());
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{Kurtosis, Max, Min, Variance, from_slice_parallel, assert_almost_eq};
+
+#[test]
+fn matches_sequential() {
+    let data: Vec<f64> = (0..10_007).map(|i| f64::from(i * 7919 % 10_007).sqrt()).collect();
+    let expected: Kurtosis = data.iter().collect();
+    for &n_threads in &[1, 2, 3, 8, 64] {
+        let a: Kurtosis = from_slice_parallel(&data, n_threads);
+        assert_eq!(a.len(), expected.len());
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+        assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-10);
+        assert_almost_eq!(a.kurtosis(), expected.kurtosis(), 1e-10);
+        let min: Min = from_slice_parallel(&data, n_threads);
+        let max: Max = from_slice_parallel(&data, n_threads);
+        assert_eq!((min.min(), max.max()), (0., f64::from(10_006).sqrt()));
+    }
+}
+
+#[test]
+fn deterministic() {
+    let data: Vec<f64> = (0..1000).map(|i| f64::from(i).ln_1p()).collect();
+    let a: Variance = from_slice_parallel(&data, 7);
+    let b: Variance = from_slice_parallel(&data, 7);
+    assert_eq!(a, b);
+}
+
+#[test]
+fn short() {
+    let a: Variance = from_slice_parallel(&[], 4);
+    assert!(a.is_empty());
+    let a: Variance = from_slice_parallel(&[1., 2., 3.], 4);
+    assert_eq!(a.mean(), 2.);
+}
+
+#[test]
+#[should_panic]
+fn no_threads() {
+    let _: Variance = from_slice_parallel(&[1.], 0);
+}
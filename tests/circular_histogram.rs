@@ -0,0 +1,90 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+use core::f64::consts::PI;
+
+use average::{Histogram, Merge, define_circular_histogram, assert_almost_eq};
+
+define_circular_histogram!(angles, 36);
+define_circular_histogram!(hours, 24);
+
+#[test]
+fn wrap() {
+    let h = angles::Histogram::new();
+    assert_almost_eq!(h.period(), 2. * PI, 1e-15);
+    assert_eq!(h.wrap(1.), 1.);
+    assert_almost_eq!(h.wrap(1. + 2. * PI), 1., 1e-14);
+    assert_almost_eq!(h.wrap(-1.), 2. * PI - 1., 1e-14);
+    assert_eq!(h.wrap(2. * PI), 0.);
+    assert!(h.wrap(-1e-20) < h.range_max());
+}
+
+#[test]
+fn add() {
+    let mut h = hours::Histogram::with_period(24.);
+    for &x in &[0.5, 24.5, -23.5, 12., 23.9] {
+        h.add(x).unwrap();
+    }
+    assert_eq!(h.bins()[0], 3);
+    assert_eq!(h.bins()[12], 1);
+    assert_eq!(h.bins()[23], 1);
+    assert_eq!(h.add(f64::NAN), Err(()));
+    assert_eq!(h.add(f64::INFINITY), Err(()));
+    assert_eq!(h.sum(), 5.);
+}
+
+#[test]
+fn circular_mean() {
+    let mut h = hours::Histogram::with_period(24.);
+    assert!(h.circular_mean().is_nan());
+    assert_eq!(h.resultant(), (0., 0.));
+    for &x in &[23.5, 0.5, 1.5, 22.5, 0.5] {
+        h.add(x).unwrap();
+    }
+    // A linear mean would be close to 9.7.
+    assert_almost_eq!(h.circular_mean(), 0.1, 1e-2);
+
+    let mut h = angles::Histogram::new();
+    for _ in 0..10 {
+        h.add(PI / 2. + 0.01).unwrap();
+    }
+    // The bin center is used as the angle.
+    assert_almost_eq!(h.circular_mean(), PI / 2. + PI / 36., 1e-12);
+}
+
+#[test]
+fn concentration() {
+    let mut h = angles::Histogram::new();
+    for _ in 0..10 {
+        h.add(1.).unwrap();
+    }
+    assert_almost_eq!(h.mean_resultant_length(), 1., 1e-12);
+    assert_almost_eq!(h.circular_variance(), 0., 1e-12);
+
+    let mut uniform = angles::Histogram::new();
+    for i in 0..360 {
+        uniform.add(f64::from(i) / 180. * PI).unwrap();
+    }
+    assert_almost_eq!(uniform.mean_resultant_length(), 0., 1e-12);
+    assert_almost_eq!(uniform.circular_variance(), 1., 1e-12);
+}
+
+#[test]
+fn merge() {
+    let mut a = angles::Histogram::new();
+    let mut b = angles::Histogram::default();
+    a.add(1.).unwrap();
+    b.add(-1.).unwrap();
+    a.merge(&b);
+    assert_eq!(a.sum(), 2.);
+    assert_almost_eq!(a.resultant().1, 0., 1e-12);
+}
+
+#[test]
+fn add_checked() {
+    use average::AddError;
+
+    let mut h = angles::Histogram::new();
+    h.add_checked(-PI).unwrap();
+    assert_eq!(h.add_checked(f64::NAN), Err(AddError::NonFinite));
+    assert_eq!(h.bins().iter().sum::<u64>(), 1);
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{Columns, Max, Mean, Merge, Min};
+
+#[test]
+fn rows() {
+    let mut a: Columns<Mean> = Columns::new(3);
+    assert_eq!(a.num_columns(), 3);
+    assert!(a.is_empty());
+    for i in 0..10 {
+        let x = f64::from(i);
+        a.add_row(&[x, 2. * x, -x]).unwrap();
+    }
+    assert_eq!(a.add_row(&[1., 2.]), Err(()));
+    assert_eq!(a.add_row(&[1., 2., 3., 4.]), Err(()));
+    assert_eq!(a.rows(), 10);
+    assert_eq!(a.estimates(), [4.5, 9., -4.5]);
+    assert_eq!(a.column(2).unwrap().len(), 10);
+    assert!(a.column(3).is_none());
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.columns()[0].len(), 0);
+}
+
+#[test]
+fn with_estimators() {
+    let mut a = Columns::with_estimators(vec![Min::new(), Min::new()]);
+    a.add_row(&[3., 1.]).unwrap();
+    a.add_row(&[2., 5.]).unwrap();
+    let mins: Vec<f64> = a.into_columns().iter().map(Min::min).collect();
+    assert_eq!(mins, [2., 1.]);
+}
+
+#[test]
+fn merge() {
+    let mut a: Columns<Max> = Columns::new(2);
+    let mut b: Columns<Max> = Columns::new(2);
+    a.add_row(&[1., 8.]).unwrap();
+    b.add_row(&[4., 2.]).unwrap();
+    b.add_row(&[3., 7.]).unwrap();
+    a.merge(&b);
+    assert_eq!(a.rows(), 3);
+    assert_eq!(a.estimates(), [4., 8.]);
+}
+
+#[test]
+#[should_panic]
+fn merge_mismatch() {
+    let mut a: Columns<Max> = Columns::new(2);
+    a.merge(&Columns::new(3));
+}
@@ -0,0 +1,99 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Variance, anova, cohens_d, pooled_variance, assert_almost_eq};
+
+#[test]
+fn anova_reference() {
+    // The example of the one-way ANOVA on Wikipedia.
+    let groups: Vec<Variance> = vec![
+        [6., 8., 4., 5., 3., 4.].iter().collect(),
+        [8., 12., 9., 11., 6., 8.].iter().collect(),
+        [13., 9., 11., 8., 7., 12.].iter().collect(),
+    ];
+    let test = anova(&groups).unwrap();
+    assert_almost_eq!(test.statistic, 42. / (68. / 15.), 1e-12);
+    assert_eq!(test.degrees_of_freedom_between, 2);
+    assert_eq!(test.degrees_of_freedom_within, 15);
+    // For two degrees of freedom between the groups, the p-value is
+    // `(1 + 2 F / d)^(-d / 2)`.
+    assert_almost_eq!(test.p_value, 0.002398777329392908, 1e-12);
+}
+
+#[test]
+fn anova_equal_means() {
+    let groups: Vec<Variance> = vec![
+        [1., 2., 3.].iter().collect(),
+        Variance::new(),
+        [3., 2., 1.].iter().collect(),
+    ];
+    let test = anova(&groups).unwrap();
+    assert_eq!(test.statistic, 0.);
+    assert_eq!(test.degrees_of_freedom_between, 1);
+    assert_eq!(test.p_value, 1.);
+}
+
+#[test]
+fn anova_degenerate() {
+    let one: Variance = [1., 2., 3.].iter().collect();
+    assert_eq!(anova(&[one.clone(), Variance::new()]), Err(()));
+    let single: Vec<Variance> = vec![[1.].iter().collect(), [2.].iter().collect()];
+    assert_eq!(anova(&single), Err(()));
+    let constant: Vec<Variance> = vec![[1., 1.].iter().collect(), [2., 2.].iter().collect()];
+    let test = anova(&constant).unwrap();
+    assert_eq!(test.statistic, f64::INFINITY);
+    assert_eq!(test.p_value, 0.);
+    let equal: Vec<Variance> = vec![[1., 1.].iter().collect(), [1., 1.].iter().collect()];
+    assert!(anova(&equal).unwrap().p_value.is_nan());
+}
+
+#[test]
+fn pooled() {
+    let groups: Vec<Variance> = vec![
+        [6., 8., 4., 5., 3., 4.].iter().collect(),
+        Variance::new(),
+        [8., 12., 9., 11., 6., 8.].iter().collect(),
+        [13., 9., 11., 8., 7., 12.].iter().collect(),
+    ];
+    let pooled = pooled_variance(&groups).unwrap();
+    assert_eq!(pooled.degrees_of_freedom, 15);
+    // The pooled variance is the mean square within the groups of the ANOVA.
+    assert_almost_eq!(pooled.variance, 68. / 15., 1e-14);
+    assert_almost_eq!(pooled.std_dev(), f64::sqrt(68. / 15.), 1e-14);
+    assert_almost_eq!(pooled.difference_error(&groups[0], &groups[2]),
+                      f64::sqrt(68. / 15. / 3.), 1e-14);
+    assert_eq!(pooled.difference_error(&groups[0], &groups[1]), f64::INFINITY);
+
+    let single: Vec<Variance> = vec![[1.].iter().collect(), [2.].iter().collect()];
+    assert_eq!(pooled_variance(&single), Err(()));
+    assert_eq!(pooled_variance(&[]), Err(()));
+}
+
+#[test]
+fn effect_size() {
+    let a: Variance = [6., 8., 4., 5., 3., 4.].iter().collect();
+    let b: Variance = [8., 12., 9., 11., 6., 8.].iter().collect();
+    // The means are 5 and 9, the variances 3.2 and 4.8.
+    let d = cohens_d(&a, &b).unwrap();
+    assert_almost_eq!(d.value(), 2., 1e-14);
+    assert_almost_eq!(d.error(), f64::sqrt(1. / 3. + 4. / 24.), 1e-14);
+    assert_almost_eq!(cohens_d(&b, &a).unwrap().value(), -d.value(), 1e-14);
+
+    let g = d.hedges_g();
+    assert_almost_eq!(g.value(), d.value() * (1. - 3. / 39.), 1e-14);
+    assert_almost_eq!(g.error(), d.error() * (1. - 3. / 39.), 1e-14);
+
+    let (lower, upper) = d.confidence_interval(0.95);
+    assert_almost_eq!(lower, d.value() - 1.959963984540054 * d.error(), 1e-9);
+    assert_almost_eq!(upper, d.value() + 1.959963984540054 * d.error(), 1e-9);
+    assert_eq!(d.confidence_interval(0.), (d.value(), d.value()));
+}
+
+#[test]
+fn effect_size_degenerate() {
+    let a: Variance = [1., 2.].iter().collect();
+    assert_eq!(cohens_d(&a, &Variance::new()), Err(()));
+    let single: Variance = [1.].iter().collect();
+    assert_eq!(cohens_d(&single, &single), Err(()));
+    let constant: Variance = [1., 1.].iter().collect();
+    assert_eq!(cohens_d(&constant, &constant), Err(()));
+}
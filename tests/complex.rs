@@ -0,0 +1,56 @@
+#![cfg(feature = "num-complex")]
+#![allow(clippy::float_cmp)]
+
+use average::{ComplexMean, ComplexVariance, Merge, assert_almost_eq};
+use num_complex::Complex64;
+
+#[test]
+fn trivial() {
+    let mut a = ComplexVariance::new();
+    assert!(a.is_empty());
+    a.add(Complex64::new(1., -1.));
+    assert_eq!(a.mean(), Complex64::new(1., -1.));
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(a.error(), 0.);
+    a.add(Complex64::new(1., -1.));
+    assert_eq!(a.mean(), Complex64::new(1., -1.));
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.sample_variance(), 0.);
+}
+
+#[test]
+fn simple() {
+    let values: Vec<Complex64> = (1..6)
+        .map(|i| Complex64::new(f64::from(i), -2. * f64::from(i)))
+        .collect();
+    let a: ComplexMean = values.iter().collect();
+    assert_eq!(a.mean(), Complex64::new(3., -6.));
+    assert_eq!(a.len(), 5);
+    let b: ComplexVariance = values.iter().collect();
+    assert_eq!(b.mean(), Complex64::new(3., -6.));
+    // The variance is the mean squared distance from the mean.
+    let expected: f64 = values.iter()
+        .map(|z| (z - b.mean()).norm_sqr()).sum::<f64>() / 4.;
+    assert_almost_eq!(b.sample_variance(), expected, 1e-14);
+    assert_almost_eq!(b.population_variance(), expected * 4. / 5., 1e-14);
+    assert_almost_eq!(b.error(), (expected / 5.).sqrt(), 1e-14);
+}
+
+#[test]
+fn merge() {
+    let sequence: Vec<Complex64> = (0..9)
+        .map(|i| Complex64::new(f64::from(i).cos(), f64::from(i).sin()))
+        .collect();
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: ComplexVariance = sequence.iter().collect();
+        let mut a: ComplexVariance = left.iter().collect();
+        let b: ComplexVariance = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        assert_almost_eq!(a.mean().re, total.mean().re, 1e-14);
+        assert_almost_eq!(a.mean().im, total.mean().im, 1e-14);
+        assert_almost_eq!(a.sample_variance(), total.sample_variance(), 1e-14);
+    }
+}
@@ -0,0 +1,97 @@
+#![allow(clippy::float_cmp)]
+
+use core::time::Duration;
+
+use average::{DecayingMean, DecayingVariance, Estimate, MeanWithError, Reset,
+              WeightedMeanWithError, assert_almost_eq};
+
+#[test]
+fn trivial() {
+    let mut a = DecayingVariance::new(10.);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(a.error(), 0.);
+    a.add(1.);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.sum_weights(), 1.);
+    assert_eq!(a.effective_len(), 1.);
+    assert_eq!(a.sample_variance(), 0.);
+    a.add(1.);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.sample_variance(), 0.);
+}
+
+#[test]
+fn half_life() {
+    let mut a = DecayingMean::new(2);
+    assert_eq!(a.half_life(), 2.);
+    a.add(0.);
+    a.add(1.);
+    a.add(1.);
+    // The weight of the first sample halved.
+    assert_almost_eq!(a.mean(), (1. + 2f64.sqrt().recip()) / (1.5 + 2f64.sqrt().recip()), 1e-14);
+
+    let mut b = DecayingMean::new(Duration::from_secs(10));
+    b.add_at(0., Duration::from_secs(100));
+    b.add_at(3., Duration::from_secs(110));
+    assert_almost_eq!(b.mean(), 2., 1e-14);
+    assert_almost_eq!(b.sum_weights(), 1.5, 1e-14);
+    // Time stamps in the past are weighted less.
+    b.add_at(1.5, Duration::from_secs(100));
+    assert_almost_eq!(b.mean(), 1.875, 1e-14);
+}
+
+#[test]
+fn infinite_half_life() {
+    let values = [1., -2., 3.5, 4., 0.25, 7.];
+    let mut a = DecayingVariance::new(f64::INFINITY);
+    for &x in &values {
+        a.add(x);
+    }
+    let b: MeanWithError = values.iter().collect();
+    assert_almost_eq!(a.mean(), b.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-14);
+    assert_almost_eq!(a.population_variance(), b.population_variance(), 1e-14);
+    assert_almost_eq!(a.error(), b.error(), 1e-14);
+    assert_almost_eq!(a.estimate(), b.population_variance(), 1e-14);
+}
+
+#[test]
+fn weights() {
+    let values = [1., -2., 3.5, 4., 0.25, 7.];
+    let mut a = DecayingVariance::new(3.);
+    let mut b = WeightedMeanWithError::new();
+    let mut weights = Vec::new();
+    for (i, &x) in values.iter().enumerate() {
+        a.add(x);
+        let age = (values.len() - 1 - i) as f64;
+        weights.push(0.5f64.powf(age / 3.));
+        b.add(x, weights[i]);
+    }
+    assert_almost_eq!(a.mean(), b.weighted_mean(), 1e-14);
+    assert_almost_eq!(a.sum_weights(), b.sum_weights(), 1e-14);
+    assert_almost_eq!(a.effective_len(), b.effective_len(), 1e-13);
+    let w: f64 = weights.iter().sum();
+    let w2: f64 = weights.iter().map(|w| w * w).sum();
+    let sum_2: f64 = values.iter().zip(&weights)
+        .map(|(x, w)| w * (x - b.weighted_mean()).powi(2)).sum();
+    assert_almost_eq!(a.population_variance(), sum_2 / w, 1e-13);
+    assert_almost_eq!(a.sample_variance(), sum_2 / (w - w2 / w), 1e-13);
+}
+
+#[test]
+#[should_panic]
+fn zero_half_life() {
+    DecayingMean::new(0.);
+}
+
+#[test]
+fn reset() {
+    let mut a = DecayingVariance::new(Duration::from_secs(5));
+    a.add_at(1., Duration::from_secs(3));
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.half_life(), 5.);
+    assert_eq!(a.mean(), 0.);
+}
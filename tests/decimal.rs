@@ -0,0 +1,60 @@
+#![cfg(feature = "rust_decimal")]
+
+use average::{DecimalMean, Merge};
+use rust_decimal::Decimal;
+
+#[test]
+fn trivial() {
+    let mut a = DecimalMean::new();
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), Decimal::ZERO);
+    a.add(Decimal::ONE);
+    assert_eq!(a.mean(), Decimal::ONE);
+    assert_eq!(a.len(), 1);
+    a.add(Decimal::ONE);
+    assert_eq!(a.mean(), Decimal::ONE);
+    assert_eq!(a.len(), 2);
+}
+
+#[test]
+fn exact() {
+    // 0.1 + 0.2 is not 0.3 in binary floating-point arithmetic.
+    let a: DecimalMean = [Decimal::new(1, 1), Decimal::new(2, 1), Decimal::new(6, 1)]
+        .iter().collect();
+    assert_eq!(a.sum(), Decimal::new(9, 1));
+    assert_eq!(a.mean(), Decimal::new(3, 1));
+}
+
+#[test]
+fn checked_add() {
+    let mut a = DecimalMean::new();
+    a.checked_add(Decimal::MAX).unwrap();
+    assert_eq!(a.checked_add(Decimal::ONE), Err(()));
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.sum(), Decimal::MAX);
+}
+
+#[test]
+fn merge() {
+    let sequence: Vec<Decimal> = (1..10).map(|i| Decimal::new(i, 2)).collect();
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: DecimalMean = sequence.iter().collect();
+        let mut a: DecimalMean = left.iter().collect();
+        let b: DecimalMean = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        assert_eq!(a.mean(), total.mean());
+        assert_eq!(a.mean(), Decimal::new(5, 2));
+    }
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: DecimalMean = [Decimal::new(1, 1), Decimal::new(2, 1)].iter().collect();
+    let b = serde_json::to_string(&a).unwrap();
+    assert_eq!(&b, "{\"sum\":\"0.3\",\"n\":2}");
+    let c: DecimalMean = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.mean(), Decimal::new(15, 2));
+}
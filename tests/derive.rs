@@ -0,0 +1,52 @@
+#![cfg(feature = "derive")]
+
+use average::{Estimate, Merge, Reset, Mean, Variance, Min, Max};
+
+#[derive(Debug, Default, Estimate, Merge, Reset)]
+struct Summary {
+    min: Min,
+    #[estimate]
+    max: Max,
+    variance: Variance,
+}
+
+#[derive(Debug, Default, Estimate, Merge)]
+struct Wrapped(Mean);
+
+#[test]
+fn derive_estimate() {
+    let mut s = Summary::default();
+    for &x in &[1., 2., 3., 4.] {
+        s.add(x);
+    }
+    assert_eq!(s.min.min(), 1.);
+    assert_eq!(s.variance.mean(), 2.5);
+    assert_eq!(s.estimate(), 4.);
+    s.reset();
+    assert!(s.variance.is_empty());
+    assert_eq!(s.min.min(), f64::INFINITY);
+}
+
+#[test]
+fn derive_merge() {
+    let mut a = Summary::default();
+    let mut b = Summary::default();
+    a.add(1.);
+    a.add(2.);
+    b.add(3.);
+    b.add(4.);
+    a.merge(&b);
+    assert_eq!(a.variance.len(), 4);
+    assert_eq!(a.min.min(), 1.);
+    assert_eq!(a.max.max(), 4.);
+}
+
+#[test]
+fn derive_single_field() {
+    let mut a = Wrapped::default();
+    a.add(1.);
+    let mut b = Wrapped::default();
+    b.add(3.);
+    a.merge(&b);
+    assert_eq!(a.estimate(), 2.);
+}
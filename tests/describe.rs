@@ -0,0 +1,60 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Describe, Kurtosis, assert_almost_eq};
+
+#[test]
+fn describe() {
+    let d: Describe = (1..=100).map(f64::from).collect();
+    assert_eq!(d.len(), 100);
+    assert_eq!(d.mean(), 50.5);
+    assert_almost_eq!(d.std(), 29.011491975882016, 1e-12);
+    assert_eq!((d.min(), d.max()), (1., 100.));
+    let q = d.quantiles();
+    assert_eq!([q[0].0, q[1].0, q[2].0], [0.25, 0.5, 0.75]);
+    assert_almost_eq!(q[0].1, 25.75, 1.);
+    assert_almost_eq!(q[1].1, 50.5, 1.);
+    assert_almost_eq!(q[2].1, 75.25, 1.);
+    assert_almost_eq!(d.error(), 2.901149197588202, 1e-12);
+}
+
+#[test]
+fn shape() {
+    let data = [1., 2., 2., 3., 3., 3., 10.];
+    let d: Describe = data.iter().collect();
+    let k: Kurtosis = data.iter().collect();
+    assert_eq!(d.sample_skewness(), k.sample_skewness());
+    assert_eq!(d.sample_kurtosis(), k.sample_kurtosis());
+    assert!(d.sample_skewness() > 1.);
+}
+
+#[test]
+fn custom_quantiles() {
+    let mut d = Describe::with_quantiles([0.1, 0.9, 0.99]);
+    d.add(1.);
+    d += 2.;
+    assert_eq!(d.len(), 2);
+    d.reset();
+    assert!(d.is_empty());
+    assert_eq!(d.quantiles()[2].0, 0.99);
+}
+
+#[test]
+fn display() {
+    let d: Describe = [2., 4.].iter().copied().collect();
+    assert_eq!(format!("{}", d), "\
+count 2
+mean  3 ± 1
+std   1.4142135623730951
+min   2
+25%   2
+50%   3
+75%   4
+max   4
+skew  0
+kurt  0
+");
+    let d = Describe::with_quantiles([0.05, 0.5, 0.999]);
+    let s = format!("{:.1}", d);
+    assert!(s.contains("\n5%    0.0\n"));
+    assert!(s.contains("\n99.9% 0.0\n"));
+}
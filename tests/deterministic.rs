@@ -0,0 +1,35 @@
+#![cfg(feature = "deterministic")]
+
+use average::{DecayingMean, Estimate};
+
+#[test]
+fn decaying() {
+    let mut a = DecayingMean::new(2.);
+    for i in 0..100 {
+        a.add(f64::from(i) / 7.);
+    }
+    // The decay uses `libm`, so this is the same on all platforms.
+    assert_eq!(a.estimate().to_bits(), 0x402b_988f_7506_def5);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn independent_of_threads() {
+    use average::{Kurtosis, par_merge};
+    use rayon::prelude::*;
+
+    let data: Vec<f64> = (0..100_000).map(|i| f64::from(i).sqrt().fract()).collect();
+    let run = |threads: usize| {
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+        pool.install(|| {
+            let a: Kurtosis = data.par_iter().collect();
+            let chunks: Vec<Kurtosis> = data.par_chunks(1000).map(|c| c.iter().collect()).collect();
+            let b: Kurtosis = par_merge(chunks);
+            [a.mean(), a.kurtosis(), b.mean(), b.kurtosis()].map(f64::to_bits)
+        })
+    };
+    let expected = run(1);
+    for &threads in &[2, 3, 8] {
+        assert_eq!(run(threads), expected);
+    }
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "rand_distr")]
+#![allow(clippy::float_cmp)]
+
+use std::convert::TryFrom;
+
+use average::{Kurtosis, MeanWithError, Stats, assert_almost_eq};
+use average::fit;
+use rand::SeedableRng;
+use rand_distr::Distribution;
+
+#[test]
+fn to_normal() {
+    let a: MeanWithError = [1., 2., 3., 4., 5.].iter().collect();
+    let normal = a.to_normal().unwrap();
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let b: MeanWithError = normal.sample_iter(&mut rng).take(100_000).collect();
+    assert_almost_eq!(b.mean(), 3., 0.05);
+    assert_almost_eq!(b.sample_variance(), 2.5, 0.05);
+
+    let s: Stats = [1., 2., 3., 4., 5.].iter().collect();
+    assert!(rand_distr::Normal::try_from(&s).is_ok());
+    assert!(MeanWithError::new().to_normal().is_err());
+    let single: MeanWithError = [1.].iter().collect();
+    assert!(single.to_normal().is_ok());
+}
+
+#[test]
+fn fitted() {
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let a: Kurtosis = rand_distr::Gamma::new(2., 3.).unwrap()
+        .sample_iter(&mut rng).take(10_000).collect();
+    let gamma = fit::Gamma::fit(&a).unwrap();
+    let distr = rand_distr::Gamma::try_from(&gamma).unwrap();
+    let b: Kurtosis = distr.sample_iter(&mut rng).take(100_000).collect();
+    assert_almost_eq!(b.mean(), a.mean(), 0.1);
+
+    let normal = fit::Normal::fit(&a).unwrap();
+    assert!(rand_distr::Normal::try_from(&normal).is_ok());
+    let log_normal = fit::LogNormal::fit(&a).unwrap();
+    assert!(rand_distr::LogNormal::try_from(&log_normal).is_ok());
+    let exponential = fit::Exponential::fit(&a).unwrap();
+    assert!(rand_distr::Exp::try_from(&exponential).is_ok());
+    let a: Kurtosis = [0.1, 0.2, 0.4].iter().collect();
+    let beta = fit::Beta::fit(&a).unwrap();
+    assert!(rand_distr::Beta::try_from(&beta).is_ok());
+}
@@ -0,0 +1,46 @@
+#![allow(clippy::float_cmp)]
+
+use average::{FanOut, Estimate, Merge, Mean, Variance, Min, Max};
+
+#[test]
+fn tuple() {
+    let mut a = FanOut::new((Min::new(), Max::new(), Variance::new()));
+    a.extend(vec![1., 2., 3.]);
+    a += 4.;
+    {
+        let (min, max, var) = a.get();
+        assert_eq!((min.min(), max.max()), (1., 4.));
+        assert_eq!(var.mean(), 2.5);
+    }
+    a.get_mut().0.add(0.);
+    assert_eq!(a.get().0.min(), 0.);
+    a.reset();
+    let (min, _, var) = a.into_inner();
+    assert_eq!(min.min(), f64::INFINITY);
+    assert!(var.is_empty());
+}
+
+#[test]
+fn tuple_merge() {
+    let mut a = FanOut::new((Mean::new(), Max::new()));
+    let mut b = a.clone();
+    a.add(1.);
+    b.add(3.);
+    a.merge(&b);
+    assert_eq!(a.get().0.mean(), 2.);
+    assert_eq!(a.get().1.max(), 3.);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn boxed() {
+    let mut a = FanOut::default();
+    a.push(Mean::new());
+    a.push(Variance::new());
+    a.push(Max::new());
+    for &x in &[1., 2., 3., 6.] {
+        a += x;
+    }
+    let estimates: Vec<f64> = a.get().iter().map(|e| e.estimate()).collect();
+    assert_eq!(estimates, [3., 3.5, 6.]);
+}
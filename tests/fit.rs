@@ -0,0 +1,97 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Kurtosis, assert_almost_eq};
+use average::fit::{Normal, LogNormal, Gamma, Beta, Exponential, Parameter};
+use rand::SeedableRng;
+use rand_distr::Distribution;
+
+/// Draw samples from a distribution with a fixed seed.
+fn sample<D: Distribution<f64>>(distr: D, n: usize) -> Kurtosis {
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    distr.sample_iter(&mut rng).take(n).collect()
+}
+
+/// Assert that the parameter is consistent with the true value.
+fn assert_consistent(p: Parameter, expected: f64) {
+    assert!(p.error() > 0.);
+    assert!((p.value() - expected).abs() < 5. * p.error(),
+            "{} ± {} is inconsistent with {}", p.value(), p.error(), expected);
+}
+
+#[test]
+fn normal() {
+    let a: Kurtosis = [1., 2., 3., 4., 5.].iter().collect();
+    let normal = Normal::fit(&a).unwrap();
+    assert_eq!(normal.mean().value(), a.mean());
+    assert_almost_eq!(normal.mean().error(), a.error_mean(), 1e-15);
+    assert_eq!(normal.std_dev().value(), a.sample_variance().sqrt());
+
+    let a = sample(rand_distr::Normal::new(2., 3.).unwrap(), 10_000);
+    let normal = Normal::fit(&a).unwrap();
+    assert_consistent(normal.mean(), 2.);
+    assert_consistent(normal.std_dev(), 3.);
+    // For a normal population, the standard error of the standard deviation
+    // is about `sigma / sqrt(2 n)`.
+    assert_almost_eq!(normal.std_dev().error(), 3. / 20_000f64.sqrt(), 2e-3);
+}
+
+#[test]
+fn log_normal() {
+    let a = sample(rand_distr::LogNormal::new(0.5, 0.25).unwrap(), 10_000);
+    let log_normal = LogNormal::fit(&a).unwrap();
+    let (mu, sigma) = (log_normal.mu().value(), log_normal.sigma().value());
+    assert_almost_eq!((mu + 0.5 * sigma * sigma).exp(), a.mean(), 1e-12);
+    assert_consistent(log_normal.mu(), 0.5);
+    assert_consistent(log_normal.sigma(), 0.25);
+}
+
+#[test]
+fn gamma() {
+    let a: Kurtosis = [0., 2., 2., 4., 4., 4., 5.].iter().collect();
+    let gamma = Gamma::fit(&a).unwrap();
+    assert_eq!(gamma.shape().value(), 3.);
+    assert_eq!(gamma.scale().value(), 1.);
+
+    let a = sample(rand_distr::Gamma::new(2., 3.).unwrap(), 10_000);
+    let gamma = Gamma::fit(&a).unwrap();
+    assert_consistent(gamma.shape(), 2.);
+    assert_consistent(gamma.scale(), 3.);
+}
+
+#[test]
+fn beta() {
+    let a = sample(rand_distr::Beta::new(2., 5.).unwrap(), 10_000);
+    let beta = Beta::fit(&a).unwrap();
+    let (alpha, b) = (beta.alpha().value(), beta.beta().value());
+    assert_almost_eq!(alpha / (alpha + b), a.mean(), 1e-12);
+    assert_consistent(beta.alpha(), 2.);
+    assert_consistent(beta.beta(), 5.);
+
+    // The variance is too large for a beta distribution.
+    let a: Kurtosis = [0., 0., 1., 1.].iter().collect();
+    assert_eq!(Beta::fit(&a), Err(()));
+    let a: Kurtosis = [1., 2.].iter().collect();
+    assert_eq!(Beta::fit(&a), Err(()));
+}
+
+#[test]
+fn exponential() {
+    let a = sample(rand_distr::Exp::new(4.).unwrap(), 10_000);
+    let exponential = Exponential::fit(&a).unwrap();
+    assert_eq!(exponential.rate().value(), 1. / a.mean());
+    assert_consistent(exponential.rate(), 4.);
+}
+
+#[test]
+fn invalid() {
+    let empty = Kurtosis::new();
+    assert_eq!(Normal::fit(&empty), Err(()));
+    assert_eq!(Exponential::fit(&empty), Err(()));
+    let constant: Kurtosis = [1., 1., 1.].iter().collect();
+    assert_eq!(Normal::fit(&constant), Err(()));
+    assert_eq!(Gamma::fit(&constant), Err(()));
+    let negative: Kurtosis = [-1., -2., -3.].iter().collect();
+    assert_eq!(Gamma::fit(&negative), Err(()));
+    assert_eq!(LogNormal::fit(&negative), Err(()));
+    assert_eq!(Exponential::fit(&negative), Err(()));
+}
@@ -0,0 +1,33 @@
+#![allow(clippy::float_cmp)]
+
+use average::{FiveNumber, assert_almost_eq};
+
+#[test]
+fn uniform() {
+    let a: FiveNumber = (0..=1000).map(f64::from).collect();
+    assert_eq!(a.len(), 1001);
+    let [min, q1, q2, q3, max] = a.five_number();
+    assert_eq!((min, max), (0., 1000.));
+    assert_almost_eq!(q1, 250., 5.);
+    assert_almost_eq!(q2, 500., 5.);
+    assert_almost_eq!(q3, 750., 5.);
+    assert_almost_eq!(a.iqr(), 500., 10.);
+    assert_eq!(a.whiskers(), (0., 1000.));
+}
+
+#[test]
+fn outliers() {
+    let mut a = FiveNumber::new();
+    for i in 0..1000 {
+        a.add(f64::from(i % 100));
+    }
+    a.add(1e6);
+    a.add(-1e6);
+    let (lower, upper) = a.whiskers();
+    assert_almost_eq!(lower, 25. - 75., 5.);
+    assert_almost_eq!(upper, 75. + 75., 5.);
+    let (lower, upper) = a.whiskers_with_factor(0.);
+    assert_eq!((lower, upper), (a.lower_quartile(), a.upper_quartile()));
+    a.reset();
+    assert!(a.is_empty());
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "fma")]
+
+use average::{Variance, Merge};
+
+/// Samples with a large offset, which makes the products in the updates
+/// ill-conditioned.
+fn samples() -> (Vec<f64>, f64) {
+    let n = 100_000u64;
+    let offset = 1e15;
+    let deviations: Vec<f64> = (0..n).map(|i| ((i * 7919) % 3) as f64).collect();
+    let mean = deviations.iter().sum::<f64>() / n as f64;
+    let variance = deviations.iter().map(|d| (d - mean) * (d - mean)).sum::<f64>() / n as f64;
+    (deviations.iter().map(|d| offset + d).collect(), variance)
+}
+
+#[test]
+fn merge_ill_conditioned() {
+    let (x, variance) = samples();
+    let mut chunks = x.chunks(997).map(|c| c.iter().collect::<Variance>());
+    let mut a = chunks.next().unwrap();
+    for c in chunks {
+        a.merge(&c);
+    }
+    // Without fused multiply-add, the relative error is about 0.6.
+    let error = (a.population_variance() - variance).abs() / variance;
+    assert!(error < 0.05, "relative error {}", error);
+}
+
+#[test]
+fn add_ill_conditioned() {
+    let (x, variance) = samples();
+    let a: Variance = x.iter().collect();
+    let error = (a.population_variance() - variance).abs() / variance;
+    assert!(error < 1e-3, "relative error {}", error);
+}
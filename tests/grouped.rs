@@ -0,0 +1,43 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{Grouped, Mean, Merge, Variance};
+
+#[test]
+fn group_by() {
+    let mut g: Grouped<String, Variance> = Grouped::new();
+    assert!(g.is_empty());
+    for i in 0..30 {
+        g.add(format!("g{}", i % 3), f64::from(i));
+    }
+    assert_eq!(g.len(), 3);
+    assert_eq!(g.get("g0").unwrap().len(), 10);
+    assert_eq!(g.get("g2").unwrap().mean(), 15.5);
+    assert!(g.get("g3").is_none());
+    let mut keys: Vec<&str> = g.iter().map(|(k, _)| k.as_str()).collect();
+    keys.sort_unstable();
+    assert_eq!(keys, ["g0", "g1", "g2"]);
+    g.clear();
+    assert!(g.is_empty());
+}
+
+#[test]
+fn top_n() {
+    let g: Grouped<u32, Mean> = (0..100).map(|i| (i % 10, f64::from(i % 10) * 2.)).collect();
+    let top: Vec<(u32, f64)> = g.top_n(3).into_iter().map(|(&k, e)| (k, e.mean())).collect();
+    assert_eq!(top, [(9, 18.), (8, 16.), (7, 14.)]);
+    assert_eq!(g.top_n(20).len(), 10);
+    let bottom: Vec<u32> = g.top_n_by(2, |e| -e.mean()).into_iter().map(|(&k, _)| k).collect();
+    assert_eq!(bottom, [0, 1]);
+}
+
+#[test]
+fn merge() {
+    let mut a: Grouped<&str, Mean> = [("x", 1.), ("y", 2.)].iter().cloned().collect();
+    let b: Grouped<&str, Mean> = [("x", 3.), ("z", 4.)].iter().cloned().collect();
+    a.merge(&b);
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.get("x").unwrap().mean(), 2.);
+    assert_eq!(a.get("x").unwrap().len(), 2);
+    assert_eq!(a.get("z").unwrap().mean(), 4.);
+}
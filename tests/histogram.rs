@@ -2,7 +2,8 @@ use core::iter::Iterator;
 use rand::SeedableRng;
 use rand_distr::Distribution;
 
-use average::{Histogram, Merge, define_histogram, assert_almost_eq};
+use average::{Axis, Histogram, Merge, define_histogram, define_weighted_histogram,
+              assert_almost_eq};
 
 define_histogram!(hist10, 10);
 define_histogram!(hist100, 100);
@@ -45,7 +46,7 @@ fn iter() {
 
 #[test]
 fn normalized_bins() {
-    let inf = std::f64::INFINITY;
+    let inf = f64::INFINITY;
     let mut h = Histogram10::from_ranges(
         [-inf, 0.1, 0.2, 0.3, 0.4, 0.4, 0.7, 0.8, 0.9, 1.0, inf].iter().cloned()).unwrap();
     for &i in &[0.05, 0.1, 0.7, 1.0, 1.5] {
@@ -60,7 +61,7 @@ fn normalized_bins() {
 
 #[test]
 fn widths() {
-    let inf = std::f64::INFINITY;
+    let inf = f64::INFINITY;
     let h = Histogram10::from_ranges(
         [-inf, 0.1, 0.2, 0.3, 0.4, 0.4, 0.7, 0.8, 0.9, 1.0, inf].iter().cloned()).unwrap();
     let widths: Vec<f64> = h.widths().collect();
@@ -72,7 +73,7 @@ fn widths() {
 
 #[test]
 fn centers() {
-    let inf = std::f64::INFINITY;
+    let inf = f64::INFINITY;
     let h = Histogram10::from_ranges(
         [-inf, 0.1, 0.2, 0.3, 0.4, 0.4, 0.7, 0.8, 0.9, 1.0, inf].iter().cloned()).unwrap();
     let centers: Vec<f64> = h.centers().collect();
@@ -84,7 +85,7 @@ fn centers() {
 
 #[test]
 fn from_ranges_infinity() {
-    let inf = std::f64::INFINITY;
+    let inf = f64::INFINITY;
     let mut h = Histogram10::from_ranges(
         [-inf, -0.4, -0.3, -0.2, -0.1, 0.0, 0.1, 0.2, 0.3, 0.4, inf].iter().cloned()).unwrap();
     for &i in &[-100., -0.45, 0., 0.25, 0.4, 100.] {
@@ -99,7 +100,7 @@ fn from_ranges_invalid() {
     let valid = vec![0., 0.1, 0.2, 0.3, 0.4, 0.5, 0.7, 0.8, 0.9, 1.0, 2.0];
     assert!(Histogram10::from_ranges(valid.iter().cloned()).is_ok());
     let mut invalid_nan = valid.clone();
-    invalid_nan[3] = std::f64::NAN;
+    invalid_nan[3] = f64::NAN;
     assert!(Histogram10::from_ranges(invalid_nan.iter().cloned()).is_err());
     let mut invalid_order = valid.clone();
     invalid_order[10] = 0.9;
@@ -195,7 +196,7 @@ fn variance() {
     let sum: u64 = h.bins().iter().sum();
     let sum = sum as f64;
     for (i, v) in h.variances().enumerate() {
-        assert_almost_eq!(v, h.variance(i), 1e-14);
+        assert_almost_eq!(v, h.variance(i).unwrap(), 1e-14);
         let poissonian_variance = h.bins()[i] as f64;
         assert_almost_eq!(v.sqrt() / sum, poissonian_variance.sqrt() / sum, 1e-4);
     }
@@ -224,6 +225,43 @@ fn merge() {
     assert_eq!(h.bins(), h1.bins());
 }
 
+#[test]
+fn axis() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    assert!(h.axis().is_empty());
+    h.set_axis(Axis::new().title("Latency").label("time").unit("ms"));
+    assert_eq!(h.axis().title, Some("Latency"));
+    assert_eq!(h.axis().label, Some("time"));
+    assert_eq!(h.axis().unit, Some("ms"));
+    assert_eq!(format!("{}", h.axis()), "Latency: time [ms]");
+    assert_eq!(format!("{}", Axis::new().unit("ms")), "[ms]");
+    assert_eq!(format!("{}", Axis::new().title("Latency")), "Latency");
+}
+
+#[test]
+fn display() {
+    let mut h = Histogram10::with_const_width(0., 10.);
+    h.add(0.5).unwrap();
+    h.add(9.).unwrap();
+    h.add(9.5).unwrap();
+    assert_eq!(format!("{}", h), "\
+        [0, 1): 1\n[1, 2): 0\n[2, 3): 0\n[3, 4): 0\n[4, 5): 0\n\
+        [5, 6): 0\n[6, 7): 0\n[7, 8): 0\n[8, 9): 0\n[9, 10): 2\n");
+    h.set_axis(Axis::new().label("x").unit("m"));
+    assert!(format!("{}", h).starts_with("x [m]\n[0, 1): 1\n"));
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn axis_serde() {
+    let mut a = Histogram10::with_const_width(0., 10.);
+    a.set_axis(Axis::new().label("x").unit("m"));
+    let b = serde_json::to_string(&a).unwrap();
+    assert!(b.ends_with(",\"axis\":{\"label\":\"x\",\"unit\":\"m\"}}"));
+    let c: Histogram10 = serde_json::from_str(&b).unwrap();
+    assert!(c.axis().is_empty());
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn simple_serde() {
@@ -237,3 +275,308 @@ fn simple_serde() {
     let c: Histogram10 = serde_json::from_str(&b).unwrap();
     assert_eq!(c.bins(), &[1, 0, 0, 0, 0, 0, 1, 0, 0, 2]);
 }
+
+#[test]
+fn checked_add() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.checked_add(1.), Ok(()));
+    assert_eq!(h.checked_add(100.), Err(()));
+    h *= u64::MAX;
+    assert_eq!(h.checked_add(1.), Err(()));
+    assert_eq!(h.bins()[0], u64::MAX);
+    assert_eq!(h.saturating_add(1.), Ok(()));
+    assert_eq!(h.bins()[0], u64::MAX);
+    assert_eq!(h.saturating_add(-1.), Err(()));
+}
+
+#[test]
+fn checked_merge() {
+    let mut h1 = Histogram10::with_const_width(0., 100.);
+    h1.add(1.).unwrap();
+    h1.add(99.).unwrap();
+    let mut h2 = h1.clone();
+    h2.checked_merge(&h1).unwrap();
+    assert_eq!(h2.bins(), &[2, 0, 0, 0, 0, 0, 0, 0, 0, 2]);
+
+    let mut full = Histogram10::with_const_width(0., 100.);
+    full.add(1.).unwrap();
+    full *= u64::MAX;
+    let before = full.clone();
+    assert_eq!(full.checked_merge(&h1), Err(()));
+    assert_eq!(full.bins(), before.bins());
+    full.saturating_merge(&h1);
+    assert_eq!(full.bins(), &[u64::MAX, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+}
+
+#[test]
+fn weighted() {
+    define_weighted_histogram!(weighted10, 10);
+    let mut h = weighted10::Histogram::with_const_width(0., 10.);
+    h.add(0.5).unwrap();
+    h.add_weighted(1.5, 0.25).unwrap();
+    h.add_weighted(1.7, 0.5).unwrap();
+    assert_eq!(h.add_weighted(10., 1.), Err(()));
+    assert_eq!(h.bins(), &[1., 0.75, 0., 0., 0., 0., 0., 0., 0., 0.]);
+    assert_eq!(h.sum(), 1.75);
+
+    let mut scaled = h.clone();
+    scaled *= 2.;
+    assert_eq!(scaled.bins(), &[2., 1.5, 0., 0., 0., 0., 0., 0., 0., 0.]);
+    scaled -= &h;
+    assert_eq!(scaled.bins(), h.bins());
+    scaled -= &h;
+    scaled -= &h;
+    assert_eq!(scaled.bins(), &[-1., -0.75, 0., 0., 0., 0., 0., 0., 0., 0.]);
+
+    let normalized: Vec<f64> = h.normalized_bins().collect();
+    assert_eq!(&normalized[..2], &[1., 0.75]);
+    let iterated: Vec<((f64, f64), f64)> = h.iter().take(2).collect();
+    assert_eq!(&iterated, &[((0., 1.), 1.), ((1., 2.), 0.75)]);
+
+    let mut merged = h.clone();
+    merged.merge(&h);
+    assert_eq!(merged.bins(), &[2., 1.5, 0., 0., 0., 0., 0., 0., 0., 0.]);
+}
+
+#[test]
+fn percentiles() {
+    let mut h = Histogram10::with_const_width(0., 100.);
+    assert_eq!(h.percentile_of(50.), 0.);
+    assert_eq!(h.value_at_percentile(50.), 0.);
+    for i in 0..100 {
+        h.add(f64::from(i)).unwrap();
+    }
+    assert_eq!(h.percentile_of(-1.), 0.);
+    assert_eq!(h.percentile_of(0.), 0.);
+    assert_almost_eq!(h.percentile_of(25.), 25., 1e-12);
+    assert_almost_eq!(h.percentile_of(99.), 99., 1e-12);
+    assert_eq!(h.percentile_of(100.), 100.);
+    assert_eq!(h.percentile_of(1000.), 100.);
+    assert_eq!(h.value_at_percentile(0.), 0.);
+    assert_almost_eq!(h.value_at_percentile(12.5), 12.5, 1e-12);
+    assert_almost_eq!(h.value_at_percentile(90.), 90., 1e-12);
+    assert_eq!(h.value_at_percentile(100.), 100.);
+    for &p in &[3., 33.3, 50., 77.7] {
+        assert_almost_eq!(h.percentile_of(h.value_at_percentile(p)), p, 1e-12);
+    }
+}
+
+#[test]
+fn percentiles_sparse() {
+    let inf = f64::INFINITY;
+    let mut h = Histogram10::from_ranges(
+        [-inf, 0.1, 0.2, 0.3, 0.4, 0.5, 0.7, 0.8, 0.9, 1.0, inf].iter().cloned()).unwrap();
+    for &i in &[-5., 0.25, 0.25, 5.] {
+        h.add(i).unwrap();
+    }
+    assert_eq!(h.value_at_percentile(0.), 0.1);
+    assert_eq!(h.value_at_percentile(25.), 0.1);
+    assert_almost_eq!(h.value_at_percentile(50.), 0.25, 1e-12);
+    assert_eq!(h.value_at_percentile(100.), 1.0);
+    assert_eq!(h.percentile_of(0.), 0.);
+    assert_eq!(h.percentile_of(0.1), 25.);
+    assert_almost_eq!(h.percentile_of(0.25), 50., 1e-12);
+    assert_eq!(h.percentile_of(2.), 100.);
+}
+
+#[cfg(feature = "hdrhistogram")]
+#[test]
+fn into_hdrhistogram() {
+    use std::convert::TryFrom;
+    use average::hdrhistogram;
+
+    let mut h = Histogram10::with_const_width(0., 1000.);
+    for i in 0..1000 {
+        h.add(f64::from(i)).unwrap();
+    }
+    let hdr = hdrhistogram::Histogram::<u64>::try_from(&h).unwrap();
+    assert_eq!(hdr.len(), 1000);
+    assert_eq!(hdr.min(), 50);
+    assert_eq!(hdr.max(), 950);
+    assert_eq!(hdr.count_at(450), 100);
+
+    let negative = Histogram10::with_const_width(-10., 0.);
+    assert!(hdrhistogram::Histogram::<u64>::try_from(&negative).is_ok());
+    let mut negative = negative;
+    negative.add(-1.).unwrap();
+    assert!(hdrhistogram::Histogram::<u64>::try_from(&negative).is_err());
+}
+
+#[cfg(feature = "hdrhistogram")]
+#[test]
+fn from_hdrhistogram() {
+    use average::hdrhistogram;
+
+    let mut hdr = hdrhistogram::Histogram::<u64>::new(3).unwrap();
+    let empty = Histogram10::from(&hdr);
+    assert_eq!(empty.bins(), &[0; 10]);
+    for i in 100..200 {
+        hdr.record(i).unwrap();
+    }
+    let h = Histogram10::from(&hdr);
+    assert_eq!(h.range_min(), 100.);
+    assert_eq!(h.range_max(), 200.);
+    assert_eq!(h.bins(), &[10; 10]);
+}
+
+#[test]
+fn chi_square_test() {
+    let mut h = Histogram10::with_const_width(0., 1.);
+    for i in 0..100 {
+        h.add((f64::from(i) + 0.5) / 100.).unwrap();
+    }
+    let uniform = |x: f64| x.clamp(0., 1.);
+    let result = h.chi_square_test(uniform, 0);
+    assert_eq!(result.degrees_of_freedom, 9);
+    assert_almost_eq!(result.statistic, 0., 1e-12);
+    assert_almost_eq!(result.p_value, 1., 1e-12);
+
+    // Only the covered probability is considered.
+    let wide = |x: f64| (x / 2.).clamp(0., 1.);
+    assert_almost_eq!(h.chi_square_test(wide, 0).statistic, 0., 1e-12);
+
+    let result = h.chi_square_test(uniform, 9);
+    assert_eq!(result.degrees_of_freedom, 0);
+    assert!(result.p_value.is_nan());
+
+    // Hand-calculated example with 3 degrees of freedom.
+    let mut h = Histogram10::from_ranges(
+        [0., 0.25, 0.5, 0.75, 1., 1., 1., 1., 1., 1., 1.].iter().cloned()).unwrap();
+    for &(x, n) in &[(0.1, 30), (0.3, 20), (0.6, 25), (0.9, 25)] {
+        for _ in 0..n {
+            h.add(x).unwrap();
+        }
+    }
+    let result = h.chi_square_test(uniform, 0);
+    assert_eq!(result.degrees_of_freedom, 3);
+    assert_almost_eq!(result.statistic, 2., 1e-12);
+    assert_almost_eq!(result.p_value, 0.572_406_704_470_88, 1e-10);
+}
+
+#[test]
+fn chi_square_test_random() {
+    let mut h = Histogram10::with_const_width(0., 1.);
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let uniform_distr = rand_distr::Uniform::new(0., 1.);
+    for _ in 0..10_000 {
+        h.add(uniform_distr.sample(&mut rng)).unwrap();
+    }
+    let uniform = |x: f64| x.clamp(0., 1.);
+    assert!(h.chi_square_test(uniform, 0).p_value > 1e-3);
+    let quadratic = |x: f64| x.clamp(0., 1.).powi(2);
+    assert!(h.chi_square_test(quadratic, 0).p_value < 1e-10);
+}
+
+#[test]
+fn add_checked() {
+    use average::AddError;
+
+    let mut h = Histogram10::with_const_width(0., 10.);
+    h.add_checked(1.).unwrap();
+    assert_eq!(h.add_checked(f64::NAN), Err(AddError::NonFinite));
+    assert_eq!(h.add_checked(f64::INFINITY), Err(AddError::NonFinite));
+    assert_eq!(h.add_checked(10.), Err(AddError::OutOfRange));
+    assert_eq!(h.add_checked(-1.), Err(AddError::OutOfRange));
+    assert_eq!(h.bins(), &[0, 1, 0, 0, 0, 0, 0, 0, 0, 0]);
+}
+
+#[test]
+fn sparse() {
+    use std::collections::BTreeMap;
+
+    /// A histogram of unit-width bins, storing only the non-empty ones.
+    struct Sparse(BTreeMap<i64, u64>);
+
+    impl<'a> IntoIterator for &'a Sparse {
+        type Item = ((f64, f64), u64);
+        type IntoIter = Box<dyn Iterator<Item = ((f64, f64), u64)> + 'a>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            Box::new(self.0.iter().map(|(&i, &n)| ((i as f64, i as f64 + 1.), n)))
+        }
+    }
+
+    impl Histogram for Sparse {
+        type Count = u64;
+    }
+
+    let mut h = Sparse(BTreeMap::new());
+    for &x in &[0.5f64, 0.7, 1000.2, 1000.9] {
+        *h.0.entry(x.floor() as i64).or_insert(0) += 1;
+    }
+    assert_eq!(h.counts().collect::<Vec<_>>(), [2, 2]);
+    assert_eq!(h.sum(), 4.);
+    assert_eq!(h.variances().nth(1), Some(1.));
+    // Only the stored bins are indexed.
+    assert_eq!(h.variance(1), Some(1.));
+    assert_eq!(h.variance(2), None);
+    assert_eq!(h.percentile_of(1.), 50.);
+    assert_eq!(h.value_at_percentile(75.), 1000.5);
+}
+
+#[test]
+fn qq_plot() {
+    let mut h = Histogram10::with_const_width(0., 1.);
+    assert_eq!(h.qq_plot(|p| p, 5).count(), 0);
+    for i in 0..100 {
+        h.add((f64::from(i) + 0.5) / 100.).unwrap();
+    }
+    let points: Vec<(f64, f64)> = h.qq_plot(|p| p, 4).collect();
+    assert_eq!(points.len(), 4);
+    for (i, &(theoretical, empirical)) in points.iter().enumerate() {
+        assert_eq!(theoretical, (i as f64 + 0.5) / 4.);
+        assert_almost_eq!(empirical, theoretical, 1e-12);
+    }
+}
+
+#[test]
+fn normal_qq_plot() {
+    let mut h = hist100::Histogram::with_const_width(-9., 11.);
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let normal = rand_distr::Normal::new(1., 2.).unwrap();
+    for _ in 0..100_000 {
+        h.add(normal.sample(&mut rng)).unwrap();
+    }
+    let points = h.normal_qq_plot(9);
+    assert_eq!(points.size_hint(), (9, Some(9)));
+    for (theoretical, empirical) in points {
+        assert_almost_eq!(empirical, 1. + 2. * theoretical, 0.05);
+    }
+}
+
+#[test]
+fn hellinger_bhattacharyya() {
+    let mut a = Histogram10::with_const_width(0., 10.);
+    let mut b = Histogram10::with_const_width(0., 10.);
+    for &x in &[0.5, 0.5, 1.5, 1.5] {
+        a.add(x).unwrap();
+    }
+    for &x in &[0.5, 2.5] {
+        b.add(x).unwrap();
+    }
+    assert_almost_eq!(a.bhattacharyya_coefficient(&b).unwrap(), 0.5, 1e-15);
+    assert_almost_eq!(a.bhattacharyya_distance(&b).unwrap(), 2f64.ln(), 1e-15);
+    assert_almost_eq!(a.hellinger_distance(&b).unwrap(), 0.5f64.sqrt(), 1e-15);
+    assert_eq!(a.hellinger_distance(&a).unwrap(), 0.);
+    assert_eq!(a.bhattacharyya_distance(&a).unwrap(), 0.);
+
+    // Disjoint bins are compatible.
+    let mut c = Histogram10::with_const_width(10., 20.);
+    c.add(15.).unwrap();
+    assert_eq!(a.bhattacharyya_coefficient(&c), Ok(0.));
+    assert_eq!(a.hellinger_distance(&c), Ok(1.));
+    assert_eq!(a.bhattacharyya_distance(&c), Ok(f64::INFINITY));
+
+    // Partially overlapping bins are not.
+    let mut d = Histogram10::with_const_width(0.5, 10.5);
+    d.add(1.).unwrap();
+    assert_eq!(a.hellinger_distance(&d), Err(()));
+    assert_eq!(a.hellinger_distance(&Histogram10::with_const_width(0., 10.)), Err(()));
+
+    // Histograms of different types can be compared.
+    define_weighted_histogram!(weighted10, 10);
+    let mut w = weighted10::Histogram::with_const_width(0., 10.);
+    w.add_weighted(0.5, 0.1).unwrap();
+    w.add_weighted(1.5, 0.1).unwrap();
+    assert_almost_eq!(a.hellinger_distance(&w).unwrap(), 0., 1e-7);
+}
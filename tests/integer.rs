@@ -0,0 +1,149 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+use average::{IntegerMean, IntegerVariance, MeanWithError, Merge, assert_almost_eq};
+
+#[test]
+fn trivial() {
+    let mut a = IntegerVariance::new();
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    a.add(1);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(a.population_variance(), 0.);
+    assert_eq!(a.error(), 0.);
+    a.add(1);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(a.error(), 0.);
+}
+
+#[test]
+fn simple() {
+    let a: IntegerVariance = (1..6).collect();
+    assert_eq!(a.sum(), 15);
+    assert_eq!(a.sum_sq(), 55);
+    assert_eq!(a.mean(), 3.);
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.sample_variance(), 2.5);
+    assert_eq!(a.population_variance(), 2.);
+    assert_almost_eq!(a.error(), f64::sqrt(0.5), 1e-16);
+
+    let b: IntegerMean = [-3, -4, -6].iter().collect();
+    assert_eq!(b.sum(), -13);
+    assert_almost_eq!(b.mean(), -13. / 3., 1e-16);
+}
+
+#[test]
+fn large_offset() {
+    // Welford's algorithm loses precision here, the exact sums do not.
+    let offset = 1 << 52;
+    let a: IntegerVariance = (0..1000).map(|i| offset + (i % 2)).collect();
+    assert_eq!(a.mean(), offset as f64 + 0.5);
+    assert_eq!(a.population_variance(), 0.25);
+    let b: MeanWithError = (0..1000).map(|i| (offset + (i % 2)) as f64).collect();
+    assert!(b.population_variance() != 0.25);
+}
+
+#[test]
+fn extreme() {
+    let a: IntegerVariance = [i64::MAX, i64::MIN, i64::MAX, i64::MIN]
+        .iter().collect();
+    assert_eq!(a.sum(), -2);
+    assert_eq!(a.mean(), -0.5);
+    assert_almost_eq!(a.population_variance(), 2f64.powi(126), 1e-16);
+    assert!(!a.is_saturated());
+}
+
+#[test]
+fn saturated() {
+    let mut a: IntegerVariance = [i64::MIN; 3].iter().collect();
+    assert!(!a.is_saturated());
+    a.add(i64::MIN);
+    assert!(a.is_saturated());
+    assert_eq!(a.sum_sq(), u128::MAX);
+    assert_eq!(a.sum(), 4 * i128::from(i64::MIN));
+    assert_eq!(a.mean(), i64::MIN as f64);
+    assert!(a.sample_variance().is_nan());
+    assert!(a.error().is_nan());
+    a.remove(i64::MIN);
+    assert!(a.is_saturated());
+
+    let mut b = IntegerVariance::new();
+    b.add_n(i64::MAX, 8);
+    assert!(b.is_saturated());
+    b.merge(&a);
+    assert!(b.is_saturated());
+    assert!(b.population_variance().is_nan());
+}
+
+#[test]
+fn merge() {
+    let sequence: &[i64] = &[1, 2, 3, 4, 5, 6, 7, 8, 9];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: IntegerVariance = sequence.iter().collect();
+        let mut a: IntegerVariance = left.iter().collect();
+        let b: IntegerVariance = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a.len(), total.len());
+        assert_eq!(a.mean(), total.mean());
+        assert_eq!(a.sample_variance(), total.sample_variance());
+    }
+}
+
+#[test]
+fn add_n() {
+    let mut a = IntegerVariance::new();
+    let mut b = IntegerVariance::new();
+    for &(x, count) in &[(1, 3), (-2, 0), (4, 1), (25, 5), (-1, 2)] {
+        a.add_n(x, count);
+        for _ in 0..count {
+            b.add(x);
+        }
+    }
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.sum(), b.sum());
+    assert_eq!(a.sum_sq(), b.sum_sq());
+}
+
+#[test]
+fn remove() {
+    let mut a: IntegerVariance = (1..6).collect();
+    a.remove(5);
+    a.remove(1);
+    let b: IntegerVariance = (2..5).collect();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.sum(), b.sum());
+    assert_eq!(a.sum_sq(), b.sum_sq());
+    assert_eq!(a.sample_variance(), 1.);
+    for i in 2..5 {
+        a.remove(i);
+    }
+    assert!(a.is_empty());
+    a.remove(1);
+    assert!(a.is_empty());
+    assert_eq!(a.sum(), 0);
+}
+
+#[test]
+fn extend() {
+    let mut a: IntegerMean = (1..3).collect();
+    a.extend(&[3, 4]);
+    a.extend(5..6);
+    assert_eq!(a.sum(), 15);
+}
+
+#[test]
+fn add_assign() {
+    let mut a = IntegerVariance::new();
+    a += 1;
+    a += 2;
+    let b: IntegerVariance = [3i64].iter().collect();
+    a += &b;
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean(), 2.);
+    assert_eq!(a.sample_variance(), 1.);
+}
@@ -0,0 +1,52 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Estimate, FiveNumber, Iqr, Reset, assert_almost_eq};
+
+#[test]
+fn uniform() {
+    let a: Iqr = (0..=1000).map(f64::from).collect();
+    assert_eq!(a.len(), 1001);
+    assert_almost_eq!(a.lower_quartile(), 250., 5.);
+    assert_almost_eq!(a.upper_quartile(), 750., 5.);
+    assert_almost_eq!(a.estimate(), 500., 10.);
+    let f: FiveNumber = (0..=1000).map(f64::from).collect();
+    assert_eq!(a.iqr(), f.iqr());
+}
+
+#[test]
+fn robust() {
+    let mut a = Iqr::new();
+    for i in 0..1000 {
+        a.add(f64::from(i % 100));
+    }
+    let iqr = a.iqr();
+    a.add(1e9);
+    a.add(-1e9);
+    assert_almost_eq!(a.iqr(), iqr, 1.);
+}
+
+#[test]
+fn empty() {
+    let mut a = Iqr::new();
+    assert!(a.is_empty());
+    assert_eq!(a.iqr(), 0.);
+    assert_eq!(a.try_iqr(), None);
+    a.add(1.);
+    assert_eq!(a.try_iqr(), Some(0.));
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn merge() {
+    let data: Vec<f64> = (0..10_000).map(|i| f64::from(i * 7919 % 10_000)).collect();
+    let mut a = Iqr::new();
+    for chunk in data.chunks(1000) {
+        let b: Iqr = chunk.iter().collect();
+        a += &b;
+    }
+    assert_eq!(a.len(), 10_000);
+    assert_almost_eq!(a.lower_quartile(), 2500., 100.);
+    assert_almost_eq!(a.upper_quartile(), 7500., 100.);
+    assert_almost_eq!(a.iqr(), 5000., 200.);
+}
@@ -0,0 +1,81 @@
+#![allow(clippy::float_cmp)]
+
+use core::time::Duration;
+
+use average::{Stats, StatsIteratorExt, Merge};
+
+#[test]
+fn one_liners() {
+    let data = [1., 2., 3., 4., 5.];
+    assert_eq!(data.iter().mean(), 3.);
+    assert_eq!(data.iter().copied().variance(), 2.5);
+    assert_eq!(data.iter().min_max(), (1., 5.));
+    assert_eq!((1..6).mean(), 3.);
+    assert_eq!([Duration::from_secs(1), Duration::from_secs(3)].iter().mean(), 2.);
+}
+
+#[test]
+fn empty() {
+    let empty: [f64; 0] = [];
+    assert_eq!(empty.iter().mean(), 0.);
+    assert_eq!(empty.iter().variance(), 0.);
+    assert_eq!(empty.iter().min_max(), (f64::INFINITY, f64::NEG_INFINITY));
+    assert!(empty.iter().stats().is_empty());
+}
+
+#[test]
+fn stats() {
+    let s = [4u32, 1, 3, 2, 5].iter().stats();
+    assert_eq!(s.len(), 5);
+    assert_eq!(s.mean(), 3.);
+    assert_eq!(s.sample_variance(), 2.5);
+    assert_eq!(s.population_variance(), 2.);
+    assert_eq!(s.min(), 1.);
+    assert_eq!(s.max(), 5.);
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., -2., 3., 4.5, 5.];
+    let total: Stats = sequence.iter().collect();
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let mut a: Stats = left.iter().collect();
+        a.merge(&right.iter().stats());
+        assert_eq!(a.len(), total.len());
+        assert_eq!(a.mean(), total.mean());
+        assert_eq!(a.min(), total.min());
+        assert_eq!(a.max(), total.max());
+    }
+}
+
+#[test]
+fn whiten() {
+    use average::{Variance, assert_almost_eq};
+
+    let data = [2., 4., 4., 4., 5., 5., 7., 9.];
+    let fitted: Variance = data.iter().collect();
+    let z: Stats = data.iter().whiten(&fitted).stats();
+    assert_almost_eq!(z.mean(), 0., 1e-15);
+    assert_almost_eq!(z.sample_variance(), 1., 1e-14);
+
+    // Integers are converted, and a zero deviation only shifts the samples.
+    let constant: Variance = [3., 3.].iter().collect();
+    let z: Vec<f64> = [1, 5].iter().whiten(&constant).collect();
+    assert_eq!(z, [-2., 2.]);
+}
+
+#[test]
+fn whiten_adaptive() {
+    use average::Variance;
+
+    let mut w = [1., 3., 5., 2.].iter().whiten_adaptive(Variance::new());
+    assert_eq!(w.size_hint(), (4, Some(4)));
+    assert_eq!(w.next(), Some(1.));
+    assert_eq!(w.next(), Some(2.));
+    assert_eq!(w.estimator().len(), 2);
+    assert_eq!(w.next(), Some(3. / f64::sqrt(2.)));
+    assert_eq!(w.next(), Some(-0.5));
+    assert_eq!(w.next(), None);
+    assert_eq!(w.estimator().mean(), 2.75);
+}
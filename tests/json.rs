@@ -0,0 +1,73 @@
+#![cfg(feature = "serde_json")]
+
+use average::{Describe, FiveNumber, Histogram, LatencyReport, Stats, assert_almost_eq,
+              define_histogram};
+use serde_json::{Value, json};
+
+define_histogram!(hist4, 4);
+
+fn parse(s: &str) -> Value {
+    serde_json::from_str(s).unwrap()
+}
+
+#[test]
+fn stats() {
+    let s: Stats = [1., 2., 3., 6.].iter().collect();
+    let mut v = parse(&s.to_json());
+    // Parsing floats is not exact without serde_json's `float_roundtrip`.
+    let error = v.as_object_mut().unwrap().remove("error").unwrap();
+    assert_almost_eq!(error.as_f64().unwrap(), s.error(), 1e-15);
+    assert_eq!(v, json!({
+        "count": 4, "mean": 3, "variance": 14. / 3., "min": 1, "max": 6,
+    }));
+    let empty = Stats::new();
+    assert_eq!(parse(&empty.to_json())["min"], Value::Null);
+}
+
+#[test]
+fn five_number() {
+    let a: FiveNumber = [2., 3., 4., 5.].iter().collect();
+    assert_eq!(parse(&a.to_json()), json!({
+        "count": 4, "min": 2, "lower_quartile": 2.5, "median": 3.5, "upper_quartile": 4.5,
+        "max": 5, "whiskers": [2, 5],
+    }));
+}
+
+#[test]
+fn describe() {
+    let d: Describe = (1..=100).map(f64::from).collect();
+    let v = parse(&d.to_json());
+    assert_eq!(v["count"], 100);
+    assert_eq!(v["mean"], 50.5);
+    assert_eq!(v["quantiles"].as_array().unwrap().len(), 3);
+    assert_eq!(v["quantiles"][1]["p"], 0.5);
+    assert_eq!(v["kurtosis"], json!(d.sample_kurtosis()));
+}
+
+#[test]
+fn histogram() {
+    let mut h = hist4::Histogram::with_const_width(0., 4.);
+    for &x in &[0.5, 1.5, 1.7, 3.9] {
+        h.add(x).unwrap();
+    }
+    assert_eq!(parse(&h.to_json()), json!({
+        "sum": 4,
+        "bins": [
+            { "lower": 0, "upper": 1, "count": 1 },
+            { "lower": 1, "upper": 2, "count": 2 },
+            { "lower": 2, "upper": 3, "count": 0 },
+            { "lower": 3, "upper": 4, "count": 1 },
+        ],
+    }));
+}
+
+#[test]
+fn latency_report() {
+    let report = LatencyReport {
+        count: 3., p50: 1., p75: 2., p90: 2.5, p99: 3., p999: f64::NAN, max: 3., unit: None,
+    };
+    assert_eq!(parse(&report.to_json()), json!({
+        "count": 3, "p50": 1, "p75": 2, "p90": 2.5, "p99": 3, "p99.9": null, "max": 3,
+    }));
+    assert_eq!(parse(&report.unit("\"µs\"").to_json())["unit"], "\"µs\"");
+}
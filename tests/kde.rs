@@ -0,0 +1,75 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{KernelDensity, Merge, assert_almost_eq};
+use rand::SeedableRng;
+use rand_distr::Distribution;
+
+/// The density of the standard normal distribution.
+fn normal_pdf(x: f64) -> f64 {
+    (-0.5 * x * x).exp() / (2. * std::f64::consts::PI).sqrt()
+}
+
+#[test]
+fn normal() {
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    let normal = rand_distr::Normal::new(0., 1.).unwrap();
+    let mut a = KernelDensity::new(50);
+    for _ in 0..10_000 {
+        a.add(normal.sample(&mut rng));
+    }
+    assert_eq!(a.len(), 10_000);
+    for &x in &[-2., -1., 0., 0.5, 1.5] {
+        assert_almost_eq!(a.pdf(x), normal_pdf(x), 0.02);
+    }
+    // The density is normalized.
+    let integral: f64 = (-800..800).map(|i| a.pdf(f64::from(i) * 0.01) * 0.01).sum();
+    assert_almost_eq!(integral, 1., 1e-3);
+}
+
+#[test]
+fn bimodal() {
+    let mut a = KernelDensity::new(20);
+    for i in 0..1000 {
+        a.add(if i % 2 == 0 { -5. } else { 5. } + f64::from(i % 7) * 0.1);
+    }
+    assert!(a.pdf(5.3) > 10. * a.pdf(0.));
+    assert!(a.pdf(-4.7) > 10. * a.pdf(0.));
+}
+
+#[test]
+fn merge() {
+    let samples: Vec<f64> = (0..2000).map(|i| f64::from(i % 100).sqrt()).collect();
+    let mut all = KernelDensity::new(40);
+    for &x in &samples {
+        all.add(x);
+    }
+    let mut a = KernelDensity::new(40);
+    let mut b = KernelDensity::new(40);
+    for &x in &samples[..700] {
+        a.add(x);
+    }
+    for &x in &samples[700..] {
+        b.add(x);
+    }
+    a.merge(&b);
+    assert_eq!(a.len(), all.len());
+    assert_almost_eq!(a.bandwidth(), all.bandwidth(), 1e-12);
+    for &x in &[0., 2., 5., 9.] {
+        assert_almost_eq!(a.pdf(x), all.pdf(x), 0.01);
+    }
+}
+
+#[test]
+fn few() {
+    let mut a = KernelDensity::new(4);
+    assert_eq!(a.pdf(0.), 0.);
+    a.add(1.);
+    assert_eq!(a.bandwidth(), 0.);
+    assert_eq!(a.pdf(1.), f64::INFINITY);
+    assert_eq!(a.pdf(2.), 0.);
+    a.add(2.);
+    assert!(a.pdf(1.5) > 0.);
+    a.reset();
+    assert!(a.is_empty());
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{KendallTau, Merge, assert_almost_eq};
+use rand::{Rng, SeedableRng};
+
+/// Calculate Kendall's tau-b by comparing all pairs.
+fn exact(data: &[(f64, f64)]) -> f64 {
+    let (mut c, mut d, mut tx, mut ty) = (0., 0., 0., 0.);
+    for (i, a) in data.iter().enumerate() {
+        for b in &data[..i] {
+            let s = (a.0 - b.0).signum() * (a.1 - b.1).signum();
+            if a.0 == b.0 && a.1 == b.1 {
+            } else if a.0 == b.0 {
+                tx += 1.;
+            } else if a.1 == b.1 {
+                ty += 1.;
+            } else if s > 0. {
+                c += 1.;
+            } else {
+                d += 1.;
+            }
+        }
+    }
+    (c - d) / f64::sqrt((c + d + tx) * (c + d + ty))
+}
+
+/// Generate correlated pairs with ties in the first variable.
+fn correlated(n: usize) -> Vec<(f64, f64)> {
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    (0..n).map(|_| {
+        let x = f64::from(rng.gen_range(0, 50));
+        (x, x + rng.gen_range(-20., 20.))
+    }).collect()
+}
+
+#[test]
+fn monotone() {
+    let mut a = KendallTau::new(10);
+    let mut b = KendallTau::new(10);
+    for i in 0..100 {
+        let x = f64::from(i);
+        a.add(x, x * x);
+        b.add(x, -x);
+    }
+    assert_eq!(a.len(), 100);
+    assert_almost_eq!(a.tau(), 1., 1e-12);
+    assert_almost_eq!(b.tau(), -1., 1e-12);
+}
+
+#[test]
+fn exact_small() {
+    let data = correlated(300);
+    let mut a = KendallTau::new(300);
+    for &(x, y) in &data {
+        a.add(x, y);
+    }
+    assert_almost_eq!(a.tau(), exact(&data), 1e-12);
+}
+
+#[test]
+fn approximate() {
+    let data = correlated(3000);
+    let mut a = KendallTau::new(200);
+    for &(x, y) in &data {
+        a.add(x, y);
+    }
+    let tau = exact(&data);
+    assert!(tau > 0.5);
+    assert_almost_eq!(a.tau(), tau, 0.03);
+}
+
+#[test]
+fn merge() {
+    let data = correlated(3000);
+    let mut a = KendallTau::new(200);
+    let mut b = KendallTau::with_seed(200, 1);
+    for (i, &(x, y)) in data.iter().enumerate() {
+        if i < 1000 { a.add(x, y) } else { b.add(x, y) }
+    }
+    a.merge(&b);
+    assert_eq!(a.len(), 3000);
+    assert_almost_eq!(a.tau(), exact(&data), 0.03);
+
+    // Merging small samples is exact.
+    let mut a = KendallTau::new(100);
+    let mut b = KendallTau::new(100);
+    for (i, &(x, y)) in data[..100].iter().enumerate() {
+        if i % 3 == 0 { a.add(x, y) } else { b.add(x, y) }
+    }
+    a.merge(&b);
+    assert_almost_eq!(a.tau(), exact(&data[..100]), 1e-12);
+}
+
+#[test]
+fn degenerate() {
+    let mut a = KendallTau::new(10);
+    assert!(a.is_empty());
+    assert_eq!(a.try_tau(), None);
+    a.add(1., 2.);
+    a.add(f64::NAN, 3.);
+    assert_eq!(a.len(), 1);
+    a.add(1., 3.);
+    assert_eq!(a.try_tau(), None);
+    assert_eq!(a.tau(), 0.);
+    a.reset();
+    assert!(a.is_empty());
+}
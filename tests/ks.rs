@@ -0,0 +1,81 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{Merge, TwoSampleKs, assert_almost_eq};
+use rand::SeedableRng;
+use rand_distr::{Distribution, Normal};
+
+/// Draw samples from a normal distribution with a fixed seed.
+fn normal(mean: f64, seed: u64, n: usize) -> Vec<f64> {
+    let rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(seed);
+    Normal::new(mean, 1.).unwrap().sample_iter(rng).take(n).collect()
+}
+
+#[test]
+fn exact() {
+    let mut a = TwoSampleKs::new(10);
+    for &x in &[1., 2., 3., 4., 5.] {
+        a.add_first(x);
+    }
+    for &x in &[3.5, 4.5, 5.5, 6.5, 7.5, 8.5] {
+        a.add_second(x);
+    }
+    assert_eq!((a.len_first(), a.len_second()), (5, 6));
+    let test = a.test().unwrap();
+    assert_almost_eq!(test.statistic, 2. / 3., 1e-15);
+    assert!(test.p_value > 0.05 && test.p_value < 0.5, "{}", test.p_value);
+}
+
+#[test]
+fn same_distribution() {
+    let mut a = TwoSampleKs::new(500);
+    for (x, y) in normal(0., 1, 10_000).into_iter().zip(normal(0., 2, 20_000)) {
+        a.add_first(x);
+        a.add_second(y);
+    }
+    let test = a.test().unwrap();
+    assert!(test.statistic < 0.1);
+    assert!(test.p_value > 0.01, "{}", test.p_value);
+}
+
+#[test]
+fn shifted_distribution() {
+    let mut a = TwoSampleKs::new(500);
+    for (x, y) in normal(0., 1, 10_000).into_iter().zip(normal(0.5, 2, 10_000)) {
+        a.add_first(x);
+        a.add_second(y);
+    }
+    let test = a.test().unwrap();
+    assert_almost_eq!(test.statistic, 0.197, 0.05);
+    assert!(test.p_value < 1e-6, "{}", test.p_value);
+}
+
+#[test]
+fn merge() {
+    let (xs, ys) = (normal(0., 1, 1000), normal(1., 2, 1000));
+    let mut a = TwoSampleKs::new(100);
+    let mut b = TwoSampleKs::with_seed(100, 3);
+    for (i, (&x, &y)) in xs.iter().zip(&ys).enumerate() {
+        let t = if i < 300 { &mut a } else { &mut b };
+        t.add_first(x);
+        t.add_second(y);
+    }
+    a.merge(&b);
+    assert_eq!((a.len_first(), a.len_second()), (1000, 1000));
+    let test = a.test().unwrap();
+    assert!(test.p_value < 1e-3, "{}", test.p_value);
+}
+
+#[test]
+fn empty() {
+    let mut a = TwoSampleKs::new(10);
+    assert_eq!(a.test(), None);
+    a.add_first(1.);
+    a.add_second(f64::NAN);
+    assert_eq!(a.len_second(), 0);
+    assert_eq!(a.test(), None);
+    a.add_second(1.);
+    assert_eq!(a.test().unwrap().statistic, 0.);
+    a.reset();
+    assert_eq!(a.len_first(), 0);
+}
@@ -1,8 +1,8 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 use core::iter::Iterator;
 
-use average::{Kurtosis, Estimate, Merge, assert_almost_eq};
+use average::{Kurtosis, Estimate, Merge, Reset, assert_almost_eq};
 
 #[test]
 fn trivial() {
@@ -37,6 +37,23 @@ fn simple() {
     assert_almost_eq!(a.kurtosis(), -1.365, 1e-15);
 }
 
+#[test]
+fn bias_corrected() {
+    let mut a: Kurtosis = [1., 2., 3., 4., 5., 1.].iter().collect();
+    assert_almost_eq!(a.sample_skewness(), 0.38273277230987207, 1e-15);
+    assert_almost_eq!(a.sample_kurtosis(), -1.48125, 1e-14);
+    assert_eq!(a.try_sample_kurtosis(), Some(a.sample_kurtosis()));
+    a.reset();
+    for _ in 0..3 {
+        a.add(1.);
+    }
+    assert_eq!(a.sample_skewness(), 0.);
+    assert_eq!(a.try_sample_skewness(), None);
+    a.add(1.);
+    assert_eq!(a.sample_kurtosis(), 0.);
+    assert_eq!(a.try_sample_kurtosis(), None);
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn simple_serde() {
@@ -70,3 +87,114 @@ fn merge() {
         assert_almost_eq!(avg_total.kurtosis(), avg_left.kurtosis(), 1e-14);
     }
 }
+
+#[test]
+fn add_n() {
+    let mut a = Kurtosis::new();
+    let mut b = Kurtosis::new();
+    for &(x, count) in &[(1., 3), (-2., 0), (4., 1), (2.5, 5), (-1., 2)] {
+        a.add_n(x, count);
+        for _ in 0..count {
+            b.add(x);
+        }
+    }
+    assert_eq!(a.len(), b.len());
+    assert_almost_eq!(a.mean(), b.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-14);
+    assert_almost_eq!(a.kurtosis(), b.kurtosis(), 1e-14);
+}
+
+#[test]
+fn try_estimates() {
+    let mut a = Kurtosis::new();
+    assert_eq!(a.try_mean(), None);
+    assert_eq!(a.try_population_variance(), None);
+    assert_eq!(a.try_kurtosis(), None);
+    a.add(1.);
+    assert_eq!(a.try_kurtosis(), None);
+    a.add(2.);
+    a.add(4.);
+    assert_eq!(a.try_skewness(), Some(a.skewness()));
+    assert_eq!(a.try_kurtosis(), Some(a.kurtosis()));
+}
+
+#[test]
+fn add_slice() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let seq: Kurtosis = sequence.iter().collect();
+    for mid in 0..sequence.len() {
+        let mut a = Kurtosis::new();
+        a.add_slice(&sequence[..mid]);
+        a.add_slice(&sequence[mid..]);
+        assert_eq!(a.len(), seq.len());
+        assert_almost_eq!(a.mean(), seq.mean(), 1e-14);
+        assert_almost_eq!(a.sample_variance(), seq.sample_variance(), 1e-13);
+        assert_almost_eq!(a.skewness(), seq.skewness(), 1e-13);
+        assert_almost_eq!(a.kurtosis(), seq.kurtosis(), 1e-13);
+    }
+}
+
+#[test]
+fn from_moments() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+    let (left, right) = sequence.split_at(4);
+    let total: Kurtosis = sequence.iter().collect();
+    let r: Kurtosis = right.iter().collect();
+    let imported = Kurtosis::from_moments(
+        r.len(), r.mean(), r.sample_variance(), r.skewness(), r.kurtosis());
+    let mut a: Kurtosis = left.iter().collect();
+    a.merge(&imported);
+    assert_eq!(a.len(), total.len());
+    assert_almost_eq!(a.mean(), total.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), total.sample_variance(), 1e-13);
+    assert_almost_eq!(a.skewness(), total.skewness(), 1e-13);
+    assert_almost_eq!(a.kurtosis(), total.kurtosis(), 1e-13);
+
+    let empty = Kurtosis::from_moments(0, 1., 2., 3., 4.);
+    assert!(empty.is_empty());
+    assert_eq!(empty.mean(), 0.);
+    let single = Kurtosis::from_moments(1, 1., 2., 3., 4.);
+    assert_eq!(single.mean(), 1.);
+    assert_eq!(single.kurtosis(), 0.);
+}
+
+#[test]
+fn sums() {
+    let a: Kurtosis = [1., 2., 3., 6.].iter().collect();
+    assert_eq!(a.sum_2(), 14.);
+    assert_eq!(a.sum_3(), 18.);
+    assert_eq!(a.sum_4(), 98.);
+    assert_eq!(a.sum_2(), a.sample_variance() * 3.);
+}
+
+#[test]
+fn const_new() {
+    static K: Kurtosis = Kurtosis::new();
+    static Q: average::Quantile = average::Quantile::new(0.5);
+    static M: average::Min = average::Min::new();
+    assert!(K.is_empty());
+    assert_eq!(Q.p(), 0.5);
+    assert_eq!(M.min(), f64::INFINITY);
+}
+
+#[test]
+fn merge_commutative() {
+    let data: Vec<f64> = (0..100).map(|i| (f64::from(i) * 0.37).sin() * 1e3 + 1e6).collect();
+    for &(i, j) in &[(0, 30), (10, 45), (50, 51), (99, 100)] {
+        let a: Kurtosis = data[..i].iter().collect();
+        let b: Kurtosis = data[i..j].iter().collect();
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+        assert_eq!(ab, ba);
+
+        let a: average::Variance = data[..i].iter().collect();
+        let b: average::Variance = data[i..j].iter().collect();
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+        assert_eq!(ab, ba);
+    }
+}
@@ -0,0 +1,70 @@
+#![allow(clippy::float_cmp)]
+
+use average::{LatencyReport, assert_almost_eq, define_histogram, define_weighted_histogram};
+
+define_histogram!(hist, 100);
+define_weighted_histogram!(weighted, 10);
+
+#[test]
+fn from_histogram() {
+    let mut h = hist::Histogram::with_const_width(0., 1000.);
+    for i in 0..10_000 {
+        h.add(f64::from(i % 1000)).unwrap();
+    }
+    let report = LatencyReport::from_histogram(&h);
+    assert_eq!(report.count, 10_000.);
+    assert_eq!((report.p50, report.p75, report.p90), (500., 750., 900.));
+    assert_eq!((report.p99, report.max), (990., 1000.));
+    assert_almost_eq!(report.p999, 999., 1e-9);
+    assert_eq!(report.unit, None);
+}
+
+#[test]
+fn weighted() {
+    let mut h = weighted::Histogram::with_const_width(0., 10.);
+    h.add_weighted(0.5, 1.5).unwrap();
+    h.add_weighted(9.5, 0.5).unwrap();
+    let report = LatencyReport::from_histogram(&h);
+    assert_eq!(report.count, 2.);
+    assert_eq!(report.p50, 2. / 3.);
+    assert_eq!(report.max, 10.);
+}
+
+#[test]
+fn empty() {
+    let h = hist::Histogram::with_const_width(0., 1.);
+    let report = LatencyReport::from_histogram(&h);
+    assert_eq!(report.count, 0.);
+    assert_eq!(report.max, 0.);
+}
+
+#[test]
+fn display() {
+    let report = LatencyReport {
+        count: 3., p50: 1., p75: 2., p90: 2.5, p99: 3., p999: 3., max: 3., unit: None,
+    };
+    assert_eq!(format!("{}", report), "\
+count 3
+p50   1
+p75   2
+p90   2.5
+p99   3
+p99.9 3
+max   3
+");
+}
+
+#[cfg(feature = "hdrhistogram")]
+#[test]
+fn from_hdrhistogram() {
+    use average::hdrhistogram;
+
+    let mut hdr = hdrhistogram::Histogram::<u64>::new(3).unwrap();
+    for i in 1..=1000 {
+        hdr.record(i).unwrap();
+    }
+    let report = LatencyReport::from(&hdr).unit("ms");
+    assert_eq!(report.count, 1000.);
+    assert_eq!((report.p50, report.p90, report.max), (500., 900., 1000.));
+    assert_eq!(report.unit, Some("ms"));
+}
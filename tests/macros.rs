@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp))]
+#![allow(clippy::float_cmp)]
 
 use average::{Estimate, Min, Max, concatenate};
 
@@ -48,3 +48,55 @@ fn concatenate_moments() {
     assert_eq!(e.sample_variance(), 2.5);
     assert_eq!(e.quantile(), 3.0);
 }
+
+#[test]
+fn concatenate_reset() {
+    let mut s: MinMax = (1..6).map(f64::from).collect();
+    s.reset();
+    assert_eq!(s.min(), Min::new().min());
+    assert_eq!(s.max(), Max::new().max());
+    s.add(3.);
+    assert_eq!(s.min(), 3.0);
+    assert_eq!(s.max(), 3.0);
+}
+
+#[test]
+fn concatenate_extend() {
+    let mut s = MinMax::new();
+    s.extend(&[3., 1., 2.]);
+    s.extend(vec![5., 4.]);
+    assert_eq!(s.min(), 1.0);
+    assert_eq!(s.max(), 5.0);
+}
+
+#[test]
+fn concatenate_samples() {
+    let s: MinMax = [3i32, -1, 2].iter().collect();
+    assert_eq!(s.min(), -1.0);
+    assert_eq!(s.max(), 3.0);
+}
+
+#[test]
+fn concatenate_add_assign() {
+    let mut e = MinMax::new();
+    e += 2.;
+    e += -1.;
+    assert_eq!(e.min(), -1.);
+    assert_eq!(e.max(), 2.);
+}
+
+mod exported {
+    use average::{Min, Variance, Estimate, concatenate};
+
+    concatenate!(pub Summary, [Min, min, min], [Variance, variance, mean, sample_variance]);
+}
+
+#[test]
+fn concatenate_pub() {
+    let mut s = exported::Summary::new();
+    s += 3.;
+    s += 5.;
+    assert_eq!(s.min(), 3.);
+    assert_eq!(s.mean(), 4.);
+    assert_eq!(s.sample_variance(), 2.);
+}
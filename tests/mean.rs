@@ -1,8 +1,8 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 use core::iter::Iterator;
 
-use average::{MeanWithError, Estimate, Merge, assert_almost_eq};
+use average::{MeanWithError, Estimate, Merge, Reset, assert_almost_eq};
 
 #[test]
 fn trivial() {
@@ -67,3 +67,371 @@ fn merge() {
         assert_eq!(avg_total.sample_variance(), avg_left.sample_variance());
     }
 }
+
+#[test]
+fn generic_float() {
+    use average::{GenericMean, GenericVariance};
+
+    let mut a: GenericVariance<f32> = (1..6).map(|x| x as f32).collect();
+    assert_eq!(a.mean(), 3.0f32);
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.sample_variance(), 2.5f32);
+    assert_eq!(a.population_variance(), 2.0f32);
+    assert_almost_eq!(a.error(), f32::sqrt(0.5), 1e-7);
+    Estimate::add(&mut a, 3.);
+    assert_almost_eq!(a.estimate(), 5. / 3., 1e-6);
+
+    let b: GenericVariance<f32> = [6f32, 7., 8.].iter().collect();
+    a.merge(&b);
+    assert_eq!(a.len(), 9);
+    assert_eq!(a.mean(), 4.333_333_5);
+
+    let mut m = GenericMean::<f32>::new();
+    m.add(1.);
+    m.add(2.);
+    assert_eq!(m.mean(), 1.5f32);
+    assert_eq!(m.estimate(), 1.5f64);
+}
+
+#[test]
+fn durations() {
+    use core::time::Duration;
+    use average::{Mean, GenericMean};
+
+    let a: Mean = [Duration::from_millis(10), Duration::from_millis(20)]
+        .iter().collect();
+    assert_eq!(a.mean(), 0.015);
+    assert_eq!(a.mean_duration(), Duration::from_micros(15_000));
+
+    let mut b = MeanWithError::new();
+    for i in 1..4 {
+        b.add_duration(Duration::from_secs(i));
+    }
+    assert_eq!(b.mean_duration(), Duration::from_secs(2));
+    assert_eq!(b.sample_variance(), 1.);
+
+    let mut c = GenericMean::<f32>::new();
+    assert_eq!(c.mean_duration(), Duration::from_secs(0));
+    c.add_duration(Duration::from_millis(1500));
+    assert_eq!(c.mean_duration(), Duration::from_millis(1500));
+}
+
+#[test]
+fn add_n() {
+    let mut a = MeanWithError::new();
+    let mut b = MeanWithError::new();
+    let mut c = average::Mean::new();
+    for &(x, count) in &[(1., 3), (-2., 0), (4., 1), (2.5, 5), (-1., 2)] {
+        a.add_n(x, count);
+        c.add_n(x, count);
+        for _ in 0..count {
+            b.add(x);
+        }
+    }
+    assert_eq!(a.len(), b.len());
+    assert_eq!(c.len(), b.len());
+    assert_almost_eq!(a.mean(), b.mean(), 1e-14);
+    assert_almost_eq!(c.mean(), b.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-14);
+}
+
+#[test]
+fn remove() {
+    let mut a: MeanWithError = (1..6).map(f64::from).collect();
+    a.remove(5.);
+    a.remove(1.);
+    let b: MeanWithError = (2..5).map(f64::from).collect();
+    assert_eq!(a.len(), 3);
+    assert_almost_eq!(a.mean(), b.mean(), 1e-15);
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-14);
+    a.remove(2.);
+    a.remove(3.);
+    assert_eq!(a.mean(), 4.);
+    assert_eq!(a.sample_variance(), 0.);
+    a.remove(4.);
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    a.remove(4.);
+    assert!(a.is_empty());
+
+    let mut c: average::Mean = (1..6).map(f64::from).collect();
+    c.remove(1.);
+    assert_eq!(c.mean(), 3.5);
+}
+
+#[test]
+fn merge_empty() {
+    let mut a = MeanWithError::new();
+    a.merge(&MeanWithError::new());
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    assert_eq!(a.sample_variance(), 0.);
+    let b: MeanWithError = (1..6).map(f64::from).collect();
+    a.merge(&b);
+    a.merge(&MeanWithError::new());
+    assert_eq!(a.mean(), 3.);
+    assert_eq!(a.sample_variance(), 2.5);
+}
+
+#[test]
+fn reset() {
+    let mut a: MeanWithError = (1..6).map(f64::from).collect();
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.mean(), 0.);
+    a.add(2.);
+    assert_eq!(a.mean(), 2.);
+}
+
+#[test]
+fn try_estimates() {
+    let mut a = MeanWithError::new();
+    assert_eq!(a.try_mean(), None);
+    assert_eq!(a.try_population_variance(), None);
+    assert_eq!(a.try_sample_variance(), None);
+    assert_eq!(a.try_error(), None);
+    a.add(2.);
+    assert_eq!(a.try_mean(), Some(2.));
+    assert_eq!(a.try_population_variance(), Some(0.));
+    assert_eq!(a.try_sample_variance(), None);
+    assert_eq!(a.try_error(), None);
+    a.add(4.);
+    assert_eq!(a.try_sample_variance(), Some(2.));
+    assert_eq!(a.try_error(), Some(1.));
+
+    let b = average::Mean::new();
+    assert_eq!(b.try_mean(), None);
+}
+
+#[test]
+fn add_checked() {
+    use average::AddError;
+
+    let mut a = MeanWithError::new();
+    a.add_checked(1.).unwrap();
+    assert_eq!(a.add_checked(f64::NAN), Err(AddError::NonFinite));
+    assert_eq!(a.add_checked(f64::NEG_INFINITY), Err(AddError::NonFinite));
+    a.add_checked(3.).unwrap();
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.mean(), 2.);
+    assert_eq!(format!("{}", AddError::NonFinite), "sample is not finite");
+}
+
+#[test]
+fn extend() {
+    let mut a: MeanWithError = (1..3).map(f64::from).collect();
+    a.extend(&[3., 4.]);
+    a.extend((5..6).map(f64::from));
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.mean(), 3.);
+    assert_eq!(a.sample_variance(), 2.5);
+}
+
+#[test]
+fn from_iterator_samples() {
+    let a: MeanWithError = (1..6).collect();
+    assert_eq!(a.mean(), 3.);
+    let b: MeanWithError = [1u32, 2, 3, 4, 5].iter().collect();
+    assert_eq!(b.sample_variance(), 2.5);
+    let c: average::Mean = vec![1.5f32, 2.5].into_iter().collect();
+    assert_eq!(c.mean(), 2.);
+    let mut d: average::Mean = [-1i64, 1].iter().collect();
+    d.extend(&[3i64]);
+    assert_eq!(d.mean(), 1.);
+}
+
+#[test]
+fn add_assign() {
+    let mut a = MeanWithError::new();
+    a += 1.;
+    a += 2.;
+    let mut b = MeanWithError::new();
+    b += 3.;
+    a += &b;
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean(), 2.);
+    assert_eq!(a.sample_variance(), 1.);
+}
+
+#[test]
+fn add_slice() {
+    let data: Vec<f64> = (0..1000).map(|i| 1e9 + f64::from(i % 10)).collect();
+    let seq: MeanWithError = data.iter().collect();
+    let mut a = MeanWithError::new();
+    a.add_slice(&data[..300]);
+    a.add_slice(&[]);
+    a.add_slice(&data[300..]);
+    assert_eq!(a.len(), 1000);
+    assert_eq!(a.mean(), 1e9 + 4.5);
+    assert_almost_eq!(a.sample_variance(), 8.25 * 1000. / 999., 1e-9);
+    assert_almost_eq!(a.mean(), seq.mean(), 1e-6);
+
+    let mut b = average::Mean::new();
+    b.add_slice(&data);
+    assert_eq!(b.mean(), 1e9 + 4.5);
+}
+
+#[test]
+fn from_mean_and_variance() {
+    let sequence: &[f64] = &[1., 2., 3., 4., 5., 6., 7., 8., 9.];
+    let (left, right) = sequence.split_at(3);
+    let total: MeanWithError = sequence.iter().collect();
+    let r: MeanWithError = right.iter().collect();
+    let mut a: MeanWithError = left.iter().collect();
+    a.merge(&MeanWithError::from_mean_and_variance(r.len(), r.mean(), r.sample_variance()));
+    assert_eq!(a.len(), total.len());
+    assert_almost_eq!(a.mean(), total.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), total.sample_variance(), 1e-14);
+
+    let m = average::Mean::from_mean(4, 2.5);
+    assert_eq!(m.len(), 4);
+    assert_eq!(m.mean(), 2.5);
+    assert!(average::Mean::from_mean(0, 2.5).is_empty());
+    assert_eq!(MeanWithError::from_mean_and_variance(1, 2., 3.).sample_variance(), 0.);
+}
+
+#[test]
+fn sums() {
+    let a: MeanWithError = [1., 2., 3., 6.].iter().collect();
+    assert_eq!(a.sum_2(), 14.);
+    let m: average::Mean = [1., 2., 3., 6.].iter().collect();
+    assert_eq!(m.sum(), 12.);
+}
+
+#[test]
+fn display() {
+    let a = MeanWithError::from_mean_and_variance(100, 3.2149, 0.1681);
+    assert_eq!(format!("{}", a), "3.21 ± 0.04");
+    assert_eq!(format!("{:.2}", a), "3.215 ± 0.041");
+    // The rounding of the error carries into the next digit.
+    let b = MeanWithError::from_mean_and_variance(100, 1.234, 0.9604);
+    assert_eq!(format!("{}", b), "1.2 ± 0.1");
+    let c = MeanWithError::from_mean_and_variance(4, 12345., 1681. * 1681. * 4.);
+    assert_eq!(format!("{}", c), "12000 ± 2000");
+    let d: MeanWithError = [2., 2.].iter().collect();
+    assert_eq!(format!("{}", d), "2 ± 0");
+}
+
+#[test]
+fn const_empty() {
+    const A: average::Mean = average::Mean::EMPTY;
+    static B: MeanWithError = MeanWithError::EMPTY;
+    assert!(A.is_empty());
+    let mut b = B.clone();
+    b.add(1.);
+    assert_eq!(b.mean(), 1.);
+}
+
+#[test]
+fn huge_len() {
+    let mut a = average::Mean::from_mean(u64::MAX - 1, 2.);
+    a.add(2.);
+    assert_eq!(a.len(), u64::MAX);
+    assert_eq!(a.mean(), 2.);
+    let mut b = average::GenericMean::<f32>::from_mean(u64::MAX - 1, 2.);
+    b.add(2.);
+    assert_eq!(b.mean(), 2.);
+    assert_eq!(b.estimate(), 2.);
+}
+
+#[test]
+fn ddof() {
+    let a: MeanWithError = [1., 2., 3., 4.].iter().collect();
+    assert_eq!(a.variance_ddof(0), a.population_variance());
+    assert_eq!(a.variance_ddof(1), a.sample_variance());
+    assert_eq!(a.variance_ddof(3), 5.);
+    assert_eq!(a.variance_ddof(4), 0.);
+    assert_eq!(a.try_variance_ddof(4), None);
+    assert_eq!(a.try_variance_ddof(2), Some(2.5));
+    assert_eq!(a.variance_with_known_mean(2.5), a.population_variance());
+    assert_eq!(a.variance_with_known_mean(0.), 7.5);
+    assert_eq!(MeanWithError::new().variance_with_known_mean(1.), 0.);
+
+    let k: average::Kurtosis = [1., 2., 3., 4.].iter().collect();
+    assert_eq!(k.variance_ddof(3), 5.);
+    assert_eq!(k.try_variance_ddof(5), None);
+}
+
+#[test]
+fn coefficient_of_variation() {
+    let a: MeanWithError = [1., 2., 3., 4.].iter().collect();
+    let cv = a.sample_variance().sqrt() / 2.5;
+    assert_eq!(a.coefficient_of_variation(), cv);
+    assert_eq!(a.try_coefficient_of_variation(), Some(cv));
+    assert_eq!(a.corrected_coefficient_of_variation(), cv * (1. + 1. / 16.));
+    assert_eq!(MeanWithError::new().coefficient_of_variation(), 0.);
+    assert_eq!(MeanWithError::new().try_coefficient_of_variation(), None);
+    assert_eq!(MeanWithError::new().corrected_coefficient_of_variation(), 0.);
+    let single: MeanWithError = [3.].iter().collect();
+    assert_eq!(single.coefficient_of_variation(), 0.);
+    assert_eq!(single.corrected_coefficient_of_variation(), 0.);
+    let zero: MeanWithError = [-1., 1.].iter().collect();
+    assert_eq!(zero.try_coefficient_of_variation(), None);
+
+    let k: average::Kurtosis = [1., 2., 3., 4.].iter().collect();
+    assert_eq!(k.coefficient_of_variation(), cv);
+    let s: average::Stats = [1., 2., 3., 4.].iter().collect();
+    assert_eq!(s.corrected_coefficient_of_variation(), a.corrected_coefficient_of_variation());
+}
+
+#[test]
+fn finite_population_correction() {
+    let a: MeanWithError = [1., 2., 3., 4.].iter().collect();
+    assert_almost_eq!(a.error_with_population_size(7), a.error() * 0.5f64.sqrt(), 1e-15);
+    assert_eq!(a.error_with_population_size(4), 0.);
+    assert!((a.error_with_population_size(1_000_000) - a.error()).abs() < 1e-6);
+
+    let k: average::Kurtosis = [1., 2., 3., 4.].iter().collect();
+    assert_eq!(k.error_mean_with_population_size(7), a.error_with_population_size(7));
+
+    let empty = MeanWithError::new();
+    assert_eq!(empty.error_with_population_size(1), 0.);
+    assert_eq!(empty.error_with_population_size(0), 0.);
+}
+
+#[test]
+#[should_panic]
+fn finite_population_smaller_than_sample() {
+    let a: MeanWithError = [1., 2., 3., 4.].iter().collect();
+    a.error_with_population_size(2);
+}
+
+#[test]
+fn counter_width() {
+    use average::{GenericMean, GenericVariance};
+
+    let a: GenericVariance<f32, u32> = (1..6).map(|x| x as f32).collect();
+    assert_eq!(a.len(), 5);
+    assert_eq!(a.sample_variance(), 2.5);
+    assert_eq!(core::mem::size_of::<GenericMean<f32, u32>>(), 8);
+
+    let mut b = GenericMean::<f64, u32>::from_mean(u64::from(u32::MAX) + 10, 1.);
+    assert_eq!(b.len(), u64::from(u32::MAX));
+    b.add(1.);
+    assert_eq!(b.len(), u64::from(u32::MAX));
+    assert_eq!(b.mean(), 1.);
+    let c = b.clone();
+    b.merge(&c);
+    assert_eq!(b.len(), u64::from(u32::MAX));
+
+    let d: GenericMean<f64, usize> = [1., 3.].iter().collect();
+    assert_eq!(d.mean(), 2.);
+}
+
+#[test]
+fn merge_reduce() {
+    use average::{Max, merge_reduce};
+
+    let empty: MeanWithError = merge_reduce(Vec::new());
+    assert!(empty.is_empty());
+    for &shards in &[1, 2, 7, 64, 1000] {
+        let data: Vec<f64> = (0..shards * 3).map(|i| f64::from(i) * 0.1).collect();
+        let expected: MeanWithError = data.iter().collect();
+        let a: MeanWithError = merge_reduce(data.chunks(3).map(|c| c.iter().collect()));
+        assert_eq!(a.len(), expected.len());
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-12);
+        assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-9);
+    }
+    let m: Max = merge_reduce((0..100).map(|i| Max::from_value(f64::from(i))));
+    assert_eq!(m.max(), 99.);
+}
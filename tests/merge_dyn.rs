@@ -0,0 +1,42 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Estimate, Max, Mean, MergeDyn, Variance};
+
+fn pipeline(samples: &[f64]) -> Vec<Box<dyn MergeDyn>> {
+    let mut mean = Mean::new();
+    let mut variance = Variance::new();
+    let mut max = Max::new();
+    for &x in samples {
+        mean.add(x);
+        variance.add(x);
+        max.add(x);
+    }
+    vec![Box::new(mean), Box::new(variance), Box::new(max)]
+}
+
+#[test]
+fn heterogeneous() {
+    let mut a = pipeline(&[1., 2., 3.]);
+    let b = pipeline(&[4., 5.]);
+    for (a, b) in a.iter_mut().zip(&b) {
+        a.merge_dyn(b.as_ref()).unwrap();
+    }
+    let mean = a[0].as_any().downcast_ref::<Mean>().unwrap();
+    assert_eq!(mean.mean(), 3.);
+    assert_eq!(mean.len(), 5);
+    let variance = a[1].as_any().downcast_ref::<Variance>().unwrap();
+    assert_eq!(variance.sample_variance(), 2.5);
+    let max = a[2].as_any().downcast_ref::<Max>().unwrap();
+    assert_eq!(max.max(), 5.);
+}
+
+#[test]
+fn mismatch() {
+    let mut a: Box<dyn MergeDyn> = Box::new(Mean::new());
+    let mut b = Mean::new();
+    b.add(1.);
+    assert_eq!(a.merge_dyn(&Variance::new()), Err(()));
+    assert_eq!(a.merge_dyn(&Max::new()), Err(()));
+    a.merge_dyn(&b).unwrap();
+    assert_eq!(a.as_any().downcast_ref::<Mean>().unwrap().len(), 1);
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 use core::iter::Iterator;
 
@@ -44,3 +44,13 @@ fn merge() {
         assert_eq!(min_total.min(), min_left.min());
     }
 }
+
+#[test]
+fn add_assign() {
+    let mut a = Min::new();
+    a += 2.;
+    a += 3.;
+    let b: Min = [1., 4.].iter().collect();
+    a += &b;
+    assert_eq!(a.min(), 1.);
+}
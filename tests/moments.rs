@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 use core::iter::Iterator;
 
@@ -26,6 +26,21 @@ fn trivial() {
     assert_eq!(a.central_moment(3), 0.0);
 }
 
+#[test]
+fn try_standardized_moment() {
+    let mut a = Moments4::new();
+    assert_eq!(a.try_standardized_moment(2), Some(1.0));
+    assert_eq!(a.try_standardized_moment(3), None);
+    a.add(1.0);
+    a.add(1.0);
+    assert_eq!(a.try_standardized_moment(4), None);
+    a.add(2.0);
+    a.add(4.0);
+    assert_eq!(a.try_standardized_moment(3), Some(a.standardized_moment(3)));
+    assert_eq!(a.try_standardized_moment(4), Some(a.standardized_moment(4)));
+    assert_eq!(a.try_standardized_moment(5), None);
+}
+
 #[test]
 fn simple() {
     let mut a: Moments4 = (1..6).map(f64::from).collect();
@@ -88,3 +103,35 @@ fn merge() {
         assert_almost_eq!(avg_total.central_moment(4), avg_left.central_moment(4), 1e-12);
     }
 }
+
+#[test]
+fn merge_commutative() {
+    let data: Vec<f64> = (0..100).map(|i| (f64::from(i) * 0.37).sin() * 1e3 + 1e6).collect();
+    for &(i, j) in &[(0, 30), (10, 45), (50, 51), (99, 100)] {
+        let a: Moments4 = data[..i].iter().collect();
+        let b: Moments4 = data[i..j].iter().collect();
+        let mut ab = a.clone();
+        ab.merge(&b);
+        let mut ba = b.clone();
+        ba.merge(&a);
+        assert_eq!(ab.len(), ba.len());
+        assert_eq!(ab.mean(), ba.mean());
+        for p in 2..=4 {
+            assert_eq!(ab.central_moment(p), ba.central_moment(p));
+        }
+    }
+}
+
+#[test]
+fn add_assign() {
+    let sequence: &[f64] = &[1., 2., 3., -4., 5.1];
+    let total: Moments4 = sequence.iter().collect();
+    let mut a = Moments4::new();
+    a += 1.;
+    a += 2.;
+    let b: Moments4 = sequence[2..].iter().collect();
+    a += &b;
+    assert_eq!(total.len(), a.len());
+    assert_almost_eq!(total.mean(), a.mean(), 1e-14);
+    assert_almost_eq!(total.central_moment(4), a.central_moment(4), 1e-12);
+}
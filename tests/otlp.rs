@@ -0,0 +1,62 @@
+#![cfg(feature = "opentelemetry")]
+#![allow(clippy::float_cmp)]
+
+use std::time::{Duration, UNIX_EPOCH};
+
+use average::{Max, Quantile, Stats, ToOtlp, define_histogram};
+use average::opentelemetry_proto::tonic::metrics::v1::{metric::Data, number_data_point::Value};
+
+define_histogram!(hist, 3);
+
+#[test]
+fn gauge() {
+    let a: Max = (1..4).collect();
+    let metric = a.to_otlp_metric("max", UNIX_EPOCH + Duration::from_secs(2));
+    assert_eq!(metric.name, "max");
+    match metric.data {
+        Some(Data::Gauge(g)) => {
+            assert_eq!(g.data_points.len(), 1);
+            assert_eq!(g.data_points[0].value, Some(Value::AsDouble(3.)));
+            assert_eq!(g.data_points[0].time_unix_nano, 2_000_000_000);
+        }
+        _ => panic!("expected gauge"),
+    }
+    let q = Quantile::new(0.5);
+    assert!(matches!(q.to_otlp_data(), Data::Gauge(_)));
+}
+
+#[test]
+fn summary() {
+    let s: Stats = [2., 4., 9.].iter().collect();
+    match s.to_otlp_data() {
+        Data::Summary(s) => {
+            let p = &s.data_points[0];
+            assert_eq!((p.count, p.sum), (3, 15.));
+            assert_eq!(p.quantile_values.len(), 2);
+            assert_eq!((p.quantile_values[0].quantile, p.quantile_values[0].value), (0., 2.));
+            assert_eq!((p.quantile_values[1].quantile, p.quantile_values[1].value), (1., 9.));
+        }
+        _ => panic!("expected summary"),
+    }
+    match Stats::new().to_otlp_data() {
+        Data::Summary(s) => assert!(s.data_points[0].quantile_values.is_empty()),
+        _ => panic!("expected summary"),
+    }
+}
+
+#[test]
+fn histogram() {
+    let mut h = hist::Histogram::with_const_width(0., 3.);
+    for &x in &[0.5, 1.5, 1.7, 2.2] {
+        h.add(x).unwrap();
+    }
+    match h.to_otlp_data() {
+        Data::Histogram(h) => {
+            let p = &h.data_points[0];
+            assert_eq!(p.count, 4);
+            assert_eq!(p.explicit_bounds, [0., 1., 2., 3.]);
+            assert_eq!(p.bucket_counts, [0, 1, 2, 1, 0]);
+        }
+        _ => panic!("expected histogram"),
+    }
+}
@@ -0,0 +1,56 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Merge, OutlierDetector, assert_almost_eq};
+
+#[test]
+fn flag() {
+    let mut a = OutlierDetector::new(3.);
+    for i in 0..1000 {
+        assert!(!a.add(f64::from(i % 10)));
+    }
+    let std_dev = a.variance().sample_variance().sqrt();
+    assert!(!a.check(4.5 + 2.9 * std_dev));
+    assert!(a.check(4.5 + 3.1 * std_dev));
+    assert!(a.check(4.5 - 3.1 * std_dev));
+    assert!(a.check(f64::NAN));
+    assert_almost_eq!(a.z_score(4.5 + std_dev).unwrap(), 1., 1e-12);
+    assert!(a.add(1e3));
+    assert_eq!(a.outliers(), 1);
+    assert_eq!(a.len(), 1001);
+}
+
+#[test]
+fn min_samples() {
+    let mut a = OutlierDetector::with_min_samples(2., 3);
+    assert!(!a.add(1.));
+    assert!(!a.add(1e6));
+    assert_eq!(a.z_score(0.), None);
+    assert!(!a.add(2.));
+    assert!(a.z_score(0.).is_some());
+
+    let mut b = OutlierDetector::new(2.);
+    for _ in 0..10 {
+        b.add(5.);
+    }
+    assert_eq!(b.z_score(5.), None);
+    assert!(!b.check(5.));
+    assert!(b.check(5.1));
+    b.reset();
+    assert!(b.is_empty());
+    assert!(!b.check(100.));
+}
+
+#[test]
+fn merge() {
+    let mut a = OutlierDetector::new(3.);
+    let mut b = OutlierDetector::new(3.);
+    for i in 0..100 {
+        a.add(f64::from(i % 10));
+        b.add(f64::from(i % 10));
+    }
+    a.add(1e3);
+    b.add(1e3);
+    a.merge(&b);
+    assert_eq!(a.len(), 202);
+    assert_eq!(a.outliers(), 2);
+}
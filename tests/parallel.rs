@@ -0,0 +1,62 @@
+#![cfg(feature = "rayon")]
+#![allow(clippy::float_cmp)]
+
+use rayon::prelude::*;
+
+use average::{Mean, Variance, Kurtosis, Moments4, Min, Max, Range, Quantile, Describe, Merge,
+              par_merge, assert_almost_eq};
+
+#[test]
+fn collect() {
+    let data: Vec<f64> = (0..10_000).map(|i| f64::from(i % 97)).collect();
+    let seq: Kurtosis = data.iter().collect();
+    let par: Kurtosis = data.par_iter().collect();
+    assert_eq!(par.len(), seq.len());
+    assert_almost_eq!(par.mean(), seq.mean(), 1e-10);
+    assert_almost_eq!(par.sample_variance(), seq.sample_variance(), 1e-9);
+    assert_almost_eq!(par.kurtosis(), seq.kurtosis(), 1e-9);
+
+    let min: Min = data.par_iter().collect();
+    let max: Max = data.par_iter().collect();
+    assert_eq!(min.min(), 0.);
+    assert_eq!(max.max(), 96.);
+
+    let a: Mean = (1..6).into_par_iter().collect();
+    assert_eq!(a.mean(), 3.);
+}
+
+#[test]
+fn merge() {
+    let data: Vec<f64> = (1..=100).map(f64::from).collect();
+    let chunks: Vec<Variance> = data.chunks(7).map(|c| c.iter().collect()).collect();
+    let mut seq = Variance::new();
+    for c in &chunks {
+        seq.merge(c);
+    }
+    let par: Variance = par_merge(chunks);
+    assert_eq!(par.len(), 100);
+    assert_almost_eq!(par.mean(), seq.mean(), 1e-12);
+    assert_almost_eq!(par.sample_variance(), seq.sample_variance(), 1e-10);
+}
+
+#[test]
+fn empty() {
+    let a: Variance = par_merge(Vec::new());
+    assert!(a.is_empty());
+    let b: Mean = Vec::<f64>::new().into_par_iter().collect();
+    assert!(b.is_empty());
+}
+
+#[test]
+fn collect_summaries() {
+    let data: Vec<f64> = (1..=1000).map(f64::from).collect();
+    let range: Range = data.par_iter().collect();
+    assert_eq!((range.min(), range.max()), (1., 1000.));
+    let median: Quantile = data.par_iter().collect();
+    assert_almost_eq!(median.quantile(), 500.5, 5.);
+    let d: Describe = data.par_iter().collect();
+    assert_eq!(d.len(), 1000);
+    assert_almost_eq!(d.mean(), 500.5, 1e-10);
+    let m: Moments4 = data.par_iter().collect();
+    assert_eq!(m.len(), 1000);
+}
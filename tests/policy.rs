@@ -0,0 +1,70 @@
+#![allow(clippy::float_cmp)]
+
+use average::{AddError, Estimate, Mean, Merge, NanPolicy, Reset, Variance, WithNanPolicy};
+
+#[test]
+fn skip() {
+    let mut a = WithNanPolicy::new(Mean::new(), NanPolicy::Skip);
+    assert_eq!(a.policy(), NanPolicy::Skip);
+    for &x in &[1., f64::NAN, 2., f64::INFINITY, 3., f64::NEG_INFINITY] {
+        a.try_add(x).unwrap();
+    }
+    assert_eq!(a.get().len(), 3);
+    assert_eq!(a.skipped(), 3);
+    assert_eq!(a.estimate(), 2.);
+}
+
+#[test]
+fn propagate() {
+    let mut a = WithNanPolicy::new(Mean::new(), NanPolicy::default());
+    assert_eq!(a.policy(), NanPolicy::Propagate);
+    a.try_add(1.).unwrap();
+    a.try_add(f64::NAN).unwrap();
+    a.try_add(2.).unwrap();
+    assert_eq!(a.skipped(), 0);
+    assert!(a.estimate().is_nan());
+}
+
+#[test]
+fn error() {
+    let mut a = WithNanPolicy::new(Variance::new(), NanPolicy::Error);
+    a.try_add(1.).unwrap();
+    assert_eq!(a.try_add(f64::NAN), Err(AddError::NonFinite));
+    assert_eq!(a.try_add(f64::INFINITY), Err(AddError::NonFinite));
+    a.add(f64::NAN);
+    a.add(3.);
+    assert_eq!(a.skipped(), 3);
+    let v = a.into_inner();
+    assert_eq!(v.len(), 2);
+    assert_eq!(v.sample_variance(), 2.);
+}
+
+#[test]
+fn merge_reset() {
+    let mut a = WithNanPolicy::new(Mean::new(), NanPolicy::Skip);
+    let mut b = WithNanPolicy::new(Mean::new(), NanPolicy::Skip);
+    a.add(1.);
+    b.add(f64::NAN);
+    b.add(3.);
+    a.add(f64::NAN);
+    a.merge(&b);
+    assert_eq!(a.estimate(), 2.);
+    assert_eq!(a.skipped(), 2);
+    a.reset();
+    assert_eq!(a.skipped(), 0);
+    assert!(a.get().is_empty());
+    assert_eq!(a.policy(), NanPolicy::Skip);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let mut a = WithNanPolicy::new(Mean::new(), NanPolicy::Skip);
+    a.add(1.);
+    a.add(f64::NAN);
+    let b = serde_json::to_string(&a).unwrap();
+    assert_eq!(&b, "{\"inner\":{\"avg\":1.0,\"n\":1},\"policy\":\"Skip\",\"skipped\":1}");
+    let c: WithNanPolicy<Mean> = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.skipped(), 1);
+    assert_eq!(c.policy(), NanPolicy::Skip);
+}
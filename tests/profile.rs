@@ -0,0 +1,37 @@
+#![cfg(feature = "csv")]
+#![allow(clippy::float_cmp)]
+
+use average::describe_csv;
+
+#[test]
+fn columns() {
+    let data = "\
+id, name , value,flag
+1, a, 2.5, yes
+2, b, , no
+3, c, -1e3, yes
+4, d, NaN,
+";
+    let columns = describe_csv(data.as_bytes()).unwrap();
+    let names: Vec<&str> = columns.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["id", "value"]);
+    let id = &columns[0].1;
+    assert_eq!(id.len(), 4);
+    assert_eq!(id.mean(), 2.5);
+    let value = &columns[1].1;
+    assert_eq!(value.len(), 2);
+    assert_eq!(value.min(), -1e3);
+}
+
+#[test]
+fn empty() {
+    assert!(describe_csv(&b""[..]).unwrap().is_empty());
+    let columns = describe_csv(&b"x,y\n"[..]).unwrap();
+    assert_eq!(columns.len(), 2);
+    assert!(columns[0].1.is_empty());
+}
+
+#[test]
+fn invalid() {
+    assert!(describe_csv(&b"x,y\n1,2\n3\n"[..]).is_err());
+}
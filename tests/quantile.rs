@@ -1,6 +1,6 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
-use average::{Estimate, Quantile};
+use average::{Estimate, Merge, Reset, Quantile};
 use quantiles::ckms::CKMS;
 
 #[test]
@@ -22,6 +22,24 @@ fn few_observations() {
     assert_eq!(q.quantile(), 2.5);
 }
 
+#[test]
+fn few_observations_unsorted() {
+    // Less than 5 samples are stored in the order they were added, and have
+    // to be sorted for estimating the quantile.
+    let mut q = Quantile::new(0.5);
+    for &x in &[5., 1., 3.] {
+        q.add(x);
+    }
+    assert_eq!(q.quantile(), 3.);
+    q.add(2.);
+    assert_eq!(q.quantile(), 2.5);
+    let mut q = Quantile::new(0.25);
+    for &x in &[4., 3., 1., 2.] {
+        q.add(x);
+    }
+    assert_eq!(q.quantile(), 1.5);
+}
+
 #[cfg(feature = "serde1")]
 #[test]
 fn few_observations_serde() {
@@ -381,3 +399,127 @@ fn percentile_99() {
     assert!((q.quantile() - 9.).abs() < TOL);
 }
 
+
+#[test]
+fn reset() {
+    let mut q = Quantile::new(0.9);
+    for i in 1..101 {
+        q.add(f64::from(i));
+    }
+    q.reset();
+    assert_eq!(q.len(), 0);
+    assert_eq!(q.p(), 0.9);
+    assert_eq!(q.quantile(), 0.);
+}
+
+#[test]
+fn try_quantile() {
+    let mut q = Quantile::new(0.5);
+    assert_eq!(q.try_quantile(), None);
+    q.add(0.);
+    assert_eq!(q.try_quantile(), Some(0.));
+}
+
+#[test]
+fn merge_few() {
+    let mut a = Quantile::new(0.5);
+    a.add(1.);
+    a.add(5.);
+    let mut b = Quantile::new(0.5);
+    b.add(3.);
+    a.merge(&b);
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.quantile(), 3.);
+
+    let mut large = Quantile::new(0.5);
+    for i in 0..100 {
+        large.add(f64::from(i));
+    }
+    let mut small = Quantile::new(0.5);
+    small.add(1000.);
+    small.merge(&large);
+    assert_eq!(small.len(), 101);
+    assert!((small.quantile() - 50.).abs() < 1.);
+}
+
+#[test]
+fn merge() {
+    for &p in &[0.1, 0.5, 0.9] {
+        let data: Vec<f64> = (0..10_000).map(|i| f64::from(i * 7919 % 10_000)).collect();
+        let mut a = Quantile::new(p);
+        for chunk in data.chunks(1000) {
+            let mut b = Quantile::new(p);
+            for &x in chunk {
+                b.add(x);
+            }
+            a += &b;
+        }
+        assert_eq!(a.len(), 10_000);
+        assert!((a.quantile() - p * 10_000.).abs() < 100., "{} vs. {}", a.quantile(), p * 10_000.);
+        // Adding more samples after merging still works.
+        for &x in &data {
+            a.add(x);
+        }
+        assert!((a.quantile() - p * 10_000.).abs() < 100.);
+    }
+}
+
+#[test]
+fn confidence_interval() {
+    let mut q = Quantile::new(0.5);
+    assert_eq!(q.confidence_interval(0.95), None);
+    for &x in &[3., 1., 2.] {
+        q.add(x);
+    }
+    // Too few samples, so the interval is the whole range.
+    assert_eq!(q.confidence_interval(0.95), Some((1., 3.)));
+
+    // For n = 1000, the exact binomial ranks of the 95 % interval of the
+    // median are 470 and 532.
+    let mut q = Quantile::new(0.5);
+    for i in 0..1000 {
+        q.add(f64::from(i * 7919 % 1000) + 1.);
+    }
+    let (lower, upper) = q.confidence_interval(0.95).unwrap();
+    assert!((lower - 470.).abs() < 5., "{}", lower);
+    assert!((upper - 532.).abs() < 5., "{}", upper);
+    let (lower_99, upper_99) = q.confidence_interval(0.99).unwrap();
+    assert!(lower_99 < lower && upper < upper_99);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn hybrid() {
+    use average::HybridQuantile;
+
+    let mut q = HybridQuantile::new(0.5, 10);
+    assert_eq!(q.try_quantile(), None);
+    for &x in &[5., 1., 4., 2.] {
+        q.add(x);
+    }
+    assert_eq!(q.quantile(), 3.);
+    for &x in &[3., 100., 0., 6., 7., 8.] {
+        q.add(x);
+    }
+    assert!(q.is_exact());
+    assert_eq!(q.len(), 10);
+    assert_eq!(q.quantile(), 4.5);
+    q.add(9.);
+    assert!(!q.is_exact());
+    assert_eq!(q.len(), 11);
+
+    let mut a = HybridQuantile::new(0.5, 10);
+    let mut b = HybridQuantile::new(0.5, 10);
+    for i in 0..5 {
+        a.add(f64::from(i));
+        b.add(f64::from(i + 5));
+    }
+    a += &b;
+    assert!(a.is_exact());
+    assert_eq!(a.quantile(), 4.5);
+    a.merge(&q);
+    assert!(!a.is_exact());
+    assert_eq!(a.len(), 21);
+    a.reset();
+    assert!(a.is_empty() && a.is_exact());
+}
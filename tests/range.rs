@@ -0,0 +1,43 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Estimate, Merge, Reset, Range};
+
+#[test]
+fn trivial() {
+    let mut a = Range::new();
+    assert!(a.is_empty());
+    assert_eq!(a.range(), 0.);
+    assert_eq!(a.midrange(), 0.);
+    assert_eq!(a.try_range(), None);
+    assert_eq!(a.try_midrange(), None);
+    a.add(2.);
+    assert!(!a.is_empty());
+    assert_eq!(a.range(), 0.);
+    assert_eq!(a.midrange(), 2.);
+    a.add(-4.);
+    assert_eq!(a.estimate(), 6.);
+    assert_eq!(a.try_midrange(), Some(-1.));
+    a.reset();
+    assert!(a.is_empty());
+}
+
+#[test]
+fn merge() {
+    let sequence: &[f64] = &[1., 9., 3., 4., 5., 6., 7., 8., 2.];
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let total: Range = sequence.iter().collect();
+        let mut a: Range = left.iter().collect();
+        let b: Range = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a, total);
+        assert_eq!(a.range(), 8.);
+        assert_eq!(a.midrange(), 5.);
+    }
+}
+
+#[test]
+fn extreme() {
+    let a: Range = [f64::MAX, f64::MAX].iter().collect();
+    assert_eq!(a.midrange(), f64::MAX);
+}
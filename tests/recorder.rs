@@ -0,0 +1,39 @@
+#![cfg(feature = "metrics")]
+#![allow(clippy::float_cmp)]
+
+use average::{MetricsRecorder, Mean, Max};
+use metrics::Key;
+
+#[test]
+fn histograms() {
+    let recorder: MetricsRecorder<Mean> = MetricsRecorder::new();
+    assert!(recorder.is_empty());
+    metrics::with_local_recorder(&recorder, || {
+        metrics::histogram!("a").record(1.);
+        metrics::histogram!("a").record(3.);
+        metrics::histogram!("a", "host" => "x").record_many(5., 3);
+        metrics::counter!("ignored").increment(1);
+    });
+    assert_eq!(recorder.len(), 2);
+    let a = recorder.get(&Key::from_name("a")).unwrap();
+    assert_eq!((a.len(), a.mean()), (2, 2.));
+    let snapshot = recorder.snapshot();
+    assert_eq!(snapshot.len(), 2);
+    let (key, labeled) = &snapshot[1];
+    assert_eq!(key.name(), "a");
+    assert_eq!((labeled.len(), labeled.mean()), (3, 5.));
+    assert!(recorder.get(&Key::from_name("ignored")).is_none());
+
+    recorder.reset();
+    assert_eq!(recorder.len(), 2);
+    assert!(recorder.get(&Key::from_name("a")).unwrap().is_empty());
+}
+
+#[test]
+fn shared_handle() {
+    let recorder: MetricsRecorder<Max> = MetricsRecorder::default();
+    let h = metrics::with_local_recorder(&recorder, || metrics::histogram!("m"));
+    h.record(2.);
+    h.record(7.);
+    assert_eq!(recorder.get(&"m".into()).unwrap().max(), 7.);
+}
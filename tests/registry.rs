@@ -0,0 +1,63 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{StatsRegistry, Mean, Variance, Max};
+
+#[test]
+fn register() {
+    let mut a = StatsRegistry::new();
+    assert!(a.is_empty());
+    assert!(a.register("a", Mean::new()).is_none());
+    assert!(a.register("b", Max::new()).is_none());
+    assert!(a.register("a", Variance::new()).is_some());
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.add("c", 1.), Err(()));
+    a.add("a", 1.).unwrap();
+    a.add("a", 3.).unwrap();
+    assert_eq!(a.get("a").unwrap().estimate(), 1.);
+    assert!(a.get("c").is_none());
+    let names: Vec<&str> = a.iter().map(|(k, _)| k).collect();
+    assert_eq!(names, ["a", "b"]);
+    assert!(a.unregister("b").is_some());
+    assert_eq!(a.len(), 1);
+}
+
+#[test]
+fn snapshot_and_reset() {
+    let mut a = StatsRegistry::new();
+    a.register("mean", Mean::new());
+    a.register("max", Max::new());
+    for &x in &[1., 2., 6.] {
+        a.add("mean", x).unwrap();
+        a.add("max", x).unwrap();
+    }
+    let s = a.snapshot();
+    assert_eq!(s["mean"], 3.);
+    assert_eq!(s["max"], 6.);
+    a.reset();
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.snapshot()["max"], f64::NEG_INFINITY);
+}
+
+#[test]
+fn merge() {
+    let mut a = StatsRegistry::new();
+    a.register("mean", Mean::new());
+    a.add("mean", 1.).unwrap();
+    let mut b = StatsRegistry::new();
+    b.register("mean", Mean::new());
+    b.register("max", Max::new());
+    b.add("mean", 3.).unwrap();
+    b.add("max", 5.).unwrap();
+    a.merge(&b).unwrap();
+    let s = a.snapshot();
+    assert_eq!(s["mean"], 2.);
+    assert_eq!(s["max"], 5.);
+
+    let mut c = StatsRegistry::new();
+    c.register("mean", Max::new());
+    c.register("other", Max::new());
+    assert_eq!(a.merge(&c), Err(()));
+    assert_eq!(a.len(), 2);
+    assert_eq!(a.snapshot()["mean"], 2.);
+}
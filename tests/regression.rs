@@ -0,0 +1,111 @@
+#![allow(clippy::float_cmp)]
+
+use average::{HuberRegression, LinearRegression, Merge, assert_almost_eq};
+
+#[test]
+fn simple() {
+    let data = [(1., 2.), (2., 4.), (3., 5.), (4., 4.), (5., 5.)];
+    let a: LinearRegression = data.iter().collect();
+    assert_eq!(a.len(), 5);
+    assert_almost_eq!(a.slope(), 0.6, 1e-15);
+    assert_almost_eq!(a.intercept(), 2.2, 1e-15);
+    assert_almost_eq!(a.predict(6.), 5.8, 1e-14);
+    assert_almost_eq!(a.sample_covariance(), 1.5, 1e-15);
+    assert_almost_eq!(a.residual_sum_of_squares(), 2.4, 1e-14);
+    assert_almost_eq!(a.r_squared(), 0.6, 1e-15);
+    assert_almost_eq!(a.try_adjusted_r_squared().unwrap(), 1. - 0.4 * 4. / 3., 1e-15);
+    assert_almost_eq!(a.try_residual_std().unwrap(), f64::sqrt(0.8), 1e-15);
+    assert_almost_eq!(a.try_slope_error().unwrap(), f64::sqrt(0.08), 1e-15);
+    assert_almost_eq!(a.try_intercept_error().unwrap(), f64::sqrt(0.88), 1e-15);
+}
+
+#[test]
+fn degenerate() {
+    let mut a = LinearRegression::new();
+    assert!(a.is_empty());
+    assert_eq!(a.try_slope(), None);
+    assert_eq!(a.try_r_squared(), None);
+    a.add(1., 2.);
+    a.add(1., 3.);
+    assert_eq!(a.try_slope(), None);
+    assert_eq!(a.slope(), 0.);
+    assert_eq!(a.intercept(), 2.5);
+    assert_eq!(a.try_residual_std(), None);
+    a.add(2., 4.);
+    assert!(a.try_adjusted_r_squared().is_some());
+    let b: LinearRegression = [(1., 1.), (2., 1.), (3., 1.)].iter().collect();
+    assert_eq!(b.slope(), 0.);
+    assert_eq!(b.try_r_squared(), None);
+    assert_eq!(b.try_residual_std(), Some(0.));
+    assert_eq!(b.try_slope_error(), Some(0.));
+}
+
+#[test]
+fn merge() {
+    let data: Vec<(f64, f64)> = (0..100)
+        .map(|i| (f64::from(i), f64::from(i * 7919 % 101) + 0.5 * f64::from(i)))
+        .collect();
+    let expected: LinearRegression = data.iter().collect();
+    for mid in 0..data.len() {
+        let (left, right) = data.split_at(mid);
+        let mut a: LinearRegression = left.iter().collect();
+        let b: LinearRegression = right.iter().collect();
+        a.merge(&b);
+        assert_eq!(a.len(), expected.len());
+        assert_almost_eq!(a.slope(), expected.slope(), 1e-12);
+        assert_almost_eq!(a.intercept(), expected.intercept(), 1e-10);
+        assert_almost_eq!(a.r_squared(), expected.r_squared(), 1e-12);
+        assert_almost_eq!(a.try_slope_error().unwrap(),
+                          expected.try_slope_error().unwrap(), 1e-12);
+    }
+}
+
+/// Generate noisy samples of `y = 2 x + 1`, every tenth of which is a wild
+/// outlier.
+fn contaminated(n: u32) -> Vec<(f64, f64)> {
+    (0..n).map(|i| {
+        let x = f64::from(i);
+        let noise = f64::from(i * 7919 % 11) / 10. - 0.5;
+        let y = if i % 10 == 9 { 1e4 } else { 2. * x + 1. + noise };
+        (x, y)
+    }).collect()
+}
+
+#[test]
+fn huber_robust() {
+    let data = contaminated(1000);
+    let mut a = HuberRegression::new(1.);
+    a.extend(data.iter());
+    let ols: LinearRegression = data.iter().collect();
+    assert_eq!(a.len(), 1000);
+    assert!(a.sum_weights() < 1000.);
+    assert_almost_eq!(a.slope(), 2., 0.01);
+    assert_almost_eq!(a.intercept(), 1., 5.);
+    assert!((ols.slope() - 2.).abs() > 0.1);
+    assert!(a.weight(100., 1e4) < 1e-3);
+    assert_eq!(a.weight(100., 201.), 1.);
+}
+
+#[test]
+fn huber_without_outliers() {
+    let data = [(1., 2.), (2., 4.), (3., 5.), (4., 4.), (5., 5.)];
+    let mut a = HuberRegression::new(10.);
+    a.extend(data.iter());
+    let ols: LinearRegression = data.iter().collect();
+    assert_eq!(a.sum_weights(), 5.);
+    assert_almost_eq!(a.slope(), ols.slope(), 1e-15);
+    assert_almost_eq!(a.intercept(), ols.intercept(), 1e-15);
+    assert_almost_eq!(a.try_r_squared().unwrap(), ols.r_squared(), 1e-15);
+}
+
+#[test]
+fn huber_merge() {
+    let data = contaminated(1000);
+    let mut a = HuberRegression::new(1.);
+    a.extend(data[..500].iter());
+    let mut b = HuberRegression::new(1.);
+    b.extend(data[500..].iter());
+    a.merge(&b);
+    assert_eq!(a.len(), 1000);
+    assert_almost_eq!(a.slope(), 2., 0.01);
+}
@@ -0,0 +1,49 @@
+#![cfg(feature = "rkyv")]
+#![allow(clippy::float_cmp)]
+
+use rkyv::ser::serializers::BufferSerializer;
+use rkyv::ser::Serializer;
+use rkyv::{AlignedBytes, Archive, Deserialize, Infallible};
+
+use average::{Kurtosis, Max, Quantile, Variance, WithNanPolicy, NanPolicy, Mean, Estimate};
+
+/// Archive the value into a buffer and deserialize it again.
+fn roundtrip<T>(value: &T) -> T
+    where T: Archive + rkyv::Serialize<BufferSerializer<AlignedBytes<512>>>,
+          T::Archived: Deserialize<T, Infallible>
+{
+    let mut serializer = BufferSerializer::new(AlignedBytes([0; 512]));
+    let pos = serializer.serialize_value(value).unwrap();
+    let bytes = serializer.into_inner();
+    let archived = unsafe { rkyv::archived_value::<T>(&bytes.0[..], pos) };
+    archived.deserialize(&mut Infallible).unwrap()
+}
+
+#[test]
+fn estimators() {
+    let data: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+
+    let a: Variance = data.iter().collect();
+    let b = roundtrip(&a);
+    assert_eq!(a, b);
+
+    let a: Kurtosis = data.iter().collect();
+    assert_eq!(roundtrip(&a), a);
+
+    let a: Max = data.iter().collect();
+    assert_eq!(roundtrip(&a).max(), 9.);
+
+    let mut a = Quantile::new(0.2);
+    for &x in data {
+        a.add(x);
+    }
+    assert_eq!(roundtrip(&a).quantile(), a.quantile());
+
+    let mut a = WithNanPolicy::new(Mean::new(), NanPolicy::Skip);
+    a.add(1.);
+    a.add(f64::NAN);
+    let b = roundtrip(&a);
+    assert_eq!(b.policy(), NanPolicy::Skip);
+    assert_eq!(b.skipped(), 1);
+    assert_eq!(b.get().mean(), 1.);
+}
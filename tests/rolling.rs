@@ -0,0 +1,76 @@
+#![allow(clippy::float_cmp)]
+
+use average::{define_rolling_stats, Estimate, MeanWithError, assert_almost_eq};
+
+define_rolling_stats!(window, 4);
+use window::RollingStats;
+
+#[test]
+fn trivial() {
+    let mut a = RollingStats::new();
+    assert!(a.is_empty());
+    assert_eq!(a.capacity(), 4);
+    assert_eq!(a.mean(), 0.);
+    a.add(1.);
+    assert_eq!(a.len(), 1);
+    assert_eq!(a.mean(), 1.);
+    assert_eq!(a.sample_variance(), 0.);
+    assert_eq!(a.iter().collect::<Vec<_>>(), vec![1.]);
+}
+
+#[test]
+fn sliding() {
+    let values: Vec<f64> = (0..50).map(|i| f64::from(i * i % 17) - 3.5).collect();
+    let mut a = RollingStats::new();
+    for (i, &x) in values.iter().enumerate() {
+        a.add(x);
+        let start = if i >= 4 { i - 3 } else { 0 };
+        let window = &values[start..=i];
+        assert_eq!(a.len(), window.len());
+        assert_eq!(a.is_full(), window.len() == 4);
+        assert_eq!(a.iter().collect::<Vec<_>>(), window);
+        let expected: MeanWithError = window.iter().collect();
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-13);
+        assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-12);
+        assert_almost_eq!(a.population_variance(), expected.population_variance(), 1e-12);
+        assert_almost_eq!(a.estimate(), expected.mean(), 1e-13);
+    }
+}
+
+#[test]
+fn large_offset() {
+    // Rounding errors from removing samples do not accumulate.
+    let mut a: RollingStats = (0..10_000)
+        .map(|i| 1e9 + f64::from(i % 2)).collect();
+    assert_eq!(a.mean(), 1e9 + 0.5);
+    assert_almost_eq!(a.population_variance(), 0.25, 1e-6);
+    a.add(1e9);
+    a.add(1e9);
+    a.add(1e9);
+    assert_almost_eq!(a.population_variance(), 0.1875, 1e-6);
+}
+
+#[test]
+fn debug() {
+    let a: RollingStats = [1., 2., 3., 4., 5.].iter().collect();
+    assert_eq!(format!("{:?}", a), "RollingStats { window: [2.0, 3.0, 4.0, 5.0] }");
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn simple_serde() {
+    let a: RollingStats = [1., 2., 3., 4., 5.].iter().collect();
+    let b = serde_json::to_string(&a).unwrap();
+    let c: RollingStats = serde_json::from_str(&b).unwrap();
+    assert_eq!(c.iter().collect::<Vec<_>>(), a.iter().collect::<Vec<_>>());
+    assert_eq!(c.mean(), a.mean());
+
+    let mut v: serde_json::Value = serde_json::from_str(&b).unwrap();
+    v["head"] = 4.into();
+    assert!(serde_json::from_value::<RollingStats>(v.clone()).is_err());
+    v["head"] = 0.into();
+    v["len"] = 5.into();
+    assert!(serde_json::from_value::<RollingStats>(v.clone()).is_err());
+    v["len"] = 2.into();
+    assert!(serde_json::from_value::<RollingStats>(v).is_err());
+}
@@ -0,0 +1,43 @@
+#![allow(clippy::float_cmp, clippy::map_clone)]
+
+use std::time::Duration;
+
+use average::{Estimate, IntoSample, Max, Mean, Min, Variance};
+
+#[test]
+fn into_sample() {
+    assert_eq!(1.5f64.into_sample(), 1.5);
+    assert_eq!(1.5f32.into_sample(), 1.5);
+    assert_eq!((-3i8).into_sample(), -3.);
+    assert_eq!(u64::MAX.into_sample(), 18_446_744_073_709_551_615.);
+    assert_eq!(7usize.into_sample(), 7.);
+    assert_eq!((&42u32).into_sample(), 42.);
+    assert_eq!(Duration::from_millis(1500).into_sample(), 1.5);
+    assert_eq!(Duration::new(2, 1).into_sample(), 2.000_000_001);
+}
+
+#[test]
+fn add_sample() {
+    let mut a = Variance::new();
+    a.add_sample(1u8);
+    a.add_sample(2i32);
+    for x in &[3u64, 4] {
+        a.add_sample(x);
+    }
+    a.add_sample(5.);
+    assert_eq!(a.mean(), 3.);
+    assert_eq!(a.sample_variance(), 2.5);
+
+    let mut min = Min::new();
+    let mut max = Max::new();
+    let mut mean = Mean::new();
+    for &ms in &[10u64, 30, 20] {
+        let d = Duration::from_millis(ms);
+        min.add_sample(d);
+        max.add_sample(d);
+        mean.add_sample(d);
+    }
+    assert_eq!(min.min(), 0.01);
+    assert_eq!(max.max(), 0.03);
+    assert!((mean.mean() - 0.02).abs() < 1e-15);
+}
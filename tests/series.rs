@@ -0,0 +1,45 @@
+#![cfg(feature = "polars")]
+#![allow(clippy::float_cmp)]
+
+use average::{Kurtosis, Mean, Merge, Quantile, Variance, Min, StatsSeriesExt, from_series,
+              assert_almost_eq};
+use polars::prelude::*;
+
+#[test]
+fn series() {
+    let s = Series::new("x".into(), &[1i32, 2, 3, 4]);
+    let a: Mean = from_series(&s).unwrap();
+    assert_eq!(a.mean(), 2.5);
+    let s = Series::new("x".into(), &[Some(1.5f32), None, Some(-1.)]);
+    let a: Min = s.estimator().unwrap();
+    assert_eq!(a.min(), -1.);
+    let s = Series::new("x".into(), &["a", "b"]);
+    assert!(from_series::<Mean>(&s).is_err());
+    let s = Series::new_empty("x".into(), &DataType::Float64);
+    assert!(from_series::<Mean>(&s).unwrap().is_empty());
+}
+
+#[test]
+fn chunks() {
+    let data: Vec<f64> = (0..1000).map(|i| f64::from(i).sqrt()).collect();
+    let mut a = Float64Chunked::from_slice("x".into(), &data[..100]);
+    for chunk in data[100..].chunks(300) {
+        a.append(&Float64Chunked::from_slice("x".into(), chunk)).unwrap();
+    }
+    assert_eq!(a.chunks().len(), 4);
+    let expected: Kurtosis = data.iter().collect();
+    let k: Kurtosis = a.estimator().unwrap();
+    assert_eq!(k.len(), 1000);
+    assert_almost_eq!(k.mean(), expected.mean(), 1e-12);
+    assert_almost_eq!(k.sample_variance(), expected.sample_variance(), 1e-12);
+    assert_almost_eq!(k.kurtosis(), expected.kurtosis(), 1e-9);
+
+    // Estimators of separate series can be merged as well.
+    let mut v: Variance = a.estimator().unwrap();
+    let b = Int64Chunked::from_slice("y".into(), &[1, 2, 3]);
+    v.merge(&b.estimator().unwrap());
+    assert_eq!(v.len(), 1003);
+
+    let q: Quantile = a.estimator().unwrap();
+    assert_almost_eq!(q.quantile(), 500f64.sqrt(), 0.5);
+}
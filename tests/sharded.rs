@@ -0,0 +1,40 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{ShardedEstimate, Variance, Max, Merge};
+
+#[test]
+fn concurrent() {
+    let a: ShardedEstimate<Variance> = ShardedEstimate::with_shards(3);
+    assert_eq!(a.shards(), 3);
+    std::thread::scope(|s| {
+        for t in 0..8 {
+            let a = &a;
+            s.spawn(move || {
+                for i in 0..1000 {
+                    a.add(f64::from(t * 1000 + i));
+                }
+            });
+        }
+    });
+    let expected: Variance = (0..8000).map(f64::from).collect();
+    let v = a.merged();
+    assert_eq!(v.len(), 8000);
+    assert!((v.mean() - expected.mean()).abs() < 1e-9);
+    assert!((v.sample_variance() - expected.sample_variance()).abs() < 1e-6);
+    assert_eq!(a.into_inner().len(), 8000);
+}
+
+#[test]
+fn estimate_and_reset() {
+    let a: ShardedEstimate<Max> = ShardedEstimate::new();
+    assert_eq!(a.estimate(), f64::NEG_INFINITY);
+    a.add(1.);
+    a.add(3.);
+    assert_eq!(a.estimate(), 3.);
+    let mut b = Max::new();
+    b.merge(&a.merged());
+    assert_eq!(b.max(), 3.);
+    a.reset();
+    assert_eq!(a.estimate(), f64::NEG_INFINITY);
+}
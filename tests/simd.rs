@@ -0,0 +1,32 @@
+#![cfg(feature = "wide")]
+#![allow(clippy::float_cmp)]
+
+use average::{Mean, Variance, assert_almost_eq};
+
+#[test]
+fn add_slice_simd() {
+    let data: Vec<f64> = (0..1003).map(|i| 1e6 + f64::from(i % 17) * 0.5).collect();
+    for len in [0, 1, 3, 4, 5, 1003] {
+        let mut expected = Variance::new();
+        expected.add_slice(&data[..len]);
+        let mut a = Variance::new();
+        a.add_slice_simd(&data[..len]);
+        assert_eq!(a.len(), expected.len());
+        assert_almost_eq!(a.mean(), expected.mean(), 1e-8);
+        assert_almost_eq!(a.sample_variance(), expected.sample_variance(), 1e-8);
+
+        let mut m = Mean::new();
+        m.add_slice_simd(&data[..len]);
+        assert_eq!(m.len(), expected.len());
+        assert_almost_eq!(m.mean(), expected.mean(), 1e-8);
+    }
+}
+
+#[test]
+fn merge_with_existing() {
+    let mut a: Variance = [1., 2.].iter().collect();
+    a.add_slice_simd(&[3., 4., 5., 6., 7.]);
+    assert_eq!(a.len(), 7);
+    assert_eq!(a.mean(), 4.);
+    assert_almost_eq!(a.sample_variance(), 28. / 6., 1e-14);
+}
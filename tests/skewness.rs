@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 use core::iter::Iterator;
 
@@ -67,3 +67,33 @@ fn merge() {
         assert_almost_eq!(avg_total.skewness(), avg_left.skewness(), 1e-14);
     }
 }
+
+#[test]
+fn add_n() {
+    let mut a = Skewness::new();
+    let mut b = Skewness::new();
+    for &(x, count) in &[(1., 3), (-2., 0), (4., 1), (2.5, 5), (-1., 2)] {
+        a.add_n(x, count);
+        for _ in 0..count {
+            b.add(x);
+        }
+    }
+    assert_eq!(a.len(), b.len());
+    assert_almost_eq!(a.mean(), b.mean(), 1e-14);
+    assert_almost_eq!(a.sample_variance(), b.sample_variance(), 1e-14);
+    assert_almost_eq!(a.skewness(), b.skewness(), 1e-14);
+}
+
+#[test]
+fn try_estimates() {
+    let mut a = Skewness::new();
+    assert_eq!(a.try_mean(), None);
+    assert_eq!(a.try_skewness(), None);
+    a.add(1.);
+    a.add(1.);
+    assert_eq!(a.try_mean(), Some(1.));
+    assert_eq!(a.try_sample_variance(), Some(0.));
+    assert_eq!(a.try_skewness(), None);
+    a.add(4.);
+    assert_eq!(a.try_skewness(), Some(a.skewness()));
+}
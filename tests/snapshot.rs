@@ -0,0 +1,76 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Mean, Variance, Skewness, Kurtosis, Min, Max, Quantile, Estimate};
+
+#[test]
+fn layout() {
+    let a: Mean = [1., 2.].iter().collect();
+    let mut expected = vec![1, 1];
+    expected.extend_from_slice(&1.5f64.to_le_bytes());
+    expected.extend_from_slice(&2u64.to_le_bytes());
+    assert_eq!(&a.to_bytes()[..], &expected[..]);
+}
+
+#[test]
+fn roundtrip() {
+    let data: &[f64] = &[1., 2., 3., -4., 5.1, 6.3, 7.3, -8., 9., 1.];
+
+    let a: Variance = data.iter().collect();
+    let b = Variance::from_bytes(&a.to_bytes()).unwrap();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.mean(), b.mean());
+    assert_eq!(a.sample_variance(), b.sample_variance());
+
+    let a: Skewness = data.iter().collect();
+    let b = Skewness::from_bytes(&a.to_bytes()).unwrap();
+    assert_eq!(a.skewness(), b.skewness());
+
+    let a: Kurtosis = data.iter().collect();
+    let b = Kurtosis::from_bytes(&a.to_bytes()).unwrap();
+    assert_eq!(a.len(), b.len());
+    assert_eq!(a.kurtosis(), b.kurtosis());
+
+    let a: Min = data.iter().collect();
+    assert_eq!(Min::from_bytes(&a.to_bytes()).unwrap().min(), -8.);
+    let a: Max = data.iter().collect();
+    assert_eq!(Max::from_bytes(&a.to_bytes()).unwrap().max(), 9.);
+
+    let mut a = Quantile::new(0.9);
+    for &x in data {
+        a.add(x);
+    }
+    let mut b = Quantile::from_bytes(&a.to_bytes()).unwrap();
+    assert_eq!(b.p(), 0.9);
+    assert_eq!(a.quantile(), b.quantile());
+    a.add(3.);
+    b.add(3.);
+    assert_eq!(a.quantile(), b.quantile());
+}
+
+#[test]
+fn invalid() {
+    let a = Mean::new().to_bytes();
+    assert!(Mean::from_bytes(&a[1..]).is_err());
+    let mut b = a;
+    b[0] = 0;
+    assert!(Mean::from_bytes(&b).is_err());
+    // Other estimator of the same length
+    let min = Min::new().to_bytes();
+    assert!(Max::from_bytes(&min).is_err());
+    assert!(Min::from_bytes(&min).is_ok());
+    let mut q = Quantile::new(0.5).to_bytes();
+    q[2..10].copy_from_slice(&2f64.to_le_bytes());
+    assert!(Quantile::from_bytes(&q).is_err());
+}
+
+#[test]
+fn newer_version() {
+    // A newer version may append fields, which are ignored.
+    let a: Variance = [1., 2., 4.].iter().collect();
+    let mut bytes = a.to_bytes().to_vec();
+    bytes[0] = 2;
+    bytes.extend_from_slice(&42f64.to_le_bytes());
+    let b = Variance::from_bytes(&bytes).unwrap();
+    assert_eq!(b.len(), 3);
+    assert_eq!(b.sample_variance(), a.sample_variance());
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "statrs")]
+#![allow(clippy::float_cmp)]
+
+use std::convert::TryFrom;
+
+use average::{Histogram, Kurtosis, Max, Min, Stats, Variance, assert_almost_eq, define_histogram, fit};
+use statrs::distribution::{self, ContinuousCDF};
+use statrs::statistics::Distribution;
+
+define_histogram!(hist, 10);
+
+/// Calculate the mean and variance via the `statrs` trait.
+fn moments<D: Distribution<f64>>(d: &D) -> (Option<f64>, Option<f64>) {
+    (d.mean(), d.variance())
+}
+
+#[test]
+fn distribution() {
+    let a: Kurtosis = [1., 2., 3., 4., 10.].iter().collect();
+    assert_eq!(moments(&a), (Some(4.), Some(12.5)));
+    assert_eq!(Distribution::skewness(&a), Some(a.skewness()));
+    assert_eq!(moments(&Variance::new()), (None, None));
+    let single: Stats = [1.].iter().collect();
+    assert_eq!(moments(&single), (Some(1.), None));
+
+    let min: Min = [3., 1., 2.].iter().collect();
+    let max: Max = [3., 1., 2.].iter().collect();
+    assert_eq!(statrs::statistics::Min::min(&min), 1.);
+    assert_eq!(statrs::statistics::Max::max(&max), 3.);
+}
+
+#[test]
+fn normal() {
+    let a: Variance = [1., 2., 3., 4., 5.].iter().collect();
+    let normal = distribution::Normal::try_from(&a).unwrap();
+    assert_eq!(normal.mean(), Some(3.));
+    assert_almost_eq!(normal.variance().unwrap(), 2.5, 1e-12);
+    let single: Variance = [1.].iter().collect();
+    assert!(distribution::Normal::try_from(&single).is_err());
+}
+
+#[test]
+fn fitted() {
+    let a: Kurtosis = [0., 2., 2., 4., 4., 4., 5.].iter().collect();
+    let gamma = fit::Gamma::fit(&a).unwrap();
+    let distr = distribution::Gamma::try_from(&gamma).unwrap();
+    assert_almost_eq!(distr.mean().unwrap(), a.mean(), 1e-12);
+    assert_almost_eq!(distr.variance().unwrap(), a.sample_variance(), 1e-12);
+
+    let log_normal = distribution::LogNormal::try_from(&fit::LogNormal::fit(&a).unwrap()).unwrap();
+    assert_almost_eq!(log_normal.mean().unwrap(), a.mean(), 1e-12);
+    let normal = distribution::Normal::try_from(&fit::Normal::fit(&a).unwrap()).unwrap();
+    assert_almost_eq!(normal.mean().unwrap(), a.mean(), 1e-12);
+    let exponential = distribution::Exp::try_from(&fit::Exponential::fit(&a).unwrap()).unwrap();
+    assert_almost_eq!(exponential.mean().unwrap(), a.mean(), 1e-12);
+    let a: Kurtosis = [0.1, 0.2, 0.4].iter().collect();
+    let beta = distribution::Beta::try_from(&fit::Beta::fit(&a).unwrap()).unwrap();
+    assert_almost_eq!(beta.mean().unwrap(), a.mean(), 1e-12);
+}
+
+#[test]
+fn chi_square_test() {
+    let mut h = hist::Histogram::with_const_width(0., 10.);
+    for i in 0..1000 {
+        h.add(f64::from(i % 100) / 10.).unwrap();
+    }
+    let uniform = distribution::Uniform::new(0., 10.).unwrap();
+    let result = h.chi_square_test(|x| uniform.cdf(x), 0);
+    let null = result.null_distribution().unwrap();
+    assert_almost_eq!(null.sf(result.statistic), result.p_value, 1e-12);
+    assert!(null.inverse_cdf(0.95) > result.statistic);
+}
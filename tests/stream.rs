@@ -0,0 +1,30 @@
+#![cfg(feature = "futures")]
+#![allow(clippy::float_cmp)]
+
+use average::{Variance, Quantile, from_stream, collect_stream, StatsStreamExt};
+use futures::{executor::block_on, stream, StreamExt};
+
+#[test]
+fn from_stream_matches_iterator() {
+    let data: Vec<f64> = (1..=100).map(f64::from).collect();
+    let a: Variance = block_on(from_stream(stream::iter(data.clone())));
+    let b: Variance = data.iter().collect();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn collect_stream_continues() {
+    let mut a = Variance::new();
+    a.add(1.);
+    let a = block_on(collect_stream(stream::iter(&[2., 3.]), a));
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.mean(), 2.);
+}
+
+#[test]
+fn ext() {
+    let q: Quantile = block_on(stream::iter(1..=5).map(|x| x * 2).collect_estimator());
+    assert_eq!(q.len(), 5);
+    let empty: Variance = block_on(stream::empty::<f64>().collect_estimator());
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,104 @@
+#![cfg(feature = "std")]
+#![allow(clippy::float_cmp)]
+
+use average::{Merge, TheilSen, assert_almost_eq};
+use rand::{Rng, SeedableRng};
+
+/// Generate noisy samples of `y = 0.5 x - 3`, a fifth of which are wild
+/// outliers.
+fn contaminated(n: usize) -> Vec<(f64, f64)> {
+    let mut rng = rand_xoshiro::Xoshiro256StarStar::seed_from_u64(42);
+    (0..n).map(|_| {
+        let x: f64 = rng.gen_range(0., 100.);
+        let y = if rng.gen_range(0, 5) == 0 {
+            rng.gen_range(1e3, 1e4)
+        } else {
+            0.5 * x - 3. + rng.gen_range(-1., 1.)
+        };
+        (x, y)
+    }).collect()
+}
+
+#[test]
+fn simple() {
+    let mut a = TheilSen::new(10);
+    assert!(a.is_empty());
+    assert_eq!(a.try_slope(), None);
+    assert_eq!(a.intercept(), 0.);
+    a.add(1., 1.);
+    a.add(1., 3.);
+    assert_eq!(a.try_slope(), None);
+    assert_eq!(a.intercept(), 2.);
+    a.add(2., f64::NAN);
+    a.add(f64::INFINITY, 2.);
+    assert_eq!(a.len(), 2);
+    a.add(3., 4.);
+    // The slopes are 1.5 and 0.5.
+    assert_eq!(a.slope(), 1.);
+    assert_eq!(a.intercept(), 1.);
+    a.reset();
+    assert!(a.is_empty());
+    assert_eq!(a.capacity(), 10);
+}
+
+#[test]
+fn overflow() {
+    let mut a = TheilSen::new(10);
+    a.add(0., -f64::MAX);
+    a.add(1e-300, f64::MAX);
+    a.add(1., 1.);
+    a.add(2., 2.);
+    // The slope between the first two samples overflows and is skipped.
+    assert!(a.slope().is_finite());
+    assert!(a.intercept().is_finite());
+
+    let mut b = TheilSen::new(10);
+    b.add(0., f64::MAX);
+    b.add(1., f64::MAX);
+    assert_eq!(b.slope(), 0.);
+    assert_eq!(b.intercept(), f64::MAX);
+}
+
+#[test]
+fn robust() {
+    let data = contaminated(200);
+    let mut a = TheilSen::new(200);
+    for &(x, y) in &data {
+        a.add(x, y);
+    }
+    assert_almost_eq!(a.slope(), 0.5, 0.05);
+    assert_almost_eq!(a.predict(50.), 22., 2.);
+}
+
+#[test]
+fn sampled() {
+    let data = contaminated(10_000);
+    let mut a = TheilSen::new(300);
+    for &(x, y) in &data {
+        a.add(x, y);
+    }
+    assert_eq!(a.len(), 10_000);
+    assert_almost_eq!(a.slope(), 0.5, 0.05);
+}
+
+#[test]
+fn merge() {
+    let data = contaminated(200);
+    let mut expected = TheilSen::new(200);
+    for &(x, y) in &data {
+        expected.add(x, y);
+    }
+    let (left, right) = data.split_at(80);
+    let mut a = TheilSen::new(200);
+    for &(x, y) in left {
+        a.add(x, y);
+    }
+    let mut b = TheilSen::with_seed(200, 1);
+    for &(x, y) in right {
+        b.add(x, y);
+    }
+    a.merge(&b);
+    assert_eq!(a.len(), 200);
+    assert_eq!(a.slope(), expected.slope());
+    assert_eq!(a.intercept(), expected.intercept());
+}
@@ -0,0 +1,94 @@
+#![allow(clippy::float_cmp)]
+
+use core::time::Duration;
+
+use average::{define_time_buckets, Mean, MeanWithError, Max, Merge, assert_almost_eq};
+
+define_time_buckets!(buckets, 3);
+use buckets::TimeBuckets;
+
+#[test]
+fn trivial() {
+    let b: TimeBuckets<Mean> = TimeBuckets::new(10.);
+    assert_eq!(b.interval(), 10.);
+    assert_eq!(b.current_interval(), None);
+    assert!(b.bucket(0).is_empty());
+    assert!(b.merged(3).is_empty());
+    assert_eq!(b.merged(3).mean(), 0.);
+}
+
+#[test]
+fn rollup() {
+    let mut b: TimeBuckets<MeanWithError> = TimeBuckets::new(Duration::from_secs(60));
+    for t in 0..300u32 {
+        b.add_at(f64::from(t / 60), Duration::from_secs(t.into())).unwrap();
+    }
+    assert_eq!(b.current_interval(), Some(4));
+    assert_eq!(b.bucket(0).mean(), 4.);
+    assert_eq!(b.bucket(0).len(), 60);
+    assert_eq!(b.bucket(2).mean(), 2.);
+    let merged = b.merged(3);
+    assert_eq!(merged.len(), 180);
+    assert_eq!(merged.mean(), 3.);
+    let expected: MeanWithError = (120..300).map(|t| f64::from(t / 60)).collect();
+    assert_almost_eq!(merged.sample_variance(), expected.sample_variance(), 1e-14);
+
+    // Samples in the expired intervals are rejected.
+    assert_eq!(b.add_at(0., 119.), Err(()));
+    b.add_at(1., 120.).unwrap();
+    assert_eq!(b.bucket(2).len(), 61);
+}
+
+#[test]
+fn expire() {
+    let mut b: TimeBuckets<Max> = TimeBuckets::new(1.);
+    b.add_at(5., -0.5).unwrap();
+    assert_eq!(b.current_interval(), Some(-1));
+    b.add_at(3., 0.5).unwrap();
+    assert_eq!(b.merged(2).max(), 5.);
+    b.advance_to(2.);
+    assert_eq!(b.merged(3).max(), 3.);
+    // Advancing to the past does nothing.
+    b.advance_to(-10.);
+    assert_eq!(b.current_interval(), Some(2));
+    b.advance_to(100.);
+    assert_eq!(b.merged(3).max(), Max::new().max());
+    b.add_at(1., 100.).unwrap();
+    let mut m = Max::new();
+    m.merge(b.bucket(0));
+    assert_eq!(m.max(), 1.);
+}
+
+#[test]
+#[should_panic]
+fn too_many_buckets() {
+    let b: TimeBuckets<Mean> = TimeBuckets::new(1.);
+    b.merged(4);
+}
+
+#[test]
+fn reset() {
+    let mut b: TimeBuckets<Mean> = TimeBuckets::new(1.);
+    b.add_at(1., 0.).unwrap();
+    b.add_at(2., 1.).unwrap();
+    b.reset();
+    assert_eq!(b.current_interval(), None);
+    assert!(b.merged(3).is_empty());
+    assert_eq!(b.interval(), 1.);
+}
+
+#[cfg(feature = "serde1")]
+#[test]
+fn serde() {
+    let mut b: TimeBuckets<Mean> = TimeBuckets::new(10.);
+    b.add_at(1., 5.).unwrap();
+    b.add_at(3., 25.).unwrap();
+    let s = serde_json::to_string(&b).unwrap();
+    assert_eq!(s, "{\"buckets\":[{\"avg\":1.0,\"n\":1},{\"avg\":0.0,\"n\":0},\
+                   {\"avg\":3.0,\"n\":1}],\"interval\":10.0,\"current\":2}");
+    let c: TimeBuckets<Mean> = serde_json::from_str(&s).unwrap();
+    assert_eq!(c.current_interval(), Some(2));
+    assert_eq!(c.merged(3).mean(), 2.);
+    assert!(serde_json::from_str::<TimeBuckets<Mean>>(
+        "{\"buckets\":[],\"interval\":10.0,\"current\":null}").is_err());
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "nalgebra")]
+#![allow(clippy::float_cmp)]
+
+use average::{Merge, Variance, VectorCovariance, VectorMean, assert_almost_eq};
+use nalgebra::{Matrix2, Vector2, Vector3};
+
+fn samples() -> Vec<Vector3<f64>> {
+    (0..100).map(|i| {
+        let t = f64::from(i);
+        Vector3::new(t, 2. * t + 1., (t * 0.1).sin())
+    }).collect()
+}
+
+#[test]
+fn mean() {
+    let a: VectorMean<3> = samples().iter().collect();
+    assert_eq!(a.len(), 100);
+    assert_almost_eq!(a.mean()[0], 49.5, 1e-12);
+    assert_almost_eq!(a.mean()[1], 100., 1e-12);
+    assert!(VectorMean::<3>::new().try_mean().is_none());
+}
+
+#[test]
+fn covariance() {
+    let data = samples();
+    let a: VectorCovariance<3> = data.iter().collect();
+    let cov = a.sample_covariance();
+    assert_eq!(cov, cov.transpose());
+    for i in 0..3 {
+        let v: Variance = data.iter().map(|x| x[i]).collect();
+        assert_almost_eq!(cov[(i, i)], v.sample_variance(), 1e-9);
+        assert_almost_eq!(a.error()[i], v.error(), 1e-12);
+    }
+    // The second component is a linear function of the first.
+    assert_almost_eq!(cov[(0, 1)], 2. * cov[(0, 0)], 1e-9);
+    assert_almost_eq!(a.population_covariance()[(0, 0)] * 100., cov[(0, 0)] * 99., 1e-9);
+}
+
+#[test]
+fn merge() {
+    let data = samples();
+    let all: VectorCovariance<3> = data.iter().collect();
+    let mut a: VectorCovariance<3> = data[..30].iter().collect();
+    let b: VectorCovariance<3> = data[30..].iter().collect();
+    a.merge(&b);
+    assert_eq!(a.len(), all.len());
+    assert_almost_eq!((a.mean() - all.mean()).norm(), 0., 1e-12);
+    assert_almost_eq!((a.sample_covariance() - all.sample_covariance()).norm(), 0., 1e-9);
+
+    let mut m: VectorMean<3> = data[..30].iter().collect();
+    m += &data[30..].iter().collect();
+    assert_almost_eq!((m.mean() - all.mean()).norm(), 0., 1e-12);
+}
+
+#[test]
+fn few() {
+    let mut a = VectorCovariance::<2>::new();
+    assert_eq!(a.sample_covariance(), Matrix2::zeros());
+    a += Vector2::new(1., 2.);
+    assert_eq!(a.sample_covariance(), Matrix2::zeros());
+    assert_eq!(a.population_covariance(), Matrix2::zeros());
+}
@@ -1,4 +1,4 @@
-#![cfg_attr(feature = "cargo-clippy", allow(clippy::float_cmp, map_clone))]
+#![allow(clippy::float_cmp, clippy::map_clone)]
 
 use core::iter::Iterator;
 
@@ -117,3 +117,68 @@ fn merge_weighted() {
         assert_almost_eq!(avg_total.sample_variance(), avg_left.sample_variance(), 1e-14);
     }
 }
+
+#[test]
+fn merge_scaled() {
+    let sequence: &[(f64, f64)] = &[
+        (1., 0.1), (2., 0.2), (3., 0.3), (4., 0.4), (5., 0.5),
+        (6., 0.6), (7., 0.7), (8., 0.8), (9., 0.9)];
+    let scale = 0.25;
+    for mid in 0..sequence.len() {
+        let (left, right) = sequence.split_at(mid);
+        let avg_total: WeightedMeanWithError = left.iter().cloned()
+            .chain(right.iter().map(|&(x, w)| (x, scale * w))).collect();
+        let mut avg_left: WeightedMeanWithError = left.iter().collect();
+        let avg_right: WeightedMeanWithError = right.iter().collect();
+        avg_left.merge_weighted(&avg_right, scale);
+        assert_eq!(avg_total.len(), avg_left.len());
+        assert_almost_eq!(avg_total.sum_weights(), avg_left.sum_weights(), 1e-15);
+        assert_almost_eq!(avg_total.sum_weights_sq(), avg_left.sum_weights_sq(), 1e-15);
+        assert_almost_eq!(avg_total.weighted_mean(), avg_left.weighted_mean(), 1e-14);
+        assert_almost_eq!(avg_total.unweighted_mean(), avg_left.unweighted_mean(), 1e-15);
+        assert_almost_eq!(avg_total.error(), avg_left.error(), 1e-14);
+    }
+    let mut a: WeightedMeanWithError = sequence.iter().collect();
+    let mean = a.weighted_mean();
+    a.merge_weighted(&a.clone(), 0.);
+    assert_eq!(a.weighted_mean(), mean);
+    assert_eq!(a.len(), 2 * sequence.len() as u64);
+}
+
+#[test]
+fn try_estimates() {
+    let mut a = WeightedMeanWithError::new();
+    assert_eq!(a.try_weighted_mean(), None);
+    assert_eq!(a.try_unweighted_mean(), None);
+    assert_eq!(a.try_error(), None);
+    a.add(1., 0.);
+    assert_eq!(a.try_weighted_mean(), None);
+    assert_eq!(a.try_unweighted_mean(), Some(1.));
+    a.add(3., 1.);
+    a.add(5., 1.);
+    assert_eq!(a.try_weighted_mean(), Some(4.));
+    assert_eq!(a.try_error(), Some(a.error()));
+
+    let mut b = average::WeightedMean::new();
+    assert_eq!(b.try_mean(), None);
+    b.add(2., 0.5);
+    assert_eq!(b.try_mean(), Some(2.));
+}
+
+#[test]
+fn extend() {
+    let mut a = WeightedMeanWithError::new();
+    a.extend(&[(1., 1.), (2., 3.)]);
+    a.extend(vec![(5., 0.)]);
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.weighted_mean(), 1.75);
+}
+
+#[test]
+fn display() {
+    let mut a = WeightedMeanWithError::new();
+    a.add(1., 1.);
+    a.add(2., 1.);
+    a.add(3., 1.);
+    assert_eq!(format!("{}", a), "2.0 ± 0.6");
+}
@@ -0,0 +1,89 @@
+#![allow(clippy::float_cmp)]
+
+use average::{Estimate, Merge, Reset, Quantile, WeightedQuantile};
+
+#[test]
+fn few_observations() {
+    let mut q = WeightedQuantile::new(0.5);
+    assert_eq!(q.quantile(), 0.);
+    assert_eq!(q.try_quantile(), None);
+    q.add_weighted(3., 2.);
+    q.add_weighted(1., 1.);
+    assert_eq!(q.quantile(), 3.);
+    q.add_weighted(2., 1.);
+    assert_eq!(q.quantile(), 2.5);
+    q.add_weighted(5., 0.);
+    assert_eq!(q.len(), 3);
+    assert_eq!(q.sum_weights(), 4.);
+}
+
+#[test]
+fn unit_weights_match_quantile() {
+    for &p in &[0.1, 0.5, 0.9] {
+        let mut a = WeightedQuantile::new(p);
+        let mut b = Quantile::new(p);
+        for i in 0..10_000 {
+            let x = f64::from(i * 7919 % 10_000);
+            a.add(x);
+            b.add(x);
+        }
+        assert_eq!(a.len(), 10_000);
+        assert!((a.quantile() - p * 10_000.).abs() < 50., "{} vs. {}", a.quantile(), p * 10_000.);
+        assert!((a.quantile() - b.quantile()).abs() < 50.);
+    }
+}
+
+#[test]
+fn frequency_weights() {
+    // Weighting each value by its frequency is equivalent to repeating it.
+    for &p in &[0.25, 0.5, 0.75] {
+        let mut weighted = WeightedQuantile::new(p);
+        let mut repeated = Quantile::new(p);
+        for i in 0..1000 {
+            let x = f64::from(i * 37 % 1000);
+            let w = 1 + i % 5;
+            weighted.add_weighted(x, f64::from(w));
+            for _ in 0..w {
+                repeated.add(x);
+            }
+        }
+        assert_eq!(weighted.sum_weights(), 3000.);
+        assert!((weighted.quantile() - repeated.quantile()).abs() < 20.,
+                "{} vs. {}", weighted.quantile(), repeated.quantile());
+    }
+}
+
+#[test]
+fn skewed_weights() {
+    // Values above 900 carry 90 % of the weight.
+    let mut q = WeightedQuantile::new(0.5);
+    for i in 0..1000 {
+        let x = f64::from(i * 7919 % 1000);
+        q.add_weighted(x, if x >= 900. { 81. } else { 1. });
+    }
+    let expected = 900. + 100. * (0.5 * 9000. - 900.) / 8100.;
+    assert!((q.quantile() - expected).abs() < 10., "{} vs. {}", q.quantile(), expected);
+}
+
+#[test]
+fn merge() {
+    let mut a = WeightedQuantile::new(0.5);
+    for chunk in 0..10 {
+        let mut b = WeightedQuantile::new(0.5);
+        for i in 0..1000 {
+            let x = f64::from((chunk * 1000 + i) * 7919 % 10_000);
+            b.add_weighted(x, 0.5);
+        }
+        a.merge(&b);
+    }
+    assert_eq!(a.len(), 10_000);
+    assert_eq!(a.sum_weights(), 5000.);
+    assert!((a.quantile() - 5000.).abs() < 100., "{}", a.quantile());
+
+    let mut small = WeightedQuantile::new(0.5);
+    small.add_weighted(1., 3.);
+    small += &a;
+    assert_eq!(small.len(), 10_001);
+    a.reset();
+    assert!(a.is_empty());
+}